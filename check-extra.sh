@@ -32,6 +32,7 @@ cargo build --package 'yash-cli' --all-targets
 cargo build --package 'yash-env' --all-targets
 cargo build --package 'yash-env-test-helper' --all-targets
 cargo build --package 'yash-executor' --all-targets
+cargo build --package 'yash-executor' --all-targets --all-features
 cargo build --package 'yash-fnmatch' --all-targets
 cargo build --package 'yash-prompt' --all-targets
 cargo build --package 'yash-quote' --all-targets
@@ -43,6 +44,7 @@ cargo build --package 'yash-syntax' --all-targets --features annotate-snippets
 #cargo test --package 'yash-arith' -- $quiet
 #cargo test --package 'yash-builtin' -- $quiet
 cargo test --package 'yash-builtin' --no-default-features -- $quiet
+cargo test --package 'yash-executor' --all-features -- $quiet
 #cargo test --package 'yash-cli' -- $quiet
 #cargo test --package 'yash-env' -- $quiet
 #cargo test --package 'yash-fnmatch' -- $quiet
@@ -40,7 +40,7 @@ check yash-builtin
 check yash-cli
 check yash-env
 check yash-env-test-helper
-check yash-executor
+check yash-executor '' '--features std'
 check yash-fnmatch
 check yash-prompt
 check yash-quote
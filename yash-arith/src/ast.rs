@@ -57,6 +57,8 @@ pub enum PostfixOperator {
 /// Postfix operator kind
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum BinaryOperator {
+    /// `,`
+    Comma,
     /// `=`
     Assign,
     /// `||`
@@ -149,6 +151,7 @@ impl Operator {
         use Associativity::*;
         use BinaryOperator::*;
         match self {
+            Operator::Comma => Some((Comma, Left)),
             Operator::Equal => Some((Assign, Right)),
             Operator::BarEqual => Some((BitwiseOrAssign, Right)),
             Operator::CaretEqual => Some((BitwiseXorAssign, Right)),
@@ -190,20 +193,21 @@ impl Operator {
         use Operator::*;
         match self {
             CloseParen | Colon => 0,
+            Comma => 1,
             Equal | BarEqual | CaretEqual | AndEqual | LessLessEqual | GreaterGreaterEqual
-            | PlusEqual | MinusEqual | AsteriskEqual | SlashEqual | PercentEqual => 1,
-            Question => 2,
-            BarBar => 3,
-            AndAnd => 4,
-            Bar => 5,
-            Caret => 6,
-            And => 7,
-            EqualEqual | BangEqual => 8,
-            Less | LessEqual | Greater | GreaterEqual => 9,
-            LessLess | GreaterGreater => 10,
-            Plus | Minus => 11,
-            Asterisk | Slash | Percent => 12,
-            Tilde | Bang | PlusPlus | MinusMinus | OpenParen => 13,
+            | PlusEqual | MinusEqual | AsteriskEqual | SlashEqual | PercentEqual => 2,
+            Question => 3,
+            BarBar => 4,
+            AndAnd => 5,
+            Bar => 6,
+            Caret => 7,
+            And => 8,
+            EqualEqual | BangEqual => 9,
+            Less | LessEqual | Greater | GreaterEqual => 10,
+            LessLess | GreaterGreater => 11,
+            Plus | Minus => 12,
+            Asterisk | Slash | Percent => 13,
+            Tilde | Bang | PlusPlus | MinusMinus | OpenParen => 14,
         }
     }
 }
@@ -1106,6 +1110,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn comma_operator_is_left_associative() {
+        assert_eq!(
+            parse_str("1,2,3").unwrap(),
+            [
+                Ast::Term(Term::Value(Value::Integer(1))),
+                Ast::Term(Term::Value(Value::Integer(2))),
+                Ast::Binary {
+                    operator: BinaryOperator::Comma,
+                    rhs_len: 1,
+                    location: 1..2,
+                },
+                Ast::Term(Term::Value(Value::Integer(3))),
+                Ast::Binary {
+                    operator: BinaryOperator::Comma,
+                    rhs_len: 1,
+                    location: 3..4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn assignment_operator_in_comma_operator() {
+        assert_eq!(
+            parse_str("a=1,a").unwrap(),
+            [
+                Ast::Term(Term::Variable {
+                    name: "a",
+                    location: 0..1,
+                }),
+                Ast::Term(Term::Value(Value::Integer(1))),
+                Ast::Binary {
+                    operator: BinaryOperator::Assign,
+                    rhs_len: 1,
+                    location: 1..2,
+                },
+                Ast::Term(Term::Variable {
+                    name: "a",
+                    location: 4..5,
+                }),
+                Ast::Binary {
+                    operator: BinaryOperator::Comma,
+                    rhs_len: 1,
+                    location: 3..4,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn logical_or_operator() {
         assert_eq!(
@@ -255,6 +255,7 @@ fn binary_result<E1, E2>(
     let Value::Integer(rhs) = rhs;
     use BinaryOperator::*;
     let result = match operator {
+        Comma => Some(rhs),
         LogicalOr => Some((lhs != 0 || rhs != 0) as _),
         LogicalAnd => Some((lhs != 0 && rhs != 0) as _),
         BitwiseOr | BitwiseOrAssign => Some(lhs | rhs),
@@ -308,9 +309,9 @@ fn apply_binary<'a, E: Env>(
 ) -> Result<Value, Error<E::GetVariableError, E::AssignVariableError>> {
     use BinaryOperator::*;
     match operator {
-        LogicalOr | LogicalAnd | BitwiseOr | BitwiseXor | BitwiseAnd | EqualTo | NotEqualTo
-        | LessThan | GreaterThan | LessThanOrEqualTo | GreaterThanOrEqualTo | ShiftLeft
-        | ShiftRight | Add | Subtract | Multiply | Divide | Remainder => {
+        Comma | LogicalOr | LogicalAnd | BitwiseOr | BitwiseXor | BitwiseAnd | EqualTo
+        | NotEqualTo | LessThan | GreaterThan | LessThanOrEqualTo | GreaterThanOrEqualTo
+        | ShiftLeft | ShiftRight | Add | Subtract | Multiply | Divide | Remainder => {
             let lhs = into_value(lhs, env)?;
             let rhs = into_value(rhs, env)?;
             binary_result(lhs, rhs, operator, op_location)
@@ -353,6 +354,17 @@ pub fn eval<'a, E: Env>(
             apply_postfix(term, *operator, location, env).map(Term::Value)
         }
 
+        Ast::Binary {
+            operator: BinaryOperator::Comma,
+            rhs_len,
+            location: _,
+        } => {
+            let (lhs_ast, rhs_ast) = children.split_at(children.len() - rhs_len);
+            into_value(eval(lhs_ast, env)?, env)?;
+            let rhs = into_value(eval(rhs_ast, env)?, env)?;
+            Ok(Term::Value(rhs))
+        }
+
         Ast::Binary {
             operator: BinaryOperator::LogicalOr,
             rhs_len,
@@ -1508,6 +1520,34 @@ mod tests {
         assert_eq!(eval(ast, env), Ok(Term::Value(Value::Integer(0))));
     }
 
+    #[test]
+    fn eval_comma_evaluates_lhs_before_rhs() {
+        let env = &mut HashMap::new();
+        let ast = &[
+            Ast::Term(Term::Variable {
+                name: "a",
+                location: 0..1,
+            }),
+            Ast::Term(Term::Value(Value::Integer(5))),
+            Ast::Binary {
+                operator: BinaryOperator::Assign,
+                rhs_len: 1,
+                location: 1..2,
+            },
+            Ast::Term(Term::Variable {
+                name: "a",
+                location: 4..5,
+            }),
+            Ast::Binary {
+                operator: BinaryOperator::Comma,
+                rhs_len: 1,
+                location: 3..4,
+            },
+        ];
+        assert_eq!(eval(ast, env), Ok(Term::Value(Value::Integer(5))));
+        assert_eq!(env["a"], "5");
+    }
+
     #[test]
     fn eval_logical_or_short_circuit() {
         let env = &mut HashMap::new();
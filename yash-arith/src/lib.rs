@@ -255,6 +255,31 @@ mod tests {
         assert_eq!(env["c"], "7");
     }
 
+    #[test]
+    fn comma_operator() {
+        let env = &mut HashMap::new();
+        assert_eq!(eval("a=1, a+2", env), Ok(Value::Integer(3)));
+        assert_eq!(env["a"], "1");
+
+        assert_eq!(eval("b = 2, c = b * 3, b + c", env), Ok(Value::Integer(8)));
+        assert_eq!(env["b"], "2");
+        assert_eq!(env["c"], "6");
+
+        assert_eq!(eval("(1, 2) + 3", env), Ok(Value::Integer(5)));
+        assert_eq!(eval("1 ? 2, 3 : 4", env), Ok(Value::Integer(3)));
+    }
+
+    #[test]
+    fn short_circuit_operators_in_comma_operator() {
+        let env = &mut HashMap::new();
+        assert_eq!(eval("0 && (x = 1), x", env), Ok(Value::Integer(0)));
+        assert_eq!(env.get("x"), None);
+        assert_eq!(eval("1 || (y = 1), y", env), Ok(Value::Integer(0)));
+        assert_eq!(env.get("y"), None);
+        assert_eq!(eval("0 || (z = 1), z", env), Ok(Value::Integer(1)));
+        assert_eq!(env["z"], "1");
+    }
+
     #[test]
     fn conditional_operator() {
         let env = &mut HashMap::new();
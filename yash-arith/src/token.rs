@@ -59,6 +59,8 @@ pub enum Operator {
     Question,
     /// `:`
     Colon,
+    /// `,`
+    Comma,
     /// `|`
     Bar,
     /// `||`
@@ -181,6 +183,7 @@ pub struct Error {
 const OPERATORS: &[(&str, Operator)] = &[
     ("?", Operator::Question),
     (":", Operator::Colon),
+    (",", Operator::Comma),
     ("|=", Operator::BarEqual),
     ("||", Operator::BarBar),
     ("|", Operator::Bar),
@@ -265,7 +268,14 @@ impl<'a> Tokens<'a> {
             })
         } else {
             // The next token should be a term. Try parsing it.
-            let remainder = source.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_');
+            let is_term_char = |c: char| c.is_alphanumeric() || c == '_';
+            let mut remainder = source.trim_start_matches(is_term_char);
+            if first_char.is_ascii_digit() {
+                if let Some(digits) = remainder.strip_prefix('#') {
+                    // This is a constant of the form `base#digits`.
+                    remainder = digits.trim_start_matches(is_term_char);
+                }
+            }
             let token_len = source.len() - remainder.len();
             if token_len == 0 {
                 return Err(Error {
@@ -277,18 +287,9 @@ impl<'a> Tokens<'a> {
             let location = start_of_token..end_of_token;
             let token = &source[..token_len];
             let term = if first_char.is_ascii_digit() {
-                let parse = if let Some(token_source) = token.strip_prefix("0X") {
-                    i64::from_str_radix(token_source, 0x10)
-                } else if let Some(token_source) = token.strip_prefix("0x") {
-                    i64::from_str_radix(token_source, 0x10)
-                } else if source.starts_with('0') {
-                    i64::from_str_radix(token, 0o10)
-                } else {
-                    token.parse()
-                };
-                match parse {
-                    Ok(i) => Term::Value(Value::Integer(i)),
-                    Err(_) => {
+                match parse_integer(token) {
+                    Some(i) => Term::Value(Value::Integer(i)),
+                    None => {
                         return Err(Error {
                             cause: TokenError::InvalidNumericConstant,
                             location,
@@ -311,6 +312,28 @@ impl<'a> Tokens<'a> {
     }
 }
 
+/// Parses an integer constant.
+///
+/// The constant may be decimal, octal (with a leading `0`), hexadecimal (with
+/// a leading `0x` or `0X`), or of the form `base#digits` where `base` is a
+/// decimal integer between 2 and 36. In the last form, digits greater than 9
+/// are represented by letters regardless of case.
+fn parse_integer(token: &str) -> Option<i64> {
+    let result = if let Some((base, digits)) = token.split_once('#') {
+        let radix = base.parse().ok().filter(|radix| (2..=36).contains(radix))?;
+        i64::from_str_radix(digits, radix)
+    } else if let Some(digits) = token.strip_prefix("0X") {
+        i64::from_str_radix(digits, 0x10)
+    } else if let Some(digits) = token.strip_prefix("0x") {
+        i64::from_str_radix(digits, 0x10)
+    } else if token.starts_with('0') {
+        i64::from_str_radix(token, 0o10)
+    } else {
+        token.parse()
+    };
+    result.ok()
+}
+
 impl<'a> Iterator for Tokens<'a> {
     type Item = Result<Token<'a>, Error>;
 
@@ -509,6 +532,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn integer_constants_with_base() {
+        assert_eq!(
+            Tokens::new("16#FF").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Value(Value::Integer(0xFF))),
+                location: 0..5,
+            }))
+        );
+        assert_eq!(
+            Tokens::new(" 16#ff ").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Value(Value::Integer(0xFF))),
+                location: 1..6,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("2#1010").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Value(Value::Integer(0b1010))),
+                location: 0..6,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("36#z").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Value(Value::Integer(35))),
+                location: 0..4,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("010#10").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Value(Value::Integer(10))),
+                location: 0..6,
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_digit_in_constant_with_base() {
+        assert_eq!(
+            Tokens::new("2#102").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 0..5,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("  8#_7 ").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 2..6,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("16#").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 0..3,
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_base_in_constant_with_base() {
+        assert_eq!(
+            Tokens::new("37#1").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 0..4,
+            }))
+        );
+        assert_eq!(
+            Tokens::new(" 1#0").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 1..4,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("0#0").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 0..3,
+            }))
+        );
+        assert_eq!(
+            Tokens::new("0x10#1").next(),
+            Some(Err(Error {
+                cause: TokenError::InvalidNumericConstant,
+                location: 0..6,
+            }))
+        );
+    }
+
+    #[test]
+    fn number_sign_after_variable() {
+        assert_eq!(
+            Tokens::new("a#1").next(),
+            Some(Ok(Token {
+                value: TokenValue::Term(Term::Variable {
+                    name: "a",
+                    location: 0..1,
+                }),
+                location: 0..1,
+            }))
+        );
+    }
+
     // TODO Float constants
 
     #[test]
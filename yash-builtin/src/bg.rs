@@ -80,6 +80,7 @@
 //! [owned]: yash_env::job::Job::is_owned
 //! [expected state]: yash_env::job::Job::expected_state
 
+use crate::common::job_spec;
 use crate::common::report_error;
 use crate::common::report_failure;
 use crate::common::report_simple_failure;
@@ -90,9 +91,6 @@ use std::borrow::Cow;
 use std::fmt::Display;
 use thiserror::Error;
 use yash_env::io::Fd;
-use yash_env::job::id::parse;
-use yash_env::job::id::FindError;
-use yash_env::job::id::ParseError;
 #[cfg(doc)]
 use yash_env::job::JobList;
 use yash_env::job::ProcessState;
@@ -121,12 +119,9 @@ pub(crate) enum ResumeError {
 /// Errors that may occur when processing an operand
 #[derive(Clone, Debug, Error, Eq, PartialEq)]
 pub(crate) enum OperandErrorKind {
-    /// The operand is not a job ID.
+    /// The operand does not specify a single job.
     #[error(transparent)]
-    InvalidJobId(#[from] ParseError),
-    /// The job ID does not specify a single job.
-    #[error(transparent)]
-    UnidentifiedJob(#[from] FindError),
+    JobSpec(#[from] job_spec::Error),
     /// The job cannot be resumed.
     #[error(transparent)]
     CannotResume(#[from] ResumeError),
@@ -193,8 +188,7 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<(), ResumeEr
 
 /// Resumes the job specified by the operand.
 async fn resume_job_by_id(env: &mut Env, job_id: &str) -> Result<(), OperandErrorKind> {
-    let job_id = parse(job_id)?;
-    let index = job_id.find(&env.jobs)?;
+    let index = job_spec::resolve(&env.jobs, job_id)?;
     resume_job_by_index(env, index).await?;
     Ok(())
 }
@@ -459,6 +453,38 @@ mod tests {
         assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
     }
 
+    #[test]
+    fn main_without_operands_resumes_most_recently_suspended_job() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        let older_pgid = Pid(100);
+        let newer_pgid = Pid(200);
+        for pgid in [older_pgid, newer_pgid] {
+            let mut job = Job::new(pgid);
+            job.job_controlled = true;
+            env.jobs.add(job);
+            let mut process = Process::with_parent_and_group(system.process_id, pgid);
+            _ = process.set_state(ProcessState::stopped(SIGSTOP));
+            system.state.borrow_mut().processes.insert(pgid, process);
+        }
+        // The job list designates the most recently suspended job as the
+        // current job.
+        env.jobs
+            .update_status(older_pgid, ProcessState::stopped(SIGSTOP));
+        env.jobs
+            .update_status(newer_pgid, ProcessState::stopped(SIGSTOP));
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+
+        let state = system.state.borrow();
+        assert_eq!(state.processes[&newer_pgid].state(), ProcessState::Running);
+        assert_eq!(
+            state.processes[&older_pgid].state(),
+            ProcessState::stopped(SIGSTOP),
+        );
+    }
+
     #[test]
     fn main_without_operands_fails_if_there_is_no_current_job() {
         let system = VirtualSystem::new();
@@ -94,6 +94,7 @@
 //! - The operand is omitted and `$HOME` is not set or empty.
 //! - The operand is a single hyphen (`-`) and `$OLDPWD` is not set or empty.
 //! - The resolved pathname of the new working directory is too long.
+//! - The shell is in the restricted mode (the `restricted` option is on).
 //!
 //! The built-in may also fail in the following cases, but the working directory
 //! will remain changed and the exit status will be zero:
@@ -146,7 +147,10 @@
 
 use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_simple_failure;
 use crate::Result;
+use yash_env::option::Option::Restricted;
+use yash_env::option::State::On;
 use yash_env::path::Path;
 use yash_env::semantics::Field;
 use yash_env::variable::PWD;
@@ -197,6 +201,14 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         Err(e) => return report_error(env, &e).await,
     };
 
+    if env.options.get(Restricted) == On {
+        return report_simple_failure(
+            env,
+            "cannot change the working directory in the restricted shell",
+        )
+        .await;
+    }
+
     let pwd = get_pwd(env);
 
     let (path, origin) = match target::target(env, &command, &pwd) {
@@ -219,3 +231,124 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
 
     Result::default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::stack::{Builtin, Frame};
+    use yash_env::system::r#virtual::FileBody;
+    use yash_env::system::r#virtual::Inode;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::Mode;
+    use yash_env::variable::Scope::Global;
+    use yash_env::variable::CDPATH;
+    use yash_env::variable::OLDPWD;
+    use yash_env::System as _;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+
+    /// Creates an environment with the directory `/real/dir` and the symbolic
+    /// link `/link` to it. The working directory is `/`.
+    fn env_with_symlink() -> (Env, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut state_ref = state.borrow_mut();
+        let file = Rc::new(RefCell::new(Inode::new("")));
+        state_ref.file_system.save("/real/dir/file", file).unwrap();
+        let symlink = Inode {
+            body: FileBody::Symlink {
+                target: "/real/dir".into(),
+            },
+            permissions: Mode::default(),
+        };
+        state_ref
+            .file_system
+            .save("/link", Rc::new(RefCell::new(symlink)))
+            .unwrap();
+        drop(state_ref);
+
+        let mut env = Env::with_system(Box::new(system));
+        env.system.chdir(c"/").unwrap();
+        env.get_or_create_variable(PWD, Global)
+            .assign("/", None)
+            .unwrap();
+        (env, state)
+    }
+
+    #[test]
+    fn logical_mode_keeps_symlink_in_pwd() {
+        let (mut env, state) = env_with_symlink();
+
+        let result = main(&mut env, Field::dummies(["-L", "/link"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Result::default());
+        assert_eq!(env.system.getcwd().unwrap(), Path::new("/real/dir"));
+        assert_eq!(env.variables.get_scalar(PWD), Some("/link"));
+        assert_eq!(env.variables.get_scalar(OLDPWD), Some("/"));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn physical_mode_resolves_symlink_in_pwd() {
+        let (mut env, state) = env_with_symlink();
+
+        let result = main(&mut env, Field::dummies(["-P", "/link"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Result::default());
+        assert_eq!(env.system.getcwd().unwrap(), Path::new("/real/dir"));
+        assert_eq!(env.variables.get_scalar(PWD), Some("/real/dir"));
+        assert_eq!(env.variables.get_scalar(OLDPWD), Some("/"));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn cdpath_hit_prints_new_directory() {
+        let (mut env, state) = env_with_symlink();
+        env.get_or_create_variable(CDPATH, Global)
+            .assign("/no/such/dir:/real", None)
+            .unwrap();
+
+        let result = main(&mut env, Field::dummies(["dir"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Result::default());
+        assert_eq!(env.system.getcwd().unwrap(), Path::new("/real/dir"));
+        assert_eq!(env.variables.get_scalar(PWD), Some("/real/dir"));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "/real/dir\n"));
+    }
+
+    #[test]
+    fn restricted_shell_rejects_cd() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let file = Rc::new(RefCell::new(Inode::new("")));
+        state
+            .borrow_mut()
+            .file_system
+            .save("/tmp/file", file)
+            .unwrap();
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Restricted, On);
+        let old_cwd = env.system.getcwd().unwrap();
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("cd"),
+            is_special: false,
+        }));
+
+        let result = main(&mut env, Field::dummies(["/tmp"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(env.system.getcwd().unwrap(), old_cwd);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("restricted"), "{stderr}")
+        });
+    }
+}
@@ -147,7 +147,7 @@ pub struct Search {
     ///
     /// If `true`, the built-in searches for the utility in the standard search
     /// path instead of the current `$PATH`. The standard path is obtained from
-    /// TBD.
+    /// [`System::confstr_path`].
     pub standard_path: bool,
 
     /// Acceptable categories of the command name resolution
@@ -310,8 +310,14 @@ impl Identify {
 mod tests {
     use super::*;
     use crate::command::Search;
+    use std::cell::RefCell;
     use yash_env::builtin::Builtin;
     use yash_env::function::Function;
+    use yash_env::system::r#virtual::Inode;
+    use yash_env::system::Mode;
+    use yash_env::variable::Scope;
+    use yash_env::variable::PATH;
+    use yash_env::VirtualSystem;
     use yash_syntax::alias::HashEntry;
     use yash_syntax::source::Location;
     use yash_syntax::syntax::FullCompoundCommand;
@@ -650,4 +656,39 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn identify_result_with_standard_path() {
+        let system = VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        state.path = "/std/bin".into();
+        let mut content = Inode::default();
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        state.file_system.save("/std/bin/foo", content).unwrap();
+        drop(state);
+        let env = &mut Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/usr/bin", None)
+            .unwrap();
+
+        let mut identify = Identify {
+            names: Field::dummies(["foo"]),
+            ..Identify::default()
+        };
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "");
+        assert_eq!(
+            errors,
+            [NotFound {
+                name: &Field::dummy("foo")
+            }]
+        );
+
+        identify.search.standard_path = true;
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "/std/bin/foo\n");
+        assert_eq!(errors, []);
+    }
 }
@@ -23,6 +23,7 @@
 use super::Category;
 use super::Search;
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::rc::Rc;
 use yash_env::builtin::Builtin;
 use yash_env::function::Function;
@@ -65,6 +66,28 @@ impl yash_semantics::command_search::PathEnv for SearchEnv<'_> {
     fn is_executable_file(&self, path: &CStr) -> bool {
         self.env.is_executable_file(path)
     }
+
+    /// Remembers the path in the environment.
+    ///
+    /// If [`Search::standard_path`] is `true`, the path is not remembered
+    /// because it was not found in the `$PATH` variable.
+    fn remember_path(&mut self, name: &str, path: &CStr) {
+        if !self.params.standard_path {
+            self.env.remember_path(name, path)
+        }
+    }
+
+    /// Returns the path cached in the environment.
+    ///
+    /// If [`Search::standard_path`] is `true`, the cache is not used because
+    /// it is for the `$PATH` variable.
+    fn cached_path(&mut self, name: &str) -> Option<CString> {
+        if self.params.standard_path {
+            None
+        } else {
+            self.env.cached_path(name)
+        }
+    }
 }
 
 impl yash_semantics::command_search::SearchEnv for SearchEnv<'_> {
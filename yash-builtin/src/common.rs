@@ -16,8 +16,9 @@
 
 //! Common items for implementing built-ins
 //!
-//! This module contains some utility functions for printing messages and a
-//! submodule for [parsing command line arguments](syntax).
+//! This module contains some utility functions for printing messages and
+//! submodules for [parsing command line arguments](syntax) and [resolving job
+//! specifications](job_spec).
 
 use std::ops::ControlFlow::{Break, Continue};
 use yash_env::io::Fd;
@@ -34,6 +35,7 @@ use yash_syntax::source::pretty::Message;
 use yash_syntax::source::pretty::MessageBase;
 use yash_syntax::source::Location;
 
+pub mod job_spec;
 pub mod syntax;
 
 /// Convenience function for constructing an error message and a divert value.
@@ -0,0 +1,133 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Resolving job specifications
+//!
+//! Built-ins that operate on jobs, such as `bg` and `fg`, take operands that
+//! specify jobs by [job IDs](yash_env::job::id). The [`resolve`] function
+//! parses such an operand and finds the job it refers to in the job list.
+
+use thiserror::Error;
+use yash_env::job::id::parse;
+use yash_env::job::id::FindError;
+use yash_env::job::id::ParseError;
+use yash_env::job::JobList;
+
+/// Error in resolving a job specification
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    /// The job specification is not a valid job ID.
+    #[error(transparent)]
+    InvalidJobId(#[from] ParseError),
+    /// The job ID does not specify a single job.
+    #[error(transparent)]
+    UnidentifiedJob(#[from] FindError),
+}
+
+/// Resolves a job specification to the index of the job in the job list.
+///
+/// The job specification must be a job ID starting with `%`. The
+/// [current job](JobList::current_job) and [previous job](JobList::previous_job)
+/// are selected by `%+` (or `%%`) and `%-`, respectively.
+pub fn resolve(jobs: &JobList, job_spec: &str) -> Result<usize, Error> {
+    let job_id = parse(job_spec)?;
+    Ok(job_id.find(jobs)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yash_env::job::Job;
+    use yash_env::job::Pid;
+    use yash_env::job::ProcessState;
+    use yash_env::system::r#virtual::SIGSTOP;
+
+    fn sample_jobs() -> (JobList, [usize; 3]) {
+        let mut jobs = JobList::new();
+        let mut job = Job::new(Pid(10));
+        job.name = "sleep 1".to_string();
+        job.state = ProcessState::stopped(SIGSTOP);
+        let i10 = jobs.add(job);
+        let mut job = Job::new(Pid(11));
+        job.name = "sleep 2".to_string();
+        job.state = ProcessState::stopped(SIGSTOP);
+        let i11 = jobs.add(job);
+        let mut job = Job::new(Pid(12));
+        job.name = "cat foo".to_string();
+        let i12 = jobs.add(job);
+        jobs.set_current_job(i11).unwrap();
+        (jobs, [i10, i11, i12])
+    }
+
+    #[test]
+    fn current_job() {
+        let (jobs, [_, i11, _]) = sample_jobs();
+        assert_eq!(resolve(&jobs, "%+"), Ok(i11));
+        assert_eq!(resolve(&jobs, "%%"), Ok(i11));
+    }
+
+    #[test]
+    fn previous_job() {
+        let (jobs, [i10, _, _]) = sample_jobs();
+        assert_eq!(resolve(&jobs, "%-"), Ok(i10));
+    }
+
+    #[test]
+    fn job_number() {
+        let (jobs, [i10, _, i12]) = sample_jobs();
+        assert_eq!(resolve(&jobs, "%1"), Ok(i10));
+        assert_eq!(resolve(&jobs, "%3"), Ok(i12));
+    }
+
+    #[test]
+    fn name_prefix_and_substring() {
+        let (jobs, [_, _, i12]) = sample_jobs();
+        assert_eq!(resolve(&jobs, "%cat"), Ok(i12));
+        assert_eq!(resolve(&jobs, "%?foo"), Ok(i12));
+    }
+
+    #[test]
+    fn ambiguous_job() {
+        let (jobs, _) = sample_jobs();
+        assert_eq!(
+            resolve(&jobs, "%sleep"),
+            Err(Error::UnidentifiedJob(FindError::Ambiguous)),
+        );
+        assert_eq!(
+            resolve(&jobs, "%?sleep"),
+            Err(Error::UnidentifiedJob(FindError::Ambiguous)),
+        );
+    }
+
+    #[test]
+    fn unknown_job() {
+        let (jobs, _) = sample_jobs();
+        assert_eq!(
+            resolve(&jobs, "%4"),
+            Err(Error::UnidentifiedJob(FindError::NotFound)),
+        );
+        assert_eq!(
+            resolve(&jobs, "%?bar"),
+            Err(Error::UnidentifiedJob(FindError::NotFound)),
+        );
+    }
+
+    #[test]
+    fn invalid_job_id() {
+        let (jobs, _) = sample_jobs();
+        assert_eq!(resolve(&jobs, "1"), Err(Error::InvalidJobId(ParseError)));
+    }
+}
@@ -68,7 +68,6 @@ use yash_env::semantics::Field;
 use yash_env::Env;
 use yash_semantics::read_eval_loop;
 use yash_syntax::input::Memory;
-use yash_syntax::parser::lex::Lexer;
 use yash_syntax::source::Source;
 
 /// Entry point of the `eval` built-in execution
@@ -79,7 +78,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
     };
 
     // Parse and execute the command string
-    let mut config = Lexer::config();
+    let mut config = env.lexer_config();
     config.source = Some(Rc::new(Source::Eval {
         original: command.origin,
     }));
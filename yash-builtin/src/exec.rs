@@ -76,6 +76,11 @@
 //!
 //! If the external utility is invoked successfully, it replaces the shell
 //! executing the built-in, so there is no exit status of the built-in.
+//! If the utility is a script that cannot be executed directly nor by
+//! re-invoking the shell, the script is executed in the current shell process
+//! as described in [`replace_current_process`]. The built-in then returns a
+//! `Divert` value of [`Exit`] with the exit status of the script so that the
+//! shell exits even if it is interactive.
 //! If the built-in fails to invoke the utility, the exit status will be 126.
 //! If there is no utility matching the first operand, the exit status will be
 //! 127.
@@ -96,6 +101,8 @@ use yash_env::Env;
 use yash_semantics::command::simple_command::{replace_current_process, to_c_strings};
 use yash_semantics::command_search::search_path;
 use yash_semantics::Divert::Abort;
+#[cfg(doc)]
+use yash_semantics::Divert::Exit;
 use yash_semantics::ExitStatus;
 
 // TODO Split into syntax and semantics submodules
@@ -120,8 +127,13 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         if let Some(path) = path {
             let location = name.origin.clone();
             let args = to_c_strings(args);
-            replace_current_process(env, path, args, location).await;
+            let divert = replace_current_process(env, path, args, location).await;
             result.set_exit_status(env.exit_status);
+            if divert.is_break() {
+                // The script was executed in this process as a fallback, so
+                // the shell must exit even if it is interactive.
+                result.set_divert(divert);
+            }
         } else {
             print_error(
                 env,
@@ -144,12 +156,14 @@ mod tests {
     use std::cell::RefCell;
     use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
+    use std::time::Instant;
     use yash_env::option::Option::Interactive;
     use yash_env::option::State::On;
     use yash_env::system::r#virtual::{FileBody, Inode};
     use yash_env::system::Mode;
     use yash_env::variable::{Scope, PATH};
     use yash_env::VirtualSystem;
+    use yash_semantics::Divert;
 
     fn executable_file() -> Inode {
         let mut content = Inode::default();
@@ -314,4 +328,27 @@ mod tests {
         assert_eq!(result.exit_status(), ExitStatus::NOEXEC);
         assert_eq!(result.divert(), Continue(()));
     }
+
+    #[test]
+    fn interactive_shell_exits_after_script_executed_as_fallback() {
+        let system = VirtualSystem::new();
+        system.state.borrow_mut().now = Some(Instant::now());
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.options.set(Interactive, On);
+
+        // Prepare a script file that is not a native executable
+        let mut content = Inode::new("");
+        content.permissions.set(Mode::USER_EXEC, true);
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/some/script", Rc::new(RefCell::new(content)))
+            .unwrap();
+
+        let args = Field::dummies(["/some/script"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::SUCCESS);
+        assert_eq!(result.divert(), Break(Divert::Exit(None)));
+    }
 }
@@ -0,0 +1,749 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Fc built-in
+//!
+//! The **`fc`** built-in lists or re-executes commands in the
+//! [command history](yash_env::history).
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! fc -l [-nr] [first [last]]
+//! ```
+//!
+//! ```sh
+//! fc -s [old=new] [first]
+//! ```
+//!
+//! # Description
+//!
+//! With the `-l` option, the built-in prints the history entries in the range
+//! specified by the operands.
+//!
+//! With the `-s` option, the built-in re-executes the history entry specified
+//! by the operand. If an operand of the form `old=new` is given, the first
+//! occurrence of `old` in the command is replaced with `new` before execution.
+//! The command to be executed is printed to the standard output and added to
+//! the history.
+//!
+//! When the shell is interactive, the most recent history entry is the `fc`
+//! command being executed, so it is not considered in selecting entries.
+//! With the `-s` option, the entry is replaced with the re-executed command.
+//!
+//! # Options
+//!
+//! The **`-l`** (**`--list`**) option makes the built-in list history entries.
+//!
+//! The **`-n`** (**`--no-numbers`**) option suppresses history numbers in the
+//! listing.
+//!
+//! The **`-r`** (**`--reverse`**) option reverses the order of the listing.
+//!
+//! The **`-s`** (**`--silent`**) option makes the built-in re-execute a history
+//! entry without editing.
+//!
+//! The **`-e`** (**`--editor`**) option specifies the editor used to edit
+//! history entries. Editing is not supported yet, so only `-e -`, which is
+//! equivalent to `-s`, is accepted.
+//!
+//! # Operands
+//!
+//! The *first* and *last* operands specify the range of history entries.
+//! A positive integer is a history number. A negative integer is an offset
+//! from the history number of the current command. Other strings select the
+//! most recent entry that starts with the string.
+//!
+//! When listing, *first* defaults to `-16` and *last* to `-1`. If *first* is
+//! more recent than *last*, the entries are listed in reverse order. Numbers
+//! that are out of the range of the existing entries are adjusted to the
+//! nearest entry.
+//!
+//! When re-executing, *first* defaults to `-1`, the previous command.
+//!
+//! # Standard output
+//!
+//! When listing, each entry is printed in the format `"%d\t%s\n"` with the
+//! history number and the command. With the `-n` option, the number is
+//! omitted. If the command spans multiple lines, the subsequent lines are
+//! preceded by a tab.
+//!
+//! When re-executing, the command is printed before it is executed.
+//!
+//! # Errors
+//!
+//! It is an error if no history entry matches an operand.
+//! It is also an error to re-execute a command when the history is empty.
+//!
+//! The `-l` and `-s` options are mutually exclusive.
+//!
+//! # Exit status
+//!
+//! When listing, zero unless an error occurs.
+//!
+//! When re-executing, the exit status of the re-executed command.
+//!
+//! # Portability
+//!
+//! The fc built-in is specified in POSIX. Long options are non-standard
+//! extensions. This implementation does not support editing history entries
+//! with an editor, which POSIX requires when neither `-l` nor `-s` is given.
+
+use crate::common::output;
+use crate::common::report_error;
+use crate::common::report_failure;
+use crate::common::report_simple_failure;
+use crate::common::syntax::parse_arguments;
+use crate::common::syntax::ConflictingOptionError;
+use crate::common::syntax::Mode;
+use crate::common::syntax::OptionArgumentSpec;
+use crate::common::syntax::OptionSpec;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::rc::Rc;
+use thiserror::Error;
+use yash_env::history::Entry;
+use yash_env::option::Option::Interactive;
+use yash_env::option::State::On;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Field;
+use yash_env::Env;
+use yash_semantics::read_eval_loop;
+use yash_syntax::input::Memory;
+use yash_syntax::source::pretty::Annotation;
+use yash_syntax::source::pretty::AnnotationType;
+use yash_syntax::source::pretty::MessageBase;
+use yash_syntax::source::Source;
+
+/// List of all options supported by the `fc` built-in
+pub const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new()
+        .short('e')
+        .long("editor")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new().short('l').long("list"),
+    OptionSpec::new().short('n').long("no-numbers"),
+    OptionSpec::new().short('r').long("reverse"),
+    OptionSpec::new().short('s').long("silent"),
+];
+
+/// Replacement applied to a re-executed command
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Substitution {
+    /// String to be replaced
+    pub old: String,
+    /// Replacement string
+    pub new: String,
+}
+
+/// Parsed command line arguments
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    /// Lists history entries
+    List {
+        /// Specifier of the first entry
+        first: Option<Field>,
+        /// Specifier of the last entry
+        last: Option<Field>,
+        /// Whether to omit history numbers
+        suppress_numbers: bool,
+        /// Whether to list entries in reverse order
+        reverse: bool,
+    },
+    /// Re-executes a history entry
+    Rerun {
+        /// Replacement applied to the command
+        substitution: Option<Substitution>,
+        /// Specifier of the entry
+        first: Option<Field>,
+    },
+    /// Edits and re-executes history entries (not supported)
+    Edit,
+}
+
+/// Error in parsing command line arguments
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred in the common parser.
+    #[error(transparent)]
+    CommonError(#[from] crate::common::syntax::ParseError<'static>),
+
+    /// Mutually exclusive options are used together.
+    #[error(transparent)]
+    ConflictingOption(#[from] ConflictingOptionError<'static>),
+
+    /// More operands than allowed are given.
+    #[error("too many operands")]
+    TooManyOperands(Field),
+}
+
+impl MessageBase for Error {
+    fn message_title(&self) -> Cow<'_, str> {
+        self.to_string().into()
+    }
+
+    fn main_annotation(&self) -> Annotation<'_> {
+        match self {
+            Error::CommonError(inner) => inner.main_annotation(),
+            Error::ConflictingOption(inner) => inner.main_annotation(),
+            Error::TooManyOperands(operand) => Annotation::new(
+                AnnotationType::Error,
+                "unexpected operand".into(),
+                &operand.origin,
+            ),
+        }
+    }
+
+    fn additional_annotations<'a, T: Extend<Annotation<'a>>>(&'a self, results: &mut T) {
+        match self {
+            Error::CommonError(inner) => inner.additional_annotations(results),
+            Error::ConflictingOption(inner) => inner.additional_annotations(results),
+            Error::TooManyOperands(_) => {}
+        }
+    }
+}
+
+/// Error indicating that no history entry matches an operand
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("no history entry starts with `{}`", .0.value)]
+pub struct EntryNotFound(pub Field);
+
+impl MessageBase for EntryNotFound {
+    fn message_title(&self) -> Cow<'_, str> {
+        "cannot find history entry".into()
+    }
+
+    fn main_annotation(&self) -> Annotation<'_> {
+        Annotation::new(
+            AnnotationType::Error,
+            self.to_string().into(),
+            &self.0.origin,
+        )
+    }
+}
+
+/// Tests whether the argument is a negative integer.
+///
+/// Such arguments are operands rather than options for this built-in.
+fn is_negative_number(arg: &str) -> bool {
+    arg.strip_prefix('-')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Parses command line arguments.
+pub fn parse(env: &Env, mut args: Vec<Field>) -> Result<Command, Error> {
+    let split = args
+        .iter()
+        .position(|arg| is_negative_number(&arg.value))
+        .unwrap_or(args.len());
+    let rest = args.split_off(split);
+    let (options, mut operands) = parse_arguments(OPTION_SPECS, Mode::with_env(env), args)?;
+    operands.extend(rest);
+
+    let mut list = None;
+    let mut rerun = None;
+    let mut edit = None;
+    let mut suppress_numbers = false;
+    let mut reverse = false;
+    for (index, option) in options.iter().enumerate() {
+        match option.spec.get_short() {
+            Some('e') => edit = Some(index),
+            Some('l') => list = Some(index),
+            Some('n') => suppress_numbers = true,
+            Some('r') => reverse = true,
+            Some('s') => rerun = Some(index),
+            _ => unreachable!(),
+        }
+    }
+
+    let mode_options = [list, rerun, edit];
+    let mut mode_options = mode_options.into_iter().flatten();
+    if let (Some(i), Some(j)) = (mode_options.next(), mode_options.next()) {
+        return Err(ConflictingOptionError::pick_from_indexes(options, [i, j]).into());
+    }
+
+    if list.is_some() {
+        if let Some(operand) = operands.get(2) {
+            return Err(Error::TooManyOperands(operand.clone()));
+        }
+        let mut operands = operands.into_iter();
+        let first = operands.next();
+        let last = operands.next();
+        return Ok(Command::List {
+            first,
+            last,
+            suppress_numbers,
+            reverse,
+        });
+    }
+
+    let is_rerun = rerun.is_some()
+        || edit.is_some_and(|i| options[i].argument.as_ref().is_some_and(|e| e.value == "-"));
+    if !is_rerun {
+        return Ok(Command::Edit);
+    }
+
+    let mut operands = operands.into_iter().peekable();
+    let substitution = operands
+        .next_if(|operand| operand.value.contains('='))
+        .map(|operand| {
+            let (old, new) = operand.value.split_once('=').unwrap();
+            Substitution {
+                old: old.to_owned(),
+                new: new.to_owned(),
+            }
+        });
+    let first = operands.next();
+    if let Some(operand) = operands.next() {
+        return Err(Error::TooManyOperands(operand));
+    }
+    Ok(Command::Rerun {
+        substitution,
+        first,
+    })
+}
+
+/// Returns the history entries that precede the current command.
+///
+/// If the shell is interactive, the most recent entry is the `fc` command
+/// being executed, so it is excluded.
+fn previous_entries(env: &Env) -> Vec<&Entry> {
+    let mut entries = env.history.iter().collect::<Vec<_>>();
+    if env.options.get(Interactive) == On {
+        entries.pop();
+    }
+    entries
+}
+
+/// Finds the index of the entry specified by the operand.
+///
+/// `entries` must not be empty. Numbers out of range are adjusted to the
+/// nearest entry.
+fn resolve(entries: &[&Entry], operand: &Field) -> Result<usize, EntryNotFound> {
+    match operand.value.parse::<isize>() {
+        Ok(n) => Ok(resolve_number(entries, n)),
+        Err(_) => entries
+            .iter()
+            .rposition(|entry| entry.command.starts_with(&operand.value))
+            .ok_or_else(|| EntryNotFound(operand.clone())),
+    }
+}
+
+/// Finds the index of the entry with the history number.
+///
+/// A non-positive number is an offset from the current command number, which
+/// is one more than the number of the last entry.
+fn resolve_number(entries: &[&Entry], n: isize) -> usize {
+    let first = entries[0].number;
+    let last = entries[entries.len() - 1].number;
+    let target = if n > 0 {
+        n.unsigned_abs()
+    } else {
+        (last + 1).saturating_sub(n.unsigned_abs())
+    };
+    target.clamp(first, last) - first
+}
+
+/// Formats history entries in the listing format.
+fn list(entries: &[&Entry], suppress_numbers: bool) -> String {
+    let mut output = String::new();
+    for entry in entries {
+        if !suppress_numbers {
+            write!(output, "{}", entry.number).unwrap();
+        }
+        writeln!(output, "\t{}", entry.command.replace('\n', "\n\t")).unwrap();
+    }
+    output
+}
+
+impl Command {
+    /// Executes the built-in.
+    pub async fn execute(self, env: &mut Env) -> crate::Result {
+        match self {
+            Command::List {
+                first,
+                last,
+                suppress_numbers,
+                reverse,
+            } => {
+                let entries = previous_entries(env);
+                if entries.is_empty() {
+                    return crate::Result::default();
+                }
+
+                let start = match first {
+                    None => Ok(resolve_number(&entries, -16)),
+                    Some(first) => resolve(&entries, &first),
+                };
+                let end = match last {
+                    None => Ok(entries.len() - 1),
+                    Some(last) => resolve(&entries, &last),
+                };
+                let (start, end) = match (start, end) {
+                    (Ok(start), Ok(end)) => (start, end),
+                    (Err(error), _) | (_, Err(error)) => return report_failure(env, &error).await,
+                };
+
+                let mut selected = entries[start.min(end)..=start.max(end)].to_vec();
+                if (start > end) != reverse {
+                    selected.reverse();
+                }
+                let content = list(&selected, suppress_numbers);
+                output(env, &content).await
+            }
+
+            Command::Rerun {
+                substitution,
+                first,
+            } => {
+                let entries = previous_entries(env);
+                if entries.is_empty() {
+                    return report_simple_failure(env, "no history entry to re-execute").await;
+                }
+
+                let index = match first {
+                    None => entries.len() - 1,
+                    Some(first) => match resolve(&entries, &first) {
+                        Ok(index) => index,
+                        Err(error) => return report_failure(env, &error).await,
+                    },
+                };
+                let number = entries[index].number;
+                let mut command = entries[index].command.clone();
+                if let Some(Substitution { old, new }) = substitution {
+                    command = command.replacen(&old, &new, 1);
+                }
+
+                if env.options.get(Interactive) == On {
+                    env.history.pop();
+                    env.add_history(&command);
+                }
+
+                let result = output(env, &format!("{command}\n")).await;
+                if result.exit_status() != ExitStatus::SUCCESS {
+                    return result;
+                }
+
+                let mut config = env.lexer_config();
+                config.source = Some(Rc::new(Source::Other {
+                    label: format!("history entry {number}"),
+                }));
+                let mut lexer = config.input(Box::new(Memory::new(&command)));
+                let divert = read_eval_loop(&RefCell::new(&mut *env), &mut lexer).await;
+                crate::Result::with_exit_status_and_divert(env.exit_status, divert)
+            }
+
+            Command::Edit => {
+                report_simple_failure(env, "editing history entries is not supported").await
+            }
+        }
+    }
+}
+
+/// Entry point of the `fc` built-in
+pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
+    match parse(env, args) {
+        Ok(command) => command.execute(env).await,
+        Err(error) => report_error(env, &error).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::Special;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+
+    fn env_with_history(commands: &[&str]) -> (Env, Rc<RefCell<SystemState>>) {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        for command in commands {
+            env.history.push(command);
+        }
+        (env, state)
+    }
+
+    fn echo_builtin() -> Builtin {
+        Builtin::new(Special, |env, args| {
+            Box::pin(async move {
+                let fields = args.iter().map(|f| &f.value[..]).collect::<Vec<_>>();
+                output(env, &format!("{}\n", fields.join(" "))).await
+            })
+        })
+    }
+
+    #[test]
+    fn parse_list_with_negative_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-lr", "-3", "-1"]));
+        assert_eq!(
+            result,
+            Ok(Command::List {
+                first: Some(Field::dummy("-3")),
+                last: Some(Field::dummy("-1")),
+                suppress_numbers: false,
+                reverse: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_rerun_with_substitution() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-s", "foo=bar", "echo"]));
+        assert_eq!(
+            result,
+            Ok(Command::Rerun {
+                substitution: Some(Substitution {
+                    old: "foo".to_string(),
+                    new: "bar".to_string(),
+                }),
+                first: Some(Field::dummy("echo")),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_editor_dash_as_rerun() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-e", "-", "-2"]));
+        assert_eq!(
+            result,
+            Ok(Command::Rerun {
+                substitution: None,
+                first: Some(Field::dummy("-2")),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_conflicting_options() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-l", "-s"]));
+        assert_matches!(result, Err(Error::ConflictingOption(_)));
+    }
+
+    #[test]
+    fn parse_too_many_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-s", "1", "2"]));
+        assert_eq!(result, Err(Error::TooManyOperands(Field::dummy("2"))));
+    }
+
+    #[test]
+    fn list_all_entries_by_default() {
+        let (mut env, state) = env_with_history(&["echo 1", "echo 2", "echo 3"]);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "1\techo 1\n2\techo 2\n3\techo 3\n")
+        });
+    }
+
+    #[test]
+    fn list_last_16_entries_by_default() {
+        let commands = (1..=20).map(|i| format!("echo {i}")).collect::<Vec<_>>();
+        let commands = commands.iter().map(String::as_str).collect::<Vec<_>>();
+        let (mut env, state) = env_with_history(&commands);
+
+        let result = main(&mut env, Field::dummies(["-ln"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            let lines = stdout.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), 16);
+            assert_eq!(lines[0], "\techo 5");
+            assert_eq!(lines[15], "\techo 20");
+        });
+    }
+
+    #[test]
+    fn list_range_with_numbers_and_prefix() {
+        let (mut env, state) = env_with_history(&["a", "b", "c", "d", "e"]);
+
+        let result = main(&mut env, Field::dummies(["-l", "2", "d"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "2\tb\n3\tc\n4\td\n"));
+    }
+
+    #[test]
+    fn list_range_with_relative_numbers() {
+        let (mut env, state) = env_with_history(&["a", "b", "c", "d", "e"]);
+
+        let result = main(&mut env, Field::dummies(["-l", "-3", "-2"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "3\tc\n4\td\n"));
+    }
+
+    #[test]
+    fn list_reversed_range() {
+        let (mut env, state) = env_with_history(&["a", "b", "c", "d"]);
+
+        let result = main(&mut env, Field::dummies(["-l", "3", "2"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "3\tc\n2\tb\n"));
+    }
+
+    #[test]
+    fn list_with_reverse_option() {
+        let (mut env, state) = env_with_history(&["a", "b", "c"]);
+
+        let result = main(&mut env, Field::dummies(["-lr", "1", "2"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "2\tb\n1\ta\n"));
+    }
+
+    #[test]
+    fn list_clamps_out_of_range_numbers() {
+        let (mut env, state) = env_with_history(&["a", "b"]);
+
+        let result = main(&mut env, Field::dummies(["-l", "-100", "100"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\ta\n2\tb\n"));
+    }
+
+    #[test]
+    fn list_multi_line_entry() {
+        let (mut env, state) = env_with_history(&["for i in 1\ndo echo\ndone"]);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "1\tfor i in 1\n\tdo echo\n\tdone\n")
+        });
+    }
+
+    #[test]
+    fn list_excludes_current_command_in_interactive_shell() {
+        let (mut env, state) = env_with_history(&["a", "b", "fc -l"]);
+        env.options.set(Interactive, On);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\ta\n2\tb\n"));
+    }
+
+    #[test]
+    fn list_with_unmatched_prefix() {
+        let (mut env, state) = env_with_history(&["a"]);
+
+        let result = main(&mut env, Field::dummies(["-l", "x"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn rerun_previous_command() {
+        let (mut env, state) = env_with_history(&["echo foo", "echo bar"]);
+        env.builtins.insert("echo", echo_builtin());
+
+        let result = main(&mut env, Field::dummies(["-s"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "echo bar\nbar\n"));
+    }
+
+    #[test]
+    fn rerun_with_substitution() {
+        let (mut env, state) = env_with_history(&["echo foo foo", "echo bar"]);
+        env.builtins.insert("echo", echo_builtin());
+
+        let result = main(&mut env, Field::dummies(["-s", "foo=baz", "1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "echo baz foo\nbaz foo\n");
+        });
+    }
+
+    #[test]
+    fn rerun_entry_selected_by_prefix() {
+        let (mut env, state) = env_with_history(&["echo foo", "true", "echo bar", "true"]);
+        env.builtins.insert("echo", echo_builtin());
+
+        let result = main(&mut env, Field::dummies(["-s", "ec"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "echo bar\nbar\n"));
+    }
+
+    #[test]
+    fn rerun_replaces_current_command_in_interactive_shell() {
+        let (mut env, _) = env_with_history(&["echo foo", "fc -s foo=bar"]);
+        env.builtins.insert("echo", echo_builtin());
+        env.options.set(Interactive, On);
+
+        let result = main(&mut env, Field::dummies(["-s", "foo=bar"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        let commands = env.history.iter().map(|entry| &entry.command[..]);
+        assert_eq!(commands.collect::<Vec<_>>(), ["echo foo", "echo bar"]);
+        assert_eq!(env.history.last().unwrap().number, 2);
+    }
+
+    #[test]
+    fn rerun_with_empty_history() {
+        let (mut env, state) = env_with_history(&[]);
+
+        let result = main(&mut env, Field::dummies(["-s"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn editing_is_not_supported() {
+        let (mut env, _) = env_with_history(&["a"]);
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+    }
+}
@@ -82,12 +82,12 @@
 
 use crate::bg::OperandErrorKind;
 use crate::bg::ResumeError;
+use crate::common::job_spec;
 use crate::common::report_error;
 use crate::common::report_simple_failure;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use yash_env::io::Fd;
-use yash_env::job::id::parse;
 #[cfg(doc)]
 use yash_env::job::JobList;
 use yash_env::job::Pid;
@@ -115,10 +115,12 @@ async fn wait_until_halt(env: &mut Env, pid: Pid) -> Result<ProcessState, Errno>
 ///
 /// This function puts the target job in the foreground and sends the `SIGCONT`
 /// signal to it. It then waits for the job to finish (or suspend again).
+/// If the controlling terminal is not available, the job is resumed without
+/// changing the foreground process group.
 ///
 /// This function panics if there is no job at the specified index.
 async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<ProcessState, ResumeError> {
-    let tty = env.get_tty()?;
+    let tty = env.get_tty().ok();
 
     let job = &env.jobs[index];
     if !job.is_owned {
@@ -138,7 +140,9 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<ProcessState
 
         // Make sure to put the target job in the foreground before sending the
         // SIGCONT signal, or the job may be immediately re-suspended.
-        env.system.tcsetpgrp_without_block(tty, job.pid)?;
+        if let Some(tty) = tty {
+            env.system.tcsetpgrp_without_block(tty, job.pid)?;
+        }
 
         let pgid = -job.pid;
         let sigcont = env.system.signal_number_from_name(signal::Name::Cont);
@@ -149,7 +153,9 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<ProcessState
         state = wait_until_halt(env, job.pid).await?;
 
         // Move the shell back to the foreground.
-        env.system.tcsetpgrp_with_block(tty, env.main_pgid)?;
+        if let Some(tty) = tty {
+            env.system.tcsetpgrp_with_block(tty, env.main_pgid)?;
+        }
     }
 
     // Remove the job if it has finished.
@@ -162,8 +168,7 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<ProcessState
 
 /// Resumes the job specified by the operand.
 async fn resume_job_by_id(env: &mut Env, job_id: &str) -> Result<ProcessState, OperandErrorKind> {
-    let job_id = parse(job_id)?;
-    let index = job_id.find(&env.jobs)?;
+    let index = job_spec::resolve(&env.jobs, job_id)?;
     Ok(resume_job_by_index(env, index).await?)
 }
 
@@ -250,6 +255,31 @@ mod tests {
         })
     }
 
+    #[test]
+    fn resume_job_by_index_without_tty() {
+        in_virtual_system(|mut env, state| async move {
+            env.options.set(Monitor, On);
+            let subshell = Subshell::new(|env, _| {
+                Box::pin(async move {
+                    suspend(env).await;
+                    env.exit_status = ExitStatus(7);
+                })
+            })
+            .job_control(JobControl::Foreground);
+            let (pid, subshell_result) = subshell.start_and_wait(&mut env).await.unwrap();
+            assert_eq!(subshell_result, ProcessResult::Stopped(SIGSTOP));
+            let mut job = Job::new(pid);
+            job.job_controlled = true;
+            job.state = subshell_result.into();
+            let index = env.jobs.add(job);
+
+            let result = resume_job_by_index(&mut env, index).await.unwrap();
+
+            assert_eq!(result, ProcessState::exited(7));
+            assert_eq!(state.borrow().foreground, None);
+        })
+    }
+
     #[test]
     fn resume_job_by_index_prints_job_name() {
         in_virtual_system(|mut env, state| async move {
@@ -449,6 +479,43 @@ mod tests {
         })
     }
 
+    #[test]
+    fn main_without_operands_resumes_most_recently_suspended_job() {
+        in_virtual_system(|mut env, state| async move {
+            stub_tty(&state);
+            env.options.set(Monitor, On);
+            let subshell = Subshell::new(|env, _| {
+                Box::pin(async move {
+                    suspend(env).await;
+                    unreachable!("older job should not be resumed");
+                })
+            })
+            .job_control(JobControl::Foreground);
+            let (pid1, subshell_result_1) = subshell.start_and_wait(&mut env).await.unwrap();
+            let mut job = Job::new(pid1);
+            job.job_controlled = true;
+            env.jobs.add(job);
+            let subshell =
+                Subshell::new(|env, _| Box::pin(suspend(env))).job_control(JobControl::Foreground);
+            let (pid2, subshell_result_2) = subshell.start_and_wait(&mut env).await.unwrap();
+            let mut job = Job::new(pid2);
+            job.job_controlled = true;
+            let index2 = env.jobs.add(job);
+            // The job list designates the most recently suspended job as the
+            // current job.
+            env.jobs.update_status(pid1, subshell_result_1.into());
+            env.jobs.update_status(pid2, subshell_result_2.into());
+            assert_eq!(env.jobs.current_job(), Some(index2));
+
+            let result = main(&mut env, vec![]).await;
+
+            assert_eq!(result, crate::Result::default());
+            assert_eq!(env.jobs.get(index2), None);
+            let state = state.borrow().processes[&pid1].state();
+            assert_eq!(state, ProcessState::stopped(SIGSTOP));
+        })
+    }
+
     #[test]
     fn main_without_operands_fails_if_there_is_no_current_job() {
         let system = VirtualSystem::new();
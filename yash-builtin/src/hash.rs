@@ -0,0 +1,322 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Hash built-in
+//!
+//! The **`hash`** built-in manages the remembered paths of external utilities.
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! hash [-r] [name…]
+//! ```
+//!
+//! # Description
+//!
+//! The shell remembers the path to an external utility when it finds the
+//! utility by searching `$PATH`. The remembered paths are stored in
+//! [`Env::command_paths`].
+//!
+//! Without options or operands, the built-in prints the remembered paths.
+//!
+//! With operands, the built-in searches `$PATH` for the named utilities and
+//! remembers the paths found.
+//!
+//! # Options
+//!
+//! The **`-r`** (**`--remove`**) option makes the shell forget all remembered
+//! paths before processing the operands.
+//!
+//! # Operands
+//!
+//! Each operand is the name of an external utility to search for. Operands
+//! containing a slash are ignored since such names are not searched for in
+//! `$PATH`.
+//!
+//! # Standard output
+//!
+//! When printing, the remembered paths are printed one per line, sorted by the
+//! utility name.
+//!
+//! # Errors
+//!
+//! It is an error if a utility named by an operand is not found in `$PATH`.
+//!
+//! # Exit status
+//!
+//! Zero unless an error occurs.
+//!
+//! # Portability
+//!
+//! The hash built-in is specified in POSIX, but the output format is
+//! unspecified. The `-r` option is the only option defined in POSIX.
+//! Using the `-r` option with operands is a non-standard extension.
+
+use crate::common::output;
+use crate::common::report_error;
+use crate::common::report_failure;
+use crate::common::syntax::parse_arguments;
+use crate::common::syntax::Mode;
+use crate::common::syntax::OptionSpec;
+use crate::common::to_single_message;
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use thiserror::Error;
+use yash_env::semantics::Field;
+use yash_env::Env;
+use yash_semantics::command_search::search_path;
+use yash_syntax::source::pretty::Annotation;
+use yash_syntax::source::pretty::AnnotationType;
+use yash_syntax::source::pretty::MessageBase;
+
+/// List of all options supported by the `hash` built-in
+pub const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('r').long("remove")];
+
+/// Parsed command line arguments
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    /// Prints the remembered paths
+    Print,
+    /// Searches for and remembers the paths to utilities
+    Remember {
+        /// Whether to forget all remembered paths first
+        clear: bool,
+        /// Names of the utilities to search for
+        names: Vec<Field>,
+    },
+}
+
+/// Error indicating that a utility is not found in `$PATH`
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("utility `{}` not found", .0.value)]
+pub struct NotFound(pub Field);
+
+impl MessageBase for NotFound {
+    fn message_title(&self) -> Cow<'_, str> {
+        "cannot remember utility path".into()
+    }
+
+    fn main_annotation(&self) -> Annotation<'_> {
+        Annotation::new(
+            AnnotationType::Error,
+            self.to_string().into(),
+            &self.0.origin,
+        )
+    }
+}
+
+/// Parses command line arguments.
+pub fn parse(
+    env: &Env,
+    args: Vec<Field>,
+) -> Result<Command, crate::common::syntax::ParseError<'static>> {
+    let (options, names) = parse_arguments(OPTION_SPECS, Mode::with_env(env), args)?;
+    let clear = !options.is_empty();
+    if !clear && names.is_empty() {
+        Ok(Command::Print)
+    } else {
+        Ok(Command::Remember { clear, names })
+    }
+}
+
+/// Returns the remembered paths in the output format.
+#[must_use]
+pub fn print(env: &Env) -> String {
+    let mut entries = env.command_paths.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|(name, _)| *name);
+
+    let mut output = String::new();
+    for (_, path) in entries {
+        writeln!(output, "{}", path.display()).unwrap();
+    }
+    output
+}
+
+impl Command {
+    /// Executes the built-in except for printing the output.
+    ///
+    /// Returns the output to be printed and the errors that occurred.
+    pub fn execute(self, env: &mut Env) -> (String, Vec<NotFound>) {
+        match self {
+            Command::Print => (print(env), vec![]),
+            Command::Remember { clear, names } => {
+                if clear {
+                    env.command_paths.clear();
+                }
+                let errors = names
+                    .into_iter()
+                    .filter(|name| !name.value.contains('/'))
+                    .filter(|name| search_path(env, &name.value).is_none())
+                    .map(NotFound)
+                    .collect();
+                (String::new(), errors)
+            }
+        }
+    }
+}
+
+/// Entry point of the `hash` built-in
+pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
+    match parse(env, args) {
+        Ok(command) => {
+            let (content, errors) = command.execute(env);
+            let mut result = output(env, &content).await;
+            if let Some(message) = to_single_message(&errors) {
+                result = result.max(report_failure(env, message).await);
+            }
+            result
+        }
+        Err(error) => report_error(env, &error).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::path::PathBuf;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::{Inode, Mode, SystemState, VirtualSystem};
+    use yash_env::variable::{Scope, PATH};
+    use yash_env_test_helper::assert_stdout;
+
+    fn env_with_executables(paths: &[&str]) -> (Env, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        for path in paths {
+            let mut content = Inode::default();
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state.borrow_mut().file_system.save(path, content).unwrap();
+        }
+        let mut env = Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/usr/bin:/bin", None)
+            .unwrap();
+        (env, state)
+    }
+
+    #[test]
+    fn parse_no_arguments() {
+        let env = Env::new_virtual();
+        assert_eq!(parse(&env, vec![]), Ok(Command::Print));
+    }
+
+    #[test]
+    fn parse_remove_option() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-r"])),
+            Ok(Command::Remember {
+                clear: true,
+                names: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_operands() {
+        let env = Env::new_virtual();
+        let names = Field::dummies(["foo", "bar"]);
+        assert_eq!(
+            parse(&env, names.clone()),
+            Ok(Command::Remember {
+                clear: false,
+                names
+            })
+        );
+    }
+
+    #[test]
+    fn remembering_found_utility() {
+        let (mut env, _) = env_with_executables(&["/bin/foo"]);
+
+        let result = main(&mut env, Field::dummies(["foo"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_eq!(
+            env.command_paths.get("foo"),
+            Some(&PathBuf::from("/bin/foo"))
+        );
+    }
+
+    #[test]
+    fn remembering_missing_utility() {
+        let (mut env, _) = env_with_executables(&["/bin/foo"]);
+
+        let result = main(&mut env, Field::dummies(["bar", "foo"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::FAILURE));
+        assert_eq!(env.command_paths.len(), 1);
+        assert!(env.command_paths.contains_key("foo"));
+    }
+
+    #[test]
+    fn command_search_populates_paths() {
+        let (mut env, _) = env_with_executables(&["/usr/bin/foo", "/bin/foo"]);
+
+        yash_semantics::command_search::search(&mut env, "foo");
+        assert_eq!(
+            env.command_paths.get("foo"),
+            Some(&PathBuf::from("/usr/bin/foo"))
+        );
+    }
+
+    #[test]
+    fn printing_remembered_paths() {
+        let (mut env, state) = env_with_executables(&[]);
+        env.command_paths.insert("foo".into(), "/bin/foo".into());
+        env.command_paths
+            .insert("bar".into(), "/usr/bin/bar".into());
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "/usr/bin/bar\n/bin/foo\n");
+        });
+    }
+
+    #[test]
+    fn removing_remembered_paths() {
+        let (mut env, _) = env_with_executables(&["/bin/foo"]);
+        yash_semantics::command_search::search(&mut env, "foo");
+        assert!(!env.command_paths.is_empty());
+
+        let result = main(&mut env, Field::dummies(["-r"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert!(env.command_paths.is_empty());
+    }
+
+    #[test]
+    fn operands_are_remembered_after_removing() {
+        let (mut env, _) = env_with_executables(&["/bin/foo"]);
+        env.command_paths.insert("bar".into(), "/bin/bar".into());
+
+        let result = main(&mut env, Field::dummies(["-r", "foo"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_eq!(env.command_paths.len(), 1);
+        assert!(env.command_paths.contains_key("foo"));
+    }
+}
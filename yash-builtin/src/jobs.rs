@@ -46,7 +46,16 @@
 //!
 //! ## Filtering
 //!
-//! TODO `-n`, `-r`, `-s`, `-t`
+//! The **`-n`** (**`--new`**) option limits the output to jobs whose state has
+//! changed since the last report, either by this built-in or by the automatic
+//! report before a prompt. Other jobs are neither printed nor removed.
+//!
+//! The **`-r`** (**`--running-only`**) option limits the output to running
+//! jobs, and the **`-s`** (**`--stopped-only`**) option to stopped jobs. If
+//! both are specified, jobs that are either running or stopped are printed.
+//! With either option, finished jobs are neither printed nor removed.
+//!
+//! TODO `-t`
 //!
 //! # Operands
 //!
@@ -94,6 +103,8 @@ use yash_env::job::fmt::Accumulator;
 use yash_env::job::id::parse;
 use yash_env::job::id::parse_tail;
 use yash_env::job::id::FindError;
+use yash_env::job::Job;
+use yash_env::job::ProcessState;
 use yash_env::semantics::Field;
 use yash_env::Env;
 use yash_syntax::source::pretty::Annotation;
@@ -104,9 +115,34 @@ use yash_syntax::source::pretty::Message;
 
 const OPTIONS: &[OptionSpec] = &[
     OptionSpec::new().short('l').long("verbose"),
+    OptionSpec::new().short('n').long("new"),
     OptionSpec::new().short('p').long("pgid-only"),
+    OptionSpec::new().short('r').long("running-only"),
+    OptionSpec::new().short('s').long("stopped-only"),
 ];
 
+/// Conditions for selecting jobs to report
+#[derive(Clone, Copy, Debug, Default)]
+struct Filter {
+    new_only: bool,
+    running_only: bool,
+    stopped_only: bool,
+}
+
+impl Filter {
+    /// Tests if the job should be reported.
+    fn accepts(&self, job: &Job) -> bool {
+        if self.new_only && !job.state_changed {
+            return false;
+        }
+        if !self.running_only && !self.stopped_only {
+            return true;
+        }
+        (self.running_only && job.state == ProcessState::Running)
+            || (self.stopped_only && job.state.is_stopped())
+    }
+}
+
 fn find_error_message(error: FindError, operand: &Field) -> Message {
     Message {
         r#type: AnnotationType::Error,
@@ -136,11 +172,16 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         indices_reported: Vec::new(),
     };
 
+    let mut filter = Filter::default();
+
     // Apply options
     for option in options {
         match option.spec.get_short() {
             Some('l') => accumulator.show_pid = true,
+            Some('n') => filter.new_only = true,
             Some('p') => accumulator.pgid_only = true,
+            Some('r') => filter.running_only = true,
+            Some('s') => filter.stopped_only = true,
             _ => unreachable!("unhandled option: {:?}", option),
         }
     }
@@ -148,14 +189,21 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
     if operands.is_empty() {
         // Report all jobs
         for (index, job) in &env.jobs {
-            accumulator.add(index, job, &env.system)
+            if filter.accepts(job) {
+                accumulator.add(index, job, &env.system)
+            }
         }
     } else {
         // Report jobs specified by the operands
         for operand in operands {
             let job_id = parse(&operand.value).unwrap_or_else(|_| parse_tail(&operand.value));
             match job_id.find(&env.jobs) {
-                Ok(index) => accumulator.add(index, &env.jobs[index], &env.system),
+                Ok(index) => {
+                    let job = &env.jobs[index];
+                    if filter.accepts(job) {
+                        accumulator.add(index, job, &env.system)
+                    }
+                }
                 Err(error) => {
                     return report_failure(env, find_error_message(error, &operand)).await
                 }
@@ -186,10 +234,8 @@ mod tests {
     use futures_util::future::FutureExt;
     use std::rc::Rc;
     use yash_env::io::Fd;
-    use yash_env::job::Job;
     use yash_env::job::Pid;
     use yash_env::job::ProcessResult;
-    use yash_env::job::ProcessState;
     use yash_env::semantics::ExitStatus;
     use yash_env::stack::Builtin;
     use yash_env::stack::Frame;
@@ -513,6 +559,171 @@ mod tests {
         });
     }
 
+    #[test]
+    fn n_option_shows_only_changed_jobs() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let mut job = Job::new(Pid(42));
+        job.name = "echo first".to_string();
+        job.state_changed = false;
+        env.jobs.add(job);
+        let mut job = Job::new(Pid(72));
+        job.state = ProcessState::stopped(SIGSTOP);
+        job.name = "echo second".to_string();
+        env.jobs.add(job);
+        let mut job = Job::new(Pid(102));
+        job.state = ProcessState::exited(0);
+        job.name = "echo third".to_string();
+        job.state_changed = false;
+        let i102 = env.jobs.add(job);
+
+        let args = Field::dummies(["-n"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[2] + Stopped(SIGSTOP)     echo second\n")
+        });
+        // The unreported finished job is not removed.
+        assert_eq!(env.jobs[i102].name, "echo third");
+    }
+
+    #[test]
+    fn n_option_with_operands() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let mut job = Job::new(Pid(42));
+        job.name = "echo first".to_string();
+        env.jobs.add(job);
+        let mut job = Job::new(Pid(72));
+        job.state = ProcessState::stopped(SIGSTOP);
+        job.name = "echo second".to_string();
+        job.state_changed = false;
+        env.jobs.add(job);
+
+        let args = Field::dummies(["-n", "%?sec", "%?fir"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[1] - Running              echo first\n")
+        });
+    }
+
+    #[test]
+    fn n_option_shows_nothing_after_report() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let mut job = Job::new(Pid(42));
+        job.name = "echo first".to_string();
+        env.jobs.add(job);
+
+        let args = Field::dummies(["-n"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        let args = Field::dummies(["-n"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[1] + Running              echo first\n")
+        });
+    }
+
+    fn add_jobs_in_mixed_states(env: &mut Env) {
+        let mut job = Job::new(Pid(42));
+        job.name = "echo running".to_string();
+        env.jobs.add(job);
+        let mut job = Job::new(Pid(72));
+        job.state = ProcessState::stopped(SIGSTOP);
+        job.name = "echo stopped".to_string();
+        env.jobs.add(job);
+        let mut job = Job::new(Pid(102));
+        job.state = ProcessState::exited(3);
+        job.name = "echo exited".to_string();
+        env.jobs.add(job);
+    }
+
+    #[test]
+    fn r_option() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        add_jobs_in_mixed_states(&mut env);
+
+        let args = Field::dummies(["-r"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[1] - Running              echo running\n")
+        });
+        // The finished job is not reported and therefore not removed.
+        assert_eq!(env.jobs.len(), 3);
+    }
+
+    #[test]
+    fn s_option() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        add_jobs_in_mixed_states(&mut env);
+
+        let args = Field::dummies(["-s"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[2] + Stopped(SIGSTOP)     echo stopped\n")
+        });
+        assert_eq!(env.jobs.len(), 3);
+    }
+
+    #[test]
+    fn r_and_s_options() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        add_jobs_in_mixed_states(&mut env);
+
+        let args = Field::dummies(["-rs"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(
+                stdout,
+                "[1] - Running              echo running\n[2] + Stopped(SIGSTOP)     echo stopped\n"
+            )
+        });
+        assert_eq!(env.jobs.len(), 3);
+    }
+
+    #[test]
+    fn s_option_with_l_and_operands() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        add_jobs_in_mixed_states(&mut env);
+
+        let args = Field::dummies(["-ls", "%1", "%2", "%3"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[2] +    72 Stopped(SIGSTOP)     echo stopped\n")
+        });
+    }
+
+    #[test]
+    fn r_option_with_p_option() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        add_jobs_in_mixed_states(&mut env);
+
+        let args = Field::dummies(["-pr"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "42\n"));
+    }
+
     #[test]
     fn p_option() {
         let system = Box::new(VirtualSystem::new());
@@ -49,8 +49,13 @@
 //! directly after the hyphen like `-TERM` and `-15` instead of `-s TERM` and
 //! `-n 15`.
 //!
-//! The **`-l`** option lists signal names. The names are printed one per line,
-//! without the `SIG` prefix.
+//! The **`-l`** option lists signal names without the `SIG` prefix. Without
+//! operands, all signal names are arranged in columns, filled from top to
+//! bottom, to fit in the width given by the `COLUMNS` variable (80 if the
+//! variable is not set to a positive integer). If operands are given, each
+//! operand is translated and printed on its own line: a signal number or exit
+//! status is printed as the signal name, and a signal name is printed as the
+//! signal number.
 //!
 //! The **`-v`** option lists signal descriptions. This works like the `-l`
 //! option, but prints the signal number, name, and description instead of
@@ -137,7 +142,8 @@
 //!
 //! Using the `-l` option with more than one operand is a non-standard
 //! extension. Specifying a signal name operand to the `-l` option is a
-//! non-standard extension.
+//! non-standard extension. Printing the signal number for a signal name
+//! operand is compatible with bash and ksh.
 //!
 //! The `-v` option is a non-standard extension.
 //!
@@ -31,6 +31,7 @@ use yash_env::semantics::Field;
 use yash_env::signal::{Name, Number};
 use yash_env::system::System;
 use yash_env::system::SystemEx;
+use yash_env::variable::COLUMNS;
 use yash_env::Env;
 use yash_syntax::source::pretty::{Annotation, AnnotationType, MessageBase};
 
@@ -71,6 +72,40 @@ fn write_one_signal(name: Name, number: Number, verbose: bool, output: &mut Stri
     }
 }
 
+/// Default width of the output used when the `COLUMNS` variable is not set
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Number of spaces between columns in the list of all signals
+const COLUMN_GAP: usize = 2;
+
+/// Writes the names into the output string in columns.
+///
+/// The names are arranged in as many columns as fit in `width`, filling the
+/// columns from top to bottom. At least one column is used even if the longest
+/// name is wider than `width`.
+fn write_in_columns(names: &[String], width: usize, output: &mut String) {
+    let Some(name_width) = names.iter().map(|name| name.chars().count()).max() else {
+        return;
+    };
+    let column_width = name_width + COLUMN_GAP;
+    let columns = ((width + COLUMN_GAP) / column_width).max(1);
+    let rows = names.len().div_ceil(columns);
+
+    for row in 0..rows {
+        let mut index = row;
+        loop {
+            let name = &names[index];
+            index += rows;
+            if index < names.len() {
+                write!(output, "{name:column_width$}").unwrap();
+            } else {
+                writeln!(output, "{name}").unwrap();
+                break;
+            }
+        }
+    }
+}
+
 /// Error indicating that a signal is not recognized.
 ///
 /// This error may be returned from [`print`](print()).
@@ -99,17 +134,30 @@ impl MessageBase for InvalidSignal<'_> {
 
 /// Lists the specified signals into a string.
 ///
-/// If `signals` is empty, all signals are listed.
+/// If `signals` is empty, all signals are listed. In the non-verbose mode, the
+/// signal names are arranged in columns that fit in `width`.
 /// If `signals` contains invalid signals, the function returns an error.
+///
+/// In the non-verbose mode, a signal specified by number (or exit status) is
+/// printed as its name, and a signal specified by name is printed as its
+/// number. This allows the caller to translate between signal names and
+/// numbers.
 pub fn print<'a, S: SystemEx>(
     system: &S,
     signals: &'a [(Signal, Field)],
     verbose: bool,
+    width: usize,
 ) -> Result<String, Vec<InvalidSignal<'a>>> {
     let mut output = String::new();
     let mut errors = Vec::new();
 
-    if signals.is_empty() {
+    if signals.is_empty() && !verbose {
+        // Print all signal names in columns
+        let names = all_signals(system)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        write_in_columns(&names, width, &mut output);
+    } else if signals.is_empty() {
         // Print all signals
         for (name, number) in all_signals(system) {
             write_one_signal(name, number, verbose, &mut output);
@@ -121,7 +169,10 @@ pub fn print<'a, S: SystemEx>(
                 errors.push(InvalidSignal { signal, origin });
                 continue;
             };
-            write_one_signal(name, number, verbose, &mut output);
+            match signal {
+                Signal::Name(_) if !verbose => writeln!(output, "{number}").unwrap(),
+                _ => write_one_signal(name, number, verbose, &mut output),
+            }
         }
     }
 
@@ -133,8 +184,17 @@ pub fn print<'a, S: SystemEx>(
 }
 
 /// Executes the `Print` command.
+///
+/// The width of the output is taken from the `COLUMNS` variable if it is set
+/// to a positive integer. Otherwise, [`DEFAULT_WIDTH`] is used.
 pub async fn execute(env: &mut Env, signals: &[(Signal, Field)], verbose: bool) -> crate::Result {
-    match print(&env.system, signals, verbose) {
+    let width = env
+        .variables
+        .get_scalar(COLUMNS)
+        .and_then(|value| value.parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_WIDTH);
+    match print(&env.system, signals, verbose, width) {
         Ok(output) => crate::common::output(env, &output).await,
         Err(errors) => report_failure(env, to_single_message(&errors).unwrap()).await,
     }
@@ -152,8 +212,8 @@ mod tests {
         let system = &VirtualSystem::new();
         let signals = &[(Signal::Name(Name::Int), Field::dummy("INT"))];
 
-        let result = print(system, signals, false).unwrap();
-        assert_eq!(result, "INT\n");
+        let result = print(system, signals, false, DEFAULT_WIDTH).unwrap();
+        assert_eq!(result, "2\n");
     }
 
     #[test]
@@ -167,8 +227,35 @@ mod tests {
             ),
         ];
 
-        let result = print(system, signals, false).unwrap();
-        assert_eq!(result, "TERM\nKILL\n");
+        let result = print(system, signals, false, DEFAULT_WIDTH).unwrap();
+        assert_eq!(result, "15\nKILL\n");
+    }
+
+    #[test]
+    fn print_number_to_name() {
+        let system = &VirtualSystem::new();
+        let signals = &[(Signal::Number(9), Field::dummy("9"))];
+
+        let result = print(system, signals, false, DEFAULT_WIDTH).unwrap();
+        assert_eq!(result, "KILL\n");
+    }
+
+    #[test]
+    fn print_name_to_number() {
+        let system = &VirtualSystem::new();
+        let signals = &[(Signal::Name(Name::Kill), Field::dummy("KILL"))];
+
+        let result = print(system, signals, false, DEFAULT_WIDTH).unwrap();
+        assert_eq!(result, "9\n");
+    }
+
+    #[test]
+    fn print_name_verbose() {
+        let system = &VirtualSystem::new();
+        let signals = &[(Signal::Name(Name::Kill), Field::dummy("KILL"))];
+
+        let result = print(system, signals, true, DEFAULT_WIDTH).unwrap();
+        assert_eq!(result, "9\tKILL\n");
     }
 
     #[test]
@@ -176,7 +263,7 @@ mod tests {
         let system = &VirtualSystem::new();
         let signals = &[(Signal::Name(Name::Int), Field::dummy("INT"))];
 
-        let result = print(system, signals, true).unwrap();
+        let result = print(system, signals, true, DEFAULT_WIDTH).unwrap();
         assert_eq!(result, "2\tINT\n");
     }
 
@@ -189,7 +276,7 @@ mod tests {
             (Signal::Name(Name::Rtmin(-1)), Field::dummy("RTMIN-1")),
         ];
 
-        let errors = print(system, signals, false).unwrap_err();
+        let errors = print(system, signals, false, DEFAULT_WIDTH).unwrap_err();
         assert_eq!(
             errors,
             [
@@ -208,14 +295,49 @@ mod tests {
     #[test]
     fn print_all_non_verbose() {
         let system = &VirtualSystem::new();
-        let result = print(system, &[], false).unwrap();
+        let result = print(system, &[], false, DEFAULT_WIDTH).unwrap();
         assert_eq!(
             result,
-            "ABRT\nALRM\nBUS\nCHLD\nCLD\nCONT\nEMT\nFPE\nHUP\nILL\nINFO\nINT\n\
-            IO\nIOT\nKILL\nLOST\nPIPE\nPOLL\nPROF\nPWR\nQUIT\nSEGV\nSTKFLT\n\
-            STOP\nSYS\nTERM\nTHR\nTRAP\nTSTP\nTTIN\nTTOU\nURG\nUSR1\nUSR2\n\
-            VTALRM\nWINCH\nXCPU\nXFSZ\nRTMIN\nRTMIN+1\nRTMIN+2\nRTMIN+3\n\
-            RTMIN+4\nRTMAX-3\nRTMAX-2\nRTMAX-1\nRTMAX\n"
+            "ABRT     EMT      IO       PROF     SYS      TTOU     XCPU     RTMIN+4\n\
+             ALRM     FPE      IOT      PWR      TERM     URG      XFSZ     RTMAX-3\n\
+             BUS      HUP      KILL     QUIT     THR      USR1     RTMIN    RTMAX-2\n\
+             CHLD     ILL      LOST     SEGV     TRAP     USR2     RTMIN+1  RTMAX-1\n\
+             CLD      INFO     PIPE     STKFLT   TSTP     VTALRM   RTMIN+2  RTMAX\n\
+             CONT     INT      POLL     STOP     TTIN     WINCH    RTMIN+3\n"
         );
     }
+
+    #[test]
+    fn print_all_non_verbose_in_narrow_width() {
+        let system = &VirtualSystem::new();
+        let result = print(system, &[], false, 30).unwrap();
+        let lines = result.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 16, "{result}");
+        assert_eq!(lines[0], "ABRT     PIPE     USR1");
+        assert_eq!(lines[15], "LOST     URG");
+        assert!(lines.iter().all(|line| line.len() <= 30), "{result}");
+    }
+
+    #[test]
+    fn columns_fill_from_top_to_bottom() {
+        let names = ["a", "bb", "c", "d", "e"].map(String::from);
+        let mut output = String::new();
+        write_in_columns(&names, 8, &mut output);
+        assert_eq!(output, "a   d\nbb  e\nc\n");
+    }
+
+    #[test]
+    fn at_least_one_column_is_used() {
+        let names = ["long", "longer"].map(String::from);
+        let mut output = String::new();
+        write_in_columns(&names, 3, &mut output);
+        assert_eq!(output, "long\nlonger\n");
+    }
+
+    #[test]
+    fn print_all_verbose() {
+        let system = &VirtualSystem::new();
+        let result = print(system, &[], true, 10).unwrap();
+        assert!(result.starts_with("6\tABRT\n14\tALRM\n"), "{result}");
+    }
 }
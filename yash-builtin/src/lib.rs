@@ -43,6 +43,8 @@
 //! - `command`
 //! - `eval`
 //! - `exec`
+//! - `fc`
+//! - `hash`
 //! - `read`
 //! - `source`
 //! - `type`
@@ -69,10 +71,15 @@ pub mod exec;
 pub mod exit;
 pub mod export;
 pub mod r#false;
+#[cfg(feature = "yash-semantics")]
+pub mod fc;
 pub mod fg;
 pub mod getopts;
+#[cfg(feature = "yash-semantics")]
+pub mod hash;
 pub mod jobs;
 pub mod kill;
+pub mod local;
 pub mod pwd;
 #[cfg(feature = "yash-semantics")]
 pub mod read;
@@ -167,6 +174,11 @@ pub const BUILTINS: &[(&str, Builtin)] = &[
         "false",
         Builtin::new(Mandatory, |env, args| Box::pin(r#false::main(env, args))),
     ),
+    #[cfg(feature = "yash-semantics")]
+    (
+        "fc",
+        Builtin::new(Mandatory, |env, args| Box::pin(fc::main(env, args))),
+    ),
     (
         "fg",
         Builtin::new(Mandatory, |env, args| Box::pin(fg::main(env, args))),
@@ -175,6 +187,11 @@ pub const BUILTINS: &[(&str, Builtin)] = &[
         "getopts",
         Builtin::new(Mandatory, |env, args| Box::pin(getopts::main(env, args))),
     ),
+    #[cfg(feature = "yash-semantics")]
+    (
+        "hash",
+        Builtin::new(Mandatory, |env, args| Box::pin(hash::main(env, args))),
+    ),
     (
         "jobs",
         Builtin::new(Mandatory, |env, args| Box::pin(jobs::main(env, args))),
@@ -183,6 +200,11 @@ pub const BUILTINS: &[(&str, Builtin)] = &[
         "kill",
         Builtin::new(Mandatory, |env, args| Box::pin(kill::main(env, args))),
     ),
+    ("local", {
+        let mut builtin = Builtin::new(Elective, |env, args| Box::pin(local::main(env, args)));
+        builtin.is_declaration_utility = Some(true);
+        builtin
+    }),
     (
         "pwd",
         Builtin::new(Mandatory, |env, args| Box::pin(pwd::main(env, args))),
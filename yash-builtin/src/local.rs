@@ -0,0 +1,227 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Local built-in
+//!
+//! The **`local`** built-in defines variables local to the current function.
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! local [-rx] [name[=value]…]
+//! ```
+//!
+//! ```sh
+//! local -p [-rx] [name…]
+//! ```
+//!
+//! # Description
+//!
+//! The local built-in (without the `-p` option) defines variables of the
+//! specified names in the context of the function being executed, with
+//! optional values. The local variables hide variables of the same names
+//! defined outside the function until the function returns, when the local
+//! variables are removed.
+//!
+//! If no names are given, or if the `-p` option is given, the names and values
+//! of the variables defined in the current function are displayed. If the `-p`
+//! option is given with operands, only the specified variables are displayed.
+//!
+//! # Options
+//!
+//! The **`-p`** (**`--print`**) option causes the shell to display the names
+//! and values of the local variables in a format that can be reused as input
+//! to restore the state of these variables.
+//!
+//! The **`-r`** (**`--readonly`**) option makes the variables read-only.
+//!
+//! The **`-x`** (**`--export`**) option exports the variables to the
+//! environment.
+//!
+//! When printing variables, the `-r` and `-x` options limit the output to
+//! read-only and exported variables, respectively.
+//!
+//! # Operands
+//!
+//! The operands are the names of shell variables to be defined or printed.
+//! When defining, each name may optionally be followed by `=` and a *value* to
+//! assign to the variable.
+//!
+//! # Standard output
+//!
+//! When defining variables, the local built-in does not produce any output.
+//!
+//! When printing variables, the built-in prints simple commands that invoke the
+//! local built-in to redefine the variables with the same values and
+//! attributes.
+//!
+//! # Errors
+//!
+//! It is an error to use this built-in outside a function.
+//!
+//! When defining a variable with a value, it is an error if the variable is
+//! read-only in the current function.
+//!
+//! When printing variables, it is an error if an operand names a non-existing
+//! variable.
+//!
+//! # Exit status
+//!
+//! Zero unless an error occurs.
+//!
+//! # Portability
+//!
+//! This built-in is not defined in the POSIX standard, but many shells
+//! implement it with slightly different behaviors.
+//!
+//! # Implementation notes
+//!
+//! The implementation of this built-in depends on that of the
+//! [`typeset`](crate::typeset) built-in. The local built-in basically works
+//! like the typeset built-in without the `-g` (`--global`) option, except that:
+//! - The built-in fails if there is no function being executed.
+//! - Printed commands name the local built-in instead of the typeset built-in.
+//! - Functions cannot be defined or printed.
+
+use crate::common::output;
+use crate::common::report_error;
+use crate::common::report_failure;
+use crate::common::report_simple_failure;
+use crate::common::to_single_message;
+use crate::typeset::syntax::interpret;
+use crate::typeset::syntax::parse;
+use crate::typeset::syntax::OptionSpec;
+use crate::typeset::syntax::EXPORT_OPTION;
+use crate::typeset::syntax::PRINT_OPTION;
+use crate::typeset::syntax::READONLY_OPTION;
+use crate::typeset::Command;
+use crate::typeset::PrintContext;
+use crate::typeset::Scope::Local;
+use yash_env::semantics::Field;
+use yash_env::Env;
+
+/// List of options applicable to the local built-in
+pub const OPTIONS: &[OptionSpec<'static>] = &[PRINT_OPTION, READONLY_OPTION, EXPORT_OPTION];
+
+/// Printing context for the local built-in
+pub const PRINT_CONTEXT: PrintContext<'static> = PrintContext {
+    builtin_name: "local",
+    builtin_is_significant: true,
+    options_allowed: &[READONLY_OPTION, EXPORT_OPTION],
+};
+
+/// Entry point of the local built-in
+pub async fn main(env: &mut Env, args: Vec<Field>) -> yash_env::builtin::Result {
+    if !env.variables.has_local_context() {
+        return report_simple_failure(env, "cannot define local variables outside a function")
+            .await;
+    }
+
+    match parse(OPTIONS, args) {
+        Ok((options, operands)) => match interpret(options, operands) {
+            Ok(mut command) => {
+                match &mut command {
+                    Command::SetVariables(sv) => sv.scope = Local,
+                    Command::PrintVariables(pv) => pv.scope = Local,
+                    Command::SetFunctions(sf) => unreachable!("{sf:?}"),
+                    Command::PrintFunctions(pf) => unreachable!("{pf:?}"),
+                }
+                match command.execute(env, &PRINT_CONTEXT) {
+                    Ok(result) => output(env, &result).await,
+                    Err(errors) => report_failure(env, to_single_message(&errors).unwrap()).await,
+                }
+            }
+            Err(error) => report_error(env, &error).await,
+        },
+        Err(error) => report_error(env, &error).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::stack::Builtin;
+    use yash_env::stack::Frame;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::variable::Context;
+    use yash_env::variable::Scope::Global;
+    use yash_env::variable::Value;
+    use yash_env_test_helper::assert_stderr;
+
+    #[test]
+    fn local_variable_hides_global_one_until_context_is_popped() {
+        let mut outer = Env::new_virtual();
+        let mut var = outer.variables.get_or_new("foo", Global);
+        var.assign("global", None).unwrap();
+
+        let mut inner = outer.push_context(Context::default());
+        let result = main(&mut inner, Field::dummies(["foo=local"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, yash_env::builtin::Result::new(ExitStatus::SUCCESS));
+        assert_eq!(
+            inner.variables.get("foo").unwrap().value,
+            Some(Value::scalar("local")),
+        );
+        Env::pop_context(inner);
+
+        assert_eq!(
+            outer.variables.get("foo").unwrap().value,
+            Some(Value::scalar("global")),
+        );
+    }
+
+    #[test]
+    fn readonly_and_export_options() {
+        let mut outer = Env::new_virtual();
+        let mut inner = outer.push_context(Context::default());
+
+        let result = main(&mut inner, Field::dummies(["-rx", "foo=bar"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, yash_env::builtin::Result::new(ExitStatus::SUCCESS));
+        let var = inner.variables.get("foo").unwrap();
+        assert_eq!(var.value, Some(Value::scalar("bar")));
+        assert!(var.is_exported);
+        assert!(var.is_read_only());
+        Env::pop_context(inner);
+
+        assert_eq!(outer.variables.get("foo"), None);
+    }
+
+    #[test]
+    fn error_outside_function() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("local"),
+            is_special: false,
+        }));
+
+        let result = main(&mut env, Field::dummies(["foo=bar"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, yash_env::builtin::Result::new(ExitStatus::FAILURE));
+        assert_eq!(env.variables.get("foo"), None);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("outside a function"), "{stderr}")
+        });
+    }
+}
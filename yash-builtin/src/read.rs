@@ -21,41 +21,62 @@
 //! # Synopsis
 //!
 //! ```sh
-//! read [-r] variable…
+//! read [-r] [-d delimiter] [-p prompt] [-t timeout] variable…
 //! ```
 //!
 //! # Description
 //!
 //! The read built-in reads a line from the standard input and assigns it to the
-//! variables named by the operands. Field splitting is performed on the line
-//! read to produce as many fields as there are variables. If there are fewer
-//! fields than variables, the remaining variables are set to empty strings. If
-//! there are more fields than variables, the last variable receives all
-//! remaining fields, including the field separators, but not trailing
-//! whitespace separators.
+//! variables named by the operands. A line is terminated by a newline unless
+//! another delimiter is specified by the `-d` option. Field splitting is
+//! performed on the line read to produce as many fields as there are
+//! variables. If there are fewer fields than variables, the remaining
+//! variables are set to empty strings. If there are more fields than
+//! variables, the last variable receives all remaining fields, including the
+//! field separators, but not trailing whitespace separators.
 //!
 //! ## Escaping
 //!
 //! By default, backslashes in the input are treated as quoting characters that
 //! prevent the following character from being interpreted as a field separator.
-//! Backslash-newline pairs are treated as line continuations.
+//! Backslash-newline pairs are treated as line continuations. A backslash at
+//! the end of the input is removed.
 //!
-//! The `-r` option disables this behavior.
+//! The `-r` option disables this behavior, so backslashes are treated as
+//! ordinary characters.
 //!
 //! ## Prompting
 //!
 //! By default, the read built-in does not display a prompt before reading a
-//! line. (TODO: Options to display a prompt)
+//! line. If the `-p` option is specified and the input is from a terminal, the
+//! built-in writes the option argument to the standard error before reading
+//! the first line.
 //!
 //! When reading lines after the first line, the read built-in displays the
 //! value of the `PS2` variable as a prompt if the shell is interactive and the
-//! input is from a terminal.
+//! input is from a terminal. This requires the optional `yash-prompt` feature.
 //!
-//! Prompting requires the optional `yash-prompt` feature.
+//! ## Timeout
+//!
+//! If the `-t` option is specified, the built-in stops reading when the
+//! specified number of seconds has elapsed. The characters read until then
+//! are assigned to the variables as if the line ended there.
 //!
 //! # Options
 //!
-//! The **`-r`** option disables the interpretation of backslashes.
+//! The **`-d`** (**`--delimiter`**) option takes an argument and makes the
+//! built-in read until the first character of the argument instead of a
+//! newline. If the argument is empty, the null character is the delimiter.
+//!
+//! The **`-p`** (**`--prompt`**) option takes an argument and makes the
+//! built-in print it as a prompt before reading a line from a terminal.
+//!
+//! The **`-r`** (**`--raw-mode`**) option disables the interpretation of
+//! backslashes.
+//!
+//! The **`-t`** (**`--timeout`**) option takes a non-negative number of
+//! seconds, which may have a fractional part, as an argument and specifies the
+//! timeout for reading.
 //!
 //! # Operands
 //!
@@ -66,19 +87,27 @@
 //!
 //! It is an error if the standard input is not readable.
 //!
+//! It is an error if the argument to the `-t` option is not a valid number.
+//!
 //! It is an error if any variable to be assigned is read-only.
 //!
 //! # Exit status
 //!
 //! The exit status is zero if a line was read successfully and non-zero
 //! otherwise. If the built-in reaches the end of the input before finding a
-//! newline, it returns non-zero, but the variables are still assigned with the
-//! line read so far.
+//! delimiter, it returns non-zero, but the variables are still assigned with
+//! the line read so far.
+//!
+//! If the timeout expires, the exit status is [`TIMEOUT_EXIT_STATUS`], which
+//! is greater than 128. The variables are assigned with the line read so far.
 //!
 //! # Portability
 //!
 //! The read built-in is defined in the POSIX standard. The `-r` option is the
-//! only option defined in the POSIX standard.
+//! only option defined in the POSIX standard. The `-d` option is also defined
+//! in POSIX.1-2024. The `-t` option is an extension that is also found in
+//! bash and ksh. The `-p` option is an extension that is also found in bash,
+//! but ksh uses the same option for a different purpose.
 //!
 //! In this implementation, the value of the `PS2` variable is subject to
 //! parameter expansion, command substitution, and arithmetic expansion. Other
@@ -92,6 +121,7 @@
 use crate::common::report_error;
 use crate::common::report_failure;
 use crate::common::to_single_message;
+use std::time::Duration;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::Env;
@@ -108,11 +138,22 @@ pub mod syntax;
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct Command {
+    /// Character that terminates the line
+    ///
+    /// This is a newline unless specified by the `-d` option.
+    pub delimiter: char,
+
     /// Whether the `-r` option is specified
     ///
     /// If this field is `true`, backslashes are not interpreted.
     pub is_raw: bool,
 
+    /// Prompt specified by the `-p` option
+    pub prompt: Option<String>,
+
+    /// Timeout specified by the `-t` option
+    pub timeout: Option<Duration>,
+
     /// Names of variables to be assigned, except the last one
     pub variables: Vec<Field>,
 
@@ -123,6 +164,12 @@ pub struct Command {
     pub last_variable: Field,
 }
 
+/// Exit status of the built-in when the timeout expires
+///
+/// The value is the same as that of bash, that is, 128 plus the conventional
+/// signal number of `SIGALRM`.
+pub const TIMEOUT_EXIT_STATUS: ExitStatus = ExitStatus(142);
+
 /// Entry point of the `read` built-in
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let command = match syntax::parse(env, args) {
@@ -130,7 +177,12 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         Err(error) => return report_error(env, &error).await,
     };
 
-    let (input, newline_found) = match input::read(env, command.is_raw).await {
+    if let Some(prompt) = &command.prompt {
+        prompt::print_prompt(env, prompt).await;
+    }
+
+    let result = input::read(env, command.is_raw, command.delimiter, command.timeout).await;
+    let (input, termination) = match result {
         Ok(input) => input,
         Err(error) => return report_failure(env, &error).await,
     };
@@ -138,8 +190,128 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let errors = assigning::assign(env, &input, command.variables, command.last_variable);
     let message = to_single_message(&errors);
     match message {
-        None if newline_found => ExitStatus::SUCCESS.into(),
-        None => ExitStatus::FAILURE.into(),
+        None => match termination {
+            input::Termination::Delimiter => ExitStatus::SUCCESS.into(),
+            input::Termination::EndOfInput => ExitStatus::FAILURE.into(),
+            input::Termination::Timeout => TIMEOUT_EXIT_STATUS.into(),
+        },
         Some(message) => report_failure(env, message).await,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::system::r#virtual::FileBody;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+
+    #[test]
+    fn prompt_printed_before_reading_from_terminal() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        {
+            let state = state.borrow();
+            let stdin = state.file_system.get("/dev/stdin").unwrap();
+            stdin.borrow_mut().body = FileBody::Terminal {
+                content: b"foo\n".to_vec(),
+            };
+        }
+        let mut env = Env::with_system(Box::new(system));
+        let args = Field::dummies(["-p", "name? ", "var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "name? "));
+        assert_eq!(env.variables.get_scalar("var"), Some("foo"));
+    }
+
+    #[test]
+    fn prompt_not_printed_for_non_terminal_input() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        {
+            let state = state.borrow();
+            let stdin = state.file_system.get("/dev/stdin").unwrap();
+            stdin.borrow_mut().body = FileBody::new(*b"foo\n");
+        }
+        let mut env = Env::with_system(Box::new(system));
+        let args = Field::dummies(["-p", "name? ", "var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+        assert_eq!(env.variables.get_scalar("var"), Some("foo"));
+    }
+
+    fn env_with_stdin(bytes: &str) -> Env {
+        let system = VirtualSystem::new();
+        {
+            let state = system.state.borrow();
+            let stdin = state.file_system.get("/dev/stdin").unwrap();
+            stdin.borrow_mut().body = FileBody::new(bytes);
+        }
+        Env::with_system(Box::new(system))
+    }
+
+    #[test]
+    fn line_continuation_without_raw_mode() {
+        let mut env = env_with_stdin("foo\\\nbar\n");
+        let args = Field::dummies(["var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.variables.get_scalar("var"), Some("foobar"));
+    }
+
+    #[test]
+    fn line_continuation_with_raw_mode() {
+        let mut env = env_with_stdin("foo\\\nbar\n");
+        let args = Field::dummies(["-r", "var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.variables.get_scalar("var"), Some("foo\\"));
+    }
+
+    #[test]
+    fn escaped_separator_without_raw_mode() {
+        let mut env = env_with_stdin("a\\ b\\\\ c\n");
+        let args = Field::dummies(["x", "y"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.variables.get_scalar("x"), Some("a b\\"));
+        assert_eq!(env.variables.get_scalar("y"), Some("c"));
+    }
+
+    #[test]
+    fn escaped_separator_with_raw_mode() {
+        let mut env = env_with_stdin("a\\ b\\\\ c\n");
+        let args = Field::dummies(["-r", "x", "y"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.variables.get_scalar("x"), Some("a\\"));
+        assert_eq!(env.variables.get_scalar("y"), Some("b\\\\ c"));
+    }
+
+    #[test]
+    fn trailing_backslash_at_end_of_input() {
+        let mut env = env_with_stdin("foo\\");
+        let args = Field::dummies(["var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert_eq!(env.variables.get_scalar("var"), Some("foo"));
+
+        let mut env = env_with_stdin("foo\\");
+        let args = Field::dummies(["-r", "var"]);
+
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert_eq!(env.variables.get_scalar("var"), Some("foo\\"));
+    }
+}
@@ -16,9 +16,16 @@
 
 //! Reading input
 
+use std::future::poll_fn;
+use std::future::Future as _;
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
 use thiserror::Error;
 use yash_env::system::Errno;
 use yash_env::Env;
+use yash_env::System as _;
 use yash_semantics::expansion::attr::AttrChar;
 use yash_semantics::expansion::attr::Origin;
 use yash_syntax::source::pretty::AnnotationType;
@@ -83,11 +90,23 @@ fn plain(value: char) -> AttrChar {
     }
 }
 
+/// How [`read`] finished reading the input
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Termination {
+    /// The delimiter was found.
+    Delimiter,
+    /// The end of the input was reached before finding the delimiter.
+    EndOfInput,
+    /// The timeout expired before finding the delimiter.
+    Timeout,
+}
+
 /// Reads a line from the standard input.
 ///
 /// This function reads a line from the standard input and returns a vector of
-/// [`AttrChar`]s representing the line. The line is terminated by a newline
-/// character, which is not included in the returned vector.
+/// [`AttrChar`]s representing the line. The line is terminated by the
+/// `delimiter` character, which is not included in the returned vector. The
+/// delimiter is a newline unless specified by the `-d` option.
 ///
 /// If `is_raw` is `true`, the read line is not subject to backslash processing.
 /// Otherwise, backslash-newline pairs are treated as line continuations, and
@@ -98,47 +117,68 @@ fn plain(value: char) -> AttrChar {
 /// is interactive and the input is from a terminal. This requires the optional
 /// `yash-prompt` feature.
 ///
+/// If `timeout` is `Some`, this function stops reading when the specified
+/// duration has elapsed since the function was called. The characters read
+/// until then are returned.
+///
 /// If successful, this function returns a vector of [`AttrChar`]s representing
-/// the line read and a boolean value indicating whether the line was terminated
-/// by a newline character.
-pub async fn read(env: &mut Env, is_raw: bool) -> Result<(Vec<AttrChar>, bool), Error> {
+/// the line read and a [`Termination`] value indicating how reading finished.
+pub async fn read(
+    env: &mut Env,
+    is_raw: bool,
+    delimiter: char,
+    timeout: Option<Duration>,
+) -> Result<(Vec<AttrChar>, Termination), Error> {
+    let deadline = timeout.map(|timeout| env.system.now() + timeout);
     let mut result = Vec::new();
 
-    let newline_found = loop {
+    let termination = loop {
         // TODO Read in bulk if the standard input is seekable
-        match read_char(env).await? {
-            None => break false,
-            Some('\n') => break true,
+        match read_char(env, deadline).await? {
+            ReadChar::EndOfInput => break Termination::EndOfInput,
+            ReadChar::Timeout => break Termination::Timeout,
+            ReadChar::Char(c) if c == delimiter => break Termination::Delimiter,
 
             // Backslash escape
-            Some('\\') if !is_raw => {
-                let c = read_char(env).await?;
-                if c == Some('\n') {
+            ReadChar::Char('\\') if !is_raw => {
+                let c = read_char(env, deadline).await?;
+                if c == ReadChar::Char('\n') {
                     // Line continuation
                     print_prompt(env).await;
                     continue;
                 }
                 result.push(quoting('\\'));
                 match c {
-                    None => break false,
-                    Some(c) => result.push(quoted(c)),
+                    ReadChar::EndOfInput => break Termination::EndOfInput,
+                    ReadChar::Timeout => break Termination::Timeout,
+                    ReadChar::Char(c) => result.push(quoted(c)),
                 }
             }
 
             // Plain character
-            Some(c) => result.push(plain(c)),
+            ReadChar::Char(c) => result.push(plain(c)),
         }
     };
 
-    Ok((result, newline_found))
+    Ok((result, termination))
+}
+
+/// Result of [`read_char`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ReadChar {
+    Char(char),
+    EndOfInput,
+    Timeout,
 }
 
 /// Reads one character from the standard input.
 ///
 /// This function reads a single UTF-8-encoded character from the standard
-/// input. If the standard input is empty, this function returns `Ok(None)`.
-/// If the input is not a valid UTF-8 sequence, this function returns an error.
-async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
+/// input. If the standard input is empty, this function returns
+/// `Ok(ReadChar::EndOfInput)`. If the deadline passes before a whole character is
+/// read, this function returns `Ok(ReadChar::Timeout)`. If the input is not a valid
+/// UTF-8 sequence, this function returns an error.
+async fn read_char(env: &mut Env, deadline: Option<Instant>) -> Result<ReadChar, Error> {
     // Any character is at most 4 bytes in UTF-8.
     let mut buffer = [0; 4];
     let mut len = 0;
@@ -146,11 +186,13 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
         // Read from the standard input byte by byte so that we don't consume
         // more than one character.
         let byte = std::slice::from_mut(&mut buffer[len]);
-        let count = env.system.read_async(Fd::STDIN, byte).await?;
+        let Some(count) = read_byte(env, byte, deadline).await? else {
+            return Ok(ReadChar::Timeout);
+        };
         if count == 0 {
             // End of input
             return if len == 0 {
-                Ok(None)
+                Ok(ReadChar::EndOfInput)
             } else {
                 // The input ended in the middle of a UTF-8 sequence.
                 Err(Errno::EILSEQ.into())
@@ -166,7 +208,7 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
                 let c = chars.next().unwrap();
                 // And it must be the only character.
                 debug_assert_eq!(chars.next(), None);
-                return Ok(Some(c));
+                return Ok(ReadChar::Char(c));
             }
             Err(e) => match e.error_len() {
                 None => {
@@ -180,6 +222,30 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
     }
 }
 
+/// Reads from the standard input until the deadline.
+///
+/// Returns `Ok(None)` if the deadline passes before any byte is available.
+async fn read_byte(
+    env: &mut Env,
+    buffer: &mut [u8],
+    deadline: Option<Instant>,
+) -> Result<Option<usize>, Errno> {
+    let system = env.system.clone();
+    let Some(deadline) = deadline else {
+        return system.read_async(Fd::STDIN, buffer).await.map(Some);
+    };
+
+    let mut read = pin!(system.read_async(Fd::STDIN, buffer));
+    let mut timeout = pin!(system.wait_until(deadline));
+    poll_fn(|context| {
+        if let Poll::Ready(result) = read.as_mut().poll(context) {
+            return Poll::Ready(result.map(Some));
+        }
+        timeout.as_mut().poll(context).map(|()| Ok(None))
+    })
+    .await
+}
+
 /// Prints the prompt string for the continuation line.
 ///
 /// This function prints the value of the `PS2` variable as a prompt for the
@@ -228,8 +294,8 @@ mod tests {
     #[test]
     fn empty_input() {
         in_virtual_system(|mut env, _| async move {
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((vec![], Termination::EndOfInput)));
         })
     }
 
@@ -238,14 +304,14 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\nbar\n");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("foo"), true)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Termination::Delimiter)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("bar"), true)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((attr_chars("bar"), Termination::Delimiter)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((vec![], Termination::EndOfInput)));
         })
     }
 
@@ -254,11 +320,11 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "newline");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("newline"), false)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((attr_chars("newline"), Termination::EndOfInput)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((vec![], Termination::EndOfInput)));
         })
     }
 
@@ -267,8 +333,8 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "©⁉😀\n");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("©⁉😀"), true)));
+            let result = read(&mut env, false, '\n', None).await;
+            assert_eq!(result, Ok((attr_chars("©⁉😀"), Termination::Delimiter)));
         })
     }
 
@@ -277,8 +343,8 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, true).await;
-            assert_eq!(result, Ok((attr_chars("\\foo\\"), true)));
+            let result = read(&mut env, true, '\n', None).await;
+            assert_eq!(result, Ok((attr_chars("\\foo\\"), Termination::Delimiter)));
         })
     }
 
@@ -287,7 +353,7 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, '\n', None).await;
             assert_eq!(
                 result,
                 Ok((
@@ -303,7 +369,7 @@ mod tests {
                         plain('a'),
                         plain('z'),
                     ],
-                    true,
+                    Termination::Delimiter,
                 )),
             );
         })
@@ -314,12 +380,12 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\\");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, '\n', None).await;
             assert_eq!(
                 result,
                 Ok((
                     vec![plain('f'), plain('o'), plain('o'), quoting('\\')],
-                    false,
+                    Termination::EndOfInput,
                 )),
             );
         })
@@ -330,24 +396,90 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xFF");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, '\n', None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF\xD0");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, '\n', None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, '\n', None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
     }
 
+    #[test]
+    fn custom_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\nbar:baz:");
+
+            let result = read(&mut env, false, ':', None).await;
+            assert_eq!(result, Ok((attr_chars("foo\nbar"), Termination::Delimiter)));
+
+            let result = read(&mut env, false, ':', None).await;
+            assert_eq!(result, Ok((attr_chars("baz"), Termination::Delimiter)));
+        })
+    }
+
+    #[test]
+    fn escaped_custom_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "a\\:b:");
+
+            let result = read(&mut env, false, ':', None).await;
+            assert_eq!(
+                result,
+                Ok((
+                    vec![plain('a'), quoting('\\'), quoted(':'), plain('b')],
+                    Termination::Delimiter,
+                )),
+            );
+        })
+    }
+
+    #[test]
+    fn nul_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\nbar\0baz");
+
+            let result = read(&mut env, true, '\0', None).await;
+            assert_eq!(result, Ok((attr_chars("foo\nbar"), Termination::Delimiter)));
+        })
+    }
+
+    #[test]
+    fn input_before_timeout() {
+        in_virtual_system(|mut env, system| async move {
+            system.borrow_mut().now = Some(Instant::now());
+            set_stdin(&system, "foo\n");
+
+            let result = read(&mut env, false, '\n', Some(Duration::from_secs(1))).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Termination::Delimiter)));
+        })
+    }
+
+    #[test]
+    fn timeout_with_partial_input() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            let (reader, writer) = env.system.pipe().unwrap();
+            env.system.dup2(reader, Fd::STDIN).unwrap();
+            env.system.write(writer, b"foo").unwrap();
+
+            let timeout = Duration::from_secs(3);
+            let result = read(&mut env, false, '\n', Some(timeout)).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Termination::Timeout)));
+            assert_eq!(system.borrow().now, Some(start + timeout));
+        })
+    }
+
     // TODO Test PS2 prompt
 }
@@ -13,3 +13,78 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Prompting for the `-p` option
+
+use yash_env::io::Fd;
+use yash_env::Env;
+use yash_env::System as _;
+
+/// Prints the prompt specified by the `-p` option.
+///
+/// The prompt is written to the standard error as is, without a trailing
+/// newline. Nothing is written if the standard input is not a terminal, so
+/// that the prompt does not clutter the output when the input is from a file
+/// or pipe.
+///
+/// This function does not open the controlling terminal with
+/// [`Env::get_tty`], so the prompt is printed even if the terminal is not
+/// available.
+pub async fn print_prompt(env: &mut Env, prompt: &str) {
+    if prompt.is_empty() || !env.system.isatty(Fd::STDIN) {
+        return;
+    }
+    env.system.print_error(prompt).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::system::r#virtual::FileBody;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+
+    #[test]
+    fn prompt_printed_if_stdin_is_terminal() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        {
+            let state = state.borrow();
+            let stdin = state.file_system.get("/dev/stdin").unwrap();
+            stdin.borrow_mut().body = FileBody::Terminal { content: vec![] };
+        }
+        let mut env = Env::with_system(Box::new(system));
+
+        print_prompt(&mut env, "> ").now_or_never().unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "> "));
+    }
+
+    #[test]
+    fn prompt_printed_without_controlling_terminal() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        {
+            let state = state.borrow();
+            assert!(state.file_system.get("/dev/tty").is_err());
+            let stdin = state.file_system.get("/dev/stdin").unwrap();
+            stdin.borrow_mut().body = FileBody::Terminal { content: vec![] };
+        }
+        let mut env = Env::with_system(Box::new(system));
+
+        print_prompt(&mut env, "> ").now_or_never().unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "> "));
+        assert_eq!(env.tty, None);
+    }
+
+    #[test]
+    fn prompt_not_printed_if_stdin_is_not_terminal() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        print_prompt(&mut env, "> ").now_or_never().unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+}
@@ -19,10 +19,13 @@
 use super::Command;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
+use crate::common::syntax::OptionArgumentSpec;
 use crate::common::syntax::OptionSpec;
+use std::time::Duration;
 use thiserror::Error;
 use yash_env::semantics::Field;
 use yash_env::Env;
+use yash_syntax::source::pretty::Annotation;
 use yash_syntax::source::pretty::AnnotationType;
 use yash_syntax::source::pretty::Message;
 
@@ -37,6 +40,10 @@ pub enum Error {
     /// No operand is given.
     #[error("missing operand")]
     MissingOperand,
+
+    /// The argument to the `-t` option is not a valid number of seconds.
+    #[error("invalid timeout")]
+    InvalidTimeout(Field),
 }
 
 impl Error {
@@ -51,6 +58,17 @@ impl Error {
                 annotations: vec![],
                 footers: vec![],
             },
+
+            Error::InvalidTimeout(field) => Message {
+                r#type: AnnotationType::Error,
+                title: self.to_string().into(),
+                annotations: vec![Annotation::new(
+                    AnnotationType::Error,
+                    format!("{:?} is not a non-negative number", field.value).into(),
+                    &field.origin,
+                )],
+                footers: vec![],
+            },
         }
     }
 }
@@ -62,7 +80,31 @@ impl<'a> From<&'a Error> for Message<'a> {
     }
 }
 
-const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('r').long("raw-mode")];
+const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new()
+        .short('d')
+        .long("delimiter")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new()
+        .short('p')
+        .long("prompt")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new().short('r').long("raw-mode"),
+    OptionSpec::new()
+        .short('t')
+        .long("timeout")
+        .argument(OptionArgumentSpec::Required),
+];
+
+/// Parses the argument to the `-t` option.
+fn parse_timeout(field: Field) -> Result<Duration, Error> {
+    match field.value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() => {
+            Duration::try_from_secs_f64(seconds).map_err(|_| Error::InvalidTimeout(field))
+        }
+        _ => Err(Error::InvalidTimeout(field)),
+    }
+}
 
 /// Parses command line arguments.
 pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
@@ -70,10 +112,20 @@ pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
     let (options, operands) = parse_arguments(OPTION_SPECS, mode, args)?;
 
     // Parse options
+    let mut delimiter = '\n';
     let mut is_raw = false;
+    let mut prompt = None;
+    let mut timeout = None;
     for option in options {
         match option.spec.get_short() {
+            Some('d') => {
+                // An empty argument specifies the null character.
+                let argument = option.argument.unwrap();
+                delimiter = argument.value.chars().next().unwrap_or('\0');
+            }
+            Some('p') => prompt = Some(option.argument.unwrap().value),
             Some('r') => is_raw = true,
+            Some('t') => timeout = Some(parse_timeout(option.argument.unwrap())?),
             _ => unreachable!(),
         }
     }
@@ -83,7 +135,10 @@ pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
     let last_variable = variables.pop().ok_or(Error::MissingOperand)?;
 
     Ok(Command {
+        delimiter,
         is_raw,
+        prompt,
+        timeout,
         variables,
         last_variable,
     })
@@ -99,7 +154,10 @@ mod tests {
         assert_eq!(
             parse(&env, Field::dummies(["var"])),
             Ok(Command {
+                delimiter: '\n',
                 is_raw: false,
+                prompt: None,
+                timeout: None,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -112,7 +170,10 @@ mod tests {
         assert_eq!(
             parse(&env, Field::dummies(["-r", "var"])),
             Ok(Command {
+                delimiter: '\n',
                 is_raw: true,
+                prompt: None,
+                timeout: None,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -125,7 +186,10 @@ mod tests {
         assert_eq!(
             parse(&env, Field::dummies(["foo", "bar"])),
             Ok(Command {
+                delimiter: '\n',
                 is_raw: false,
+                prompt: None,
+                timeout: None,
                 variables: Field::dummies(["foo"]),
                 last_variable: Field::dummy("bar"),
             })
@@ -134,13 +198,57 @@ mod tests {
         assert_eq!(
             parse(&env, Field::dummies(["first", "second", "third"])),
             Ok(Command {
+                delimiter: '\n',
                 is_raw: false,
+                prompt: None,
+                timeout: None,
                 variables: Field::dummies(["first", "second"]),
                 last_variable: Field::dummy("third"),
             })
         );
     }
 
+    #[test]
+    fn delimiter() {
+        let env = Env::new_virtual();
+        let command = parse(&env, Field::dummies(["-d", ":", "var"])).unwrap();
+        assert_eq!(command.delimiter, ':');
+
+        let command = parse(&env, Field::dummies(["-d", "", "var"])).unwrap();
+        assert_eq!(command.delimiter, '\0');
+    }
+
+    #[test]
+    fn prompt() {
+        let env = Env::new_virtual();
+        let command = parse(&env, Field::dummies(["-p", "> ", "var"])).unwrap();
+        assert_eq!(command.prompt, Some("> ".to_string()));
+
+        let command = parse(&env, Field::dummies(["--prompt=?", "var"])).unwrap();
+        assert_eq!(command.prompt, Some("?".to_string()));
+    }
+
+    #[test]
+    fn timeout() {
+        let env = Env::new_virtual();
+        let command = parse(&env, Field::dummies(["-t", "2", "var"])).unwrap();
+        assert_eq!(command.timeout, Some(Duration::from_secs(2)));
+
+        let command = parse(&env, Field::dummies(["-t", "0.5", "var"])).unwrap();
+        assert_eq!(command.timeout, Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        let env = Env::new_virtual();
+        for timeout in ["", "foo", "-1", "inf", "nan"] {
+            assert_eq!(
+                parse(&env, Field::dummies(["-t", timeout, "var"])),
+                Err(Error::InvalidTimeout(Field::dummy(timeout))),
+            );
+        }
+    }
+
     #[test]
     fn missing_operand() {
         let env = Env::new_virtual();
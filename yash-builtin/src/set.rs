@@ -311,8 +311,10 @@ mod tests {
             assert_eq!(
                 stdout,
                 "allexport        on
+braceexpand      off
 clobber          on
 cmdline          off
+emacs            off
 errexit          off
 exec             on
 glob             on
@@ -323,11 +325,15 @@ log              on
 login            off
 monitor          off
 notify           off
+pipefail         off
 posixlycorrect   off
+procsubst        off
+restricted       off
 stdin            off
 unset            off
 verbose          off
 vi               off
+warnwritable     off
 xtrace           off
 "
             )
@@ -379,6 +385,22 @@ xtrace           off
         assert_eq!(env.options, options);
     }
 
+    #[test]
+    fn switching_editing_modes() {
+        let mut env = Env::new_virtual();
+        let args = Field::dummies(["-o", "vi"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_eq!(env.options.get(Vi), On);
+        assert_eq!(env.options.get(Emacs), Off);
+
+        let args = Field::dummies(["-o", "emacs"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_eq!(env.options.get(Vi), Off);
+        assert_eq!(env.options.get(Emacs), On);
+    }
+
     #[test]
     fn setting_some_positional_parameters() {
         let name = Field::dummy("set");
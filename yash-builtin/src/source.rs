@@ -47,6 +47,10 @@
 //! execution of the file is finished. No context is pushed if there are no
 //! operands other than the filename.
 //!
+//! The file is always executed by the current shell, so an interpreter line
+//! (`#!`) at the beginning of the file is not honored. It is simply a comment
+//! ignored like any other line starting with `#`.
+//!
 //! # Options
 //!
 //! None.
@@ -40,7 +40,6 @@ use yash_env::system::SystemEx as _;
 use yash_env::variable::PATH;
 use yash_env::Env;
 use yash_semantics::read_eval_loop;
-use yash_syntax::parser::lex::Lexer;
 use yash_syntax::source::pretty::Annotation;
 use yash_syntax::source::pretty::AnnotationType;
 use yash_syntax::source::pretty::Message;
@@ -59,12 +58,16 @@ impl Command {
             Err(errno) => return report_find_and_open_file_failure(env, &self.file, errno).await,
         };
 
+        if let Ok(stat) = env.system.fstat(fd) {
+            env.warn_writable_script(&stat, &self.file.value).await;
+        }
+
         // TODO set positional parameters
 
         // Parse and execute the command script
         let system = env.system.clone();
+        let mut config = env.lexer_config();
         let ref_env = RefCell::new(&mut *env);
-        let mut config = Lexer::config();
         config.source = Some(Rc::new(Source::DotScript {
             name: self.file.value,
             origin: self.file.origin,
@@ -166,11 +169,13 @@ mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
     use yash_env::io::MIN_INTERNAL_FD;
+    use yash_env::option::{ErrExit, Off, On, WarnWritable};
     use yash_env::path::Path;
     use yash_env::system::r#virtual::Inode;
     use yash_env::system::FdFlag;
     use yash_env::variable::Scope;
     use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
 
     fn system_with_file<P: AsRef<Path>, C: Into<Vec<u8>>>(path: P, content: C) -> VirtualSystem {
         let system = VirtualSystem::new();
@@ -277,4 +282,64 @@ mod tests {
             assert_matches!(process.get_fd(Fd(fd)), None, "fd={fd}");
         }
     }
+
+    #[test]
+    fn shebang_line_is_treated_as_comment() {
+        let system = system_with_file("/foo/file", "#!/bin/sh\nfoo=bar\n");
+        let mut env = Env::with_system(Box::new(system));
+        env.exit_status = ExitStatus(42);
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: vec![],
+        };
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.variables.get_scalar("foo"), Some("bar"));
+    }
+
+    #[test]
+    fn file_with_only_shebang_line() {
+        let system = system_with_file("/foo/file", "#!/bin/sh -e\n");
+        let mut env = Env::with_system(Box::new(system));
+        env.exit_status = ExitStatus(42);
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: vec![],
+        };
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_eq!(env.options.get(ErrExit), Off);
+    }
+
+    #[test]
+    fn warning_for_world_writable_file() {
+        let system = system_with_file("/foo/file", "");
+        system
+            .state
+            .borrow()
+            .file_system
+            .get("/foo/file")
+            .unwrap()
+            .borrow_mut()
+            .permissions = Mode::from_bits_retain(0o666);
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.arg0 = "yash".to_string();
+        env.options.set(WarnWritable, On);
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: vec![],
+        };
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::SUCCESS));
+        assert_stderr(&state, |stderr| {
+            assert_eq!(
+                stderr,
+                "yash: warning: script file \"/foo/file\" is writable by other users\n"
+            )
+        });
+    }
 }
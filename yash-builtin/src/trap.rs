@@ -28,6 +28,10 @@
 //! trap [action] condition…
 //! ```
 //!
+//! ```sh
+//! trap -p [condition…]
+//! ```
+//!
 //! # Description
 //!
 //! When the built-in is invoked with no operands, it prints the currently
@@ -46,11 +50,18 @@
 //! and *condition*. When there are more than one *condition*, the built-in sets
 //! the same *action* for all of them.
 //!
-//! # Options
+//! When the action is `-`, the trap is reset to the default action. If the
+//! condition is a signal that was ignored when the shell started, the signal
+//! disposition is restored to ignoring the signal rather than the system
+//! default.
 //!
-//! None.
+//! # Options
 //!
-//! (TODO: `-p` option)
+//! The **`-p`** (**`--print`**) option makes the built-in print the traps for
+//! the specified *condition*s in the same format as the built-in without
+//! operands. Conditions that have the default action are printed with the
+//! action `-`. If no *condition* is given, the built-in prints all traps as if
+//! invoked without operands.
 //!
 //! # Operands
 //!
@@ -76,6 +87,14 @@
 //! - The number `0` or the symbolic name `EXIT` representing the termination of
 //!   the main shell process
 //!     - This condition is not triggered when the shell exits due to a signal.
+//! - The symbolic name `ERR` representing a command failure
+//!     - This condition is triggered when a command returns a non-zero exit
+//!       status under the conditions where the `errexit` option would make the
+//!       shell exit, regardless of whether the option is actually on.
+//! - The symbolic name `DEBUG` representing the execution of a simple command
+//!     - This condition is triggered before the words of each simple command
+//!       are expanded. It is not triggered by commands executed in the `DEBUG`
+//!       trap itself.
 //!
 //! # Errors
 //!
@@ -101,6 +120,9 @@
 //! The result of setting a trap to `SIGKILL` or `SIGSTOP` is undefined by
 //! POSIX.
 //!
+//! The `ERR` and `DEBUG` conditions are not defined in POSIX. They are
+//! extensions shared with some other shells such as bash, ksh, and zsh.
+//!
 //! The mechanism for the built-in to print traps configured in the parent shell
 //! may vary among shells. This implementation remembers the old traps in the
 //! [`TrapSet`] when starting a subshell and prints them when the built-in is
@@ -136,6 +158,7 @@ use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::system::SharedSystem;
 use yash_env::trap::Action;
+use yash_env::trap::Condition;
 use yash_env::trap::SetActionError;
 use yash_env::trap::SignalSystem;
 use yash_env::trap::TrapSet;
@@ -153,6 +176,9 @@ pub enum Command {
     /// Print all traps
     PrintAll,
 
+    /// Print traps for the specified conditions
+    Print { conditions: Vec<(CondSpec, Field)> },
+
     /// Set an action for one or more conditions
     SetAction {
         action: Action,
@@ -170,10 +196,8 @@ pub mod syntax;
 pub fn display_traps<S: SignalSystem>(traps: &TrapSet, system: &S) -> String {
     let mut output = String::new();
     for (cond, current, parent) in traps {
-        let trap = match (current, parent) {
-            (Some(trap), _) => trap,
-            (None, Some(trap)) => trap,
-            (None, None) => continue,
+        let Some(trap) = current.or(parent) else {
+            continue;
         };
         let command = match &trap.action {
             Action::Default => continue,
@@ -186,6 +210,21 @@ pub fn display_traps<S: SignalSystem>(traps: &TrapSet, system: &S) -> String {
     output
 }
 
+/// Returns a line that represents the trap for the condition.
+///
+/// Unlike [`display_traps`], this function returns a line even if the trap
+/// has the default action, in which case the action is printed as `-`.
+fn display_trap<S: SignalSystem>(traps: &TrapSet, system: &S, cond: Condition) -> String {
+    let (current, parent) = traps.get_state(cond);
+    let action = current.or(parent).map(|trap| &trap.action);
+    let command = match action {
+        None | Some(Action::Default) => quoted("-"),
+        Some(Action::Ignore) => quoted(""),
+        Some(Action::Command(command)) => quoted(command),
+    };
+    format!("trap -- {} {}\n", command, cond.to_string(system))
+}
+
 /// Cause of an error that may occur while executing the `trap` built-in
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[non_exhaustive]
@@ -267,6 +306,28 @@ impl Command {
         match self {
             Self::PrintAll => Ok(display_traps(&env.traps, &env.system)),
 
+            Self::Print { conditions } => {
+                let mut output = String::new();
+                let mut errors = Vec::new();
+                for (cond, field) in conditions {
+                    match cond.resolve(&env.system) {
+                        Some(cond2) => {
+                            output.push_str(&display_trap(&env.traps, &env.system, cond2))
+                        }
+                        None => {
+                            let cause = ErrorCause::UnsupportedSignal;
+                            errors.push(Error { cause, cond, field });
+                        }
+                    }
+                }
+
+                if errors.is_empty() {
+                    Ok(output)
+                } else {
+                    Err(errors)
+                }
+            }
+
             Self::SetAction { action, conditions } => {
                 let override_ignore = env.options.get(Interactive) == On;
 
@@ -297,7 +358,8 @@ impl Command {
 
 /// Entry point for executing the `trap` built-in
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
-    let (options, operands) = match parse_arguments(&[], Mode::with_env(env), args) {
+    let (options, operands) = match parse_arguments(syntax::OPTION_SPECS, Mode::with_env(env), args)
+    {
         Ok(result) => result,
         Err(error) => return report_error(env, &error).await,
     };
@@ -389,6 +451,47 @@ mod tests {
         assert_eq!(process.disposition(SIGPIPE), Disposition::Default);
     }
 
+    #[test]
+    fn resetting_trap_after_setting() {
+        let system = Box::new(VirtualSystem::new());
+        let pid = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo", "USR1", "USR2"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let args = Field::dummies(["-", "USR1", "USR2"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        let process = &state.borrow().processes[&pid];
+        assert_eq!(process.disposition(SIGUSR1), Disposition::Default);
+        assert_eq!(process.disposition(SIGUSR2), Disposition::Default);
+    }
+
+    #[test]
+    fn resetting_trap_restores_initially_ignored_disposition() {
+        let mut system = VirtualSystem::new();
+        system
+            .current_process_mut()
+            .set_disposition(SIGINT, Disposition::Ignore);
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.options.set(Interactive, On);
+        let args = Field::dummies(["echo", "INT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(
+            system.current_process().disposition(SIGINT),
+            Disposition::Catch
+        );
+
+        let args = Field::dummies(["-", "INT"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_eq!(
+            system.current_process().disposition(SIGINT),
+            Disposition::Ignore
+        );
+    }
+
     #[test]
     fn printing_no_trap() {
         let system = Box::new(VirtualSystem::new());
@@ -430,6 +533,77 @@ mod tests {
         });
     }
 
+    #[test]
+    fn printing_err_trap() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo t", "TERM"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        let args = Field::dummies(["echo failed", "ERR"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "trap -- 'echo failed' ERR\ntrap -- 'echo t' TERM\n")
+        });
+    }
+
+    #[test]
+    fn printing_specified_traps() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo t", "INT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        let args = Field::dummies(["", "USR1"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        let args = Field::dummies(["echo", "TERM"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let args = Field::dummies(["-p", "USR1", "INT", "USR2"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(
+                stdout,
+                "trap -- '' USR1\ntrap -- 'echo t' INT\ntrap -- - USR2\n"
+            )
+        });
+    }
+
+    #[test]
+    fn printing_all_traps_with_print_option() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo", "INT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let result = main(&mut env, Field::dummies(["-p"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "trap -- echo INT\n"));
+    }
+
+    #[test]
+    fn printing_reset_trap() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo", "INT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        let args = Field::dummies(["-", "INT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let args = Field::dummies(["-p", "INT"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "trap -- - INT\n"));
+    }
+
     #[test]
     fn error_printing_traps() {
         let mut system = Box::new(VirtualSystem::new());
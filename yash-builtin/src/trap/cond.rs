@@ -26,6 +26,10 @@ use yash_env::trap::Condition;
 pub enum CondSpec {
     /// The `EXIT` condition
     Exit,
+    /// The `ERR` condition
+    Err,
+    /// The `DEBUG` condition
+    Debug,
     /// A symbolic name of a signal
     SignalName(signal::Name),
     /// A signal number (or 0 for `EXIT`)
@@ -51,6 +55,8 @@ impl CondSpec {
     pub(super) fn resolve<S: System>(&self, system: &S) -> Option<Condition> {
         match *self {
             CondSpec::Exit | CondSpec::Number(0) => Some(Condition::Exit),
+            CondSpec::Err => Some(Condition::Err),
+            CondSpec::Debug => Some(Condition::Debug),
             CondSpec::SignalName(name) => {
                 Some(Condition::Signal(system.signal_number_from_name(name)?))
             }
@@ -66,6 +72,8 @@ impl CondSpec {
     pub fn to_condition<S: System>(&self, system: &S) -> Option<Condition> {
         match self {
             Self::Exit => Some(Condition::Exit),
+            Self::Err => Some(Condition::Err),
+            Self::Debug => Some(Condition::Debug),
             Self::SignalName(name) => {
                 Some(Condition::Signal(system.signal_number_from_name(*name)?))
             }
@@ -81,6 +89,8 @@ impl CondSpec {
     pub fn from_condition<S: System>(cond: &Condition, system: &S) -> Option<Self> {
         match cond {
             Condition::Exit => Some(Self::Exit),
+            Condition::Err => Some(Self::Err),
+            Condition::Debug => Some(Self::Debug),
             Condition::Signal(number) => {
                 Some(Self::SignalName(system.signal_name_from_number(*number)))
             }
@@ -93,6 +103,8 @@ impl std::fmt::Display for CondSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Exit => "EXIT".fmt(f),
+            Self::Err => "ERR".fmt(f),
+            Self::Debug => "DEBUG".fmt(f),
             Self::SignalName(name) => name.fmt(f),
             Self::Number(number) => number.fmt(f),
         }
@@ -102,15 +114,16 @@ impl std::fmt::Display for CondSpec {
 impl std::str::FromStr for CondSpec {
     type Err = signal::UnknownNameError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self, signal::UnknownNameError> {
         if let Ok(number) = s.parse() {
             return Ok(Self::Number(number));
         }
 
-        if s == "EXIT" {
-            Ok(Self::Exit)
-        } else {
-            Ok(Self::SignalName(s.parse()?))
+        match s {
+            "EXIT" => Ok(Self::Exit),
+            "ERR" => Ok(Self::Err),
+            "DEBUG" => Ok(Self::Debug),
+            _ => Ok(Self::SignalName(s.parse()?)),
         }
     }
 }
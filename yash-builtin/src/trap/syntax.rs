@@ -18,6 +18,7 @@
 
 use super::Command;
 use crate::common::syntax::OptionOccurrence;
+use crate::common::syntax::OptionSpec;
 use itertools::Itertools;
 use std::borrow::Cow;
 use thiserror::Error;
@@ -76,6 +77,9 @@ impl MessageBase for Error {
     }
 }
 
+/// List of all options supported by the trap built-in
+pub const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('p').long("print")];
+
 /// Converts parsed command line arguments into a `Command`.
 ///
 /// The result of [`parse_arguments`](crate::common::syntax::parse_arguments)
@@ -83,9 +87,14 @@ impl MessageBase for Error {
 ///
 /// On failure, returns a non-empty list of errors.
 pub fn interpret(
-    _options: Vec<OptionOccurrence>,
+    options: Vec<OptionOccurrence>,
     operands: Vec<Field>,
 ) -> Result<Command, Vec<Error>> {
+    if !options.is_empty() {
+        // The -p option is specified
+        return interpret_print(operands);
+    }
+
     let mut operands = operands.into_iter().peekable();
 
     // Parse the first operand as an action
@@ -124,6 +133,27 @@ pub fn interpret(
     }
 }
 
+/// Interprets the operands of the `-p` option.
+fn interpret_print(operands: Vec<Field>) -> Result<Command, Vec<Error>> {
+    if operands.is_empty() {
+        return Ok(Command::PrintAll);
+    }
+
+    let (conditions, errors): (Vec<_>, Vec<_>) = operands
+        .into_iter()
+        .map(|operand| match operand.value.parse() {
+            Ok(condition) => Ok((condition, operand)),
+            Err(_) => Err(Error::UnknownCondition(operand)),
+        })
+        .partition_result();
+
+    if errors.is_empty() {
+        Ok(Command::Print { conditions })
+    } else {
+        Err(errors)
+    }
+}
+
 fn is_non_negative_integer(s: &str) -> bool {
     !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
 }
@@ -133,6 +163,7 @@ mod tests {
     use super::super::CondSpec;
     use super::*;
     use yash_env::signal::Name;
+    use yash_syntax::source::Location;
 
     #[test]
     fn print_all_for_no_operands() {
@@ -140,6 +171,50 @@ mod tests {
         assert_eq!(result, Ok(Command::PrintAll));
     }
 
+    #[test]
+    fn print_option_without_operands() {
+        let option = OptionOccurrence {
+            spec: &OPTION_SPECS[0],
+            location: Location::dummy("-p"),
+            argument: None,
+        };
+        let result = interpret(vec![option], vec![]);
+        assert_eq!(result, Ok(Command::PrintAll));
+    }
+
+    #[test]
+    fn print_option_with_conditions() {
+        let option = OptionOccurrence {
+            spec: &OPTION_SPECS[0],
+            location: Location::dummy("-p"),
+            argument: None,
+        };
+        let result = interpret(vec![option], Field::dummies(["INT", "0"]));
+        assert_eq!(
+            result,
+            Ok(Command::Print {
+                conditions: vec![
+                    (CondSpec::SignalName(Name::Int), Field::dummy("INT")),
+                    (CondSpec::Number(0), Field::dummy("0")),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn print_option_with_unknown_condition() {
+        let option = OptionOccurrence {
+            spec: &OPTION_SPECS[0],
+            location: Location::dummy("-p"),
+            argument: None,
+        };
+        let result = interpret(vec![option], Field::dummies(["FOO"]));
+        assert_eq!(
+            result,
+            Err(vec![Error::UnknownCondition(Field::dummy("FOO"))])
+        );
+    }
+
     #[test]
     fn default_action_with_one_condition() {
         let result = interpret(vec![], Field::dummies(["-", "INT"]));
@@ -237,6 +312,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn action_with_err_condition() {
+        let result = interpret(vec![], Field::dummies(["echo", "ERR"]));
+        assert_eq!(
+            result,
+            Ok(Command::SetAction {
+                action: Action::Command("echo".into()),
+                conditions: vec![(CondSpec::Err, Field::dummy("ERR"))]
+            })
+        );
+    }
+
     #[test]
     fn action_with_numeric_exit_condition() {
         let result = interpret(vec![], Field::dummies(["-", "0"]));
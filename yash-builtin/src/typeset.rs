@@ -57,7 +57,7 @@
 //! ```
 //!
 //! Note that the read-only attribute cannot be removed, so the `+r` option is
-//! of no use.
+//! of no use. Specifying `+r` for a read-only variable is an error.
 //!
 //! ## Operands
 //!
@@ -232,6 +232,9 @@
 //!
 //! It is an error to modify a non-existing function.
 //!
+//! When defining variables, it is an error if an operand does not start with
+//! a valid variable name, as in `=value` or `1abc`.
+//!
 //! When printing variables or functions, it is an error if an operand names a
 //! non-existing variable or function.
 //!
@@ -337,11 +340,26 @@ pub enum Scope {
     Local,
 }
 
+/// Operand that defines a variable
+///
+/// This is the result of [`parse_variable_operand`](syntax::parse_variable_operand).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariableOperand {
+    /// Name of the variable
+    ///
+    /// The origin of the field is that of the whole operand.
+    pub name: Field,
+    /// Value to be assigned to the variable
+    ///
+    /// This is `None` if the operand is a bare name without `=`.
+    pub value: Option<String>,
+}
+
 /// Set of information to define variables
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SetVariables {
     /// Names and optional values of the variables to be defined
-    pub variables: Vec<Field>,
+    pub variables: Vec<VariableOperand>,
     /// Attributes to be set on the variables
     pub attrs: Vec<(VariableAttr, State)>,
     /// Scope in which the variables are defined
@@ -353,6 +353,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn printing_attributes_of_all_variables() {
+        let mut vars = VariableSet::new();
+        let mut x = vars.get_or_new("x", Scope::Global.into());
+        x.assign("foo bar", None).unwrap();
+        x.export(true);
+        let mut r = vars.get_or_new("r", Scope::Global.into());
+        r.assign("'", None).unwrap();
+        r.make_read_only(Location::dummy("r location"));
+        let mut b = vars.get_or_new("b", Scope::Global.into());
+        b.assign("", None).unwrap();
+        b.export(true);
+        b.make_read_only(Location::dummy("b location"));
+        vars.get_or_new("n", Scope::Global.into())
+            .assign("1", None)
+            .unwrap();
+        let pv = PrintVariables {
+            variables: vec![],
+            attrs: vec![],
+            scope: Scope::Global,
+        };
+
+        assert_eq!(
+            pv.execute(&vars, &PRINT_CONTEXT).unwrap(),
+            // sorted by name
+            "typeset -r -x b=''\n\
+             typeset n=1\n\
+             typeset -r r=\"'\"\n\
+             typeset -x x='foo bar'\n",
+        );
+    }
+
     #[test]
     fn printing_attributes_of_valueless_variables() {
         let mut vars = VariableSet::new();
@@ -31,21 +31,12 @@ impl SetVariables {
     pub fn execute(self, env: &mut Env) -> Result<String, Vec<ExecuteError>> {
         let mut errors = Vec::new();
 
-        'field: for mut field in self.variables {
-            // Split the field into the name and the value.
-            let mut value_to_assign = None;
-            if let Some((name, value)) = field.value.split_once('=') {
-                value_to_assign = Some(Value::scalar(value));
-
-                // Modify the field value so that it contains only the name.
-                field.value.truncate(name.len());
-            }
-
+        'field: for VariableOperand { name: field, value } in self.variables {
             let mut variable = env.get_or_create_variable(&field.value, self.scope.into());
 
             // Assign the value to the variable.
-            if let Some(value) = value_to_assign {
-                if let Err(error) = variable.assign(value, field.origin.clone()) {
+            if let Some(value) = value {
+                if let Err(error) = variable.assign(Value::scalar(value), field.origin.clone()) {
                     errors.push(ExecuteError::AssignReadOnlyVariable(AssignReadOnlyError {
                         name: field.value,
                         new_value: error.new_value,
@@ -93,6 +84,13 @@ mod tests {
     use yash_env::variable::{Context, Variable};
     use yash_syntax::source::Location;
 
+    fn operands<const N: usize>(values: [&str; N]) -> Vec<VariableOperand> {
+        Field::dummies(values)
+            .into_iter()
+            .map(|field| syntax::parse_variable_operand(field).unwrap())
+            .collect()
+    }
+
     #[test]
     fn setting_local_variables() {
         let mut outer = Env::new_virtual();
@@ -101,11 +99,11 @@ mod tests {
         let mut baz = inner.get_or_create_variable("baz", Scope::Local.into());
         baz.assign("BAZ", baz_location.clone()).unwrap();
         let sv = SetVariables {
-            variables: Field::dummies(["foo=FOO", "bar", "baz"]),
+            variables: operands(["foo=FOO", "bar", "baz"]),
             attrs: vec![],
             scope: Scope::Local,
         };
-        let foo_location = sv.variables[0].origin.clone();
+        let foo_location = sv.variables[0].name.origin.clone();
 
         let result = sv.execute(&mut inner);
 
@@ -140,11 +138,11 @@ mod tests {
         baz.assign("BAZ", baz_location.clone()).unwrap();
         let mut inner = outer.push_context(Context::default());
         let sv = SetVariables {
-            variables: Field::dummies(["foo=FOO", "bar", "baz"]),
+            variables: operands(["foo=FOO", "bar", "baz"]),
             attrs: vec![],
             scope: Scope::Global,
         };
-        let foo_location = sv.variables[0].origin.clone();
+        let foo_location = sv.variables[0].name.origin.clone();
 
         let result = sv.execute(&mut inner);
 
@@ -172,12 +170,12 @@ mod tests {
     fn setting_variables_readonly() {
         let mut env = Env::new_virtual();
         let sv = SetVariables {
-            variables: Field::dummies(["foo", "bar=BAR"]),
+            variables: operands(["foo", "bar=BAR"]),
             attrs: vec![(VariableAttr::ReadOnly, State::On)],
             scope: Scope::Local,
         };
-        let foo_location = sv.variables[0].origin.clone();
-        let bar_location = sv.variables[1].origin.clone();
+        let foo_location = sv.variables[0].name.origin.clone();
+        let bar_location = sv.variables[1].name.origin.clone();
 
         let result = sv.execute(&mut env);
 
@@ -196,7 +194,7 @@ mod tests {
     fn exporting_variables() {
         let mut env = Env::new_virtual();
         let sv = SetVariables {
-            variables: Field::dummies(["foo", "bar=BAR"]),
+            variables: operands(["foo", "bar=BAR"]),
             attrs: vec![(VariableAttr::Export, State::On)],
             scope: Scope::Local,
         };
@@ -219,7 +217,7 @@ mod tests {
         var.assign("BAR", None).unwrap();
         var.export(true);
         let sv = SetVariables {
-            variables: Field::dummies(["foo", "bar=NEW_BAR"]),
+            variables: operands(["foo", "bar=NEW_BAR"]),
             attrs: vec![(VariableAttr::Export, State::Off)],
             scope: Scope::Local,
         };
@@ -241,7 +239,7 @@ mod tests {
         env.options.set(AllExport, State::On);
 
         let sv = SetVariables {
-            variables: Field::dummies(["foo=FOO"]),
+            variables: operands(["foo=FOO"]),
             attrs: vec![],
             scope: Scope::Global,
         };
@@ -250,7 +248,7 @@ mod tests {
         assert!(env.variables.get("foo").unwrap().is_exported);
 
         let sv = SetVariables {
-            variables: Field::dummies(["foo=BAR"]),
+            variables: operands(["foo=BAR"]),
             attrs: vec![(VariableAttr::Export, State::Off)],
             scope: Scope::Global,
         };
@@ -272,12 +270,12 @@ mod tests {
         w.assign("writable value", w_location).unwrap();
         let w = w.clone();
         let sv = SetVariables {
-            variables: Field::dummies(["ro", "w=foo"]),
+            variables: operands(["ro", "w=foo"]),
             attrs: vec![(VariableAttr::ReadOnly, State::Off)],
             scope: Scope::Global,
         };
-        let ro_arg_location = sv.variables[0].origin.clone();
-        let w_location = sv.variables[1].origin.clone();
+        let ro_arg_location = sv.variables[0].name.origin.clone();
+        let w_location = sv.variables[1].name.origin.clone();
 
         let errors = sv.execute(&mut env).unwrap_err();
 
@@ -308,11 +306,11 @@ mod tests {
         let ro = ro.clone();
 
         let sv = SetVariables {
-            variables: Field::dummies(["ro=foo"]),
+            variables: operands(["ro=foo"]),
             attrs: vec![],
             scope: Scope::Global,
         };
-        let assigned_location = sv.variables[0].origin.clone();
+        let assigned_location = sv.variables[0].name.origin.clone();
 
         let errors = sv.execute(&mut env).unwrap_err();
         assert_matches!(&errors[..], [ExecuteError::AssignReadOnlyVariable(error)] => {
@@ -332,11 +330,11 @@ mod tests {
         var.make_read_only(assign_location.clone());
         let mut inner = outer.push_context(Context::default());
         let sv = SetVariables {
-            variables: Field::dummies(["var=NEW"]),
+            variables: operands(["var=NEW"]),
             attrs: vec![(VariableAttr::ReadOnly, State::Off)],
             scope: Scope::Local,
         };
-        let new_location = sv.variables[0].origin.clone();
+        let new_location = sv.variables[0].name.origin.clone();
 
         let result = sv.execute(&mut inner);
 
@@ -356,14 +354,14 @@ mod tests {
     fn combination_of_readonly_attributes() {
         let mut env = Env::new_virtual();
         let sv = SetVariables {
-            variables: Field::dummies(["foo=FOO"]),
+            variables: operands(["foo=FOO"]),
             attrs: vec![
                 (VariableAttr::ReadOnly, State::On),
                 (VariableAttr::ReadOnly, State::Off),
             ],
             scope: Scope::Local,
         };
-        let foo_location = sv.variables[0].origin.clone();
+        let foo_location = sv.variables[0].name.origin.clone();
 
         let errors = sv.execute(&mut env).unwrap_err();
 
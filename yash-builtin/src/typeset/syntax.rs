@@ -19,6 +19,7 @@
 //! There are two main functions in this module: [`parse`] and [`interpret`].
 //! The former parses command line arguments into [`OptionOccurrence`]s and
 //! operands, and the latter interprets them into a [`Command`].
+//! [`parse_variable_operand`] interprets each operand that defines a variable.
 
 use super::*;
 use std::borrow::Cow;
@@ -26,6 +27,7 @@ use std::iter::Peekable;
 use thiserror::Error;
 use yash_env::option::State;
 use yash_env::semantics::Field;
+use yash_syntax::parser::lex::is_name_char;
 use yash_syntax::source::pretty::{Annotation, AnnotationType, MessageBase};
 use yash_syntax::source::Location;
 
@@ -310,6 +312,64 @@ pub fn parse<'a>(
     Ok((options, operands))
 }
 
+/// Error in a malformed variable operand
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum VariableOperandError {
+    /// Operand that starts with `=`, as in `=value`
+    #[error("missing variable name")]
+    EmptyName(Field),
+    /// Operand whose name part is not a valid variable name, as in `1abc`
+    #[error("invalid variable name")]
+    InvalidName(Field),
+}
+
+impl VariableOperandError {
+    /// Returns the malformed operand.
+    #[must_use]
+    pub fn operand(&self) -> &Field {
+        match self {
+            Self::EmptyName(operand) | Self::InvalidName(operand) => operand,
+        }
+    }
+}
+
+/// Tests whether the string is a valid variable name.
+///
+/// A valid name is a non-empty sequence of [name characters](is_name_char)
+/// that does not start with a digit.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(is_name_char)
+}
+
+/// Interprets an operand that defines a variable.
+///
+/// An operand of the form `name=value` is an assignment, and an operand
+/// without `=` is a bare name. In either case, the name must be a valid
+/// variable name. The `name` field of the result has the value truncated to
+/// the name and retains the origin of the whole operand.
+pub fn parse_variable_operand(mut operand: Field) -> Result<VariableOperand, VariableOperandError> {
+    let (name_len, value) = match operand.value.split_once('=') {
+        Some((name, value)) => (name.len(), Some(value.to_owned())),
+        None => (operand.value.len(), None),
+    };
+
+    let name = &operand.value[..name_len];
+    if name.is_empty() {
+        return Err(VariableOperandError::EmptyName(operand));
+    }
+    if !is_valid_name(name) {
+        return Err(VariableOperandError::InvalidName(operand));
+    }
+
+    operand.value.truncate(name_len);
+    Ok(VariableOperand {
+        name: operand,
+        value,
+    })
+}
+
 /// Error in interpreting command line arguments
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[non_exhaustive]
@@ -322,6 +382,10 @@ pub enum InterpretError<'a> {
         /// Occurrence of the `-f` option
         function: OptionOccurrence<'a>,
     },
+
+    /// Malformed operand for defining a variable
+    #[error(transparent)]
+    InvalidVariableOperand(#[from] VariableOperandError),
 }
 
 impl MessageBase for InterpretError<'_> {
@@ -336,6 +400,19 @@ impl MessageBase for InterpretError<'_> {
                 format!("the {} option ...", clashing.spec).into(),
                 &clashing.location,
             ),
+            InterpretError::InvalidVariableOperand(error) => {
+                let operand = error.operand();
+                let label = match error {
+                    VariableOperandError::EmptyName(_) => {
+                        format!("operand `{operand}` has no variable name")
+                    }
+                    VariableOperandError::InvalidName(_) => {
+                        let name = operand.value.split('=').next().unwrap_or_default();
+                        format!("`{name}` is not a valid variable name")
+                    }
+                };
+                Annotation::new(AnnotationType::Error, label.into(), &operand.origin)
+            }
         }
     }
 
@@ -348,6 +425,7 @@ impl MessageBase for InterpretError<'_> {
                     &function.location,
                 )])
             }
+            InterpretError::InvalidVariableOperand(_) => {}
         }
     }
 }
@@ -400,7 +478,6 @@ pub fn interpret(
             Ok((SetFunctions { functions, attrs }).into())
         }
     } else {
-        let variables = operands;
         let attrs = attrs
             .into_iter()
             .map(|(_index, attr, state)| Ok((attr.try_into()?, state)))
@@ -413,12 +490,16 @@ pub fn interpret(
 
         if print {
             let pv = PrintVariables {
-                variables,
+                variables: operands,
                 attrs,
                 scope,
             };
             Ok(pv.into())
         } else {
+            let variables = operands
+                .into_iter()
+                .map(parse_variable_operand)
+                .collect::<Result<_, _>>()?;
             let sv = SetVariables {
                 variables,
                 attrs,
@@ -571,12 +652,112 @@ mod tests {
         });
     }
 
+    fn bare_names(fields: Vec<Field>) -> Vec<VariableOperand> {
+        fields
+            .into_iter()
+            .map(|name| VariableOperand { name, value: None })
+            .collect()
+    }
+
+    #[test]
+    fn parse_variable_operand_bare_name() {
+        let operand = Field::dummy("foo_1");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(
+            result,
+            Ok(VariableOperand {
+                name: operand,
+                value: None
+            })
+        );
+    }
+
+    #[test]
+    fn parse_variable_operand_assignment() {
+        let operand = Field::dummy("_foo=bar=baz");
+        let result = parse_variable_operand(operand.clone()).unwrap();
+        assert_eq!(result.name.value, "_foo");
+        assert_eq!(result.name.origin, operand.origin);
+        assert_eq!(result.value.as_deref(), Some("bar=baz"));
+    }
+
+    #[test]
+    fn parse_variable_operand_assignment_of_empty_value() {
+        let result = parse_variable_operand(Field::dummy("foo=")).unwrap();
+        assert_eq!(result.name.value, "foo");
+        assert_eq!(result.value.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn parse_variable_operand_empty_name() {
+        let operand = Field::dummy("=x");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(result, Err(VariableOperandError::EmptyName(operand)));
+
+        let operand = Field::dummy("");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(result, Err(VariableOperandError::EmptyName(operand)));
+    }
+
+    #[test]
+    fn parse_variable_operand_name_starting_with_digit() {
+        let operand = Field::dummy("1abc");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(result, Err(VariableOperandError::InvalidName(operand)));
+
+        let operand = Field::dummy("1abc=x");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(result, Err(VariableOperandError::InvalidName(operand)));
+    }
+
+    #[test]
+    fn parse_variable_operand_name_with_invalid_character() {
+        let operand = Field::dummy("a-b=c");
+        let result = parse_variable_operand(operand.clone());
+        assert_eq!(result, Err(VariableOperandError::InvalidName(operand)));
+    }
+
+    #[test]
+    fn interpret_assignment_and_bare_name_operands() {
+        let result = interpret(vec![], Field::dummies(["foo=1", "bar"])).unwrap();
+        assert_matches!(result, Command::SetVariables(sv) => {
+            assert_matches!(&sv.variables[..], [foo, bar] => {
+                assert_eq!(foo.name.value, "foo");
+                assert_eq!(foo.value.as_deref(), Some("1"));
+                assert_eq!(bar.name.value, "bar");
+                assert_eq!(bar.value, None);
+            });
+        });
+    }
+
+    #[test]
+    fn interpret_malformed_variable_operand() {
+        let operands = Field::dummies(["foo", "=x", "1abc"]);
+        let result = interpret(vec![], operands.clone());
+        assert_eq!(
+            result,
+            Err(InterpretError::InvalidVariableOperand(
+                VariableOperandError::EmptyName(operands[1].clone())
+            ))
+        );
+    }
+
+    #[test]
+    fn interpret_malformed_operand_for_printing() {
+        // Operands are not validated when printing variables.
+        let result = interpret(
+            vec![dummy_option_occurrence(&PRINT_OPTION, State::On)],
+            Field::dummies(["1abc"]),
+        );
+        assert_matches!(result, Ok(Command::PrintVariables(_)));
+    }
+
     #[test]
     fn interpret_some_operands_without_options() {
         let vars = Field::dummies(["foo", "bar"]);
         let result = interpret(vec![], vars.clone()).unwrap();
         assert_matches!(result, Command::SetVariables(sv) => {
-            assert_eq!(sv.variables, vars);
+            assert_eq!(sv.variables, bare_names(vars));
             assert_eq!(sv.attrs, []);
             assert_eq!(sv.scope, Scope::Local);
         });
@@ -636,7 +817,7 @@ mod tests {
             vars.clone(),
         );
         assert_matches!(result, Ok(Command::SetVariables(sv)) => {
-            assert_eq!(sv.variables, vars);
+            assert_eq!(sv.variables, bare_names(vars));
             assert_eq!(sv.attrs, []);
             assert_eq!(sv.scope, Scope::Global);
         });
@@ -690,7 +871,7 @@ mod tests {
             vars.clone(),
         );
         assert_matches!(result, Ok(Command::SetVariables(sv)) => {
-            assert_eq!(sv.variables, vars);
+            assert_eq!(sv.variables, bare_names(vars));
             assert_eq!(sv.attrs, [(VariableAttr::Export, State::Off)]);
             assert_eq!(sv.scope, Scope::Local);
         });
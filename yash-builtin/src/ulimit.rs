@@ -255,3 +255,91 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         Err(e) => report_error(env, &e).await,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::resource::{LimitPair, INFINITY};
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stdout;
+
+    fn env_with_limits() -> (Env, VirtualSystem) {
+        let mut system = VirtualSystem::new();
+        let limits = LimitPair { soft: 5, hard: 12 };
+        system.setrlimit(Resource::CPU, limits).unwrap();
+        let limits = LimitPair {
+            soft: 4 << 10,
+            hard: INFINITY,
+        };
+        system.setrlimit(Resource::STACK, limits).unwrap();
+        let env = Env::with_system(Box::new(system.clone()));
+        (env, system)
+    }
+
+    #[test]
+    fn show_all_soft_limits() {
+        let (mut env, system) = env_with_limits();
+
+        let result = main(&mut env, Field::dummies(["-a"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&system.state, |stdout| {
+            assert_eq!(
+                stdout,
+                "-v: virtual address space size (KiB) unlimited\n\
+                 -c: core dump size (512-byte blocks) unlimited\n\
+                 -t: CPU time (seconds)               5\n\
+                 -d: data segment size (KiB)          unlimited\n\
+                 -f: file size (512-byte blocks)      unlimited\n\
+                 -k: number of kqueues                unlimited\n\
+                 -x: number of file locks             unlimited\n\
+                 -l: locked memory size (KiB)         unlimited\n\
+                 -q: message queue size (bytes)       unlimited\n\
+                 -e: process priority (20 - nice)     unlimited\n\
+                 -n: number of open files             unlimited\n\
+                 -u: number of processes              unlimited\n\
+                 -m: resident set size (KiB)          unlimited\n\
+                 -r: real-time priority               unlimited\n\
+                 -R: real-time timeout (microseconds) unlimited\n\
+                 -b: socket buffer size (bytes)       unlimited\n\
+                 -i: number of pending signals        unlimited\n\
+                 -s: stack size (KiB)                 4\n\
+                 -w: swap space size (KiB)            unlimited\n"
+            )
+        });
+    }
+
+    #[test]
+    fn show_all_hard_limits() {
+        let (mut env, system) = env_with_limits();
+
+        let result = main(&mut env, Field::dummies(["-aH"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&system.state, |stdout| {
+            let lines = stdout.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), Resource::ALL.len());
+            assert!(lines.contains(&"-t: CPU time (seconds)               12"));
+            assert!(lines.contains(&"-s: stack size (KiB)                 unlimited"));
+        });
+    }
+
+    #[test]
+    fn show_all_with_explicit_soft_option() {
+        let (mut env, system) = env_with_limits();
+
+        let result = main(&mut env, Field::dummies(["-S", "-a"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&system.state, |stdout| {
+            let lines = stdout.lines().collect::<Vec<_>>();
+            assert!(lines.contains(&"-t: CPU time (seconds)               5"));
+            assert!(lines.contains(&"-s: stack size (KiB)                 4"));
+        });
+    }
+}
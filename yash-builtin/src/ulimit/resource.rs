@@ -30,10 +30,20 @@ pub trait ResourceExt {
 
     /// Returns a human-readable description of the resource.
     ///
-    /// The returned string is not localized.
+    /// The returned string does not include the [unit](Self::unit). It is not
+    /// localized.
     #[must_use]
     fn description(&self) -> &'static str;
 
+    /// Returns a human-readable unit of the limit value for the resource.
+    ///
+    /// The unit applies to the value that the user sees and sets, that is,
+    /// the raw limit divided by the [scale](Self::scale). For example, the
+    /// unit for [`Resource::DATA`] is `KiB`. Returns `None` if the limit is a
+    /// plain count. The returned string is not localized.
+    #[must_use]
+    fn unit(&self) -> Option<&'static str>;
+
     /// Returns the scale of the resource.
     ///
     /// The scale is the ratio of the actual limit to the value that the user
@@ -72,29 +82,43 @@ impl ResourceExt for Resource {
 
     fn description(&self) -> &'static str {
         match self {
-            Self::AS => "virtual address space size (KiB)",
-            Self::CORE => "core dump size (512-byte blocks)",
-            Self::CPU => "CPU time (seconds)",
-            Self::DATA => "data segment size (KiB)",
-            Self::FSIZE => "file size (512-byte blocks)",
+            Self::AS => "virtual address space size",
+            Self::CORE => "core dump size",
+            Self::CPU => "CPU time",
+            Self::DATA => "data segment size",
+            Self::FSIZE => "file size",
             Self::KQUEUES => "number of kqueues",
             Self::LOCKS => "number of file locks",
-            Self::MEMLOCK => "locked memory size (KiB)",
-            Self::MSGQUEUE => "message queue size (bytes)",
-            Self::NICE => "process priority (20 - nice)",
+            Self::MEMLOCK => "locked memory size",
+            Self::MSGQUEUE => "message queue size",
+            Self::NICE => "process priority",
             Self::NOFILE => "number of open files",
             Self::NPROC => "number of processes",
-            Self::RSS => "resident set size (KiB)",
+            Self::RSS => "resident set size",
             Self::RTPRIO => "real-time priority",
-            Self::RTTIME => "real-time timeout (microseconds)",
-            Self::SBSIZE => "socket buffer size (bytes)",
+            Self::RTTIME => "real-time timeout",
+            Self::SBSIZE => "socket buffer size",
             Self::SIGPENDING => "number of pending signals",
-            Self::STACK => "stack size (KiB)",
-            Self::SWAP => "swap space size (KiB)",
+            Self::STACK => "stack size",
+            Self::SWAP => "swap space size",
             _ => "unknown resource",
         }
     }
 
+    fn unit(&self) -> Option<&'static str> {
+        match self {
+            Self::AS | Self::DATA | Self::MEMLOCK | Self::RSS | Self::STACK | Self::SWAP => {
+                Some("KiB")
+            }
+            Self::CORE | Self::FSIZE => Some("512-byte blocks"),
+            Self::CPU => Some("seconds"),
+            Self::MSGQUEUE | Self::SBSIZE => Some("bytes"),
+            Self::NICE => Some("20 - nice"),
+            Self::RTTIME => Some("microseconds"),
+            _ => None,
+        }
+    }
+
     fn scale(&self) -> Limit {
         match self {
             Self::AS | Self::DATA | Self::MEMLOCK | Self::RSS | Self::STACK | Self::SWAP => 1 << 10,
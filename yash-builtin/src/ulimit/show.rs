@@ -48,7 +48,8 @@ impl std::fmt::Display for Limit {
 /// Shows the current limits for all resources.
 ///
 /// Returns a string that contains the current limits for all resources.
-/// Each line shows the option, description, and limit for a resource.
+/// Each line shows the option, description, unit, and limit for a resource.
+/// Resources for which `getrlimit` fails are skipped.
 pub fn show_all<F>(mut getrlimit: F, limit_type: ShowLimitType) -> String
 where
     F: FnMut(Resource) -> Result<LimitPair, Errno>,
@@ -59,7 +60,10 @@ where
             continue;
         };
         let option = resource.option();
-        let desc = resource.description();
+        let desc = match resource.unit() {
+            Some(unit) => format!("{} ({unit})", resource.description()),
+            None => resource.description().to_owned(),
+        };
         let value = match limit_type {
             ShowLimitType::Soft => limits.soft,
             ShowLimitType::Hard => limits.hard,
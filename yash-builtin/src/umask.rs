@@ -113,7 +113,7 @@
 use crate::common::{output, report_error};
 use yash_env::semantics::Field;
 use yash_env::system::Mode;
-use yash_env::{Env, System};
+use yash_env::Env;
 
 pub mod eval;
 pub mod format;
@@ -156,15 +156,17 @@ impl Command {
     ///
     /// Regardless of the command type, this function performs the following steps:
     ///
-    /// 1. Obtain the current mask from the environment. ([`System::umask`])
+    /// 1. Obtain the current mask from the environment. ([`Env::get_umask`])
     /// 1. Compute a new mask to be set. ([`eval::new_mask`])
-    /// 1. Set the new mask. ([`System::umask`])
+    /// 1. Set the new mask if it differs from the current. ([`Env::set_umask`])
     ///
     /// Returns the string that should be printed to the standard output.
     pub fn execute(&self, env: &mut Env) -> String {
-        let current = !env.system.umask(Mode::empty()).bits();
+        let current = !env.get_umask().bits();
         let new_mask = eval::new_mask(current as _, self);
-        env.system.umask(Mode::from_bits_retain(!new_mask as _));
+        if new_mask != current as u16 {
+            env.set_umask(Mode::from_bits_retain(!new_mask as _));
+        }
 
         match *self {
             Self::Show { symbolic: false } => format!("{:03o}\n", !new_mask),
@@ -188,3 +190,95 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         Err(e) => report_error(env, &e).await,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::System as _;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stdout;
+
+    fn env_with_mask(mask: u32) -> (Env, VirtualSystem) {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.system.umask(Mode::from_bits_retain(mask as _));
+        (env, system)
+    }
+
+    fn current_mask(env: &mut Env) -> Mode {
+        let mask = env.system.umask(Mode::empty());
+        env.system.umask(mask);
+        mask
+    }
+
+    fn run(env: &mut Env, args: &[&str]) {
+        let result = main(env, Field::dummies(args.iter().copied()))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+    }
+
+    #[test]
+    fn show_octal() {
+        let (mut env, system) = env_with_mask(0o022);
+        run(&mut env, &[]);
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "022\n"));
+    }
+
+    #[test]
+    fn show_symbolic() {
+        let (mut env, system) = env_with_mask(0o022);
+        run(&mut env, &["-S"]);
+        assert_stdout(&system.state, |stdout| {
+            assert_eq!(stdout, "u=rwx,g=rx,o=rx\n")
+        });
+    }
+
+    #[test]
+    fn set_octal() {
+        let (mut env, _) = env_with_mask(0o022);
+        run(&mut env, &["0751"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o751));
+    }
+
+    #[test]
+    fn symbolic_round_trip() {
+        for mask in [0o000, 0o022, 0o027, 0o077, 0o135, 0o777] {
+            let (mut env, system) = env_with_mask(mask);
+            run(&mut env, &["-S"]);
+            let symbolic = assert_stdout(&system.state, |stdout| stdout.trim_end().to_owned());
+
+            let (mut env, _) = env_with_mask(!mask & 0o777);
+            run(&mut env, &[&symbolic]);
+            assert_eq!(
+                current_mask(&mut env),
+                Mode::from_bits_retain(mask as _),
+                "{symbolic}"
+            );
+        }
+    }
+
+    #[test]
+    fn set_symbolic() {
+        let (mut env, _) = env_with_mask(0o000);
+        run(&mut env, &["u=rw,go=r"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o133));
+    }
+
+    #[test]
+    fn incremental_symbolic_modifications() {
+        let (mut env, _) = env_with_mask(0o022);
+        run(&mut env, &["g+w"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o002));
+        run(&mut env, &["o-rx"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o007));
+        run(&mut env, &["a-w"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o227));
+        run(&mut env, &["+x"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o226));
+        run(&mut env, &["u+w,g-r+w"]);
+        assert_eq!(current_mask(&mut env), Mode::from_bits_retain(0o046));
+    }
+}
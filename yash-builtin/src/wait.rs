@@ -21,7 +21,7 @@
 //! # Synopsis
 //!
 //! ```sh
-//! wait [job_id_or_process_id…]
+//! wait [-n] [job_id_or_process_id…]
 //! ```
 //!
 //! # Description
@@ -31,6 +31,9 @@
 //! jobs. If the jobs are already finished, the built-in returns without
 //! waiting.
 //!
+//! With the `-n` option, the built-in returns as soon as any one of the jobs
+//! finishes, leaving the other jobs running.
+//!
 //! If a job is job-controlled (that is, running in its own process group), it
 //! is considered finished not only when it has exited but also when it has been
 //! suspended. (TODO: This behavior is contrary to POSIX 2024 and will be fixed
@@ -38,7 +41,8 @@
 //!
 //! # Options
 //!
-//! None
+//! The **`-n`** (**`--next`**) option makes the built-in wait for the next
+//! job to finish rather than all the jobs.
 //!
 //! # Operands
 //!
@@ -67,6 +71,9 @@
 //! the job specified by the last operand. If there is no operand, the exit
 //! status is 0 regardless of the awaited jobs.
 //!
+//! With the `-n` option, the exit status is that of the job that has finished.
+//! If there is no job to wait for, the exit status is 127.
+//!
 //! If the built-in was interrupted by a signal, the exit status indicates the
 //! signal.
 //!
@@ -76,6 +83,8 @@
 //!
 //! The wait built-in is contained in the POSIX standard.
 //!
+//! The `-n` option is not defined in POSIX.
+//!
 //! The exact value of an exit status resulting from a signal is
 //! implementation-dependent.
 //!
@@ -118,6 +127,9 @@ pub struct Command {
     ///
     /// If empty, the built-in waits for all existing asynchronous jobs.
     pub jobs: Vec<JobSpec>,
+
+    /// Whether to return when any one of the jobs finishes (the `-n` option)
+    pub next: bool,
 }
 
 pub mod core;
@@ -154,6 +166,25 @@ impl Command {
         status::wait_while_running(env, &mut status::any_job_is_running(job_control)).await
     }
 
+    /// Waits for any one of the jobs specified by the indexes.
+    ///
+    /// If `indexes` is empty, waits for any of the jobs owned by the shell.
+    async fn await_next_job(
+        env: &mut Env,
+        mut indexes: Vec<Option<usize>>,
+    ) -> Result<ExitStatus, core::Error> {
+        let job_control = env.options.get(Monitor);
+        if indexes.is_empty() {
+            indexes = env
+                .jobs
+                .iter()
+                .filter(|(_, job)| job.is_owned)
+                .map(|(index, _)| Some(index))
+                .collect();
+        }
+        status::wait_while_running(env, &mut status::any_job_status(indexes, job_control)).await
+    }
+
     /// Executes the `wait` built-in.
     pub async fn execute(self, env: &mut Env) -> crate::Result {
         // Resolve job specifications to indexes
@@ -166,7 +197,12 @@ impl Command {
         }
 
         // Await jobs specified by the indexes
-        match Self::await_jobs(env, indexes).await {
+        let result = if self.next {
+            Self::await_next_job(env, indexes).await
+        } else {
+            Self::await_jobs(env, indexes).await
+        };
+        match result {
             Ok(exit_status) => exit_status.into(),
             Err(core::Error::Trapped(signal, divert)) => {
                 crate::Result::with_exit_status_and_divert(ExitStatus::from(signal), divert)
@@ -183,3 +219,91 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         Err(error) => report_error(env, &error).await,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::poll;
+    use futures_util::FutureExt as _;
+    use std::future::pending;
+    use std::ops::ControlFlow::Continue;
+    use std::pin::pin;
+    use std::task::Poll;
+    use yash_env::job::Job;
+    use yash_env::subshell::Subshell;
+    use yash_env::system::r#virtual::SIGINT;
+    use yash_env::trap::Action;
+    use yash_env::variable::Value;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::in_virtual_system;
+    use yash_syntax::source::Location;
+
+    #[test]
+    fn interrupted_by_trapped_signal() {
+        in_virtual_system(|mut env, state| async move {
+            let mut system = VirtualSystem {
+                state,
+                process_id: env.main_pid,
+            };
+            env.traps
+                .set_action(
+                    &mut env.system,
+                    SIGINT,
+                    Action::Command("foo=bar".into()),
+                    Location::dummy("somewhere"),
+                    false,
+                )
+                .unwrap();
+
+            // Start a job that never finishes.
+            let subshell = Subshell::new(|_, _| Box::pin(pending()));
+            let pid = subshell.start(&mut env).await.unwrap().0;
+            env.jobs.add(Job::new(pid));
+
+            let result = {
+                let mut future = pin!(main(&mut env, vec![]));
+                assert_eq!(poll!(&mut future), Poll::Pending);
+
+                _ = system.current_process_mut().raise_signal(SIGINT);
+                future.await
+            };
+
+            assert_eq!(result.exit_status(), ExitStatus::from(SIGINT));
+            assert!(result.exit_status().0 > 128, "{result:?}");
+            assert_eq!(result.divert(), Continue(()));
+            assert_eq!(
+                env.variables.get("foo").unwrap().value,
+                Some(Value::scalar("bar")),
+            );
+        });
+    }
+
+    #[test]
+    fn n_option_returns_first_finished_job() {
+        in_virtual_system(|mut env, _state| async move {
+            // Start a job that never finishes and another that exits soon.
+            let subshell = Subshell::new(|_, _| Box::pin(pending()));
+            let pid1 = subshell.start(&mut env).await.unwrap().0;
+            let index1 = env.jobs.add(Job::new(pid1));
+            let subshell =
+                Subshell::new(|env, _| Box::pin(async move { env.exit_status = ExitStatus(42) }));
+            let pid2 = subshell.start(&mut env).await.unwrap().0;
+            let index2 = env.jobs.add(Job::new(pid2));
+
+            let result = main(&mut env, Field::dummies(["-n"])).await;
+
+            assert_eq!(result, crate::Result::from(ExitStatus(42)));
+            assert_eq!(env.jobs.get(index2), None);
+            assert_eq!(env.jobs[index1].pid, pid1);
+        });
+    }
+
+    #[test]
+    fn n_option_without_jobs() {
+        let mut env = Env::new_virtual();
+        let result = main(&mut env, Field::dummies(["-n"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::NOT_FOUND));
+    }
+}
@@ -21,9 +21,10 @@
 //! [`ControlFlow::Break`], `wait_while_running` stops waiting and returns the
 //! exit status.
 //!
-//! You can pass a closure that is created by [`job_status`] or
-//! [`any_job_is_running`] to `wait_while_running`. The former tests if a
-//! specific job has finished, and the latter tests if all jobs have finished.
+//! You can pass a closure that is created by [`job_status`],
+//! [`any_job_is_running`], or [`any_job_status`] to `wait_while_running`. The
+//! first tests if a specific job has finished, the second tests if all jobs
+//! have finished, and the last tests if any of the given jobs has finished.
 
 use super::core::wait_for_any_job_or_trap;
 use super::core::Error;
@@ -117,6 +118,32 @@ pub fn any_job_is_running(
     }
 }
 
+/// Returns a closure that tests if any of the given jobs has finished.
+///
+/// The closure applies [`job_status`] to each index in `indexes` in order and
+/// returns the first [`ControlFlow::Break`] result. A `None` index is regarded
+/// as a job that has already finished with [`ExitStatus::NOT_FOUND`]. If
+/// `indexes` is empty, the closure returns [`ControlFlow::Break`] having
+/// [`ExitStatus::NOT_FOUND`]. Otherwise, the closure returns
+/// [`ControlFlow::Continue`].
+pub fn any_job_status(
+    indexes: Vec<Option<usize>>,
+    job_control: State,
+) -> impl FnMut(&mut JobList) -> ControlFlow<ExitStatus> {
+    move |jobs| {
+        if indexes.is_empty() {
+            return ControlFlow::Break(ExitStatus::NOT_FOUND);
+        }
+        for &index in &indexes {
+            let Some(index) = index else {
+                return ControlFlow::Break(ExitStatus::NOT_FOUND);
+            };
+            job_status(index, job_control)(jobs)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +353,51 @@ mod tests {
 
         assert_eq!(any_job_is_running(On)(&mut jobs), ControlFlow::Continue(()));
     }
+
+    #[test]
+    fn any_job_status_with_no_job() {
+        let mut jobs = JobList::new();
+        assert_eq!(
+            any_job_status(vec![], Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus::NOT_FOUND),
+        );
+        assert_eq!(
+            any_job_status(vec![None], On)(&mut jobs),
+            ControlFlow::Break(ExitStatus::NOT_FOUND),
+        );
+    }
+
+    #[test]
+    fn any_job_status_with_running_jobs() {
+        let mut jobs = JobList::new();
+        let i123 = jobs.add(Job::new(Pid(123)));
+        let i456 = jobs.add(Job::new(Pid(456)));
+
+        assert_eq!(
+            any_job_status(vec![Some(i123), Some(i456)], Off)(&mut jobs),
+            ControlFlow::Continue(()),
+        );
+        assert_eq!(jobs.len(), 2);
+    }
+
+    #[test]
+    fn any_job_status_with_finished_job() {
+        let mut jobs = JobList::new();
+        let i123 = jobs.add(Job::new(Pid(123)));
+        let mut job = Job::new(Pid(456));
+        job.state = ProcessState::exited(42);
+        let i456 = jobs.add(job);
+        let mut job = Job::new(Pid(789));
+        job.state = ProcessState::exited(0);
+        let i789 = jobs.add(job);
+
+        assert_eq!(
+            any_job_status(vec![Some(i123), Some(i456), Some(i789)], Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus(42)),
+        );
+        // Only the first finished job is removed.
+        assert_eq!(jobs.get(i456), None);
+        assert_eq!(jobs[i123].pid, Pid(123));
+        assert_eq!(jobs[i789].pid, Pid(789));
+    }
 }
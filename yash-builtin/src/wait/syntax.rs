@@ -25,7 +25,7 @@ use yash_env::semantics::Field;
 use yash_env::Env;
 use yash_syntax::source::pretty::{Annotation, AnnotationType, MessageBase};
 
-use crate::common::syntax::{parse_arguments, Mode, ParseError};
+use crate::common::syntax::{parse_arguments, Mode, OptionSpec, ParseError};
 
 /// Errors that may occur while parsing command line arguments
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -76,14 +76,17 @@ impl TryFrom<Field> for JobSpec {
     }
 }
 
+const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('n').long("next")];
+
 /// Parses command line arguments for the wait built-in.
 pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
-    let (_, operands) = parse_arguments(&[], Mode::with_env(env), args)?;
+    let (options, operands) = parse_arguments(OPTION_SPECS, Mode::with_env(env), args)?;
+    let next = !options.is_empty();
     let jobs = operands
         .into_iter()
         .map(JobSpec::try_from)
         .collect::<Result<Vec<JobSpec>, Error>>()?;
-    Ok(Command { jobs })
+    Ok(Command { jobs, next })
 }
 
 #[cfg(test)]
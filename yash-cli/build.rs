@@ -0,0 +1,38 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Build script that exports build information for `--version --verbose`
+//!
+//! This script sets the following environment variables for the crate:
+//!
+//! - `YASH_TARGET`: the target triple the shell is compiled for
+//! - `YASH_FEATURES`: comma-separated names of the enabled cargo features
+
+fn main() {
+    let target = std::env::var("TARGET").unwrap();
+    println!("cargo:rustc-env=YASH_TARGET={target}");
+
+    let mut features = std::env::vars()
+        .filter_map(|(name, _)| {
+            let feature = name.strip_prefix("CARGO_FEATURE_")?;
+            Some(feature.to_ascii_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+    println!("cargo:rustc-env=YASH_FEATURES={}", features.join(","));
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -26,25 +26,56 @@
 pub mod startup;
 // mod runner;
 
-use self::startup::args::Parse;
-use self::startup::init_file::run_rcfile;
-use self::startup::input::prepare_input;
+use self::startup::args::{Parse, Source};
+use self::startup::init_file::{run_profile, run_rcfile};
+use self::startup::input::{disable_nonblocking_stdin, prepare_input};
+use self::startup::restrict;
 use std::cell::RefCell;
-use std::ops::ControlFlow::{Break, Continue};
-use yash_env::option::{Interactive, On};
+use std::ffi::CString;
+use yash_env::io::Fd;
+use yash_env::option::{Interactive, Off, On, Restricted};
 use yash_env::signal;
-use yash_env::system::{Disposition, Errno};
+use yash_env::system::{Disposition, Errno, AT_FDCWD};
 use yash_env::Env;
 use yash_env::RealSystem;
 use yash_env::System;
 use yash_executor::Executor;
-use yash_semantics::trap::run_exit_trap;
-use yash_semantics::{interactive_read_eval_loop, read_eval_loop};
-use yash_semantics::{Divert, ExitStatus};
+use yash_semantics::ExitStatus;
+use yash_semantics::{interactive_read_eval_loop, read_eval_loop, shutdown};
 
-async fn print_version(env: &mut Env) -> ExitStatus {
-    let version = env!("CARGO_PKG_VERSION");
-    let result = yash_builtin::common::output(env, &format!("yash {}\n", version)).await;
+async fn print_help(env: &mut Env) -> ExitStatus {
+    let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
+    let result = yash_builtin::common::output(env, &self::startup::args::help(&arg0)).await;
+    result.exit_status()
+}
+
+/// Build-time settings reported by `--version --verbose`
+const BUILD_SETTINGS: &[(&str, bool)] = &[
+    ("debug-assertions", cfg!(debug_assertions)),
+    ("panic-unwind", cfg!(panic = "unwind")),
+];
+
+fn version_info(verbose: bool) -> String {
+    let mut info = format!("yash {}\n", env!("CARGO_PKG_VERSION"));
+    if verbose {
+        // These variables are set by the build script.
+        info.push_str(&format!("target: {}\n", env!("YASH_TARGET")));
+        let features = match env!("YASH_FEATURES") {
+            "" => "(none)".to_string(),
+            features => features.replace(',', ", "),
+        };
+        info.push_str(&format!("features: {features}\n"));
+        info.push_str("build settings:\n");
+        for &(name, enabled) in BUILD_SETTINGS {
+            let sign = if enabled { '+' } else { '-' };
+            info.push_str(&format!("  {sign}{name}\n"));
+        }
+    }
+    info
+}
+
+async fn print_version(env: &mut Env, verbose: bool) -> ExitStatus {
+    let result = yash_builtin::common::output(env, &version_info(verbose)).await;
     result.exit_status()
 }
 
@@ -53,8 +84,8 @@ async fn print_version(env: &mut Env) -> ExitStatus {
 async fn parse_and_print(mut env: Env) -> ExitStatus {
     // Parse the command-line arguments
     let run = match self::startup::args::parse(std::env::args()) {
-        Ok(Parse::Help) => todo!("print help"),
-        Ok(Parse::Version) => return print_version(&mut env).await,
+        Ok(Parse::Help) => return print_help(&mut env).await,
+        Ok(Parse::Version { verbose }) => return print_version(&mut env, verbose).await,
         Ok(Parse::Run(run)) => run,
         Err(e) => {
             let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
@@ -70,13 +101,27 @@ async fn parse_and_print(mut env: Env) -> ExitStatus {
 
     let is_interactive = env.options.get(Interactive) == On;
 
-    // Run initialization files
-    // TODO run profile if login
+    // Run initialization files, which are not subject to the restrictions
+    let is_restricted = env.options.get(Restricted) == On;
+    env.options.set(Restricted, Off);
+    run_profile(&mut env, work.profile).await;
     run_rcfile(&mut env, work.rcfile).await;
+    if is_restricted {
+        restrict(&mut env);
+    }
 
     // Prepare the input for the main read-eval loop
+    if let Source::File { path } = &work.source {
+        if let Ok(c_path) = CString::new(path.as_str()) {
+            if let Ok(stat) = env.system.fstatat(AT_FDCWD, &c_path, true) {
+                env.warn_writable_script(&stat, path).await;
+            }
+        }
+    }
+    let stdin_was_nonblocking =
+        work.source == Source::Stdin && disable_nonblocking_stdin(&mut env.system);
     let ref_env = &RefCell::new(&mut env);
-    let lexer = match prepare_input(ref_env, &work.source) {
+    let lexer = match prepare_input(ref_env, &work.source, &work.eof) {
         Ok(lexer) => lexer,
         Err(e) => {
             let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
@@ -86,6 +131,12 @@ async fn parse_and_print(mut env: Env) -> ExitStatus {
             // instead of reusing `env`.
             // env.system.print_error(&message).await;
             ref_env.borrow_mut().system.print_error(&message).await;
+            if stdin_was_nonblocking {
+                _ = ref_env
+                    .borrow_mut()
+                    .system
+                    .get_and_set_nonblocking(Fd::STDIN, true);
+            }
             return match e.errno {
                 Errno::ENOENT | Errno::ENOTDIR | Errno::EILSEQ => ExitStatus::NOT_FOUND,
                 _ => ExitStatus::NOEXEC,
@@ -100,19 +151,11 @@ async fn parse_and_print(mut env: Env) -> ExitStatus {
         read_eval_loop(ref_env, &mut { lexer }).await
     };
 
-    env.apply_result(result);
-
-    match result {
-        Continue(())
-        | Break(Divert::Continue { .. })
-        | Break(Divert::Break { .. })
-        | Break(Divert::Return(_))
-        | Break(Divert::Interrupt(_))
-        | Break(Divert::Exit(_)) => run_exit_trap(&mut env).await,
-        Break(Divert::Abort(_)) => (),
+    let exit_status = shutdown(&mut env, result).await;
+    if stdin_was_nonblocking {
+        _ = env.system.get_and_set_nonblocking(Fd::STDIN, true);
     }
-
-    env.exit_status
+    exit_status
 }
 
 pub fn main() -> ! {
@@ -144,3 +187,52 @@ pub fn main() -> ! {
         system.select(false).ok();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::system::r#virtual::{FileBody, VirtualSystem};
+
+    #[test]
+    fn print_help_writes_to_stdout() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let exit_status = print_help(&mut env).now_or_never().unwrap();
+        assert_eq!(exit_status, ExitStatus::SUCCESS);
+        let stdout = state.borrow().file_system.get("/dev/stdout").unwrap();
+        let stdout = stdout.borrow();
+        let FileBody::Regular { content, .. } = &stdout.body else {
+            panic!("unexpected file body: {:?}", stdout.body);
+        };
+        let content = std::str::from_utf8(content).unwrap();
+        assert!(content.starts_with("Usage: "), "{content}");
+        assert!(content.contains("--help"), "{content}");
+    }
+
+    #[test]
+    fn version_info_without_verbose() {
+        let info = version_info(false);
+        assert_eq!(info, format!("yash {}\n", env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn version_info_with_verbose() {
+        let info = version_info(true);
+        let first_line = format!("yash {}\n", env!("CARGO_PKG_VERSION"));
+        assert!(info.starts_with(&first_line), "{info}");
+        let target = format!("\ntarget: {}\n", env!("YASH_TARGET"));
+        assert!(info.contains(&target), "{info}");
+        assert!(info.contains("\nfeatures: "), "{info}");
+        assert!(info.contains("\nbuild settings:\n"), "{info}");
+        let debug_assertions = if cfg!(debug_assertions) {
+            "\n  +debug-assertions\n"
+        } else {
+            "\n  -debug-assertions\n"
+        };
+        assert!(info.contains(debug_assertions), "{info}");
+    }
+}
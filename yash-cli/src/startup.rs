@@ -17,12 +17,18 @@
 //! Shell startup
 
 use self::args::{Run, Source, Work};
+use std::cell::RefCell;
+use std::num::NonZeroU64;
+use std::rc::Rc;
 use yash_builtin::BUILTINS;
 use yash_env::io::Fd;
-use yash_env::option::Option::{Interactive, Monitor, Stdin};
+use yash_env::option::Option::{Interactive, Monitor, Restricted, Stdin};
 use yash_env::option::State::On;
+use yash_env::system::{Buffering, FdKind, SystemEx as _};
+use yash_env::variable::{Scope, PATH};
 use yash_env::Env;
 use yash_env::System;
+use yash_syntax::source::{Code, Location, Source as SyntaxSource};
 
 pub mod args;
 pub mod init_file;
@@ -43,7 +49,8 @@ pub fn auto_interactive<S: System>(system: &S, run: &Run) -> bool {
     if !run.positional_params.is_empty() {
         return false;
     }
-    system.isatty(Fd::STDIN) && system.isatty(Fd::STDERR)
+    system.fd_kind(Fd::STDIN) == Ok(FdKind::Terminal)
+        && system.fd_kind(Fd::STDERR) == Ok(FdKind::Terminal)
 }
 
 /// Get the environment ready for performing the work.
@@ -79,18 +86,216 @@ pub fn configure_environment(env: &mut Env, run: Run) -> Work {
         env.traps
             .enable_internal_dispositions_for_terminators(&mut env.system)
             .ok();
+        env.traps
+            .enable_internal_disposition_for_sigwinch(&mut env.system)
+            .ok();
         if env.options.get(Monitor) == On {
             env.traps
                 .enable_internal_dispositions_for_stoppers(&mut env.system)
                 .ok();
+
+            // Open the controlling terminal in advance for job control. If it
+            // is not available, job control still works, but the shell cannot
+            // change the foreground job.
+            env.get_tty().ok();
         }
     }
 
+    // Make the standard output line-buffered if it is a terminal
+    if env.system.fd_kind(Fd::STDOUT) == Ok(FdKind::Terminal) {
+        env.system.set_stdout_buffering(Buffering::Line);
+    }
+
     // Prepare built-ins
     env.builtins.extend(BUILTINS.iter().cloned());
 
     // Prepare variables
     env.init_variables();
+    if env.options.get(Interactive) == On {
+        env.init_terminal_size_variables().ok();
+    }
 
     run.work
 }
+
+/// Puts the shell into the restricted mode.
+///
+/// This function turns on the [`Restricted`] option and makes `$PATH`
+/// read-only so that the user cannot change the set of available commands.
+/// The other restrictions are enforced by the built-ins and the command
+/// execution depending on the option.
+///
+/// The restricted mode should be entered after running the initialization
+/// files so that they can configure the environment without restrictions.
+pub fn restrict(env: &mut Env) {
+    env.options.set(Restricted, On);
+
+    let label = "restricted shell".to_string();
+    let range = 0..label.chars().count();
+    let code = Rc::new(Code {
+        value: RefCell::new(label.clone()),
+        start_line_number: NonZeroU64::MIN,
+        source: Rc::new(SyntaxSource::Other { label }),
+    });
+    let location = Location { code, range };
+    env.variables
+        .get_or_new(PATH, Scope::Global)
+        .make_read_only(location);
+}
+
+#[cfg(test)]
+mod tests {
+    use self::args::{parse, Parse};
+    use super::*;
+    use std::time::Instant;
+    use yash_env::system::r#virtual::{FileBody, VirtualSystem, SIGTSTP};
+    use yash_env::system::Disposition;
+    use yash_env::system::TerminalSize;
+    use yash_env::variable::{COLUMNS, LINES};
+    use yash_env_test_helper::stub_tty_with_size;
+
+    /// Returns a new virtual system whose clock is set so that
+    /// [`Env::init_variables`] can read the current time.
+    fn system_with_clock() -> VirtualSystem {
+        let system = VirtualSystem::new();
+        system.state.borrow_mut().now = Some(Instant::now());
+        system
+    }
+
+    #[test]
+    fn configure_environment_with_command_string_and_operands() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-c", "echo $1", "sh", "a", "b"]) else {
+            panic!("unexpected parse result");
+        };
+        let mut env = Env::with_system(Box::new(system_with_clock()));
+
+        let work = configure_environment(&mut env, run);
+        assert_eq!(work.source, Source::String("echo $1".to_string()));
+        assert_eq!(env.arg0, "sh");
+        assert_eq!(env.variables.positional_params().values, ["a", "b"]);
+    }
+
+    #[test]
+    fn configure_environment_line_buffers_terminal_stdout() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        let stdout = system
+            .state
+            .borrow()
+            .file_system
+            .get("/dev/stdout")
+            .unwrap();
+        stdout.borrow_mut().body = FileBody::Terminal { content: vec![] };
+        let mut env = Env::with_system(Box::new(system));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.system.stdout_buffering(), Buffering::Line);
+    }
+
+    #[test]
+    fn configure_environment_leaves_non_terminal_stdout_unbuffered() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let mut env = Env::with_system(Box::new(system_with_clock()));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.system.stdout_buffering(), Buffering::Unbuffered);
+    }
+
+    const TERMINAL_SIZE: TerminalSize = TerminalSize {
+        rows: 30,
+        columns: 100,
+    };
+
+    #[test]
+    fn configure_environment_opens_tty_for_job_control() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-i", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        stub_tty_with_size(&system.state, TERMINAL_SIZE);
+        let mut env = Env::with_system(Box::new(system));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.options.get(Monitor), On);
+        assert!(env.tty.is_some(), "{:?}", env.tty);
+    }
+
+    #[test]
+    fn configure_environment_sets_up_job_control_without_tty() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-i", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.options.get(Monitor), On);
+        assert_eq!(env.tty, None);
+        let state = state.borrow();
+        let disposition = state.processes[&env.main_pid].disposition(SIGTSTP);
+        assert_eq!(disposition, Disposition::Ignore);
+    }
+
+    #[test]
+    fn configure_environment_sets_terminal_size_variables_if_interactive() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-i", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        stub_tty_with_size(&system.state, TERMINAL_SIZE);
+        let mut env = Env::with_system(Box::new(system));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("100"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("30"));
+    }
+
+    #[test]
+    fn configure_environment_keeps_inherited_terminal_size_variables() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-i", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        stub_tty_with_size(&system.state, TERMINAL_SIZE);
+        let mut env = Env::with_system(Box::new(system));
+        env.variables.extend_env([(COLUMNS, "132")]);
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("132"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("30"));
+    }
+
+    #[test]
+    fn configure_environment_leaves_terminal_size_variables_if_non_interactive() {
+        let Ok(Parse::Run(run)) = parse(["yash", "-c", ""]) else {
+            panic!("unexpected parse result");
+        };
+        let system = system_with_clock();
+        stub_tty_with_size(&system.state, TERMINAL_SIZE);
+        let mut env = Env::with_system(Box::new(system));
+
+        configure_environment(&mut env, run);
+        assert_eq!(env.variables.get(COLUMNS), None);
+        assert_eq!(env.variables.get(LINES), None);
+    }
+
+    #[test]
+    fn restrict_makes_path_read_only() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/bin", None)
+            .unwrap();
+
+        restrict(&mut env);
+        assert_eq!(env.options.get(Restricted), On);
+        let mut path = env.variables.get_or_new(PATH, Scope::Global);
+        assert!(path.assign("/tmp", None).is_err());
+        assert_eq!(env.variables.get_scalar(PATH), Some("/bin"));
+    }
+}
@@ -16,12 +16,13 @@
 
 //! Command line argument parser for the shell
 
+use super::input::EofConfig;
 use std::iter::Peekable;
 use thiserror::Error;
 use yash_env::option::canonicalize;
 use yash_env::option::parse_long;
 use yash_env::option::parse_short;
-use yash_env::option::FromStrError::{Ambiguous, NoSuchOption};
+use yash_env::option::FromStrError::{self, Ambiguous, NoSuchOption};
 use yash_env::option::Option as ShellOption;
 use yash_env::option::State;
 #[cfg(doc)]
@@ -60,6 +61,10 @@ pub struct Work {
     pub profile: InitFile,
     /// Initialization file for an interactive shell
     pub rcfile: InitFile,
+    /// Behavior on EOF in an interactive shell
+    ///
+    /// This is set by the `--eof-message` and `--eof-limit` options.
+    pub eof: EofConfig,
 }
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
@@ -83,7 +88,15 @@ pub enum Parse {
     /// Prints help message and exit
     Help,
     /// Prints version information and exit
-    Version,
+    Version {
+        /// Whether to include build information
+        ///
+        /// This is true if the `-v` (`--verbose`) shell option is enabled in
+        /// the command line arguments. Options following the version option
+        /// are parsed leniently: parsing stops at the first operand or
+        /// invalid option without an error.
+        verbose: bool,
+    },
 }
 
 impl From<Run> for Parse {
@@ -130,6 +143,10 @@ pub enum Error {
     /// The `-c` option without a command string
     #[error("missing command string for `-c`")]
     MissingCommandString,
+
+    /// Argument to the `--eof-limit` option that is not a non-negative integer
+    #[error("invalid EOF limit `{0}`")]
+    InvalidEofLimit(String),
 }
 
 /// Result of parsing short options
@@ -149,6 +166,8 @@ enum LongOption {
     NoProfile,
     Rcfile { path: String },
     NoRcfile,
+    EofMessage { message: String },
+    EofLimit { limit: String },
     Help,
     Version,
 }
@@ -160,22 +179,52 @@ enum NonShellOptionConstructor {
     WithArgument(fn(String) -> LongOption),
 }
 
+/// Names of the long options that are not shell options
+const NON_SHELL_OPTIONS: &[(&str, NonShellOptionConstructor)] = &[
+    (
+        "profile",
+        NonShellOptionConstructor::WithArgument(|path| LongOption::Profile { path }),
+    ),
+    (
+        "rcfile",
+        NonShellOptionConstructor::WithArgument(|path| LongOption::Rcfile { path }),
+    ),
+    (
+        "noprofile",
+        NonShellOptionConstructor::WithoutArgument(LongOption::NoProfile),
+    ),
+    (
+        "norcfile",
+        NonShellOptionConstructor::WithoutArgument(LongOption::NoRcfile),
+    ),
+    (
+        "eof-message",
+        NonShellOptionConstructor::WithArgument(|message| LongOption::EofMessage { message }),
+    ),
+    (
+        "eof-limit",
+        NonShellOptionConstructor::WithArgument(|limit| LongOption::EofLimit { limit }),
+    ),
+    (
+        "help",
+        NonShellOptionConstructor::WithoutArgument(LongOption::Help),
+    ),
+    (
+        "version",
+        NonShellOptionConstructor::WithoutArgument(LongOption::Version),
+    ),
+];
+
 impl NonShellOptionConstructor {
-    fn from_name(name: &str) -> Option<Self> {
-        if "profile".starts_with(name) {
-            Some(Self::WithArgument(|path| LongOption::Profile { path }))
-        } else if "rcfile".starts_with(name) {
-            Some(Self::WithArgument(|path| LongOption::Rcfile { path }))
-        } else if "noprofile".starts_with(name) {
-            Some(Self::WithoutArgument(LongOption::NoProfile))
-        } else if "norcfile".starts_with(name) {
-            Some(Self::WithoutArgument(LongOption::NoRcfile))
-        } else if "help".starts_with(name) {
-            Some(Self::WithoutArgument(LongOption::Help))
-        } else if "version".starts_with(name) {
-            Some(Self::WithoutArgument(LongOption::Version))
-        } else {
-            None
+    /// Finds the non-shell option whose name starts with the given string.
+    fn from_name(name: &str) -> Result<Self, FromStrError> {
+        let mut matches = NON_SHELL_OPTIONS
+            .iter()
+            .filter(|(full_name, _)| full_name.starts_with(name));
+        match (matches.next(), matches.next()) {
+            (None, _) => Err(NoSuchOption),
+            (Some((_, constructor)), None) => Ok(constructor.clone()),
+            (Some(_), Some(_)) => Err(Ambiguous),
         }
     }
 }
@@ -203,7 +252,7 @@ where
         if let Some(option) = try_parse_short(&mut args, &mut result.options)? {
             match option {
                 ShortOption::Shell => continue,
-                ShortOption::Version => return Ok(Parse::Version),
+                ShortOption::Version => return Ok(version(&mut args, &mut result.options)),
             }
         }
 
@@ -224,8 +273,18 @@ where
                 }
             }
             LongOption::NoRcfile => result.work.rcfile = InitFile::None,
+            LongOption::EofMessage { mut message } => {
+                if !message.is_empty() {
+                    message.push('\n');
+                }
+                result.work.eof.message = message;
+            }
+            LongOption::EofLimit { limit } => match limit.parse() {
+                Ok(limit) => result.work.eof.limit = limit,
+                Err(_) => return Err(Error::InvalidEofLimit(limit)),
+            },
             LongOption::Help => return Ok(Parse::Help),
-            LongOption::Version => return Ok(Parse::Version),
+            LongOption::Version => return Ok(version(&mut args, &mut result.options)),
         }
     }
 
@@ -256,12 +315,46 @@ where
     Ok(Parse::Run(result))
 }
 
+/// Returns `Parse::Version` for the `-V` or `--version` option.
+///
+/// This function parses the shell options following the version option so
+/// that `-v` (`--verbose`) can be specified either before or after it. Parsing
+/// stops at the first argument that is not a valid option, which is ignored
+/// along with the rest of the arguments. The `verbose` flag is set if the last
+/// occurrence of the `-v` (`--verbose`) option enables it.
+fn version<I: Iterator<Item = String>>(
+    args: &mut Peekable<I>,
+    options: &mut Vec<(ShellOption, State)>,
+) -> Parse {
+    loop {
+        match try_parse_short(args, options) {
+            Ok(Some(_)) => continue,
+            Ok(None) => (),
+            Err(_) => break,
+        }
+        match try_parse_long(args) {
+            Ok(Some(LongOption::Shell(option, state))) => options.push((option, state)),
+            Ok(Some(_)) => (),
+            Ok(None) | Err(_) => break,
+        }
+    }
+
+    let verbose = options
+        .iter()
+        .rev()
+        .find(|(option, _)| *option == ShellOption::Verbose)
+        .is_some_and(|(_, state)| *state == State::On);
+    Parse::Version { verbose }
+}
+
 fn parse_arg0(arg0: &str, options: &mut Vec<(ShellOption, State)>) {
     if arg0.starts_with('-') {
         options.push((ShellOption::Login, State::On));
     }
-    if arg0.rsplit('/').next().unwrap_or("") == "sh" {
-        options.push((ShellOption::PosixlyCorrect, State::On));
+    match arg0.rsplit('/').next().unwrap_or("") {
+        "sh" => options.push((ShellOption::PosixlyCorrect, State::On)),
+        "rysh" => options.push((ShellOption::Restricted, State::On)),
+        _ => (),
     }
 }
 
@@ -271,6 +364,8 @@ fn parse_arg0(arg0: &str, options: &mut Vec<(ShellOption, State)>) {
 /// If the next argument is a short option, consumes it and returns `Ok(Some(_))`.
 /// The parsed options are added to `option_occurrences`.
 /// If the `-V` option is included, returns `Ok(Some(ShortOption::Version))`.
+/// Options following `V` in the same argument are parsed leniently as in
+/// [`version`].
 fn try_parse_short<I: Iterator<Item = String>>(
     args: &mut Peekable<I>,
     option_occurrences: &mut Vec<(ShellOption, State)>,
@@ -288,11 +383,16 @@ fn try_parse_short<I: Iterator<Item = String>>(
 
     while let Some(c) = chars.next() {
         if c == 'V' {
-            return if negate {
-                Err(Error::UnnegatableShortOption('V'))
-            } else {
-                Ok(Some(ShortOption::Version))
-            };
+            if negate {
+                return Err(Error::UnnegatableShortOption('V'));
+            }
+            for c in chars.by_ref() {
+                let Some((option, state)) = parse_short(c) else {
+                    break;
+                };
+                option_occurrences.push((option, state));
+            }
+            return Ok(Some(ShortOption::Version));
         }
         if c == 'o' {
             let name = chars.as_str();
@@ -368,13 +468,15 @@ fn try_parse_long<I: Iterator<Item = String>>(
 
     // Check if the result is unique and return the final result
     match (non_shell_option, shell_option) {
-        (_, Err(Ambiguous)) | (Some(_), Ok(_)) => Err(Error::AmbiguousLongOption(arg)),
+        (Err(Ambiguous), _) | (_, Err(Ambiguous)) | (Ok(_), Ok(_)) => {
+            Err(Error::AmbiguousLongOption(arg))
+        }
 
-        (None, Err(NoSuchOption)) => Err(Error::UnknownLongOption(arg)),
+        (Err(NoSuchOption), Err(NoSuchOption)) => Err(Error::UnknownLongOption(arg)),
 
-        (Some(_), Err(NoSuchOption)) if negate => Err(Error::UnnegatableLongOption(arg)),
+        (Ok(_), Err(NoSuchOption)) if negate => Err(Error::UnnegatableLongOption(arg)),
 
-        (Some(NonShellOptionConstructor::WithoutArgument(option)), Err(NoSuchOption)) => {
+        (Ok(NonShellOptionConstructor::WithoutArgument(option)), Err(NoSuchOption)) => {
             if value.is_none() {
                 Ok(Some(option))
             } else {
@@ -382,7 +484,7 @@ fn try_parse_long<I: Iterator<Item = String>>(
             }
         }
 
-        (Some(NonShellOptionConstructor::WithArgument(ctor)), Err(NoSuchOption)) => {
+        (Ok(NonShellOptionConstructor::WithArgument(ctor)), Err(NoSuchOption)) => {
             let value = match value {
                 Some(value) => value.to_owned(),
                 None => match args.next() {
@@ -393,8 +495,10 @@ fn try_parse_long<I: Iterator<Item = String>>(
             Ok(Some(ctor(value)))
         }
 
-        (None, Ok((option, state))) if negate => Ok(Some(LongOption::Shell(option, !state))),
-        (None, Ok((option, state))) => Ok(Some(LongOption::Shell(option, state))),
+        (Err(NoSuchOption), Ok((option, state))) if negate => {
+            Ok(Some(LongOption::Shell(option, !state)))
+        }
+        (Err(NoSuchOption), Ok((option, state))) => Ok(Some(LongOption::Shell(option, state))),
     }
 }
 
@@ -407,6 +511,49 @@ fn is_long_option(arg: &str) -> bool {
     }
 }
 
+/// Returns the help message printed for the `--help` option.
+///
+/// The message describes the invocation modes of the shell and the options
+/// accepted by [`parse`]. `arg0` is the command name shown in the usage lines.
+#[must_use]
+pub fn help(arg0: &str) -> String {
+    let mut help = format!(
+        "Usage: {arg0} [option...] [file [argument...]]
+       {arg0} [option...] -c command [name [argument...]]
+       {arg0} [option...] -s [argument...]
+
+Options:
+  -c                 read commands from the `command` operand
+  -s                 read commands from the standard input
+  -o option          enable a shell option (`+o` disables it)
+  --option           enable a shell option (`++option` disables it)
+  --profile=file     read `file` instead of the default profile
+  --noprofile        do not read the profile
+  --rcfile=file      read `file` instead of the default rcfile
+  --norcfile         do not read the rcfile
+  --eof-message=msg  print `msg` when the ignore-eof option ignores an EOF
+  --eof-limit=n      ignore up to `n` consecutive EOFs with ignore-eof
+  --help             print this help and exit
+  -V, --version      print version information and exit
+                     (with -v, also print build information)
+
+Shell options:
+"
+    );
+    for option in ShellOption::iter() {
+        let short = match option.short_name() {
+            Some((c, _)) => format!("-{c}"),
+            None => String::new(),
+        };
+        let long = match option.short_name() {
+            Some((_, State::Off)) => format!("--no{}", option.long_name()),
+            _ => format!("--{}", option.long_name()),
+        };
+        help.push_str(&format!("  {short:4} {long}\n"));
+    }
+    help
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -959,6 +1106,46 @@ mod tests {
         assert_eq!(parse(["yash", "--norcfile", "--rcfile=file"]), expected);
     }
 
+    #[test]
+    fn eof_message_option() {
+        let result = parse(["yash", "--eof-message", "Use exit"]);
+        assert_matches!(result, Ok(Parse::Run(run)) => {
+            assert_eq!(run.work.eof.message, "Use exit\n");
+            assert_eq!(run.work.eof.limit, EofConfig::default().limit);
+        });
+
+        let result = parse(["yash", "--eof-m="]);
+        assert_matches!(result, Ok(Parse::Run(run)) => {
+            assert_eq!(run.work.eof.message, "");
+        });
+    }
+
+    #[test]
+    fn eof_limit_option() {
+        let result = parse(["yash", "--eof-limit", "3"]);
+        assert_matches!(result, Ok(Parse::Run(run)) => {
+            assert_eq!(run.work.eof.message, EofConfig::default().message);
+            assert_eq!(run.work.eof.limit, 3);
+        });
+
+        let result = parse(["yash", "--eof-l=0"]);
+        assert_matches!(result, Ok(Parse::Run(run)) => {
+            assert_eq!(run.work.eof.limit, 0);
+        });
+    }
+
+    #[test]
+    fn invalid_eof_limit_option_argument() {
+        assert_eq!(
+            parse(["yash", "--eof-limit=-1"]),
+            Err(Error::InvalidEofLimit("-1".to_string())),
+        );
+        assert_eq!(
+            parse(["yash", "--eof-limit", "x"]),
+            Err(Error::InvalidEofLimit("x".to_string())),
+        );
+    }
+
     #[test]
     fn option_combinations() {
         assert_eq!(
@@ -1148,19 +1335,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn command_name_rysh_enables_restricted_mode() {
+        assert_eq!(
+            parse(["/usr/bin/rysh"]),
+            Ok(Parse::Run(Run {
+                options: vec![(ShellOption::Restricted, State::On)],
+                arg0: "/usr/bin/rysh".to_string(),
+                ..Run::default()
+            })),
+        );
+    }
+
+    #[test]
+    fn restricted_option() {
+        assert_eq!(
+            parse(["yash", "--restricted"]),
+            Ok(Parse::Run(Run {
+                options: vec![(ShellOption::Restricted, State::On)],
+                arg0: "yash".to_string(),
+                ..Run::default()
+            })),
+        );
+    }
+
     #[test]
     fn help_option() {
         assert_eq!(parse(["yash", "--help"]), Ok(Parse::Help));
         assert_eq!(parse(["yash", "-a", "--help", "file"]), Ok(Parse::Help));
     }
 
+    #[test]
+    fn help_message() {
+        let help = help("yash");
+        assert!(help.starts_with("Usage: yash "), "{help}");
+        assert!(help.contains("\n  -a   --allexport\n"), "{help}");
+        assert!(help.contains("\n  -C   --noclobber\n"), "{help}");
+        assert!(help.contains("\n       --pipefail\n"), "{help}");
+        assert!(help.ends_with("\n  -x   --xtrace\n"), "{help}");
+    }
+
     #[test]
     fn version_option() {
-        assert_eq!(parse(["yash", "-V"]), Ok(Parse::Version));
-        assert_eq!(parse(["yash", "-aV", "x"]), Ok(Parse::Version));
+        let version = Ok(Parse::Version { verbose: false });
+        assert_eq!(parse(["yash", "-V"]), version);
+        assert_eq!(parse(["yash", "-aV", "x"]), version);
+
+        assert_eq!(parse(["yash", "--version"]), version);
+        assert_eq!(parse(["yash", "-a", "--version", "x"]), version);
+    }
+
+    #[test]
+    fn version_option_ignores_following_arguments() {
+        let version = Ok(Parse::Version { verbose: false });
+        assert_eq!(parse(["yash", "-V", "-y"]), version);
+        assert_eq!(parse(["yash", "-Vy"]), version);
+        assert_eq!(parse(["yash", "--version", "--unexisting"]), version);
+        assert_eq!(parse(["yash", "-V", "x", "-v"]), version);
+        assert_eq!(parse(["yash", "-V", "-y", "-v"]), version);
+        assert_eq!(parse(["yash", "-Vyv"]), version);
+        assert_eq!(parse(["yash", "--version", "--", "--verbose"]), version);
+    }
+
+    #[test]
+    fn version_option_with_verbose_option() {
+        let version = Ok(Parse::Version { verbose: true });
+        assert_eq!(parse(["yash", "-vV"]), version);
+        assert_eq!(parse(["yash", "-v", "-V"]), version);
+        assert_eq!(parse(["yash", "--verbose", "--version"]), version);
+        assert_eq!(parse(["yash", "--verbose", "--version", "x"]), version);
+        assert_eq!(parse(["yash", "-Vv"]), version);
+        assert_eq!(parse(["yash", "-V", "-v"]), version);
+        assert_eq!(parse(["yash", "--version", "--verbose"]), version);
+        assert_eq!(
+            parse(["yash", "-V", "--norcfile", "-o", "verbose"]),
+            version
+        );
 
-        assert_eq!(parse(["yash", "--version"]), Ok(Parse::Version));
-        assert_eq!(parse(["yash", "-a", "--version", "x"]), Ok(Parse::Version));
+        let version = Ok(Parse::Version { verbose: false });
+        assert_eq!(parse(["yash", "-v", "+v", "-V"]), version);
+        assert_eq!(parse(["yash", "-v", "-V", "+v"]), version);
+        assert_eq!(
+            parse(["yash", "-v", "-o", "noverbose", "--version"]),
+            version
+        );
     }
 
     #[test]
@@ -1177,6 +1435,10 @@ mod tests {
             parse(["yash", "--ver=bose"]),
             Err(Error::AmbiguousLongOption("--ver=bose".to_string())),
         );
+        assert_eq!(
+            parse(["yash", "--eof=1"]),
+            Err(Error::AmbiguousLongOption("--eof=1".to_string())),
+        );
     }
 
     #[test]
@@ -19,13 +19,14 @@
 //! This module provides functions for running initialization files in the shell.
 //! The initialization file is a script that is executed when the shell starts up.
 //!
-//! Currently, this module only supports running the POSIX-defined rcfile, whose
-//! path is determined by the value of the `ENV` environment variable.
+//! Currently, this module supports running the POSIX-defined rcfile, whose
+//! path is determined by the value of the `ENV` environment variable, and the
+//! profiles `/etc/profile` and `~/.profile` for a login shell.
 //! (TODO: Support for yash-specific initialization files will be added later.)
 //!
-//! The [`run_rcfile`] function is the main entry point for running the rcfile.
-//! Helper functions that are used by `run_rcfile` are also provided in this
-//! module.
+//! The [`run_rcfile`] and [`run_profile`] functions are the main entry points
+//! for running the rcfile and the profiles, respectively. Helper functions that
+//! are used by them are also provided in this module.
 
 use super::args::InitFile;
 use std::cell::RefCell;
@@ -34,17 +35,17 @@ use std::rc::Rc;
 use thiserror::Error;
 use yash_env::input::{Echo, FdReader};
 use yash_env::io::Fd;
-use yash_env::option::Option::Interactive;
+use yash_env::option::Option::{Interactive, Login};
 use yash_env::option::State::Off;
 use yash_env::stack::Frame;
-use yash_env::system::{Errno, Mode, OfdAccess, OpenFlag, SystemEx};
-use yash_env::variable::ENV;
+use yash_env::system::{Errno, Mode, OfdAccess, OpenFlag, SystemEx, AT_FDCWD};
+use yash_env::variable::{ENV, HOME};
 use yash_env::Env;
 use yash_env::System;
 use yash_semantics::expansion::expand_text;
 use yash_semantics::read_eval_loop;
 use yash_semantics::Handle;
-use yash_syntax::parser::lex::Lexer;
+use yash_syntax::input::Memory;
 use yash_syntax::source::Source;
 
 /// Errors that can occur when finding the default initialization file path
@@ -91,8 +92,9 @@ pub async fn default_rcfile_path(env: &mut Env) -> Result<String, DefaultFilePat
 
     let text = {
         let name = ENV.to_owned();
-        let source = Source::VariableValue { name };
-        let mut lexer = Lexer::from_memory(raw_value, source);
+        let mut config = env.lexer_config();
+        config.source = Some(Source::VariableValue { name }.into());
+        let mut lexer = config.input(Box::new(Memory::new(raw_value)));
         lexer.text(|_| false, |_| false).await?
     };
 
@@ -167,10 +169,14 @@ pub async fn run_init_file(env: &mut Env, path: &str) {
         }
     };
 
+    if let Ok(stat) = env.system.fstat(fd) {
+        env.warn_writable_script(&stat, path).await;
+    }
+
     let env = &mut *env.push_frame(Frame::InitFile);
     let system = env.system.clone();
+    let mut config = env.lexer_config();
     let ref_env = RefCell::new(&mut *env);
-    let mut config = Lexer::config();
     config.source = Some(Rc::new(Source::InitFile {
         path: path.to_owned(),
     }));
@@ -200,12 +206,68 @@ pub async fn run_rcfile(env: &mut Env, file: InitFile) {
     }
 }
 
+/// Path to the system-wide profile
+pub const SYSTEM_PROFILE_PATH: &str = "/etc/profile";
+
+/// Resolves the paths to the profiles.
+///
+/// This function returns the paths to the profiles that should be executed by
+/// a login shell, in the order of execution.
+///
+/// If `file` is `InitFile::Default`, the result contains
+/// [`SYSTEM_PROFILE_PATH`] and `~/.profile`, where `~` is the value of the
+/// `HOME` variable. Files that do not exist are omitted from the result.
+/// `~/.profile` is also omitted if `HOME` is not set or empty.
+///
+/// If `file` is `InitFile::File`, the result contains the specified path only.
+///
+/// The result is empty if `file` is `InitFile::None` or the `Login` shell
+/// option is off.
+pub fn resolve_profile_paths(env: &Env, file: InitFile) -> Vec<String> {
+    if env.options.get(Login) == Off {
+        return Vec::new();
+    }
+
+    match file {
+        InitFile::None => Vec::new(),
+        InitFile::Default => {
+            let mut paths = vec![SYSTEM_PROFILE_PATH.to_owned()];
+            match env.variables.get_scalar(HOME) {
+                Some(home) if !home.is_empty() => {
+                    paths.push(format!("{}/.profile", home.trim_end_matches('/')))
+                }
+                _ => (),
+            }
+            paths.retain(|path| {
+                CString::new(path.as_str())
+                    .is_ok_and(|path| env.system.fstatat(AT_FDCWD, &path, true).is_ok())
+            });
+            paths
+        }
+        InitFile::File { path } => vec![path],
+    }
+}
+
+/// Runs the profiles specified by the `file` argument.
+///
+/// This function resolves the paths to the profiles using
+/// [`resolve_profile_paths`] and then runs each of them using
+/// [`run_init_file`]. Errors opening the files are reported to the standard
+/// error, but do not prevent the other profiles from being run.
+pub async fn run_profile(env: &mut Env, file: InitFile) {
+    for path in resolve_profile_paths(env, file) {
+        run_init_file(env, &path).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
+    use std::cell::RefCell;
     use yash_env::option::State::On;
+    use yash_env::system::r#virtual::{FileBody, Inode, SystemState};
     use yash_env::system::{Gid, Uid};
     use yash_env::variable::Scope::Global;
     use yash_env::VirtualSystem;
@@ -330,4 +392,89 @@ mod tests {
         let result = resolve_rcfile_path(&mut env, file).now_or_never().unwrap();
         assert_eq!(result.unwrap(), "");
     }
+
+    fn login_env_with_files(files: &[(&str, &str)]) -> (Env, Rc<RefCell<SystemState>>) {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        for &(path, content) in files {
+            let file = Rc::new(RefCell::new(Inode::new(content.as_bytes().to_vec())));
+            state.borrow_mut().file_system.save(path, file).unwrap();
+        }
+        let mut env = Env::with_system(system);
+        env.options.set(Login, On);
+        env.variables
+            .get_or_new(HOME, Global)
+            .assign("/home/user", None)
+            .unwrap();
+        (env, state)
+    }
+
+    #[test]
+    fn resolve_profile_paths_default_with_existing_files() {
+        let files = [("/etc/profile", ""), ("/home/user/.profile", "")];
+        let (env, _) = login_env_with_files(&files);
+        let result = resolve_profile_paths(&env, InitFile::Default);
+        assert_eq!(result, ["/etc/profile", "/home/user/.profile"]);
+    }
+
+    #[test]
+    fn resolve_profile_paths_default_without_files() {
+        let (env, _) = login_env_with_files(&[]);
+        let result = resolve_profile_paths(&env, InitFile::Default);
+        assert_eq!(result, [] as [&str; 0]);
+    }
+
+    #[test]
+    fn resolve_profile_paths_exact() {
+        let (env, _) = login_env_with_files(&[]);
+        let path = "/path/to/profile".to_string();
+        let result = resolve_profile_paths(&env, InitFile::File { path });
+        assert_eq!(result, ["/path/to/profile"]);
+    }
+
+    #[test]
+    fn resolve_profile_paths_none() {
+        let files = [("/etc/profile", "")];
+        let (env, _) = login_env_with_files(&files);
+        let result = resolve_profile_paths(&env, InitFile::None);
+        assert_eq!(result, [] as [&str; 0]);
+    }
+
+    #[test]
+    fn resolve_profile_paths_non_login() {
+        let files = [("/etc/profile", "")];
+        let (mut env, _) = login_env_with_files(&files);
+        env.options.set(Login, Off);
+        let result = resolve_profile_paths(&env, InitFile::Default);
+        assert_eq!(result, [] as [&str; 0]);
+    }
+
+    #[test]
+    fn run_profile_runs_system_and_user_profiles_in_order() {
+        let files = [
+            ("/etc/profile", "X=system Y=system\n"),
+            ("/home/user/.profile", "Y=user\n"),
+        ];
+        let (mut env, _) = login_env_with_files(&files);
+        run_profile(&mut env, InitFile::Default)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(env.variables.get_scalar("X"), Some("system"));
+        assert_eq!(env.variables.get_scalar("Y"), Some("user"));
+    }
+
+    #[test]
+    fn run_profile_reports_unreadable_file() {
+        let (mut env, state) = login_env_with_files(&[]);
+        let path = "/no/such/profile".to_string();
+        run_profile(&mut env, InitFile::File { path })
+            .now_or_never()
+            .unwrap();
+        let stderr = state.borrow().file_system.get("/dev/stderr").unwrap();
+        let stderr = stderr.borrow();
+        assert_matches!(&stderr.body, FileBody::Regular { content, .. } => {
+            let content = std::str::from_utf8(content).unwrap();
+            assert!(content.contains("/no/such/profile"), "{content}");
+        });
+    }
 }
@@ -36,6 +36,7 @@ use yash_env::io::Fd;
 use yash_env::option::Option::Interactive;
 use yash_env::option::State::{Off, On};
 use yash_env::system::Errno;
+use yash_env::system::FdKind;
 use yash_env::system::Mode;
 use yash_env::system::OfdAccess;
 use yash_env::system::OpenFlag;
@@ -58,6 +59,57 @@ pub struct PrepareInputError<'a> {
     pub path: &'a str,
 }
 
+/// Configuration of the [`IgnoreEof`] decorator
+///
+/// This configuration determines how an interactive shell responds to an EOF
+/// entered on the terminal while the
+/// [`ignore-eof`](yash_env::option::IgnoreEof) option is on.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EofConfig {
+    /// Message printed when an EOF is ignored
+    ///
+    /// If empty, nothing is printed.
+    pub message: String,
+    /// Maximum number of consecutive EOFs to be ignored
+    ///
+    /// The shell exits when EOF is entered more than this number of times in
+    /// a row. If this is 1, the user needs to confirm leaving the shell by
+    /// entering EOF twice. If 0, no EOF is ignored. See
+    /// [`IgnoreEof::with_limit`].
+    pub limit: usize,
+}
+
+impl Default for EofConfig {
+    fn default() -> Self {
+        Self {
+            message: "# Type `exit` to leave the shell when the ignore-eof option is on.\n"
+                .to_string(),
+            limit: 50,
+        }
+    }
+}
+
+/// Disables the non-blocking mode of the standard input.
+///
+/// If the standard input is a terminal or pipe, this function clears its
+/// non-blocking mode. It makes virtually no sense to make it blocking since the
+/// shell does non-blocking reads anyway, but POSIX requires it.
+/// <https://pubs.opengroup.org/onlinepubs/9699919799.2018edition/utilities/sh.html#tag_20_117_06>
+///
+/// The return value is true if the non-blocking mode was on, in which case the
+/// caller should restore the mode with
+/// [`get_and_set_nonblocking`](System::get_and_set_nonblocking) before the
+/// shell exits.
+pub fn disable_nonblocking_stdin<S: System>(system: &mut S) -> bool {
+    if let Ok(FdKind::Terminal | FdKind::Pipe) = system.fd_kind(Fd::STDIN) {
+        system
+            .get_and_set_nonblocking(Fd::STDIN, false)
+            .unwrap_or(false)
+    } else {
+        false
+    }
+}
+
 /// Prepares the input for the shell syntax parser.
 ///
 /// This function constructs a lexer from the given source with the
@@ -74,6 +126,7 @@ pub struct PrepareInputError<'a> {
 /// - If the [`Interactive`] option is enabled and the source is read with a
 ///   file descriptor, the [`IgnoreEof`] decorator is applied to the input to
 ///   implement the [`IgnoreEof`](yash_env::option::IgnoreEof) shell option.
+///   The decorator is configured with `eof`.
 ///
 /// The `RefCell` passed as the first argument should be shared with (and only
 /// with) the [`read_eval_loop`](yash_semantics::read_eval_loop) function that
@@ -83,28 +136,18 @@ pub struct PrepareInputError<'a> {
 pub fn prepare_input<'s: 'i + 'e, 'i, 'e>(
     env: &'i RefCell<&mut Env>,
     source: &'s Source,
+    eof: &EofConfig,
 ) -> Result<Lexer<'i>, PrepareInputError<'e>> {
-    fn lexer_with_input_and_source<'a>(
-        input: Box<dyn InputObject + 'a>,
-        source: SyntaxSource,
-    ) -> Lexer<'a> {
-        let mut config = Lexer::config();
+    let config = env.borrow().lexer_config();
+    let lexer_with_input_and_source = |input: Box<dyn InputObject + 'i>, source: SyntaxSource| {
+        let mut config = config;
         config.source = Some(source.into());
         config.input(input)
-    }
+    };
 
     match source {
         Source::Stdin => {
-            let mut system = env.borrow().system.clone();
-            if system.isatty(Fd::STDIN) || system.fd_is_pipe(Fd::STDIN) {
-                // It makes virtually no sense to make it blocking here
-                // since we will be doing non-blocking reads anyway,
-                // but POSIX requires us to do it.
-                // https://pubs.opengroup.org/onlinepubs/9699919799.2018edition/utilities/sh.html#tag_20_117_06
-                _ = system.get_and_set_nonblocking(Fd::STDIN, false);
-            }
-
-            let input = prepare_fd_input(Fd::STDIN, env);
+            let input = prepare_fd_input(Fd::STDIN, env, eof);
             let source = SyntaxSource::Stdin;
             Ok(lexer_with_input_and_source(input, source))
         }
@@ -126,7 +169,7 @@ pub fn prepare_input<'s: 'i + 'e, 'i, 'e>(
                 .and_then(|fd| system.move_fd_internal(fd))
                 .map_err(|errno| PrepareInputError { errno, path })?;
 
-            let input = prepare_fd_input(fd, env);
+            let input = prepare_fd_input(fd, env, eof);
             let path = path.to_owned();
             let source = SyntaxSource::CommandFile { path };
             Ok(lexer_with_input_and_source(input, source))
@@ -153,7 +196,11 @@ pub fn prepare_input<'s: 'i + 'e, 'i, 'e>(
 /// and wraps it with the [`Echo`] decorator. If the [`Interactive`] option is
 /// enabled, the [`Prompter`], [`Reporter`], and [`IgnoreEof`] decorators are
 /// applied to the input object.
-fn prepare_fd_input<'i>(fd: Fd, ref_env: &'i RefCell<&mut Env>) -> Box<dyn InputObject + 'i> {
+fn prepare_fd_input<'i>(
+    fd: Fd,
+    ref_env: &'i RefCell<&mut Env>,
+    eof: &EofConfig,
+) -> Box<dyn InputObject + 'i> {
     let env = ref_env.borrow();
     let system = env.system.clone();
 
@@ -166,8 +213,94 @@ fn prepare_fd_input<'i>(fd: Fd, ref_env: &'i RefCell<&mut Env>) -> Box<dyn Input
         // the job status is reported, and both should be shown again if an EOF is ignored.
         let prompter = Prompter::new(basic_input, ref_env);
         let reporter = Reporter::new(prompter, ref_env);
-        let message =
-            "# Type `exit` to leave the shell when the ignore-eof option is on.\n".to_string();
-        Box::new(IgnoreEof::new(reporter, fd, ref_env, message))
+        let ignore_eof = IgnoreEof::new(reporter, fd, ref_env, eof.message.clone());
+        Box::new(ignore_eof.with_limit(eof.limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::ops::ControlFlow::Continue;
+    use yash_env::option::Option::IgnoreEof as IgnoreEofOption;
+    use yash_env::system::r#virtual::FileBody;
+    use yash_env::VirtualSystem;
+    use yash_semantics::interactive_read_eval_loop;
+
+    /// Runs an interactive shell reading from a terminal that always returns
+    /// EOF, and returns the content of the standard error.
+    fn run_interactive_shell_on_eof(eof: &EofConfig) -> String {
+        let system = VirtualSystem::new();
+        let state = system.state.clone();
+        let stdin = state.borrow().file_system.get("/dev/stdin").unwrap();
+        stdin.borrow_mut().body = FileBody::Terminal { content: vec![] };
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Interactive, On);
+        env.options.set(IgnoreEofOption, On);
+        let ref_env = &RefCell::new(&mut env);
+
+        let mut lexer = prepare_input(ref_env, &Source::Stdin, eof).unwrap();
+        let result = interactive_read_eval_loop(ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+
+        let stderr = state.borrow().file_system.get("/dev/stderr").unwrap();
+        let stderr = stderr.borrow();
+        let FileBody::Regular { content, .. } = &stderr.body else {
+            panic!("unexpected stderr body: {:?}", stderr.body);
+        };
+        String::from_utf8(content.clone()).unwrap()
+    }
+
+    #[test]
+    fn configured_eof_message_is_printed_until_limit() {
+        let eof = EofConfig {
+            message: "Use exit to leave the shell.\n".to_string(),
+            limit: 2,
+        };
+        let stderr = run_interactive_shell_on_eof(&eof);
+        assert_eq!(stderr.matches(&eof.message).count(), 2, "{stderr:?}");
+    }
+
+    #[test]
+    fn eof_is_not_ignored_with_zero_limit() {
+        let eof = EofConfig {
+            message: "Use exit to leave the shell.\n".to_string(),
+            limit: 0,
+        };
+        let stderr = run_interactive_shell_on_eof(&eof);
+        assert!(!stderr.contains(&eof.message), "{stderr:?}");
+    }
+
+    #[test]
+    fn disable_nonblocking_stdin_on_pipe() {
+        let mut system = VirtualSystem::new();
+        let (reader, _writer) = system.pipe().unwrap();
+        system.dup2(reader, Fd::STDIN).unwrap();
+        system.get_and_set_nonblocking(Fd::STDIN, true).unwrap();
+
+        assert!(disable_nonblocking_stdin(&mut system));
+        assert_eq!(system.get_and_set_nonblocking(Fd::STDIN, false), Ok(false));
+    }
+
+    #[test]
+    fn disable_nonblocking_stdin_on_blocking_pipe() {
+        let mut system = VirtualSystem::new();
+        let (reader, _writer) = system.pipe().unwrap();
+        system.dup2(reader, Fd::STDIN).unwrap();
+
+        assert!(!disable_nonblocking_stdin(&mut system));
+        assert_eq!(system.get_and_set_nonblocking(Fd::STDIN, false), Ok(false));
+    }
+
+    #[test]
+    fn disable_nonblocking_stdin_on_regular_file() {
+        let mut system = VirtualSystem::new();
+        system.get_and_set_nonblocking(Fd::STDIN, true).unwrap();
+
+        assert!(!disable_nonblocking_stdin(&mut system));
+        assert_eq!(system.get_and_set_nonblocking(Fd::STDIN, false), Ok(true));
     }
 }
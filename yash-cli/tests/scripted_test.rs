@@ -310,6 +310,11 @@ fn lineno() {
     run("lineno-p.sh")
 }
 
+#[test]
+fn local_builtin() {
+    run("local-y.sh")
+}
+
 #[test]
 fn nop_builtins() {
     run("nop-p.sh")
@@ -346,6 +351,11 @@ fn ppid_variable() {
     run("ppid-p.sh")
 }
 
+#[test]
+fn process_substitution() {
+    run("procsubst-y.sh")
+}
+
 #[test]
 fn quotation() {
     run("quote-p.sh")
@@ -356,11 +366,21 @@ fn quotation_ex() {
     run("quote-y.sh");
 }
 
+#[test]
+fn random_variable() {
+    run("random-y.sh")
+}
+
 #[test]
 fn read_builtin() {
     run("read-p.sh")
 }
 
+#[test]
+fn read_builtin_ex() {
+    run("read-y.sh")
+}
+
 #[test]
 fn readonly_builtin() {
     run("readonly-p.sh")
@@ -371,11 +391,21 @@ fn redirection() {
     run("redir-p.sh")
 }
 
+#[test]
+fn restricted_mode() {
+    run("restricted-y.sh")
+}
+
 #[test]
 fn return_builtin() {
     run("return-p.sh")
 }
 
+#[test]
+fn seconds_variable() {
+    run("seconds-y.sh")
+}
+
 #[test]
 fn set_builtin() {
     run("set-p.sh")
@@ -417,6 +447,11 @@ fn trap_builtin() {
     run("trap-p.sh")
 }
 
+#[test]
+fn trap_ex() {
+    run("trap-y.sh")
+}
+
 #[test]
 fn trap_ex_2() {
     run_with_pty("trap2-y.sh")
@@ -493,6 +493,35 @@ cat cat cat
 x . x . echo x . x .
 __OUT__
 
+test_oE 'chained aliases ending with blank'
+alias a='b ' b='echo ' c=C
+a c c
+__IN__
+C c
+__OUT__
+
+test_oE 'alias ending with blank followed by reserved word'
+alias e='echo ' r=if
+e r
+e if
+e r if
+__IN__
+if
+if
+if if
+__OUT__
+
+test_oE 'alias ending with blank followed by alias of reserved word name'
+alias e='echo ' r='if ' if=IF
+e r
+e if
+e r if
+__IN__
+IF
+IF
+IF IF
+__OUT__
+
 test_oE 'alias ending with blank followed by line continuation'
 alias foo=bar a='echo \
 '
@@ -292,10 +292,10 @@ test_nonspecial_builtin_syntax "$LINENO" command
 # test_nonspecial_builtin_syntax "$LINENO" echo
 # No argument syntax error in non-special built-in false
 # test_nonspecial_builtin_syntax "$LINENO" false
-# TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" fc
+test_nonspecial_builtin_syntax "$LINENO" fc
 test_nonspecial_builtin_syntax "$LINENO" fg
 test_nonspecial_builtin_syntax "$LINENO" getopts
-# TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" hash
+test_nonspecial_builtin_syntax "$LINENO" hash
 # Non-standard built-in help skipped
 # test_nonspecial_builtin_syntax "$LINENO" help
 # Non-standard built-in history skipped
@@ -439,10 +439,10 @@ test_nonspecial_builtin_syntax "$LINENO" command
 # test_nonspecial_builtin_syntax "$LINENO" echo
 # No argument syntax error in non-special built-in false
 # test_nonspecial_builtin_syntax "$LINENO" false
-# TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" fc
+test_nonspecial_builtin_syntax "$LINENO" fc
 test_nonspecial_builtin_syntax "$LINENO" fg
 test_nonspecial_builtin_syntax "$LINENO" getopts
-# TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" hash
+test_nonspecial_builtin_syntax "$LINENO" hash
 # TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" help
 # TODO not implemented: test_nonspecial_builtin_syntax "$LINENO" history
 test_nonspecial_builtin_syntax "$LINENO" jobs
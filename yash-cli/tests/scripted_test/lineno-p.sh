@@ -43,3 +43,20 @@ __IN__
 8
 12
 __OUT__
+
+test_oE -e 0 'LINENO after line continuations and here-documents' -s
+echo a \
+b
+cat <<END
+foo
+END
+echo $LINENO
+\
+\
+echo $LINENO
+__IN__
+a b
+foo
+6
+9
+__OUT__
@@ -0,0 +1,72 @@
+# local-y.sh: yash-specific test of the local built-in
+
+test_oE -e 0 'local is an elective built-in'
+command -V local
+__IN__
+local: elective built-in
+__OUT__
+
+test_oE -e 0 'local variable hides global variable in function' -e
+a=global
+f() {
+    local a=local b=1
+    echo $a $b
+}
+f
+echo $a ${b-unset}
+__IN__
+local 1
+global unset
+__OUT__
+
+test_oE -e 0 'local variable is visible in called function' -e
+a=global
+g() { echo $a; }
+f() { local a=local; g; }
+f
+g
+__IN__
+local
+global
+__OUT__
+
+test_oE -e 0 'read-only local variable' -e
+f() {
+    local -r a=1
+    a=2 || echo $a
+}
+(f) 2>/dev/null || echo failed
+a=3
+echo $a
+__IN__
+failed
+3
+__OUT__
+
+test_oE -e 0 'exported local variable' -e
+f() {
+    local -x a=1
+    sh -c 'echo $a'
+}
+f
+echo ${a-unset}
+__IN__
+1
+unset
+__OUT__
+
+test_oE -e 0 'printing local variables' -e
+f() {
+    local a=1
+    local -x b=2
+    local -p a b
+}
+f
+__IN__
+local a=1
+local -x b=2
+__OUT__
+
+test_O -d -e n 'local outside function'
+local a=1
+__IN__
@@ -26,27 +26,22 @@ __IN__
 printed
 __OUT__
 
-: TODO not yet implemented <<\__OUT__
 test_OE -e 0 'pipefail on: single command successful pipe' --pipefail
 true
 __IN__
 
-: TODO not yet implemented <<\__OUT__
 test_OE -e 13 'pipefail on: single command unsuccessful pipe' --pipefail
 (exit 13)
 __IN__
 
-: TODO not yet implemented <<\__OUT__
 test_OE -e 0 'pipefail on: multi-command successful pipe' --pipefail
 true | true | true | true
 __IN__
 
-: TODO not yet implemented <<\__OUT__
 test_OE -e 7 'pipefail on: multi-command unsuccessful pipe' --pipefail
 true | exit 2 | true | exit 7 | true | true
 __IN__
 
-: TODO not yet implemented <<\__OUT__
 test_OE -e 7 'pipefail on: multi-command unsuccessful pipe in subshell' \
     --pipefail
 (true | exit 2 | true | exit 7 | true | true)
@@ -115,3 +110,17 @@ __IN__
 test_x -e 0 'concatenation of option and -o and argument' -aoerrexit
 echo $- | grep a | grep -q e
 __IN__
+
+test_O -d -e n 'nounset on: positional parameter out of range' -u
+echo "$1"
+__IN__
+
+test_O -d -e n 'nounset on: $! without asynchronous command' -u
+echo "$!"
+__IN__
+
+test_oE -e 0 'nounset on: $@ and $* without positional parameters' -u
+echo "[$@]" "[$*]"
+__IN__
+[] []
+__OUT__
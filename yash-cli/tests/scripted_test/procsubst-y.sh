@@ -0,0 +1,50 @@
+# procsubst-y.sh: yash-specific test of process substitution
+
+test_O -d -e n 'process substitution is disabled by default'
+cat <(echo foo)
+__IN__
+
+test_oE -e 0 'reading from process substitution' -o procsubst
+cat <(echo foo)
+__IN__
+foo
+__OUT__
+
+test_oE -e 0 'writing to process substitution' -o procsubst
+# The command substitution waits for cat to close the standard output.
+x=$(echo foo > >(cat))
+echo "$x"
+__IN__
+foo
+__OUT__
+
+test_oE -e 0 'multiple process substitutions' -o procsubst
+cat <(echo foo) <(echo bar)
+__IN__
+foo
+bar
+__OUT__
+
+test_oE -e 0 'process substitution as redirection target' -o procsubst
+while read -r line; do
+    echo "[$line]"
+done < <(echo foo; echo bar)
+__IN__
+[foo]
+[bar]
+__OUT__
+
+test_oE -e 0 'process substitution in the middle of word' -o procsubst
+case x<(:) in
+    (x/dev/fd/*) echo ok
+esac
+__IN__
+ok
+__OUT__
+
+test_oE -e 0 'process substitution is not split' -o procsubst
+set -- <(:)
+echo $#
+__IN__
+1
+__OUT__
@@ -0,0 +1,56 @@
+# random-y.sh: yash-specific test of the RANDOM variable
+
+test_oE -e 0 'RANDOM is between 0 and 32767' -e
+i=0
+while [ "$i" -lt 100 ]; do
+    r=$RANDOM
+    if [ "$r" -lt 0 ] || [ "$r" -gt 32767 ]; then
+        echo "out of range: $r"
+    fi
+    i=$((i + 1))
+done
+echo done
+__IN__
+done
+__OUT__
+
+test_oE -e 0 'RANDOM yields different numbers' -e
+a="$RANDOM $RANDOM $RANDOM"
+b="$RANDOM $RANDOM $RANDOM"
+[ "$a" != "$b" ] && echo ok
+__IN__
+ok
+__OUT__
+
+test_oE -e 0 'RANDOM yields different numbers in subshells' -e
+a=$(echo $RANDOM) b=$(echo $RANDOM) c=$(echo $RANDOM)
+{ [ "$a" != "$b" ] || [ "$b" != "$c" ]; } && echo ok
+__IN__
+ok
+__OUT__
+
+test_oE -e 0 'assigning seed to RANDOM' -e
+RANDOM=42
+a="$RANDOM $RANDOM $RANDOM"
+RANDOM=42
+b="$RANDOM $RANDOM $RANDOM"
+[ "$a" = "$b" ] && echo ok
+RANDOM=1
+echo $RANDOM $RANDOM $RANDOM
+__IN__
+ok
+16838 5758 10113
+__OUT__
+
+test_oE -e 0 'RANDOM is not special after assigning non-integer' -e
+RANDOM=foo
+echo $RANDOM $RANDOM
+__IN__
+foo foo
+__OUT__
+
+test_oE -e 0 'RANDOM is not special if inherited' -e
+RANDOM=5 "$TESTEE" -c 'echo $RANDOM $RANDOM'
+__IN__
+5 5
+__OUT__
@@ -0,0 +1,55 @@
+# read-y.sh: yash-specific test of the read built-in
+
+test_oE 'delimiter option'
+printf 'A\nB:C:' | {
+read -d : a
+echo $? "[$a]"
+read -d : a
+echo $? "[$a]"
+}
+__IN__
+0 [A
+B]
+0 [C]
+__OUT__
+
+test_oE 'empty delimiter means null character'
+printf 'A\nB\0C' | {
+read -d '' a
+echo $? "[$a]"
+}
+__IN__
+0 [A
+B]
+__OUT__
+
+test_oE 'delimiter option with long option name'
+printf 'A:' | {
+read --delimiter=: a
+echo $? "[$a]"
+}
+__IN__
+0 [A]
+__OUT__
+
+test_oE 'timeout with available input'
+read -t 10 a <<\END
+A
+END
+echo $? "[$a]"
+__IN__
+0 [A]
+__OUT__
+
+test_oE 'timeout expires with partial input'
+{ printf A; sleep 1; echo B; } | {
+read -t 0.1 a
+echo $? "[$a]"
+}
+__IN__
+142 [A]
+__OUT__
+
+test_O -d -e 2 'invalid timeout'
+read -t foo a
+__IN__
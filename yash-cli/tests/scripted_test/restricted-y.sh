@@ -0,0 +1,35 @@
+# restricted-y.sh: yash-specific test of the restricted mode
+
+test_o -d 'cd is rejected in restricted mode' --restricted
+cd /
+echo $?
+__IN__
+1
+__OUT__
+
+test_o -d 'command name with slash is rejected in restricted mode' --restricted
+/bin/echo foo
+echo $?
+__IN__
+126
+__OUT__
+
+test_o -d 'redirection to file is rejected in restricted mode' --restricted
+echo foo >file
+if [ -e file ]; then echo created; else echo not created; fi
+__IN__
+not created
+__OUT__
+
+test_O -d -e n 'assigning to PATH is an error in restricted mode' --restricted
+PATH=/tmp
+echo not reached
+__IN__
+
+test_oE 'command found in PATH is run in restricted mode' --restricted
+echo foo
+__IN__
+foo
+__OUT__
+
+# vim: set ft=sh ts=8 sts=4 sw=4 et:
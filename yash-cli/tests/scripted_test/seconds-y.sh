@@ -0,0 +1,27 @@
+# seconds-y.sh: yash-specific test of the SECONDS variable
+
+test_oE -e 0 'SECONDS starts from a small non-negative integer' -e
+[ "$SECONDS" -ge 0 ] && [ "$SECONDS" -lt 10 ] && echo ok
+__IN__
+ok
+__OUT__
+
+test_oE -e 0 'assigning to SECONDS rebases the counter' -e
+SECONDS=100
+[ "$SECONDS" -ge 100 ] && [ "$SECONDS" -lt 110 ] && echo ok
+__IN__
+ok
+__OUT__
+
+test_oE -e 0 'SECONDS is not special after assigning non-integer' -e
+SECONDS=foo
+echo $SECONDS
+__IN__
+foo
+__OUT__
+
+test_oE -e 0 'SECONDS is not special if inherited' -e
+SECONDS=5 "$TESTEE" -c 'echo $SECONDS'
+__IN__
+5
+__OUT__
@@ -33,6 +33,12 @@ __IN__
 [11]
 __OUT__
 
+test_oE -e 0 'positional parameters are expanded in command string (-c)' \
+    -c 'echo "$0" "$1" "$2"' sh a b
+__IN__
+sh a b
+__OUT__
+
 test_oE -e 0 'stdin is not used with -c' -c 'cat'
 printed text
 __IN__
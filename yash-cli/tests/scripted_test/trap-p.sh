@@ -141,6 +141,25 @@ test_E -e 0 'exit status of EXIT trap does not affect exit status of shell'
 trap 'false' EXIT
 __IN__
 
+test_oE -e 0 'EXIT trap in -c command string (end of string)' \
+    -c 'trap "echo bye" EXIT; echo hello'
+__IN__
+hello
+bye
+__OUT__
+
+test_oE -e 3 'EXIT trap in -c command string (exit built-in)' \
+    -c 'trap "echo bye" EXIT; exit 3; echo not reached'
+__IN__
+bye
+__OUT__
+
+test_o -d -e n 'EXIT trap in -c command string (shell error)' \
+    -c 'trap "echo bye" EXIT; set --no-such-option; echo not reached'
+__IN__
+bye
+__OUT__
+
 test_oE 'trap command is not affected by assignment in same simple command' \
     -c 'foo=1 trap "echo EXIT \$foo" EXIT; foo=2; foo=3 echo $foo'
 __IN__
@@ -0,0 +1,87 @@
+# trap-y.sh: yash-specific test for the trap built-in
+
+test_oE 'ERR trap on failing simple command'
+trap 'echo ERR $?' ERR
+false
+echo next
+(exit 3)
+__IN__
+ERR 1
+next
+ERR 3
+__OUT__
+
+test_oE 'ERR trap is not run for tested commands'
+trap 'echo ERR $?' ERR
+if false; then :; fi
+while false; do :; done
+false && :
+false || :
+! true
+echo done
+__IN__
+done
+__OUT__
+
+test_oE 'ERR trap is run before errexit' -e
+trap 'echo ERR $?' ERR
+false
+echo not reached
+__IN__
+ERR 1
+__OUT__
+
+test_oE 'exit status is preserved after ERR trap'
+trap 'true' ERR
+(exit 4)
+echo $?
+__IN__
+4
+__OUT__
+
+test_oE 'ERR trap is not run recursively'
+trap 'echo ERR; false' ERR
+false
+echo done
+__IN__
+ERR
+done
+__OUT__
+
+test_oE 'printing ERR trap'
+trap 'echo failed' ERR
+trap -p ERR
+__IN__
+trap -- 'echo failed' ERR
+__OUT__
+
+test_oE 'DEBUG trap is run before each simple command'
+trap 'count=$((count+1))' DEBUG
+echo 1
+echo 2; echo 3
+trap - DEBUG
+echo "$count"
+__IN__
+1
+2
+3
+4
+__OUT__
+
+test_oE 'exit status in DEBUG trap'
+trap 'echo DEBUG $?' DEBUG
+(exit 2)
+echo $?
+__IN__
+DEBUG 2
+2
+__OUT__
+
+test_oE 'DEBUG trap is not run recursively'
+trap 'echo DEBUG; echo again' DEBUG
+echo main
+__IN__
+DEBUG
+again
+main
+__OUT__
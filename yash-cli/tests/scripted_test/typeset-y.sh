@@ -29,6 +29,34 @@ __IN__
 unset 4
 __OUT__
 
+test_oE -e 0 'defining global variable in function (-g)' -e
+f() {
+    typeset -g a=1 b
+    echo $a ${b-unset}
+}
+f
+echo $a ${b-unset}
+typeset -p a b
+__IN__
+1 unset
+1 unset
+typeset a=1
+typeset b
+__OUT__
+
+test_oE -e 0 'modifying global variable in function (-g)' -e
+a=1
+f() {
+    typeset -gx a=2
+}
+f
+echo $a
+sh -c 'echo $a'
+__IN__
+2
+2
+__OUT__
+
 test_oE -e 0 'overwriting temporary variable' -e
 a=1 typeset a=2
 echo $a
@@ -66,6 +94,27 @@ typeset -r yash_readonly_test=bar
 typeset -x yash_export_test=baz
 __OUT__
 
+test_oE -e 0 'reusing printed variables (no option)' -e
+f() {
+    typeset -x a='foo  bar'
+    typeset -r b="'"
+    typeset c
+    typeset
+}
+e="$(f)"
+g() {
+    eval "$e"
+    echo "$a" "$b" "${c-unset}"
+    sh -c 'echo "$a"'
+    typeset -p b
+}
+g
+__IN__
+foo  bar ' unset
+foo  bar
+typeset -r b="'"
+__OUT__
+
 test_oE -e 0 'only local variables are printed by default (no option)' -e
 f() {         a=1; typeset; }
 g() { typeset a=1; typeset; }
@@ -142,8 +191,36 @@ __IN__
 unset unset
 __OUT__
 
+test_oE 'removing export attribute (+x)' -e
+typeset +x a b=3
+echo $a $b
+sh -c 'echo ${a-unset} ${b-unset}'
+typeset -p a b
+__IN__
+1 3
+unset unset
+typeset a=1
+typeset b=3
+__OUT__
+
+test_oE 'removing export attribute (++export)' -e
+typeset ++export a
+sh -c 'echo ${a-unset} ${b-unset}'
+__IN__
+unset 2
+__OUT__
+
 )
 
+test_oE -e 0 'removing read-only attribute of non-read-only variable (+r)' -e
+a=1
+typeset +r a b=2
+a=3 b=4
+echo $a $b
+__IN__
+3 4
+__OUT__
+
 test_oE -e 0 'printing all variables (-p)' -e
 typeset -p >/dev/null
 typeset -p | grep -q '^typeset -x PATH='
@@ -387,6 +464,39 @@ __IN__
 
 )
 
+test_O -d -e 2 'operand without variable name'
+typeset =x
+__IN__
+
+test_O -d -e 2 'operand with invalid variable name'
+typeset 1abc=x
+__IN__
+
+test_O -d -e 2 'export with invalid variable name'
+export a-b
+echo not reached
+__IN__
+
+test_O -d -e 2 'readonly without variable name'
+readonly =x
+echo not reached
+__IN__
+
+test_O -d -e 1 'removing read-only attribute (+r)'
+typeset -r a=1
+typeset +r a
+__IN__
+
+test_oE 'read-only attribute remains after failing +r'
+typeset -r a=1
+typeset +r a 2>/dev/null
+echo $?
+typeset -p a
+__IN__
+1
+typeset -r a=1
+__OUT__
+
 test_O -d -e 1 'assigning to read-only variable'
 typeset -r a
 typeset a=1
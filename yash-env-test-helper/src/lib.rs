@@ -27,6 +27,7 @@ use std::pin::Pin;
 use std::rc::Rc;
 use std::str::from_utf8;
 use yash_env::system::r#virtual::{Executor, FileBody, Inode, SystemState, VirtualSystem};
+use yash_env::system::TerminalSize;
 use yash_env::Env;
 
 /// Adapter for [`LocalSpawner`] to [`Executor`]
@@ -89,6 +90,24 @@ pub fn stub_tty(state: &RefCell<SystemState>) {
         .unwrap();
 }
 
+/// Creates a dummy terminal at /dev/tty with the given size.
+///
+/// Unlike [`stub_tty`], this function makes /dev/tty a terminal so that the
+/// terminal size can be obtained from it. The size is set to
+/// [`SystemState::terminal_size`].
+pub fn stub_tty_with_size(state: &RefCell<SystemState>, size: TerminalSize) {
+    let tty = Inode {
+        body: FileBody::Terminal { content: vec![] },
+        permissions: Default::default(),
+    };
+    let mut state = state.borrow_mut();
+    state
+        .file_system
+        .save("/dev/tty", Rc::new(RefCell::new(tty)))
+        .unwrap();
+    state.terminal_size = Some(size);
+}
+
 /// Helper function for asserting on the content of /dev/stdout
 ///
 /// This function asserts on the content of /dev/stdout. The argument function
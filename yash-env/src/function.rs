@@ -28,6 +28,12 @@ use thiserror::Error;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::FullCompoundCommand;
 
+/// Name of the function that is called when a command is not found
+///
+/// See [`Env::command_not_found_handler`](crate::Env::command_not_found_handler)
+/// for details.
+pub const COMMAND_NOT_FOUND_HANDLER: &str = "command_not_found_handler";
+
 /// Definition of a function.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Function {
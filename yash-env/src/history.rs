@@ -0,0 +1,302 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Command history
+//!
+//! This module defines [`History`], a bounded list of command lines entered
+//! in the interactive shell. Each entry is given a history number that
+//! increases by one for each new entry. When the history is full, the oldest
+//! entry is removed to make room for a new one.
+//!
+//! The capacity of the history is determined by the [`HISTSIZE`] variable
+//! when a new entry is [added to the environment](Env::add_history).
+
+use crate::variable::HISTSIZE;
+use crate::Env;
+use std::collections::VecDeque;
+
+/// Default capacity of the history
+///
+/// This value is used when the [`HISTSIZE`] variable is not set or does not
+/// contain a valid non-negative integer.
+pub const DEFAULT_CAPACITY: usize = 500;
+
+/// Entry of the command history
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Entry {
+    /// History number of the entry
+    pub number: usize,
+    /// Command line without a trailing newline
+    pub command: String,
+}
+
+/// Bounded list of history entries
+///
+/// History numbers start from 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History {
+    entries: VecDeque<Entry>,
+    capacity: usize,
+    next_number: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    /// Creates an empty history with the [default capacity](DEFAULT_CAPACITY).
+    #[must_use]
+    pub fn new() -> Self {
+        History {
+            entries: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+            next_number: 1,
+        }
+    }
+
+    /// Returns the maximum number of entries the history can contain.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity of the history.
+    ///
+    /// If the history contains more entries than the new capacity, the oldest
+    /// entries are removed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess);
+    }
+
+    /// Returns the number of entries in the history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tests whether the history is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the history number that will be given to the next entry.
+    #[must_use]
+    pub fn next_number(&self) -> usize {
+        self.next_number
+    }
+
+    /// Adds a command line to the history.
+    ///
+    /// A trailing newline in the argument is removed. If the history is full,
+    /// the oldest entry is removed. Returns the history number of the new
+    /// entry.
+    pub fn push(&mut self, command: &str) -> usize {
+        let number = self.next_number;
+        self.next_number += 1;
+
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity {
+                self.entries.pop_front();
+            }
+            let command = command.strip_suffix('\n').unwrap_or(command).to_owned();
+            self.entries.push_back(Entry { number, command });
+        }
+        number
+    }
+
+    /// Removes and returns the most recent entry.
+    ///
+    /// The history number of the removed entry will be reused for the next
+    /// entry.
+    pub fn pop(&mut self) -> Option<Entry> {
+        let entry = self.entries.pop_back()?;
+        self.next_number = entry.number;
+        Some(entry)
+    }
+
+    /// Returns the oldest entry.
+    #[must_use]
+    pub fn first(&self) -> Option<&Entry> {
+        self.entries.front()
+    }
+
+    /// Returns the most recent entry.
+    #[must_use]
+    pub fn last(&self) -> Option<&Entry> {
+        self.entries.back()
+    }
+
+    /// Returns the entry with the given history number.
+    #[must_use]
+    pub fn get(&self, number: usize) -> Option<&Entry> {
+        let first = self.first()?.number;
+        self.entries.get(number.checked_sub(first)?)
+    }
+
+    /// Returns an iterator over the entries from the oldest to the newest.
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, Entry> {
+        self.entries.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a History {
+    type Item = &'a Entry;
+    type IntoIter = std::collections::vec_deque::Iter<'a, Entry>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Env {
+    /// Adds a command line to the history.
+    ///
+    /// Before adding the entry, this function updates the capacity of the
+    /// history according to the current value of the [`HISTSIZE`] variable.
+    /// Command lines that contain only whitespace are not added.
+    ///
+    /// Returns the history number of the new entry, or `None` if the command
+    /// line was not added.
+    pub fn add_history(&mut self, command: &str) -> Option<usize> {
+        if command.trim().is_empty() {
+            return None;
+        }
+
+        let capacity = self
+            .variables
+            .get_scalar(HISTSIZE)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_CAPACITY);
+        self.history.set_capacity(capacity);
+
+        Some(self.history.push(command))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variable::Scope;
+
+    #[test]
+    fn push_gives_increasing_numbers() {
+        let mut history = History::new();
+        assert_eq!(history.push("foo\n"), 1);
+        assert_eq!(history.push("bar"), 2);
+
+        let entries = history.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                Entry {
+                    number: 1,
+                    command: "foo".to_string()
+                },
+                Entry {
+                    number: 2,
+                    command: "bar".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn push_removes_oldest_entry_when_full() {
+        let mut history = History::new();
+        history.set_capacity(2);
+        history.push("a");
+        history.push("b");
+        history.push("c");
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.first().unwrap().command, "b");
+        assert_eq!(history.last().unwrap().command, "c");
+        assert_eq!(history.get(1), None);
+        assert_eq!(history.get(3).unwrap().command, "c");
+    }
+
+    #[test]
+    fn push_with_zero_capacity() {
+        let mut history = History::new();
+        history.set_capacity(0);
+        assert_eq!(history.push("a"), 1);
+        assert!(history.is_empty());
+        assert_eq!(history.next_number(), 2);
+    }
+
+    #[test]
+    fn set_capacity_removes_oldest_entries() {
+        let mut history = History::new();
+        history.push("a");
+        history.push("b");
+        history.push("c");
+        history.set_capacity(1);
+
+        let commands = history.iter().map(|e| &e.command[..]).collect::<Vec<_>>();
+        assert_eq!(commands, ["c"]);
+    }
+
+    #[test]
+    fn pop_reuses_number() {
+        let mut history = History::new();
+        history.push("a");
+        history.push("b");
+
+        let entry = history.pop().unwrap();
+        assert_eq!(entry.number, 2);
+        assert_eq!(entry.command, "b");
+        assert_eq!(history.push("c"), 2);
+    }
+
+    #[test]
+    fn add_history_applies_histsize() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new(HISTSIZE, Scope::Global)
+            .assign("2", None)
+            .unwrap();
+        env.add_history("a\n");
+        env.add_history("b\n");
+        env.add_history("c\n");
+
+        assert_eq!(env.history.capacity(), 2);
+        let commands = env.history.iter().map(|e| &e.command[..]);
+        assert_eq!(commands.collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn add_history_with_invalid_histsize() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new(HISTSIZE, Scope::Global)
+            .assign("foo", None)
+            .unwrap();
+        env.add_history("a\n");
+        assert_eq!(env.history.capacity(), DEFAULT_CAPACITY);
+    }
+
+    #[test]
+    fn add_history_ignores_blank_line() {
+        let mut env = Env::new_virtual();
+        assert_eq!(env.add_history(" \n"), None);
+        assert!(env.history.is_empty());
+    }
+}
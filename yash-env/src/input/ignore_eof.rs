@@ -37,8 +37,12 @@ use std::cell::RefCell;
 ///
 /// The decorator reads from the inner input and usually returns the result
 /// as is. However, if the result is an empty string and the above conditions
-/// are met, the decorator will re-read the input until a non-empty string
-/// is obtained, an error occurs, or this process is repeated 20 times.
+/// are met, the decorator will print the message and re-read the input until a
+/// non-empty string is obtained, an error occurs, or this process is repeated
+/// as many times as the [limit](Self::with_limit) (50 by default).
+/// The count of retries starts over each time the decorator is called, so
+/// EOFs ignored before a command is entered do not count toward the limit for
+/// the next command.
 ///
 /// [`Interactive`]: crate::option::Interactive
 #[derive(Clone, Debug)]
@@ -51,6 +55,8 @@ pub struct IgnoreEof<'a, 'b, T> {
     env: &'a RefCell<&'b mut Env>,
     /// Text to be displayed when EOF is ignored
     message: String,
+    /// Maximum number of consecutive EOFs to be ignored
+    limit: usize,
 }
 
 impl<'a, 'b, T> IgnoreEof<'a, 'b, T> {
@@ -62,7 +68,8 @@ impl<'a, 'b, T> IgnoreEof<'a, 'b, T> {
     /// contains the shell option state and the system interface to interact
     /// with the system.  It is wrapped in a `RefCell` so that it can be shared
     /// with other decorators and the parser. The fourth argument is the text to
-    /// be displayed when EOF is ignored.
+    /// be displayed when EOF is ignored. It may be empty if no message should
+    /// be displayed.
     ///
     /// The second argument `fd` should match the file descriptor that the inner
     /// input reads from. If the inner input reads from a different file
@@ -74,8 +81,20 @@ impl<'a, 'b, T> IgnoreEof<'a, 'b, T> {
             fd,
             env,
             message,
+            limit: 50,
         }
     }
+
+    /// Sets the maximum number of consecutive EOFs to be ignored.
+    ///
+    /// When the inner input returns EOF more than `limit` times in a row, the
+    /// decorator gives up and returns the EOF. Setting the limit to 1 requires
+    /// the user to confirm leaving the shell by entering EOF twice. If the
+    /// limit is 0, no EOF is ignored.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
 }
 
 impl<'a, 'b, T> Input for IgnoreEof<'a, 'b, T>
@@ -84,7 +103,7 @@ where
 {
     #[allow(clippy::await_holding_refcell_ref)]
     async fn next_line(&mut self, context: &Context) -> Result {
-        let mut remaining_tries = 50;
+        let mut remaining_tries = self.limit;
 
         loop {
             let line = self.inner.next_line(context).await?;
@@ -154,6 +173,7 @@ mod tests {
                         is_readable: true,
                         is_writable: true,
                         is_appending: false,
+                        is_nonblocking: false,
                     })),
                     flags: EnumSet::empty(),
                 },
@@ -179,6 +199,7 @@ mod tests {
                         is_readable: true,
                         is_writable: true,
                         is_appending: false,
+                        is_nonblocking: false,
                     })),
                     flags: EnumSet::empty(),
                 },
@@ -264,6 +285,112 @@ mod tests {
         });
     }
 
+    #[test]
+    fn retry_count_is_reset_after_non_empty_line() {
+        let mut system = Box::new(VirtualSystem::new());
+        set_stdin_to_tty(&mut system);
+        let state = system.state.clone();
+        let mut env = Env::with_system(system);
+        env.options.set(Interactive, On);
+        env.options.set(IgnoreEofOption, On);
+        let ref_env = RefCell::new(&mut env);
+        let mut decorator = IgnoreEof::new(
+            EofStub {
+                inner: Memory::new("echo foo\necho bar\n"),
+                count: 40,
+            },
+            Fd::STDIN,
+            &ref_env,
+            "EOF ignored\n".to_string(),
+        );
+
+        let result = decorator
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "echo foo\n");
+
+        decorator.inner.count = 40;
+        let result = decorator
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "echo bar\n");
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "EOF ignored\n".repeat(80))
+        });
+    }
+
+    #[test]
+    fn decorator_returns_empty_line_after_exceeding_limit() {
+        let mut system = Box::new(VirtualSystem::new());
+        set_stdin_to_tty(&mut system);
+        let state = system.state.clone();
+        let mut env = Env::with_system(system);
+        env.options.set(Interactive, On);
+        env.options.set(IgnoreEofOption, On);
+        let ref_env = RefCell::new(&mut env);
+        let mut decorator = IgnoreEof::new(
+            EofStub {
+                inner: Memory::new("echo foo\n"),
+                count: 1,
+            },
+            Fd::STDIN,
+            &ref_env,
+            "Use exit to leave the shell.\n".to_string(),
+        )
+        .with_limit(1);
+
+        // The first EOF is ignored with the message.
+        let result = decorator
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "echo foo\n");
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "Use exit to leave the shell.\n")
+        });
+
+        // The second consecutive EOF is returned.
+        decorator.inner.count = 2;
+        let result = decorator
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "");
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "Use exit to leave the shell.\n".repeat(2))
+        });
+    }
+
+    #[test]
+    fn decorator_returns_immediately_with_zero_limit() {
+        let mut system = Box::new(VirtualSystem::new());
+        set_stdin_to_tty(&mut system);
+        let state = system.state.clone();
+        let mut env = Env::with_system(system);
+        env.options.set(Interactive, On);
+        env.options.set(IgnoreEofOption, On);
+        let ref_env = RefCell::new(&mut env);
+        let mut decorator = IgnoreEof::new(
+            EofStub {
+                inner: Memory::new("echo foo\n"),
+                count: 1,
+            },
+            Fd::STDIN,
+            &ref_env,
+            "EOF ignored\n".to_string(),
+        )
+        .with_limit(0);
+
+        let result = decorator
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "");
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
     #[test]
     fn decorator_returns_empty_line_after_reading_51_times() {
         let mut system = Box::new(VirtualSystem::new());
@@ -19,6 +19,7 @@
 #[cfg(doc)]
 use crate::system::SharedSystem;
 use crate::Env;
+use annotate_snippets::renderer::{AnsiColor, Effects, Style};
 use annotate_snippets::Renderer;
 use std::borrow::Cow;
 use yash_syntax::source::pretty::Annotation;
@@ -41,10 +42,66 @@ pub use yash_syntax::syntax::Fd;
 /// [`move_fd_internal`]: crate::system::SystemEx::move_fd_internal
 pub const MIN_INTERNAL_FD: Fd = Fd(10);
 
+/// Styles applied to parts of messages printed to the standard error
+///
+/// When the standard error accepts colored output, [`message_to_string`]
+/// decorates messages with ANSI escape sequences according to the
+/// [`Env::theme`]. The [default](Self::default) theme mimics the styles of
+/// `rustc`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Theme {
+    /// Style of the `error` keyword and the markers of error annotations
+    pub error: Style,
+    /// Style of the `warning` keyword and the markers of warning annotations
+    pub warning: Style,
+    /// Style of the `info` keyword and the markers of info annotations
+    pub info: Style,
+    /// Style of the `note` keyword and the markers of note annotations
+    pub note: Style,
+    /// Style of the `help` keyword and the markers of help annotations
+    pub help: Style,
+    /// Style of line numbers and location indicators
+    pub location: Style,
+    /// Style of the message title
+    pub message: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let bright_blue = AnsiColor::BrightBlue.on_default();
+        Theme {
+            error: AnsiColor::BrightRed.on_default().effects(Effects::BOLD),
+            warning: AnsiColor::Yellow.on_default().effects(Effects::BOLD),
+            info: bright_blue.effects(Effects::BOLD),
+            note: AnsiColor::BrightGreen.on_default().effects(Effects::BOLD),
+            help: AnsiColor::BrightCyan.on_default().effects(Effects::BOLD),
+            location: bright_blue.effects(Effects::BOLD),
+            message: Style::new().effects(Effects::BOLD),
+        }
+    }
+}
+
+impl Theme {
+    /// Creates a renderer that applies this theme.
+    #[must_use]
+    pub fn renderer(&self) -> Renderer {
+        Renderer::styled()
+            .error(self.error)
+            .warning(self.warning)
+            .info(self.info)
+            .note(self.note)
+            .help(self.help)
+            .line_no(self.location)
+            .emphasis(self.message)
+    }
+}
+
 /// Convenience function for converting an error message into a string.
 ///
 /// The returned string may contain ANSI color escape sequences if the given
-/// `env` allows it. The string will end with a newline.
+/// `env` allows it, in which case the message is styled according to
+/// [`Env::theme`]. The string will end with a newline.
 ///
 /// To print the returned string to the standard error, you can use
 /// [`SharedSystem::print_error`].
@@ -52,7 +109,7 @@ pub const MIN_INTERNAL_FD: Fd = Fd(10);
 pub fn message_to_string(env: &Env, message: &Message<'_>) -> String {
     let m = annotate_snippets::Message::from(message);
     let r = if env.should_print_error_in_color() {
-        Renderer::styled()
+        env.theme.renderer()
     } else {
         Renderer::plain()
     };
@@ -97,3 +154,73 @@ pub async fn print_error(
     };
     print_message(env, message).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::set_terminal;
+    use crate::VirtualSystem;
+
+    fn sample_message(location: &Location) -> Message<'_> {
+        Message {
+            r#type: AnnotationType::Error,
+            title: "something failed".into(),
+            annotations: vec![Annotation::new(
+                AnnotationType::Error,
+                "bad thing here".into(),
+                location,
+            )],
+            footers: vec![],
+        }
+    }
+
+    fn env_with_terminal_stderr() -> Env {
+        let system = VirtualSystem::new();
+        set_terminal(&system, "/dev/stderr");
+        Env::with_system(Box::new(system))
+    }
+
+    #[test]
+    fn message_to_string_with_theme() {
+        let mut env = env_with_terminal_stderr();
+        env.theme = Theme {
+            error: AnsiColor::Red.on_default(),
+            location: AnsiColor::Green.on_default(),
+            message: Style::new().effects(Effects::UNDERLINE),
+            ..Theme::default()
+        };
+        let location = Location::dummy("echo foo");
+
+        let result = message_to_string(&env, &sample_message(&location));
+        assert!(result.contains("\x1b[31merror"), "{result:?}");
+        assert!(result.contains("\x1b[31m^^^^^^^^\x1b[0m"), "{result:?}");
+        assert!(result.contains("\x1b[32m-->"), "{result:?}");
+        assert!(result.contains("\x1b[4msomething failed"), "{result:?}");
+    }
+
+    #[test]
+    fn message_to_string_with_default_theme() {
+        let env = env_with_terminal_stderr();
+        let location = Location::dummy("echo foo");
+
+        let result = message_to_string(&env, &sample_message(&location));
+        assert!(result.contains("\x1b[1m\x1b[91merror"), "{result:?}");
+    }
+
+    #[test]
+    fn message_to_string_without_color() {
+        let mut env = Env::new_virtual();
+        env.theme = Theme {
+            error: AnsiColor::Red.on_default(),
+            ..Theme::default()
+        };
+        let location = Location::dummy("echo foo");
+
+        let result = message_to_string(&env, &sample_message(&location));
+        assert!(!result.contains('\x1b'), "{result:?}");
+        assert!(
+            result.starts_with("error: something failed\n"),
+            "{result:?}"
+        );
+    }
+}
@@ -1519,4 +1519,28 @@ mod tests {
         assert_eq!(list.current_job(), Some(i12));
         assert_eq!(list.previous_job(), Some(i10));
     }
+
+    #[test]
+    fn adding_suspending_and_resuming_jobs_in_sequence() {
+        let mut list = JobList::default();
+        let i11 = list.add(Job::new(Pid(11)));
+        let i12 = list.add(Job::new(Pid(12)));
+        let i13 = list.add(Job::new(Pid(13)));
+
+        list.update_status(Pid(11), ProcessState::stopped(SIGTSTP));
+        assert_eq!(list.current_job(), Some(i11));
+        list.update_status(Pid(12), ProcessState::stopped(SIGTSTP));
+        assert_eq!(list.current_job(), Some(i12));
+        assert_eq!(list.previous_job(), Some(i11));
+        list.update_status(Pid(13), ProcessState::stopped(SIGTSTP));
+        assert_eq!(list.current_job(), Some(i13));
+        assert_eq!(list.previous_job(), Some(i12));
+
+        list.update_status(Pid(13), ProcessState::Running);
+        assert_eq!(list.current_job(), Some(i12));
+        assert_eq!(list.previous_job(), Some(i11));
+        list.update_status(Pid(12), ProcessState::Running);
+        assert_eq!(list.current_job(), Some(i11));
+        assert_eq!(list.previous_job(), Some(i12));
+    }
 }
@@ -33,14 +33,19 @@
 
 use self::builtin::getopts::GetoptsState;
 use self::builtin::Builtin;
+use self::function::Function;
 use self::function::FunctionSet;
+use self::history::History;
 use self::io::Fd;
+use self::io::Theme;
 use self::job::JobList;
 use self::job::Pid;
 use self::job::ProcessState;
 use self::option::On;
 use self::option::OptionSet;
-use self::option::{AllExport, ErrExit, Interactive, Monitor};
+use self::option::{AllExport, ErrExit, Interactive, Monitor, WarnWritable};
+use self::path::PathBuf;
+use self::semantics::CommandObserver;
 use self::semantics::Divert;
 use self::semantics::ExitStatus;
 use self::stack::Frame;
@@ -49,14 +54,31 @@ pub use self::system::r#virtual::VirtualSystem;
 #[cfg(unix)]
 pub use self::system::real::RealSystem;
 use self::system::Errno;
+use self::system::Mode;
 pub use self::system::SharedSystem;
+use self::system::Stat;
 pub use self::system::System;
 use self::system::SystemEx;
+use self::system::TerminalSize;
 use self::trap::TrapSet;
+use self::variable::ChangeFlag;
+use self::variable::Quirk;
 use self::variable::Scope;
+use self::variable::SecondsState;
+use self::variable::VariableHook;
 use self::variable::VariableRefMut;
 use self::variable::VariableSet;
+use self::variable::COLUMNS;
+use self::variable::LANG;
+use self::variable::LC_ALL;
+use self::variable::LC_CTYPE;
+use self::variable::LINES;
+use self::variable::PATH;
 use self::variable::PPID;
+use self::variable::RANDOM;
+use self::variable::SECONDS;
+use self::variable::SHLVL;
+use self::variable::UNDERSCORE;
 use futures_util::task::noop_waker_ref;
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -68,6 +90,8 @@ use std::task::Poll;
 pub use unix_path as path;
 pub use unix_str as str;
 use yash_syntax::alias::AliasSet;
+use yash_syntax::parser::lex::BlankClass;
+use yash_syntax::parser::lex::Config as LexerConfig;
 
 /// Whole shell execution environment.
 ///
@@ -96,6 +120,34 @@ pub struct Env {
     /// Built-in utilities available in the environment
     pub builtins: HashMap<&'static str, Builtin>,
 
+    /// Observer notified of each simple command before execution
+    ///
+    /// The default is `None`, meaning no observer is installed. Subshells
+    /// share the observer with the parent environment.
+    pub command_observer: Option<Rc<dyn CommandObserver>>,
+
+    /// Whether [`command_paths`](Self::command_paths) is used as a cache
+    ///
+    /// If this field is `false` (the default), command search always scans
+    /// `$PATH` and only records the results in `command_paths`. This ensures
+    /// that an executable file created after a previous search is found.
+    ///
+    /// If this field is `true`, command search reuses the remembered paths
+    /// instead of scanning `$PATH` again, as long as `$PATH` is unchanged.
+    /// A change to `$PATH` is detected by a [hook](VariableHook) registered in
+    /// [`variables`](Self::variables). If `$PATH` has been assigned or unset,
+    /// command search clears `command_paths` before scanning `$PATH`.
+    pub command_path_cache: bool,
+
+    /// Paths to external utilities remembered by command search
+    ///
+    /// The keys are command names and the values are the paths to the
+    /// external utilities found by searching `$PATH`. The paths may be
+    /// relative if `$PATH` contains a relative directory name.
+    ///
+    /// The `hash` built-in lists and clears the entries.
+    pub command_paths: HashMap<String, PathBuf>,
+
     /// Exit status of the last executed command
     pub exit_status: ExitStatus,
 
@@ -105,6 +157,13 @@ pub struct Env {
     /// State of the previous invocation of the `getopts` built-in
     pub getopts_state: Option<GetoptsState>,
 
+    /// Command lines entered in the interactive shell
+    ///
+    /// The interactive read-eval loop adds each command line to the history
+    /// with [`add_history`](Self::add_history). The `fc` built-in lists and
+    /// re-executes the entries.
+    pub history: History,
+
     /// Jobs managed in the environment
     pub jobs: JobList,
 
@@ -119,9 +178,33 @@ pub struct Env {
     /// Shell option settings
     pub options: OptionSet,
 
+    /// File descriptors kept open for process substitutions
+    ///
+    /// A process substitution leaves the shell's end of a pipe open so that
+    /// the command using the substituted pathname can open the file
+    /// descriptor. The file descriptor is recorded in this vector and should
+    /// be closed when the command finishes.
+    pub process_subst_fds: Vec<Fd>,
+
     /// Runtime execution context stack
     pub stack: Stack,
 
+    /// Cached size of the controlling terminal
+    ///
+    /// [`get_terminal_size`](Self::get_terminal_size) saves the size obtained
+    /// from the system in this variable, and
+    /// [`update_terminal_size`](Self::update_terminal_size) refreshes it.
+    /// [`wait_for_signals`](Self::wait_for_signals) refreshes the cache when
+    /// `SIGWINCH` is caught, which requires the internal disposition
+    /// [enabled](TrapSet::enable_internal_disposition_for_sigwinch).
+    pub terminal_size: Option<TerminalSize>,
+
+    /// Styles of messages printed to the standard error
+    ///
+    /// [`io::message_to_string`] applies this theme when the standard error is
+    /// a terminal.
+    pub theme: Theme,
+
     /// Traps defined in the environment
     pub traps: TrapSet,
 
@@ -131,9 +214,27 @@ pub struct Env {
     /// you don't have to prepare it yourself.
     pub tty: Option<Fd>,
 
+    /// Cached file mode creation mask
+    ///
+    /// [`get_umask`](Self::get_umask) saves the mask obtained from the system
+    /// in this variable, and [`set_umask`](Self::set_umask) updates it, so
+    /// that the mask can be read without system calls. If you change the mask
+    /// by calling [`System::umask`] directly, reset this variable to `None` to
+    /// invalidate the cache.
+    pub umask: Option<Mode>,
+
     /// Variables and positional parameters defined in the environment
+    ///
+    /// The environment registers a hook for `$PATH` in this variable set when
+    /// created. If you replace the whole variable set, the hook is lost and
+    /// [`command_paths`](Self::command_paths) is not cleared on changes to
+    /// `$PATH` even if [`command_path_cache`](Self::command_path_cache) is
+    /// enabled.
     pub variables: VariableSet,
 
+    /// Hook notified of changes to `$PATH`
+    path_change_flag: Rc<ChangeFlag>,
+
     /// Interface to the system-managed parts of the environment
     pub system: SharedSystem,
 }
@@ -146,21 +247,34 @@ impl Env {
     /// - `system` is initialized as `SharedSystem::new(system)`
     #[must_use]
     pub fn with_system(system: Box<dyn System>) -> Env {
+        let path_change_flag = Rc::new(ChangeFlag::default());
+        let mut variables = VariableSet::new();
+        variables.add_hook(PATH, Rc::clone(&path_change_flag) as Rc<dyn VariableHook>);
+
         Env {
             aliases: Default::default(),
             arg0: Default::default(),
             builtins: Default::default(),
+            command_observer: Default::default(),
+            command_path_cache: Default::default(),
+            command_paths: Default::default(),
             exit_status: Default::default(),
             functions: Default::default(),
             getopts_state: Default::default(),
+            history: Default::default(),
             jobs: Default::default(),
             main_pgid: system.getpgrp(),
             main_pid: system.getpid(),
             options: Default::default(),
+            process_subst_fds: Default::default(),
             stack: Default::default(),
+            terminal_size: Default::default(),
+            theme: Default::default(),
             traps: Default::default(),
             tty: Default::default(),
-            variables: Default::default(),
+            umask: Default::default(),
+            variables,
+            path_change_flag,
             system: SharedSystem::new(system),
         }
     }
@@ -182,17 +296,26 @@ impl Env {
             aliases: self.aliases.clone(),
             arg0: self.arg0.clone(),
             builtins: self.builtins.clone(),
+            command_observer: self.command_observer.clone(),
+            command_path_cache: self.command_path_cache,
+            command_paths: self.command_paths.clone(),
             exit_status: self.exit_status,
             functions: self.functions.clone(),
             getopts_state: self.getopts_state.clone(),
+            history: self.history.clone(),
             jobs: self.jobs.clone(),
             main_pgid: self.main_pgid,
             main_pid: self.main_pid,
             options: self.options,
+            process_subst_fds: self.process_subst_fds.clone(),
             stack: self.stack.clone(),
+            terminal_size: self.terminal_size,
+            theme: self.theme,
             traps: self.traps.clone(),
             tty: self.tty,
+            umask: self.umask,
             variables: self.variables.clone(),
+            path_change_flag: Rc::clone(&self.path_change_flag),
             system: SharedSystem::new(system),
         }
     }
@@ -208,6 +331,17 @@ impl Env {
     /// - `PS4='+ '`
     /// - `PPID=(parent process ID)`
     /// - `PWD=(current working directory)` (See [`Env::prepare_pwd`])
+    /// - `RANDOM` (with [`Quirk::Random`] seeded by [`System::getrandom`])
+    /// - `SECONDS` (with [`Quirk::Seconds`] counting from zero)
+    /// - `SHLVL=(inherited value + 1)` (exported)
+    /// - `_=(value of Env::arg0)`
+    ///
+    /// `RANDOM` and `SECONDS` are not assigned if they are already defined,
+    /// that is, inherited from the environment. `SECONDS` counts the seconds
+    /// elapsed since this function reads the clock with [`System::now`].
+    ///
+    /// If `SHLVL` is not set or not a valid integer, its inherited value is
+    /// regarded as zero.
     ///
     /// This function ignores any errors that may occur.
     ///
@@ -221,6 +355,66 @@ impl Env {
             .ok();
 
         self.prepare_pwd().ok();
+
+        if self.variables.get(RANDOM).is_none() {
+            if let Some(seed) = self.random_seed() {
+                let seed = std::cell::Cell::new(seed);
+                self.variables
+                    .get_or_new(RANDOM, Scope::Global)
+                    .set_quirk(Some(Quirk::Random(seed)));
+            }
+        }
+
+        if self.variables.get(SECONDS).is_none() {
+            let clock = Rc::new(self.system.clone());
+            let state = SecondsState::new(clock, 0);
+            self.variables
+                .get_or_new(SECONDS, Scope::Global)
+                .set_quirk(Some(Quirk::Seconds(state)));
+        }
+
+        let shlvl = self
+            .variables
+            .get_scalar(SHLVL)
+            .and_then(|value| value.parse::<i64>().ok())
+            .unwrap_or(0);
+        let mut shlvl_var = self.variables.get_or_new(SHLVL, Scope::Global);
+        if shlvl_var
+            .assign(shlvl.saturating_add(1).to_string(), None)
+            .is_ok()
+        {
+            shlvl_var.export(true);
+        }
+
+        self.variables
+            .get_or_new(UNDERSCORE, Scope::Global)
+            .assign(self.arg0.clone(), None)
+            .ok();
+    }
+
+    /// Obtains a seed for the `RANDOM` variable from [`System::getrandom`].
+    fn random_seed(&mut self) -> Option<u64> {
+        let mut seed = [0; 8];
+        self.system.getrandom(&mut seed).ok()?;
+        Some(u64::from_ne_bytes(seed))
+    }
+
+    /// Reseeds the `RANDOM` variable.
+    ///
+    /// If the `RANDOM` variable has [`Quirk::Random`], this function resets
+    /// its state to a new seed obtained from [`System::getrandom`]. A subshell
+    /// calls this function when it starts so that it does not repeat the
+    /// numbers the parent shell would yield. The variable is left intact if
+    /// it does not have the quirk or the seed cannot be obtained.
+    pub fn reseed_random(&mut self) {
+        let Some(seed) = self.random_seed() else {
+            return;
+        };
+        if let Some(Quirk::Random(state)) =
+            self.variables.get(RANDOM).and_then(|v| v.quirk.as_ref())
+        {
+            state.set(seed);
+        }
     }
 
     /// Waits for some signals to be caught in the current process.
@@ -230,12 +424,19 @@ impl Env {
     /// This function is a wrapper for [`SharedSystem::wait_for_signals`].
     /// Before the function returns, it passes the results to
     /// [`TrapSet::catch_signal`] so the trap set can remember the signals
-    /// caught to be handled later.
+    /// caught to be handled later. If `SIGWINCH` is caught, the function also
+    /// [updates the terminal size](Self::update_terminal_size).
     pub async fn wait_for_signals(&mut self) -> Rc<[signal::Number]> {
         let result = self.system.wait_for_signals().await;
         for signal in result.iter().copied() {
             self.traps.catch_signal(signal);
         }
+
+        let sigwinch = self.system.signal_number_from_name(signal::Name::Winch);
+        if sigwinch.is_some_and(|sigwinch| result.contains(&sigwinch)) {
+            self.update_terminal_size().ok();
+        }
+
         result
     }
 
@@ -306,6 +507,120 @@ impl Env {
         final_fd
     }
 
+    /// Returns the current file mode creation mask.
+    ///
+    /// This function returns `self.umask` if it is `Some` mask. Otherwise, it
+    /// obtains the mask from the system and saves it to `self.umask` before
+    /// returning it.
+    pub fn get_umask(&mut self) -> Mode {
+        if let Some(mask) = self.umask {
+            return mask;
+        }
+
+        // There is no way to get the mask without changing it
+        let mask = self.system.umask(Mode::empty());
+        self.system.umask(mask);
+        self.umask = Some(mask);
+        mask
+    }
+
+    /// Returns the size of the controlling terminal.
+    ///
+    /// This function returns `self.terminal_size` if it is `Some` size.
+    /// Otherwise, it [updates](Self::update_terminal_size) the size.
+    pub fn get_terminal_size(&mut self) -> Result<TerminalSize, Errno> {
+        match self.terminal_size {
+            Some(size) => Ok(size),
+            None => self.update_terminal_size(),
+        }
+    }
+
+    /// Obtains the current size of the controlling terminal.
+    ///
+    /// This function queries the size of the terminal [opened](Self::get_tty)
+    /// as `self.tty` and saves it to `self.terminal_size`.
+    ///
+    /// If the `COLUMNS` and `LINES` variables still have the values of the
+    /// previously cached size, they are updated with the new size. Variables
+    /// that have been unset or assigned a different value are regarded as
+    /// overridden by the user and left intact. Errors assigning to the
+    /// variables (e.g., because they are read-only) are ignored.
+    pub fn update_terminal_size(&mut self) -> Result<TerminalSize, Errno> {
+        let tty = self.get_tty()?;
+        let size = self.system.terminal_size(tty)?;
+        let old_size = self.terminal_size.replace(size);
+
+        let Some(old_size) = old_size else {
+            return Ok(size);
+        };
+        for (name, old_value, new_value) in [
+            (COLUMNS, old_size.columns, size.columns),
+            (LINES, old_size.rows, size.rows),
+        ] {
+            if self.variables.get_scalar(name) == Some(&old_value.to_string()) {
+                self.variables
+                    .get_or_new(name, Scope::Global)
+                    .assign(new_value.to_string(), None)
+                    .ok();
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Initializes the `COLUMNS` and `LINES` variables.
+    ///
+    /// This function [obtains the terminal size](Self::get_terminal_size) and
+    /// assigns it to the `COLUMNS` and `LINES` variables. A variable that
+    /// already exists, for example because it has been inherited from the
+    /// environment, is regarded as overridden by the user and left intact.
+    ///
+    /// The variables assigned by this function are kept up to date by
+    /// [`update_terminal_size`](Self::update_terminal_size).
+    pub fn init_terminal_size_variables(&mut self) -> Result<(), Errno> {
+        let size = self.get_terminal_size()?;
+        for (name, value) in [(COLUMNS, size.columns), (LINES, size.rows)] {
+            if self.variables.get(name).is_none() {
+                self.variables
+                    .get_or_new(name, Scope::Global)
+                    .assign(value.to_string(), None)
+                    .ok();
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the file mode creation mask.
+    ///
+    /// This function sets the mask in the system and saves it to `self.umask`.
+    /// Returns the previous mask.
+    pub fn set_umask(&mut self, mask: Mode) -> Mode {
+        let old_mask = self.system.umask(mask);
+        self.umask = Some(mask);
+        old_mask
+    }
+
+    /// Warns if a script file is writable by other users.
+    ///
+    /// This function should be called with the [`Stat`] of a script file
+    /// before the shell executes it. If the [`WarnWritable`] option is on and
+    /// the file is writable by users other than the owner and the group (that
+    /// is, the file has the [`Mode::OTHER_WRITE`] permission), a warning
+    /// naming the file is printed to the standard error.
+    ///
+    /// Such a file may have been modified by a malicious user, so executing it
+    /// may be a security risk. This function only warns and does not prevent
+    /// the file from being executed.
+    pub async fn warn_writable_script(&mut self, stat: &Stat, name: &str) {
+        if self.options.get(WarnWritable) == On && stat.mode.contains(Mode::OTHER_WRITE) {
+            let message = format!(
+                "{}: warning: script file {name:?} is writable by other users\n",
+                self.arg0
+            );
+            self.system.print_error(&message).await;
+        }
+    }
+
     /// Tests whether the current environment is an interactive shell.
     ///
     /// This function returns true if and only if:
@@ -328,6 +643,61 @@ impl Env {
         self.options.get(Monitor) == On && !self.stack.contains(&Frame::Subshell)
     }
 
+    /// Returns a lexer configuration for parsing commands in this environment.
+    ///
+    /// The [blank class](BlankClass) of the configuration depends on the
+    /// locale for character classification, which is determined by the first
+    /// non-empty value of the `LC_ALL`, `LC_CTYPE`, and `LANG` variables. If
+    /// the locale is `C` or `POSIX`, the configuration uses
+    /// [`BlankClass::Ascii`]. Otherwise, it uses [`BlankClass::Unicode`], the
+    /// default. The other fields of the configuration have default values.
+    pub fn lexer_config(&self) -> LexerConfig {
+        let locale = [LC_ALL, LC_CTYPE, LANG]
+            .into_iter()
+            .filter_map(|name| self.variables.get_scalar(name))
+            .find(|value| !value.is_empty());
+        let mut config = LexerConfig::new();
+        if matches!(locale, Some("C" | "POSIX")) {
+            config.blank_class = BlankClass::Ascii;
+        }
+        config
+    }
+
+    /// Clears [`command_paths`](Self::command_paths) if `$PATH` has changed.
+    ///
+    /// This function tests whether `$PATH` has been assigned or unset since
+    /// the last call to this function, using the hook the environment
+    /// registered in [`variables`](Self::variables). If so, this function
+    /// clears `command_paths` and returns true.
+    pub fn invalidate_command_paths_on_path_change(&mut self) -> bool {
+        let changed = self.path_change_flag.take();
+        if changed {
+            self.command_paths.clear();
+        }
+        changed
+    }
+
+    /// Returns the function to be called when a command is not found.
+    ///
+    /// When the command search finds no utility for a simple command, the
+    /// shell calls the function named [`COMMAND_NOT_FOUND_HANDLER`] instead of
+    /// failing with exit status 127, if such a function is defined. This
+    /// function returns the handler function if it is defined and the
+    /// handler is not already running. While running the handler, the caller
+    /// should push a [`Frame::CommandNotFoundHandler`] to `self.stack` so that
+    /// a command not found in the handler is not handled recursively.
+    ///
+    /// [`COMMAND_NOT_FOUND_HANDLER`]: self::function::COMMAND_NOT_FOUND_HANDLER
+    #[must_use]
+    pub fn command_not_found_handler(&self) -> Option<Rc<Function>> {
+        if self.stack.contains(&Frame::CommandNotFoundHandler) {
+            return None;
+        }
+        self.functions
+            .get(self::function::COMMAND_NOT_FOUND_HANDLER)
+            .cloned()
+    }
+
     /// Waits for a subshell to terminate, suspend, or resume.
     ///
     /// This function waits for a subshell to change its execution state. The
@@ -467,6 +837,7 @@ mod alias;
 pub mod builtin;
 mod decl_util;
 pub mod function;
+pub mod history;
 pub mod input;
 pub mod io;
 pub mod job;
@@ -490,14 +861,19 @@ mod tests {
     use crate::system::r#virtual::Inode;
     use crate::system::r#virtual::SystemState;
     use crate::system::r#virtual::SIGCHLD;
+    use crate::system::r#virtual::SIGWINCH;
     use crate::trap::Action;
+    use crate::variable::Expansion;
     use assert_matches::assert_matches;
     use futures_executor::LocalPool;
     use futures_util::task::LocalSpawnExt as _;
     use futures_util::FutureExt as _;
     use std::cell::RefCell;
     use std::str::from_utf8;
+    use std::time::Duration;
+    use std::time::Instant;
     use yash_syntax::source::Location;
+    use yash_syntax::syntax::FullCompoundCommand;
 
     /// Helper function to perform a test in a virtual system with an executor.
     pub fn in_virtual_system<F, Fut, T>(f: F) -> T
@@ -537,6 +913,158 @@ mod tests {
         })
     }
 
+    /// Returns a new virtual environment whose clock is set so that
+    /// [`Env::init_variables`] can read the current time.
+    fn env_with_clock() -> Env {
+        let system = VirtualSystem::new();
+        system.state.borrow_mut().now = Some(Instant::now());
+        Env::with_system(Box::new(system))
+    }
+
+    #[test]
+    fn init_variables_sets_underscore_to_arg0() {
+        let mut env = env_with_clock();
+        env.arg0 = "/usr/bin/yash".to_string();
+        env.init_variables();
+        assert_eq!(env.variables.get_scalar(UNDERSCORE), Some("/usr/bin/yash"));
+    }
+
+    #[test]
+    fn init_variables_sets_random() {
+        let mut env = env_with_clock();
+        env.init_variables();
+        let location = Location::dummy("");
+        let random = env.variables.get(RANDOM).unwrap();
+        assert_matches!(random.quirk, Some(Quirk::Random(_)));
+        let first = random.expand(&location).into_owned();
+        let second = random.expand(&location).into_owned();
+        assert_ne!(first, second);
+
+        let mut random = env.variables.get_or_new(RANDOM, Scope::Global);
+        random.assign("123", None).unwrap();
+        let random = env.variables.get(RANDOM).unwrap();
+        let first = random.expand(&location).into_owned();
+        let second = random.expand(&location).into_owned();
+        let mut random = env.variables.get_or_new(RANDOM, Scope::Global);
+        random.assign("123", None).unwrap();
+        let random = env.variables.get(RANDOM).unwrap();
+        assert_eq!(random.expand(&location).into_owned(), first);
+        assert_eq!(random.expand(&location).into_owned(), second);
+    }
+
+    #[test]
+    fn init_variables_seeds_random_with_system() {
+        let system = Box::new(VirtualSystem::new());
+        system.state.borrow_mut().now = Some(Instant::now());
+        system.state.borrow_mut().random_seed = 1;
+        let mut env1 = Env::with_system(system);
+        env1.init_variables();
+        let system = Box::new(VirtualSystem::new());
+        system.state.borrow_mut().now = Some(Instant::now());
+        system.state.borrow_mut().random_seed = 2;
+        let mut env2 = Env::with_system(system);
+        env2.init_variables();
+
+        let location = Location::dummy("");
+        let random1 = env1.variables.get(RANDOM).unwrap().expand(&location);
+        let random2 = env2.variables.get(RANDOM).unwrap().expand(&location);
+        assert_ne!(random1, random2);
+    }
+
+    #[test]
+    fn init_variables_keeps_inherited_random() {
+        let mut env = env_with_clock();
+        env.variables.extend_env([(RANDOM, "42")]);
+        env.init_variables();
+        let random = env.variables.get(RANDOM).unwrap();
+        assert_eq!(random.value, Some("42".into()));
+        assert_eq!(random.quirk, None);
+    }
+
+    #[test]
+    fn reseed_random_changes_sequence() {
+        let mut env = env_with_clock();
+        env.init_variables();
+        let location = Location::dummy("");
+        let original = env.variables.get(RANDOM).unwrap().clone();
+        env.reseed_random();
+        let random = env.variables.get(RANDOM).unwrap();
+        assert_ne!(random.expand(&location), original.expand(&location));
+    }
+
+    #[test]
+    fn reseed_random_ignores_variable_without_quirk() {
+        let mut env = env_with_clock();
+        env.variables
+            .get_or_new(RANDOM, Scope::Global)
+            .assign("42", None)
+            .unwrap();
+        env.reseed_random();
+        let random = env.variables.get(RANDOM).unwrap();
+        assert_eq!(random.value, Some("42".into()));
+        assert_eq!(random.quirk, None);
+    }
+
+    #[test]
+    fn init_variables_sets_seconds() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        let mut env = Env::with_system(system);
+        env.init_variables();
+        let location = Location::dummy("");
+        let seconds = env.variables.get(SECONDS).unwrap();
+        assert_matches!(seconds.quirk, Some(Quirk::Seconds(_)));
+        assert_eq!(seconds.expand(&location), Expansion::Scalar("0".into()));
+
+        *state.borrow_mut().now.as_mut().unwrap() += Duration::from_secs(7);
+        let seconds = env.variables.get(SECONDS).unwrap();
+        assert_eq!(seconds.expand(&location), Expansion::Scalar("7".into()));
+
+        let mut seconds = env.variables.get_or_new(SECONDS, Scope::Global);
+        seconds.assign("10", None).unwrap();
+        *state.borrow_mut().now.as_mut().unwrap() += Duration::from_secs(3);
+        let seconds = env.variables.get(SECONDS).unwrap();
+        assert_eq!(seconds.expand(&location), Expansion::Scalar("13".into()));
+    }
+
+    #[test]
+    fn init_variables_keeps_inherited_seconds() {
+        let mut env = Env::new_virtual();
+        env.variables.extend_env([(SECONDS, "42")]);
+        env.init_variables();
+        let seconds = env.variables.get(SECONDS).unwrap();
+        assert_eq!(seconds.value, Some("42".into()));
+        assert_eq!(seconds.quirk, None);
+    }
+
+    #[test]
+    fn init_variables_increments_shlvl() {
+        let mut env = env_with_clock();
+        env.variables.extend_env([(SHLVL, "2")]);
+        env.init_variables();
+        let shlvl = env.variables.get(SHLVL).unwrap();
+        assert_eq!(shlvl.value, Some("3".into()));
+        assert!(shlvl.is_exported);
+    }
+
+    #[test]
+    fn init_variables_sets_shlvl_to_one_if_not_inherited() {
+        let mut env = env_with_clock();
+        env.init_variables();
+        let shlvl = env.variables.get(SHLVL).unwrap();
+        assert_eq!(shlvl.value, Some("1".into()));
+        assert!(shlvl.is_exported);
+    }
+
+    #[test]
+    fn init_variables_sets_shlvl_to_one_if_invalid() {
+        let mut env = env_with_clock();
+        env.variables.extend_env([(SHLVL, "foo")]);
+        env.init_variables();
+        assert_eq!(env.variables.get_scalar(SHLVL), Some("1"));
+    }
+
     #[test]
     fn wait_for_signal_remembers_signal_in_trap_set() {
         in_virtual_system(|mut env, state| async move {
@@ -635,6 +1163,189 @@ mod tests {
             .unwrap();
     }
 
+    /// Makes the file at `path` a terminal, creating the file if it does not
+    /// exist.
+    pub(crate) fn set_terminal(system: &VirtualSystem, path: &str) {
+        let body = FileBody::Terminal { content: vec![] };
+        let mut state = system.state.borrow_mut();
+        match state.file_system.get(path) {
+            Ok(file) => file.borrow_mut().body = body,
+            Err(_) => {
+                let file = Inode {
+                    body,
+                    permissions: Default::default(),
+                };
+                let file = Rc::new(RefCell::new(file));
+                state.file_system.save(path, file).unwrap();
+            }
+        }
+    }
+
+    fn stub_terminal(system: &VirtualSystem) {
+        set_terminal(system, "/dev/tty");
+        system.state.borrow_mut().terminal_size = Some(TerminalSize {
+            rows: 24,
+            columns: 80,
+        });
+    }
+
+    #[test]
+    fn update_terminal_size_updates_cache() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let mut env = Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new(COLUMNS, Scope::Global)
+            .assign("10", None)
+            .unwrap();
+
+        let size = env.update_terminal_size().unwrap();
+        assert_eq!(size.rows, 24);
+        assert_eq!(size.columns, 80);
+        assert_eq!(env.terminal_size, Some(size));
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("10"));
+        assert_eq!(env.variables.get(LINES), None);
+    }
+
+    #[test]
+    fn update_terminal_size_keeps_variables_overridden_by_user() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.init_terminal_size_variables().unwrap();
+        env.variables
+            .get_or_new(COLUMNS, Scope::Global)
+            .assign("100", None)
+            .unwrap();
+        system.state.borrow_mut().terminal_size = Some(TerminalSize {
+            rows: 50,
+            columns: 120,
+        });
+
+        env.update_terminal_size().unwrap();
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("100"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("50"));
+    }
+
+    #[test]
+    fn init_terminal_size_variables_assigns_size() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let mut env = Env::with_system(Box::new(system));
+
+        env.init_terminal_size_variables().unwrap();
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("80"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("24"));
+        assert!(!env.variables.get(COLUMNS).unwrap().is_exported);
+    }
+
+    #[test]
+    fn init_terminal_size_variables_keeps_existing_variables() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let mut env = Env::with_system(Box::new(system));
+        env.variables.extend_env([(LINES, "10")]);
+
+        env.init_terminal_size_variables().unwrap();
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("80"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("10"));
+    }
+
+    #[test]
+    fn get_terminal_size_returns_cached_size() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let mut env = Env::with_system(Box::new(system.clone()));
+        let size = env.get_terminal_size().unwrap();
+
+        system.state.borrow_mut().terminal_size = Some(TerminalSize {
+            rows: 50,
+            columns: 120,
+        });
+        assert_eq!(env.get_terminal_size(), Ok(size));
+    }
+
+    #[test]
+    fn update_terminal_size_without_tty() {
+        let mut env = Env::new_virtual();
+        assert_eq!(env.update_terminal_size(), Err(Errno::ENOENT));
+        assert_eq!(env.terminal_size, None);
+    }
+
+    #[test]
+    fn sigwinch_updates_terminal_size() {
+        let system = VirtualSystem::new();
+        stub_terminal(&system);
+        let shared_system = SharedSystem::new(Box::new(system.clone()));
+        let mut env = Env::with_system(Box::new(shared_system));
+        env.traps
+            .enable_internal_disposition_for_sigwinch(&mut env.system)
+            .unwrap();
+        env.get_terminal_size().unwrap();
+        env.variables
+            .get_or_new(COLUMNS, Scope::Global)
+            .assign("80", None)
+            .unwrap();
+        env.variables
+            .get_or_new(LINES, Scope::Global)
+            .assign("24", None)
+            .unwrap();
+
+        {
+            let mut state = system.state.borrow_mut();
+            state.terminal_size = Some(TerminalSize {
+                rows: 50,
+                columns: 120,
+            });
+            let process = state.processes.get_mut(&system.process_id).unwrap();
+            let _ = process.raise_signal(SIGWINCH);
+        }
+        let result = env.poll_signals().unwrap();
+        assert_eq!(*result, [SIGWINCH]);
+
+        let size = TerminalSize {
+            rows: 50,
+            columns: 120,
+        };
+        assert_eq!(env.terminal_size, Some(size));
+        assert_eq!(env.variables.get_scalar(COLUMNS), Some("120"));
+        assert_eq!(env.variables.get_scalar(LINES), Some("50"));
+    }
+
+    #[test]
+    fn get_umask_caches_mask() {
+        let mut system = VirtualSystem::new();
+        system.umask(Mode::from_bits_retain(0o022));
+        let mut env = Env::with_system(Box::new(system.clone()));
+
+        assert_eq!(env.get_umask(), Mode::from_bits_retain(0o022));
+        assert_eq!(env.umask, Some(Mode::from_bits_retain(0o022)));
+
+        // Change the mask behind the environment's back
+        system.umask(Mode::from_bits_retain(0o077));
+
+        // get_umask returns the cached mask
+        assert_eq!(env.get_umask(), Mode::from_bits_retain(0o022));
+
+        // Invalidating the cache makes get_umask see the new mask
+        env.umask = None;
+        assert_eq!(env.get_umask(), Mode::from_bits_retain(0o077));
+    }
+
+    #[test]
+    fn set_umask_updates_cache() {
+        let mut system = VirtualSystem::new();
+        system.umask(Mode::from_bits_retain(0o022));
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.get_umask();
+
+        let old_mask = env.set_umask(Mode::from_bits_retain(0o027));
+        assert_eq!(old_mask, Mode::from_bits_retain(0o022));
+        assert_eq!(env.umask, Some(Mode::from_bits_retain(0o027)));
+        assert_eq!(env.get_umask(), Mode::from_bits_retain(0o027));
+        assert_eq!(system.umask(Mode::empty()), Mode::from_bits_retain(0o027));
+    }
+
     #[test]
     fn start_and_wait_for_subshell() {
         in_virtual_system(|mut env, _state| async move {
@@ -809,4 +1520,132 @@ mod tests {
         env.apply_result(Break(Divert::Exit(Some(ExitStatus(67)))));
         assert_eq!(env.exit_status, ExitStatus(67));
     }
+
+    #[test]
+    fn lexer_config_without_locale() {
+        let env = Env::new_virtual();
+        assert_eq!(env.lexer_config().blank_class, BlankClass::Unicode);
+    }
+
+    #[test]
+    fn lexer_config_with_posix_locale() {
+        let mut env = Env::new_virtual();
+        env.get_or_create_variable(LANG, Scope::Global)
+            .assign("C", None)
+            .unwrap();
+        assert_eq!(env.lexer_config().blank_class, BlankClass::Ascii);
+
+        env.get_or_create_variable(LC_CTYPE, Scope::Global)
+            .assign("en_US.UTF-8", None)
+            .unwrap();
+        assert_eq!(env.lexer_config().blank_class, BlankClass::Unicode);
+
+        env.get_or_create_variable(LC_ALL, Scope::Global)
+            .assign("POSIX", None)
+            .unwrap();
+        assert_eq!(env.lexer_config().blank_class, BlankClass::Ascii);
+    }
+
+    #[test]
+    fn lexer_config_ignores_empty_locale_variables() {
+        let mut env = Env::new_virtual();
+        env.get_or_create_variable(LC_ALL, Scope::Global)
+            .assign("", None)
+            .unwrap();
+        env.get_or_create_variable(LANG, Scope::Global)
+            .assign("C", None)
+            .unwrap();
+        assert_eq!(env.lexer_config().blank_class, BlankClass::Ascii);
+    }
+
+    #[test]
+    fn command_paths_invalidated_on_path_change() {
+        let mut env = Env::new_virtual();
+        env.command_paths.insert("foo".into(), "/bin/foo".into());
+        assert!(!env.invalidate_command_paths_on_path_change());
+        assert_eq!(env.command_paths.len(), 1);
+
+        env.get_or_create_variable(PATH, Scope::Global)
+            .assign("/bin", None)
+            .unwrap();
+        assert!(env.invalidate_command_paths_on_path_change());
+        assert!(env.command_paths.is_empty());
+
+        env.command_paths.insert("foo".into(), "/bin/foo".into());
+        assert!(!env.invalidate_command_paths_on_path_change());
+        assert_eq!(env.command_paths.len(), 1);
+    }
+
+    #[test]
+    fn command_not_found_handler_undefined() {
+        let env = Env::new_virtual();
+        assert_eq!(env.command_not_found_handler(), None);
+    }
+
+    #[test]
+    fn command_not_found_handler_defined() {
+        let mut env = Env::new_virtual();
+        let body = "{ :; }".parse::<FullCompoundCommand>().unwrap();
+        let location = Location::dummy("handler");
+        let function = Function::new(function::COMMAND_NOT_FOUND_HANDLER, body, location);
+        env.functions.define(function.clone()).unwrap();
+
+        assert_eq!(env.command_not_found_handler(), Some(Rc::new(function)));
+
+        let env = env.push_frame(Frame::CommandNotFoundHandler);
+        assert_eq!(env.command_not_found_handler(), None);
+    }
+
+    fn stat_with_mode(mode: Mode) -> Stat {
+        let mut inode = Inode::new([]);
+        inode.permissions = mode;
+        inode.stat()
+    }
+
+    #[test]
+    fn warn_writable_script_with_world_writable_file() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.arg0 = "yash".to_string();
+        env.options.set(WarnWritable, On);
+        let stat = stat_with_mode(Mode::from_bits_retain(0o666));
+
+        env.warn_writable_script(&stat, "foo.sh")
+            .now_or_never()
+            .unwrap();
+        assert_stderr(&state, |stderr| {
+            assert_eq!(
+                stderr,
+                "yash: warning: script file \"foo.sh\" is writable by other users\n"
+            )
+        });
+    }
+
+    #[test]
+    fn warn_writable_script_with_option_off() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let stat = stat_with_mode(Mode::from_bits_retain(0o666));
+
+        env.warn_writable_script(&stat, "foo.sh")
+            .now_or_never()
+            .unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn warn_writable_script_with_non_world_writable_file() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(WarnWritable, On);
+        let stat = stat_with_mode(Mode::from_bits_retain(0o664));
+
+        env.warn_writable_script(&stat, "foo.sh")
+            .now_or_never()
+            .unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
 }
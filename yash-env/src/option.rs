@@ -101,11 +101,17 @@ impl From<State> for bool {
 pub enum Option {
     /// Makes all variables exported when they are assigned.
     AllExport,
+    /// Enables brace expansion.
+    BraceExpand,
     /// Allows overwriting and truncating an existing file with the `>`
     /// redirection.
     Clobber,
     /// Executes a command string specified as a command line argument.
     CmdLine,
+    /// Enables emacs-like command line editing.
+    ///
+    /// This option is mutually exclusive with [`Vi`].
+    Emacs,
     /// Makes the shell to exit when a command returns a non-zero exit status.
     ErrExit,
     /// Makes the shell to actually run commands.
@@ -129,8 +135,16 @@ pub enum Option {
     Monitor,
     /// Automatically reports the results of asynchronous jobs.
     Notify,
+    /// Makes a pipeline's exit status reflect a failure of any of its
+    /// commands, not only the last one.
+    PipeFail,
     /// Disables most non-POSIX extensions.
     PosixlyCorrect,
+    /// Enables process substitution.
+    ProcSubst,
+    /// Disallows changing the working directory, setting `$PATH`, creating
+    /// files by redirections, and running commands specified by pathnames.
+    Restricted,
     /// Reads commands from the standard input.
     Stdin,
     /// Expands unset variables to an empty string rather than erroring out.
@@ -138,7 +152,11 @@ pub enum Option {
     /// Echos the input before parsing and executing.
     Verbose,
     /// Enables vi-like command line editing.
+    ///
+    /// This option is mutually exclusive with [`Emacs`].
     Vi,
+    /// Warns when executing a script file that is writable by other users.
+    WarnWritable,
     /// Prints expanded words during command execution.
     XTrace,
 }
@@ -151,7 +169,7 @@ impl Option {
     /// Unmodifiable options can be set only on shell startup.
     #[must_use]
     pub const fn is_modifiable(self) -> bool {
-        !matches!(self, CmdLine | Interactive | Stdin)
+        !matches!(self, CmdLine | Interactive | Restricted | Stdin)
     }
 
     /// Returns the single-character option name.
@@ -165,8 +183,10 @@ impl Option {
     pub const fn short_name(self) -> std::option::Option<(char, State)> {
         match self {
             AllExport => Some(('a', On)),
+            BraceExpand => None,
             Clobber => Some(('C', Off)),
             CmdLine => Some(('c', On)),
+            Emacs => None,
             ErrExit => Some(('e', On)),
             Exec => Some(('n', Off)),
             Glob => Some(('f', Off)),
@@ -177,11 +197,15 @@ impl Option {
             Login => Some(('l', On)),
             Monitor => Some(('m', On)),
             Notify => Some(('b', On)),
+            PipeFail => None,
             PosixlyCorrect => None,
+            ProcSubst => None,
+            Restricted => None,
             Stdin => Some(('s', On)),
             Unset => Some(('u', Off)),
             Verbose => Some(('v', On)),
             Vi => None,
+            WarnWritable => None,
             XTrace => Some(('x', On)),
         }
     }
@@ -194,8 +218,10 @@ impl Option {
     pub const fn long_name(self) -> &'static str {
         match self {
             AllExport => "allexport",
+            BraceExpand => "braceexpand",
             Clobber => "clobber",
             CmdLine => "cmdline",
+            Emacs => "emacs",
             ErrExit => "errexit",
             Exec => "exec",
             Glob => "glob",
@@ -206,11 +232,15 @@ impl Option {
             Login => "login",
             Monitor => "monitor",
             Notify => "notify",
+            PipeFail => "pipefail",
             PosixlyCorrect => "posixlycorrect",
+            ProcSubst => "procsubst",
+            Restricted => "restricted",
             Stdin => "stdin",
             Unset => "unset",
             Verbose => "verbose",
             Vi => "vi",
+            WarnWritable => "warnwritable",
             XTrace => "xtrace",
         }
     }
@@ -258,8 +288,10 @@ impl FromStr for Option {
     fn from_str(name: &str) -> Result<Self, FromStrError> {
         const OPTIONS: &[(&str, Option)] = &[
             ("allexport", AllExport),
+            ("braceexpand", BraceExpand),
             ("clobber", Clobber),
             ("cmdline", CmdLine),
+            ("emacs", Emacs),
             ("errexit", ErrExit),
             ("exec", Exec),
             ("glob", Glob),
@@ -270,11 +302,15 @@ impl FromStr for Option {
             ("login", Login),
             ("monitor", Monitor),
             ("notify", Notify),
+            ("pipefail", PipeFail),
             ("posixlycorrect", PosixlyCorrect),
+            ("procsubst", ProcSubst),
+            ("restricted", Restricted),
             ("stdin", Stdin),
             ("unset", Unset),
             ("verbose", Verbose),
             ("vi", Vi),
+            ("warnwritable", WarnWritable),
             ("xtrace", XTrace),
         ];
 
@@ -485,10 +521,18 @@ impl OptionSet {
     /// Some options should not be changed after the shell startup, but that
     /// does not affect the behavior of this function.
     ///
-    /// TODO: What if an option that is mutually exclusive with another is set?
+    /// The [`Emacs`] and [`Vi`] options are mutually exclusive. Enabling one of
+    /// them disables the other.
     pub fn set(&mut self, option: Option, state: State) {
         match state {
-            On => self.enabled_options.insert(option),
+            On => {
+                match option {
+                    Emacs => self.enabled_options.remove(Vi),
+                    Vi => self.enabled_options.remove(Emacs),
+                    _ => false,
+                };
+                self.enabled_options.insert(option)
+            }
             Off => self.enabled_options.remove(option),
         };
     }
@@ -496,7 +540,9 @@ impl OptionSet {
 
 impl Extend<Option> for OptionSet {
     fn extend<T: IntoIterator<Item = Option>>(&mut self, iter: T) {
-        self.enabled_options.extend(iter);
+        for option in iter {
+            self.set(option, On);
+        }
     }
 }
 
@@ -591,6 +637,34 @@ mod tests {
         assert_eq!(parse_long("LOG"), Err(NoSuchOption));
     }
 
+    #[test]
+    fn emacs_and_vi_are_mutually_exclusive() {
+        let mut options = OptionSet::default();
+        options.set(Emacs, On);
+        assert_eq!(options.get(Emacs), On);
+        assert_eq!(options.get(Vi), Off);
+
+        options.set(Vi, On);
+        assert_eq!(options.get(Emacs), Off);
+        assert_eq!(options.get(Vi), On);
+
+        options.set(Emacs, On);
+        assert_eq!(options.get(Emacs), On);
+        assert_eq!(options.get(Vi), Off);
+
+        options.set(Emacs, Off);
+        assert_eq!(options.get(Emacs), Off);
+        assert_eq!(options.get(Vi), Off);
+    }
+
+    #[test]
+    fn extending_with_emacs_and_vi() {
+        let mut options = OptionSet::empty();
+        options.extend([Vi, Emacs]);
+        assert_eq!(options.get(Emacs), On);
+        assert_eq!(options.get(Vi), Off);
+    }
+
     #[test]
     fn test_canonicalize() {
         assert_eq!(canonicalize(""), "");
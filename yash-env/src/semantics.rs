@@ -18,11 +18,13 @@
 
 use crate::signal;
 use crate::system::System;
+use crate::Env;
 use std::ffi::c_int;
 use std::ops::ControlFlow;
 use std::process::ExitCode;
 use std::process::Termination;
 use yash_syntax::source::Location;
+use yash_syntax::syntax::SimpleCommand;
 
 /// Resultant string of word expansion.
 ///
@@ -231,6 +233,23 @@ impl Divert {
 /// next.
 pub type Result<T = ()> = ControlFlow<Divert, T>;
 
+/// Observer of command execution
+///
+/// An implementor of this trait can be installed in
+/// [`Env::command_observer`] to be notified of each simple command right
+/// before it is executed. This allows embedders to implement custom tracing,
+/// profiling, and the like.
+pub trait CommandObserver: std::fmt::Debug {
+    /// Called before a simple command is executed.
+    ///
+    /// `command` is the simple command to be executed, and `fields` are the
+    /// results of expanding its words. The observer is called after the
+    /// expansion and before the command search, redirections, and
+    /// assignments. `fields` is empty if the command has no words or the words
+    /// expanded to nothing.
+    fn before_simple_command(&self, env: &mut Env, command: &SimpleCommand, fields: &[Field]);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +80,11 @@ pub enum Frame {
     // TODO function
     /// File executed during shell startup
     InitFile,
+
+    /// Function executed as the handler for a command that was not found
+    ///
+    /// See [`Env::command_not_found_handler`](crate::Env::command_not_found_handler).
+    CommandNotFoundHandler,
 }
 
 impl From<Builtin> for Frame {
@@ -153,7 +158,8 @@ impl Stack {
     /// This function returns the number of lexically enclosing `for`, `while`,
     /// and `until` loops in the current execution environment. That is, the
     /// result is the count of `Frame::Loop`s pushed after the last
-    /// `Frame::Subshell`, `Frame::DotScript`, or `Frame::Trap(_)`.
+    /// `Frame::Subshell`, `Frame::DotScript`, `Frame::Trap(_)`, or
+    /// `Frame::CommandNotFoundHandler`.
     ///
     /// The function stops counting when `max_count` is reached. The parameter
     /// is useful if you don't have to count more than a specific number.
@@ -163,7 +169,11 @@ impl Stack {
         fn retains_context(frame: &Frame) -> bool {
             match frame {
                 Frame::Loop | Frame::Condition | Frame::Builtin(_) => true,
-                Frame::Subshell | Frame::DotScript | Frame::Trap(_) | Frame::InitFile => false,
+                Frame::Subshell
+                | Frame::DotScript
+                | Frame::Trap(_)
+                | Frame::InitFile
+                | Frame::CommandNotFoundHandler => false,
             }
         }
 
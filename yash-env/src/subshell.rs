@@ -26,9 +26,11 @@
 //! of creating a subshell because it helps to arrange the child process
 //! properly.
 
+use crate::io::Fd;
 use crate::job::Pid;
 use crate::job::ProcessResult;
 use crate::job::ProcessState;
+use crate::semantics::ExitStatus;
 use crate::signal;
 use crate::stack::Frame;
 use crate::system::ChildProcessTask;
@@ -49,6 +51,24 @@ pub enum JobControl {
     Background,
 }
 
+/// File descriptor operation performed in a subshell before the task runs
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FdAction {
+    /// Duplicates `from` to `to` with [`System::dup2`].
+    Dup2 { from: Fd, to: Fd },
+    /// Closes the file descriptor with [`System::close`].
+    Close(Fd),
+}
+
+impl FdAction {
+    fn apply<S: System>(self, system: &mut S) -> Result<(), Errno> {
+        match self {
+            FdAction::Dup2 { from, to } => system.dup2(from, to).map(drop),
+            FdAction::Close(fd) => system.close(fd),
+        }
+    }
+}
+
 /// Subshell builder
 ///
 /// See the [module documentation](self) for details.
@@ -57,11 +77,16 @@ pub struct Subshell<F> {
     task: F,
     job_control: Option<JobControl>,
     ignores_sigint_sigquit: bool,
+    fd_actions: Vec<FdAction>,
 }
 
 impl<F> std::fmt::Debug for Subshell<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Subshell").finish_non_exhaustive()
+        f.debug_struct("Subshell")
+            .field("job_control", &self.job_control)
+            .field("ignores_sigint_sigquit", &self.ignores_sigint_sigquit)
+            .field("fd_actions", &self.fd_actions)
+            .finish_non_exhaustive()
     }
 }
 
@@ -89,6 +114,7 @@ where
             task,
             job_control: None,
             ignores_sigint_sigquit: false,
+            fd_actions: Vec::new(),
         }
     }
 
@@ -127,6 +153,29 @@ where
         self
     }
 
+    /// Makes the subshell duplicate a file descriptor before running the task.
+    ///
+    /// The subshell calls [`dup2`](System::dup2) to make `to` refer to the
+    /// same open file description as `from`. The file descriptor `from` is
+    /// left open; use [`close_fd`](Self::close_fd) to close it.
+    ///
+    /// The operations specified by this method and `close_fd` are performed in
+    /// the order they were specified. If any of them fails, the subshell prints
+    /// an error message and exits with [`ExitStatus::NOEXEC`] without running
+    /// the task. The parent's file descriptors are not affected.
+    pub fn dup2_fd(mut self, from: Fd, to: Fd) -> Self {
+        self.fd_actions.push(FdAction::Dup2 { from, to });
+        self
+    }
+
+    /// Makes the subshell close a file descriptor before running the task.
+    ///
+    /// See [`dup2_fd`](Self::dup2_fd) for how the operation is performed.
+    pub fn close_fd(mut self, fd: Fd) -> Self {
+        self.fd_actions.push(FdAction::Close(fd));
+        self
+    }
+
     /// Starts the subshell.
     ///
     /// This function creates a new child process that runs the task contained
@@ -143,7 +192,9 @@ where
     /// `JobControl::Foreground`, this function opens `env.tty` by calling
     /// [`Env::get_tty`]. The `tty` is used to change the foreground job to the
     /// new subshell. However, `job_control` is effective only when the shell is
-    /// [controlling jobs](Env::controls_jobs).
+    /// [controlling jobs](Env::controls_jobs). If the terminal cannot be opened,
+    /// the subshell still runs in a new process group, but the foreground job
+    /// is not changed.
     ///
     /// If the subshell started successfully, the return value is a pair of the
     /// child process ID and the actual job control. Otherwise, it indicates the
@@ -154,7 +205,7 @@ where
         let tty = match job_control {
             None | Some(JobControl::Background) => None,
             // Open the tty in the parent process so we can reuse the FD for other jobs
-            Some(JobControl::Foreground) => Some(env.get_tty()?),
+            Some(JobControl::Foreground) => env.get_tty().ok(),
         };
         // Block SIGINT and SIGQUIT before forking the child process to prevent
         // the child from being killed by those signals until the child starts
@@ -186,6 +237,7 @@ where
                     }
                 }
                 env.jobs.disown_all();
+                env.reseed_random();
 
                 env.traps.enter_subshell(
                     &mut env.system,
@@ -193,6 +245,16 @@ where
                     keep_internal_dispositions_for_stoppers,
                 );
 
+                for action in self.fd_actions {
+                    if let Err(errno) = action.apply(&mut env.system) {
+                        let message =
+                            format!("cannot arrange file descriptors in the subshell: {errno}\n");
+                        env.system.print_error(&message).await;
+                        env.exit_status = ExitStatus::NOEXEC;
+                        return;
+                    }
+                }
+
                 (self.task)(env, job_control).await
             })
         });
@@ -318,12 +380,19 @@ mod tests {
     use crate::semantics::ExitStatus;
     use crate::system::r#virtual::Inode;
     use crate::system::r#virtual::SystemState;
-    use crate::system::r#virtual::{SIGCHLD, SIGINT, SIGQUIT, SIGTSTP, SIGTTIN, SIGTTOU};
+    use crate::system::r#virtual::{
+        SIGCHLD, SIGINT, SIGQUIT, SIGTERM, SIGTSTP, SIGTTIN, SIGTTOU, SIGUSR1, SIGUSR2,
+    };
     use crate::system::Disposition;
     use crate::system::Errno;
+    use crate::tests::assert_stderr;
     use crate::tests::in_virtual_system;
     use crate::trap::Action;
+    use crate::variable::Quirk;
+    use crate::variable::Scope;
+    use crate::variable::RANDOM;
     use assert_matches::assert_matches;
+    use enumset::EnumSet;
     use futures_executor::LocalPool;
     use std::cell::Cell;
     use std::cell::RefCell;
@@ -366,6 +435,66 @@ mod tests {
         assert_eq!(result, Err(Errno::ENOSYS));
     }
 
+    #[test]
+    fn debug_output_includes_fd_actions() {
+        let subshell = Subshell::new(|_env, _job_control| unreachable!())
+            .dup2_fd(Fd(4), Fd::STDOUT)
+            .close_fd(Fd(4));
+        let debug = format!("{subshell:?}");
+        assert!(
+            debug.contains("fd_actions: [Dup2 { from: Fd(4), to: Fd(1) }, Close(Fd(4))]"),
+            "{debug}"
+        );
+    }
+
+    #[test]
+    fn fd_actions_in_subshell() {
+        in_virtual_system(|mut env, _state| async move {
+            let (reader, writer) = env.system.pipe().unwrap();
+            let subshell = Subshell::new(move |env, _job_control| {
+                Box::pin(async move {
+                    // The original file descriptors have been closed.
+                    assert_eq!(env.system.fcntl_getfd(reader), Err(Errno::EBADF));
+                    assert_eq!(env.system.fcntl_getfd(writer), Err(Errno::EBADF));
+                    env.system.write_all(Fd::STDOUT, b"piped").await.unwrap();
+                })
+            })
+            .dup2_fd(writer, Fd::STDOUT)
+            .close_fd(writer)
+            .close_fd(reader);
+            let pid = subshell.start(&mut env).await.unwrap().0;
+
+            // The parent's file descriptors are still open.
+            assert_eq!(env.system.fcntl_getfd(reader), Ok(EnumSet::empty()));
+            assert_eq!(env.system.fcntl_getfd(writer), Ok(EnumSet::empty()));
+            env.system.close(writer).unwrap();
+
+            let result = env.wait_for_subshell_to_finish(pid).await.unwrap().1;
+            assert_eq!(result, ExitStatus::SUCCESS);
+            let mut buffer = [0; 10];
+            let count = env.system.read_async(reader, &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..count], b"piped");
+            let count = env.system.read_async(reader, &mut buffer).await.unwrap();
+            assert_eq!(count, 0);
+        });
+    }
+
+    #[test]
+    fn failing_fd_action_in_subshell() {
+        in_virtual_system(|mut env, state| async move {
+            let subshell =
+                Subshell::new(|_env, _job_control| unreachable!("task not expected to run"))
+                    .dup2_fd(Fd(100), Fd::STDIN);
+            let pid = subshell.start(&mut env).await.unwrap().0;
+
+            let result = env.wait_for_subshell_to_finish(pid).await.unwrap().1;
+            assert_eq!(result, ExitStatus::NOEXEC);
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("file descriptors"), "{stderr}")
+            });
+        });
+    }
+
     #[test]
     fn stack_frame_in_subshell() {
         in_virtual_system(|mut env, _state| async move {
@@ -393,6 +522,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn random_reseeded_in_subshell() {
+        in_virtual_system(|mut env, _state| async move {
+            env.variables
+                .get_or_new(RANDOM, Scope::Global)
+                .set_quirk(Some(Quirk::Random(Cell::new(1))));
+            let location = Location::dummy("");
+            let parent_value = env.variables.get(RANDOM).unwrap().clone();
+            let parent_value = parent_value.expand(&location).into_owned();
+            let child_value = Rc::new(RefCell::new(None));
+            let child_value_2 = Rc::clone(&child_value);
+            let subshell = Subshell::new(move |env, _job_control| {
+                Box::pin(async move {
+                    let random = env.variables.get(RANDOM).unwrap();
+                    let value = random.expand(&Location::dummy("")).into_owned();
+                    child_value_2.replace(Some(value));
+                })
+            });
+            let pid = subshell.start(&mut env).await.unwrap().0;
+            env.wait_for_subshell(pid).await.unwrap();
+
+            let child_value = child_value.borrow().clone().unwrap();
+            assert_ne!(child_value, parent_value);
+        });
+    }
+
     #[test]
     fn trap_reset_in_subshell() {
         in_virtual_system(|mut env, _state| async move {
@@ -422,6 +577,39 @@ mod tests {
         });
     }
 
+    #[test]
+    fn signal_dispositions_reset_in_subshell() {
+        in_virtual_system(|mut env, state| async move {
+            for (signal, action) in [
+                (SIGUSR1, Action::Command("echo foo".into())),
+                (SIGUSR2, Action::Ignore),
+            ] {
+                env.traps
+                    .set_action(&mut env.system, signal, action, Location::dummy(""), false)
+                    .unwrap();
+            }
+            {
+                let state = state.borrow();
+                let parent = &state.processes[&env.main_pid];
+                assert_eq!(parent.disposition(SIGUSR1), Disposition::Catch);
+                assert_eq!(parent.disposition(SIGUSR2), Disposition::Ignore);
+            }
+
+            let subshell = Subshell::new(|_, _| Box::pin(std::future::ready(())));
+            let pid = subshell.start(&mut env).await.unwrap().0;
+            env.wait_for_subshell(pid).await.unwrap();
+
+            let state = state.borrow();
+            let child = &state.processes[&pid];
+            // The trapped signal is reset to the default.
+            assert_eq!(child.disposition(SIGUSR1), Disposition::Default);
+            // The ignored signal remains ignored.
+            assert_eq!(child.disposition(SIGUSR2), Disposition::Ignore);
+            // The signal without a trap has the default disposition.
+            assert_eq!(child.disposition(SIGTERM), Disposition::Default);
+        });
+    }
+
     #[test]
     fn subshell_with_no_job_control() {
         in_virtual_system(|mut parent_env, state| async move {
@@ -509,6 +697,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn subshell_in_foreground_without_tty() {
+        in_virtual_system(|mut parent_env, state| async move {
+            parent_env.options.set(Monitor, On);
+
+            let (child_pid, job_control) =
+                Subshell::new(move |_, _| Box::pin(std::future::ready(())))
+                    .job_control(JobControl::Foreground)
+                    .start(&mut parent_env)
+                    .await
+                    .unwrap();
+            assert_eq!(job_control, Some(JobControl::Foreground));
+            assert_eq!(parent_env.tty, None);
+
+            parent_env.wait_for_subshell(child_pid).await.unwrap();
+            assert_eq!(state.borrow().processes[&child_pid].pgid, child_pid);
+            assert_eq!(state.borrow().foreground, None);
+        });
+    }
+
     #[test]
     fn tty_after_starting_foreground_subshell() {
         in_virtual_system(|mut parent_env, state| async move {
@@ -53,6 +53,7 @@ use self::resource::LimitPair;
 use self::resource::Resource;
 use self::select::SelectSystem;
 use self::select::SignalStatus;
+pub use self::shared::Buffering;
 pub use self::shared::SharedSystem;
 use crate::io::Fd;
 use crate::io::MIN_INTERNAL_FD;
@@ -174,6 +175,13 @@ pub trait System: Debug {
     /// function to set `errno`.
     fn isatty(&self, fd: Fd) -> bool;
 
+    /// Returns the size of the terminal associated with the file descriptor.
+    ///
+    /// This is a thin wrapper around the `ioctl` system call with the
+    /// `TIOCGWINSZ` request. It fails with `ENOTTY` if the file descriptor is
+    /// not a terminal.
+    fn terminal_size(&self, fd: Fd) -> Result<TerminalSize>;
+
     /// Reads from the file descriptor.
     ///
     /// This is a thin wrapper around the `read` system call.
@@ -221,6 +229,9 @@ pub trait System: Debug {
     /// Returns consumed CPU times.
     fn times(&self) -> Result<Times>;
 
+    /// Returns the current date and time in the local time zone.
+    fn local_time(&self) -> Result<LocalTime>;
+
     /// Tests if a signal number is valid.
     ///
     /// This function returns `Some((name, number))` if the signal number refers
@@ -436,6 +447,16 @@ pub trait System: Debug {
     /// Returns `Ok(None)` if the user is not found.
     fn getpwnam_dir(&self, name: &str) -> Result<Option<PathBuf>>;
 
+    /// Returns the name of the user with the given user ID.
+    ///
+    /// Returns `Ok(None)` if the user is not found.
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<String>>;
+
+    /// Returns the host name of the system.
+    ///
+    /// This is a thin wrapper around the `gethostname` system call.
+    fn gethostname(&self) -> Result<UnixString>;
+
     /// Returns the standard `$PATH` value where all standard utilities are
     /// expected to be found.
     ///
@@ -449,6 +470,16 @@ pub trait System: Debug {
     /// shell.
     fn shell_path(&self) -> CString;
 
+    /// Fills the buffer with random bytes.
+    ///
+    /// The bytes should be unpredictable enough to be used for generating
+    /// temporary file names, but this function is not meant to be a
+    /// cryptographically secure random number generator.
+    ///
+    /// This is a thin wrapper around the `getentropy` function where
+    /// available.
+    fn getrandom(&self, buffer: &mut [u8]) -> Result<()>;
+
     /// Returns the limits for the specified resource.
     ///
     /// This function returns a pair of the soft and hard limits for the given
@@ -489,6 +520,27 @@ pub struct Times {
     pub children_system: f64,
 }
 
+/// Date and time broken down into components
+///
+/// This structure is returned by [`System::local_time`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LocalTime {
+    /// Year (e.g., 2025)
+    pub year: i32,
+    /// Month of the year (1 to 12)
+    pub month: u8,
+    /// Day of the month (1 to 31)
+    pub day: u8,
+    /// Day of the week (0 to 6, where 0 is Sunday)
+    pub weekday: u8,
+    /// Hour (0 to 23)
+    pub hour: u8,
+    /// Minute (0 to 59)
+    pub minute: u8,
+    /// Second (0 to 60, where 60 is a leap second)
+    pub second: u8,
+}
+
 /// Operation applied to the signal blocking mask
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -516,6 +568,35 @@ pub enum Disposition {
     Catch,
 }
 
+/// Size of a terminal
+///
+/// This is the result of [`System::terminal_size`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TerminalSize {
+    /// Number of lines
+    pub rows: u16,
+    /// Number of characters in a line
+    pub columns: u16,
+}
+
+/// Kind of file a file descriptor refers to
+///
+/// This is the result of [`SystemEx::fd_kind`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum FdKind {
+    /// Terminal device
+    Terminal,
+    /// Pipe or FIFO
+    Pipe,
+    /// Regular file
+    RegularFile,
+    /// Socket
+    Socket,
+    /// Any other kind of file, including non-terminal character devices
+    Other,
+}
+
 /// Task executed in a child process
 ///
 /// This is an argument passed to a [`ChildProcessStarter`]. The task is
@@ -582,12 +663,151 @@ pub trait SystemEx: System {
         new
     }
 
+    /// Moves file descriptors to [`MIN_INTERNAL_FD`] or larger.
+    ///
+    /// This function is a batch version of
+    /// [`move_fd_internal`](Self::move_fd_internal). It moves each file
+    /// descriptor in `fds` and returns the new file descriptors in the same
+    /// order as `fds`.
+    ///
+    /// If moving any of the file descriptors fails, this function closes all
+    /// the file descriptors, including the ones that have already been moved,
+    /// and returns the first error.
+    fn move_fds_internal(&mut self, fds: &[Fd]) -> Result<Vec<Fd>> {
+        let mut new_fds = Vec::with_capacity(fds.len());
+        for (index, &fd) in fds.iter().enumerate() {
+            match self.move_fd_internal(fd) {
+                Ok(new_fd) => new_fds.push(new_fd),
+                Err(errno) => {
+                    for &fd in new_fds.iter().chain(&fds[index + 1..]) {
+                        self.close(fd).ok();
+                    }
+                    return Err(errno);
+                }
+            }
+        }
+        Ok(new_fds)
+    }
+
+    /// Creates an unnamed pipe with the CLOEXEC flag set on both ends.
+    ///
+    /// This function creates a pipe with [`System::pipe`] and sets the
+    /// [`CloseOnExec`](FdFlag::CloseOnExec) flag on the reading and writing
+    /// ends with [`System::fcntl_setfd`], so that the pipe is not leaked to
+    /// external utilities the shell executes. If the pipe is to be inherited
+    /// by an external utility, it should be [`dup2`](System::dup2)ed to the
+    /// target file descriptor, which does not carry over the flag.
+    ///
+    /// On success, this function returns the reading and writing ends of the
+    /// pipe in this order. The caller owns the file descriptors and is
+    /// responsible for closing them when they are no longer needed. Note that
+    /// the reading end does not see the end of file until all copies of the
+    /// writing end are closed.
+    ///
+    /// If setting the flag fails, this function closes both ends of the pipe
+    /// and returns the error.
+    fn pipe_cloexec(&mut self) -> Result<(Fd, Fd)> {
+        let (reader, writer) = self.pipe()?;
+        let result = self
+            .fcntl_setfd(reader, FdFlag::CloseOnExec.into())
+            .and_then(|()| self.fcntl_setfd(writer, FdFlag::CloseOnExec.into()));
+        if let Err(errno) = result {
+            self.close(reader).ok();
+            self.close(writer).ok();
+            return Err(errno);
+        }
+        Ok((reader, writer))
+    }
+
+    /// Saves a file descriptor so that it can be restored later.
+    ///
+    /// This function duplicates `fd` to a new file descriptor not less than
+    /// [`MIN_INTERNAL_FD`] with the CLOEXEC flag set, and returns the new file
+    /// descriptor. If `fd` is not open, this function returns `Ok(None)`.
+    ///
+    /// The result should be passed to [`restore_fd`](Self::restore_fd) to
+    /// restore `fd` to the saved state.
+    fn save_fd(&mut self, fd: Fd) -> Result<Option<Fd>> {
+        match self.dup(fd, MIN_INTERNAL_FD, FdFlag::CloseOnExec.into()) {
+            Ok(saved) => Ok(Some(saved)),
+            Err(Errno::EBADF) => Ok(None),
+            Err(errno) => Err(errno),
+        }
+    }
+
+    /// Restores a file descriptor saved by [`save_fd`](Self::save_fd).
+    ///
+    /// If `saved` is `Some`, this function makes `fd` a copy of the saved file
+    /// descriptor and closes the saved one. Otherwise, `fd` was not open when
+    /// saved, so this function closes `fd`. Errors are ignored.
+    fn restore_fd(&mut self, fd: Fd, saved: Option<Fd>) {
+        match saved {
+            Some(saved) => {
+                self.dup2(saved, fd).ok();
+                self.close(saved).ok();
+            }
+            None => {
+                self.close(fd).ok();
+            }
+        }
+    }
+
+    /// Runs a function with a file descriptor temporarily replaced.
+    ///
+    /// This function saves `fd` with [`save_fd`](Self::save_fd), makes `fd` a
+    /// copy of `replacement` with [`System::dup2`], and calls `f`. After `f`
+    /// returns, this function restores `fd` with
+    /// [`restore_fd`](Self::restore_fd). The restoration also happens if `f`
+    /// panics, in which case the panic is resumed after the restoration.
+    ///
+    /// `replacement` is not closed by this function. The FD flags of `fd`
+    /// (that is, the CLOEXEC flag) are not restored.
+    ///
+    /// This function returns the result of `f`. If saving or replacing `fd`
+    /// fails, this function returns the error without calling `f`.
+    fn with_saved_fd<F, R>(&mut self, fd: Fd, replacement: Fd, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let saved = self.save_fd(fd)?;
+
+        if let Err(errno) = self.dup2(replacement, fd) {
+            self.restore_fd(fd, saved);
+            return Err(errno);
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(self)));
+        self.restore_fd(fd, saved);
+        match result {
+            Ok(result) => Ok(result),
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+
     /// Tests if a file descriptor is a pipe.
     fn fd_is_pipe(&self, fd: Fd) -> bool {
         self.fstat(fd)
             .is_ok_and(|stat| stat.r#type == FileType::Fifo)
     }
 
+    /// Returns the kind of file the file descriptor refers to.
+    ///
+    /// This function first tests if the file descriptor is a terminal with
+    /// [`isatty`](System::isatty). Otherwise, the kind is determined from the
+    /// file type obtained by [`fstat`](System::fstat), which fails if the file
+    /// descriptor is not open.
+    fn fd_kind(&self, fd: Fd) -> Result<FdKind> {
+        if self.isatty(fd) {
+            return Ok(FdKind::Terminal);
+        }
+        Ok(match self.fstat(fd)?.r#type {
+            FileType::Fifo => FdKind::Pipe,
+            FileType::Regular => FdKind::RegularFile,
+            FileType::Socket => FdKind::Socket,
+            _ => FdKind::Other,
+        })
+    }
+
     /// Switches the foreground process group with SIGTTOU blocked.
     ///
     /// This is a convenience function to change the foreground process group
@@ -691,3 +911,234 @@ pub trait SystemEx: System {
 }
 
 impl<T: System + ?Sized> SystemEx for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::r#virtual::{FileBody, VirtualSystem};
+    use super::*;
+
+    fn open_file(system: &mut VirtualSystem, path: &CStr) -> Fd {
+        system
+            .open(
+                path,
+                OfdAccess::ReadWrite,
+                OpenFlag::Create.into(),
+                Mode::ALL_9,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn move_fds_internal_moves_all_fds() {
+        let mut system = VirtualSystem::new();
+        let fd1 = open_file(&mut system, c"/foo");
+        let fd2 = open_file(&mut system, c"/bar");
+        let fd3 = system.dup(fd1, Fd(20), EnumSet::empty()).unwrap();
+
+        let new_fds = system.move_fds_internal(&[fd1, fd2, fd3]).unwrap();
+        assert_eq!(new_fds.len(), 3);
+        assert!(
+            new_fds.iter().all(|&fd| fd >= MIN_INTERNAL_FD),
+            "{new_fds:?}"
+        );
+        assert_eq!(new_fds[2], fd3);
+        assert_eq!(system.write(fd1, b"x"), Err(Errno::EBADF));
+        assert_eq!(system.write(fd2, b"x"), Err(Errno::EBADF));
+
+        system.write(new_fds[1], b"bar").unwrap();
+        let state = system.state.borrow();
+        let file = state.file_system.get("/bar").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new(*b"bar"));
+    }
+
+    #[test]
+    fn move_fds_internal_closes_all_fds_on_failure() {
+        let mut system = VirtualSystem::new();
+        let fd1 = open_file(&mut system, c"/foo");
+        let fd2 = open_file(&mut system, c"/bar");
+        let closed_fd = Fd(7);
+
+        let result = system.move_fds_internal(&[fd1, closed_fd, fd2]);
+        assert_eq!(result, Err(Errno::EBADF));
+
+        let process = system.current_process();
+        let open_fds = process.fds().keys().copied().collect::<Vec<_>>();
+        assert_eq!(open_fds, [Fd::STDIN, Fd::STDOUT, Fd::STDERR]);
+    }
+
+    #[test]
+    fn save_fd_and_restore_fd() {
+        let mut system = VirtualSystem::new();
+        let original = open_file(&mut system, c"/original");
+        let replacement = open_file(&mut system, c"/replacement");
+
+        let saved = system.save_fd(original).unwrap().unwrap();
+        assert!(saved >= MIN_INTERNAL_FD, "{saved:?}");
+        system.dup2(replacement, original).unwrap();
+        system.restore_fd(original, Some(saved));
+
+        system.write(original, b"foo").unwrap();
+        let state = system.state.borrow();
+        let file = state.file_system.get("/original").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new(*b"foo"));
+        drop(state);
+        assert_eq!(system.write(saved, b""), Err(Errno::EBADF));
+    }
+
+    #[test]
+    fn save_fd_and_restore_fd_that_was_not_open() {
+        let mut system = VirtualSystem::new();
+        let replacement = open_file(&mut system, c"/replacement");
+        let fd = Fd(5);
+
+        let saved = system.save_fd(fd).unwrap();
+        assert_eq!(saved, None);
+        system.dup2(replacement, fd).unwrap();
+        system.restore_fd(fd, saved);
+        assert_eq!(system.write(fd, b"foo"), Err(Errno::EBADF));
+    }
+
+    #[test]
+    fn with_saved_fd_replaces_and_restores_fd() {
+        let mut system = VirtualSystem::new();
+        let original = open_file(&mut system, c"/original");
+        let replacement = open_file(&mut system, c"/replacement");
+
+        let result = system.with_saved_fd(original, replacement, |system| {
+            system.write(original, b"foo").unwrap();
+            42
+        });
+        assert_eq!(result, Ok(42));
+
+        system.write(original, b"bar").unwrap();
+        let state = system.state.borrow();
+        let file = state.file_system.get("/replacement").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new(*b"foo"));
+        let file = state.file_system.get("/original").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new(*b"bar"));
+        drop(state);
+
+        // The saved copy is closed
+        let fd = system
+            .dup(original, MIN_INTERNAL_FD, EnumSet::empty())
+            .unwrap();
+        assert_eq!(fd, MIN_INTERNAL_FD);
+    }
+
+    #[test]
+    fn with_saved_fd_closes_fd_that_was_not_open() {
+        let mut system = VirtualSystem::new();
+        let replacement = open_file(&mut system, c"/replacement");
+        let fd = Fd(5);
+
+        let result = system.with_saved_fd(fd, replacement, |system| {
+            system.write(fd, b"foo").unwrap();
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(system.write(fd, b"bar"), Err(Errno::EBADF));
+    }
+
+    #[test]
+    fn with_saved_fd_fails_with_invalid_replacement() {
+        let mut system = VirtualSystem::new();
+        let original = open_file(&mut system, c"/original");
+
+        let result = system.with_saved_fd(original, Fd(9), |_| unreachable!());
+        assert_eq!(result, Err(Errno::EBADF));
+        system.write(original, b"foo").unwrap();
+    }
+
+    #[test]
+    fn with_saved_fd_restores_fd_after_panic() {
+        let mut system = VirtualSystem::new();
+        let original = open_file(&mut system, c"/original");
+        let replacement = open_file(&mut system, c"/replacement");
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            system.with_saved_fd(original, replacement, |_| panic!("oops"))
+        }));
+        assert!(result.is_err());
+
+        system.write(original, b"bar").unwrap();
+        let state = system.state.borrow();
+        let file = state.file_system.get("/original").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new(*b"bar"));
+        let file = state.file_system.get("/replacement").unwrap();
+        assert_eq!(file.borrow().body, FileBody::new([]));
+    }
+
+    fn replace_stdin_body(system: &VirtualSystem, body: FileBody) {
+        let state = system.state.borrow();
+        let stdin = state.file_system.get("/dev/stdin").unwrap();
+        stdin.borrow_mut().body = body;
+    }
+
+    #[test]
+    fn pipe_cloexec_sets_flag_on_both_ends() {
+        let mut system = VirtualSystem::new();
+        let (reader, writer) = system.pipe_cloexec().unwrap();
+        assert_ne!(reader, writer);
+        assert_eq!(system.fcntl_getfd(reader), Ok(FdFlag::CloseOnExec.into()));
+        assert_eq!(system.fcntl_getfd(writer), Ok(FdFlag::CloseOnExec.into()));
+    }
+
+    #[test]
+    fn pipe_cloexec_read_write() {
+        let mut system = VirtualSystem::new();
+        let (reader, writer) = system.pipe_cloexec().unwrap();
+        assert_eq!(system.write(writer, b"foo"), Ok(3));
+        system.close(writer).unwrap();
+
+        let mut buffer = [0; 4];
+        assert_eq!(system.read(reader, &mut buffer), Ok(3));
+        assert_eq!(&buffer[..3], b"foo");
+        assert_eq!(system.read(reader, &mut buffer), Ok(0));
+    }
+
+    #[test]
+    fn fd_kind_terminal() {
+        let system = VirtualSystem::new();
+        replace_stdin_body(&system, FileBody::Terminal { content: vec![] });
+        assert_eq!(system.fd_kind(Fd::STDIN), Ok(FdKind::Terminal));
+    }
+
+    #[test]
+    fn fd_kind_pipe() {
+        let mut system = VirtualSystem::new();
+        let (reader, writer) = system.pipe().unwrap();
+        assert_eq!(system.fd_kind(reader), Ok(FdKind::Pipe));
+        assert_eq!(system.fd_kind(writer), Ok(FdKind::Pipe));
+    }
+
+    #[test]
+    fn fd_kind_regular_file() {
+        let mut system = VirtualSystem::new();
+        let fd = open_file(&mut system, c"/foo");
+        assert_eq!(system.fd_kind(fd), Ok(FdKind::RegularFile));
+    }
+
+    #[test]
+    fn fd_kind_socket() {
+        let system = VirtualSystem::new();
+        replace_stdin_body(&system, FileBody::Socket);
+        assert_eq!(system.fd_kind(Fd::STDIN), Ok(FdKind::Socket));
+    }
+
+    #[test]
+    fn fd_kind_other() {
+        let system = VirtualSystem::new();
+        replace_stdin_body(
+            &system,
+            FileBody::Directory {
+                files: Default::default(),
+            },
+        );
+        assert_eq!(system.fd_kind(Fd::STDIN), Ok(FdKind::Other));
+    }
+
+    #[test]
+    fn fd_kind_closed_fd() {
+        let system = VirtualSystem::new();
+        assert_eq!(system.fd_kind(Fd(100)), Err(Errno::EBADF));
+    }
+}
@@ -38,6 +38,7 @@ use super::Env;
 use super::Errno;
 use super::FdFlag;
 use super::Gid;
+use super::LocalTime;
 use super::Mode;
 use super::OfdAccess;
 use super::OpenFlag;
@@ -45,6 +46,7 @@ use super::Result;
 use super::SigmaskOp;
 use super::Stat;
 use super::System;
+use super::TerminalSize;
 use super::Times;
 use super::Uid;
 use crate::io::Fd;
@@ -364,6 +366,18 @@ impl System for RealSystem {
         (unsafe { nix::libc::isatty(fd.0) } != 0)
     }
 
+    fn terminal_size(&self, fd: Fd) -> Result<TerminalSize> {
+        let mut winsize = MaybeUninit::<nix::libc::winsize>::uninit();
+        unsafe { nix::libc::ioctl(fd.0, nix::libc::TIOCGWINSZ, winsize.as_mut_ptr()) }
+            .errno_if_m1()?;
+        // SAFETY: `ioctl` has initialized `winsize`.
+        let winsize = unsafe { winsize.assume_init() };
+        Ok(TerminalSize {
+            rows: winsize.ws_row,
+            columns: winsize.ws_col,
+        })
+    }
+
     fn read(&mut self, fd: Fd, buffer: &mut [u8]) -> Result<usize> {
         loop {
             let result = unsafe { nix::libc::read(fd.0, buffer.as_mut_ptr().cast(), buffer.len()) }
@@ -445,6 +459,31 @@ impl System for RealSystem {
         })
     }
 
+    fn local_time(&self) -> Result<LocalTime> {
+        let now = unsafe { nix::libc::time(std::ptr::null_mut()) };
+        if now == -1 {
+            return Err(Errno::last());
+        }
+
+        let mut tm = MaybeUninit::<nix::libc::tm>::uninit();
+        let result = unsafe { nix::libc::localtime_r(&now, tm.as_mut_ptr()) };
+        if result.is_null() {
+            return Err(Errno::last());
+        }
+        // SAFETY: `localtime_r` has initialized `tm`.
+        let tm = unsafe { tm.assume_init() };
+
+        Ok(LocalTime {
+            year: tm.tm_year.saturating_add(1900),
+            month: (tm.tm_mon + 1) as u8,
+            day: tm.tm_mday as u8,
+            weekday: tm.tm_wday as u8,
+            hour: tm.tm_hour as u8,
+            minute: tm.tm_min as u8,
+            second: tm.tm_sec as u8,
+        })
+    }
+
     fn validate_signal(&self, number: signal::RawNumber) -> Option<(signal::Name, signal::Number)> {
         let non_zero = NonZeroI32::new(number)?;
         let name = signal::Name::try_from_raw_real(number)?;
@@ -770,6 +809,20 @@ impl System for RealSystem {
         }))
     }
 
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<String>> {
+        let user = nix::unistd::User::from_uid(nix::unistd::Uid::from_raw(uid.0))?;
+        Ok(user.map(|user| user.name))
+    }
+
+    fn gethostname(&self) -> Result<UnixString> {
+        // 256 bytes is enough for the host name on all known platforms.
+        let mut buffer = [0u8; 256];
+        let result = unsafe { nix::libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) };
+        result.errno_if_m1()?;
+        let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+        Ok(UnixString::from_vec(buffer[..len].to_vec()))
+    }
+
     fn confstr_path(&self) -> Result<UnixString> {
         // TODO Support other platforms
         #[cfg(any(
@@ -830,6 +883,38 @@ impl System for RealSystem {
         c"/bin/sh".to_owned()
     }
 
+    fn getrandom(&self, buffer: &mut [u8]) -> Result<()> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                all(target_os = "linux", target_env = "gnu"),
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "illumos",
+                target_os = "ios",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "solaris",
+                target_os = "tvos",
+                target_os = "watchos"
+            ))] {
+                // getentropy fails for a buffer longer than 256 bytes.
+                buffer.chunks_mut(256).try_for_each(|chunk| {
+                    let result =
+                        unsafe { nix::libc::getentropy(chunk.as_mut_ptr().cast(), chunk.len()) };
+                    result.errno_if_m1().map(drop)
+                })
+            } else {
+                use std::io::Read as _;
+                let to_errno = |e: std::io::Error| e.raw_os_error().map_or(Errno::EIO, Errno);
+                std::fs::File::open("/dev/urandom")
+                    .and_then(|mut file| file.read_exact(buffer))
+                    .map_err(to_errno)
+            }
+        }
+    }
+
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         let raw_resource = resource.as_raw_type().ok_or(Errno::EINVAL)?;
 
@@ -902,6 +987,27 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn real_system_getrandom() {
+        let system = unsafe { RealSystem::new() };
+        let mut buffer = [0; 300];
+        system.getrandom(&mut buffer).unwrap();
+        // The chance of 300 zero bytes is negligible.
+        assert_ne!(buffer, [0; 300]);
+    }
+
+    #[test]
+    fn real_system_local_time() {
+        let system = unsafe { RealSystem::new() };
+        let time = system.local_time().unwrap();
+        assert!((1..=12).contains(&time.month), "{time:?}");
+        assert!((1..=31).contains(&time.day), "{time:?}");
+        assert!(time.weekday <= 6, "{time:?}");
+        assert!(time.hour <= 23, "{time:?}");
+        assert!(time.minute <= 59, "{time:?}");
+        assert!(time.second <= 60, "{time:?}");
+    }
+
     // This test depends on static variables.
     #[test]
     fn real_system_caught_signals() {
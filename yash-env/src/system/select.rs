@@ -16,6 +16,7 @@
 
 //! [`SelectSystem`] and related items
 
+use super::shared::Buffering;
 use super::signal;
 use super::Disposition;
 use super::Errno;
@@ -58,6 +59,10 @@ pub struct SelectSystem {
     /// This is the mask the shell inherited from the parent shell minus the
     /// signals the shell wants to catch.
     wait_mask: Option<Vec<signal::Number>>,
+    /// Buffering policy for the standard output
+    stdout_buffering: Buffering,
+    /// Bytes written to the standard output but not yet passed to the system
+    stdout_buffer: Vec<u8>,
 }
 
 impl Deref for SelectSystem {
@@ -82,9 +87,61 @@ impl SelectSystem {
             time: AsyncTime::new(),
             signal: AsyncSignal::new(),
             wait_mask: None,
+            stdout_buffering: Buffering::default(),
+            stdout_buffer: Vec::new(),
         }
     }
 
+    /// Returns the current buffering policy for the standard output.
+    #[must_use]
+    pub fn stdout_buffering(&self) -> Buffering {
+        self.stdout_buffering
+    }
+
+    /// Sets the buffering policy for the standard output.
+    ///
+    /// This function does not flush the buffer. The caller should flush it
+    /// before changing the policy.
+    pub fn set_stdout_buffering(&mut self, buffering: Buffering) {
+        self.stdout_buffering = buffering;
+    }
+
+    /// Appends bytes to the standard output buffer.
+    ///
+    /// Returns true if the buffer should be flushed according to the current
+    /// buffering policy.
+    pub fn buffer_stdout(&mut self, bytes: &[u8]) -> bool {
+        self.stdout_buffer.extend_from_slice(bytes);
+        match self.stdout_buffering {
+            Buffering::Unbuffered => true,
+            Buffering::Line => bytes.contains(&b'\n'),
+            Buffering::Full => self.stdout_buffer.len() >= Buffering::CAPACITY,
+        }
+    }
+
+    /// Removes and returns the content of the standard output buffer.
+    pub fn take_stdout_buffer(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.stdout_buffer)
+    }
+
+    /// Writes out the standard output buffer synchronously.
+    ///
+    /// This function calls [`System::write`] repeatedly until the buffer is
+    /// empty. If `write` fails with an error other than `EINTR`, the rest of
+    /// the buffer is discarded and the error is returned.
+    pub fn flush_stdout(&mut self) -> Result<()> {
+        let buffer = self.take_stdout_buffer();
+        let mut buffer = buffer.as_slice();
+        while !buffer.is_empty() {
+            match self.system.write(Fd::STDOUT, buffer) {
+                Ok(count) => buffer = &buffer[count..],
+                Err(Errno::EINTR) => (),
+                Err(errno) => return Err(errno),
+            }
+        }
+        Ok(())
+    }
+
     /// Calls `sigmask` and updates `self.wait_mask`.
     fn sigmask(&mut self, op: SigmaskOp, signal: signal::Number) -> Result<()> {
         match &mut self.wait_mask {
@@ -24,6 +24,7 @@ use super::Errno;
 use super::FdFlag;
 use super::Gid;
 use super::LimitPair;
+use super::LocalTime;
 use super::Mode;
 use super::OfdAccess;
 use super::OpenFlag;
@@ -38,6 +39,7 @@ use super::SignalSystem;
 use super::Stat;
 use super::System;
 use super::SystemEx;
+use super::TerminalSize;
 use super::Times;
 use super::Uid;
 use super::UnixString;
@@ -61,6 +63,42 @@ use std::task::Poll;
 use std::time::Duration;
 use std::time::Instant;
 
+/// Buffering policy for the standard output
+///
+/// A [`SharedSystem`] can batch data written to the standard output with
+/// [`write_all`](SharedSystem::write_all) to reduce the number of `write`
+/// system calls. This enum specifies when the buffered data is written out.
+///
+/// Regardless of the policy, the buffer is flushed before the shell reads
+/// from or writes to another file descriptor, before the standard output is
+/// closed or replaced with another file descriptor, and before the shell
+/// starts a child process or executes an external utility. It is also flushed
+/// by [`SharedSystem::flush`].
+///
+/// Note that an error in writing buffered data is not reported to the writer
+/// who originally wrote the data. Built-ins that need to detect write errors
+/// (e.g., `echo` writing to a closed standard output) cannot report them if
+/// the standard output is buffered, so the default policy is `Unbuffered`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Buffering {
+    /// Data is written immediately.
+    #[default]
+    Unbuffered,
+    /// Data is written when a newline is written.
+    ///
+    /// This is suitable when the standard output is a terminal.
+    Line,
+    /// Data is written when the buffer reaches [`CAPACITY`](Self::CAPACITY)
+    /// bytes.
+    Full,
+}
+
+impl Buffering {
+    /// Size of the buffer at which fully buffered data is written out
+    pub const CAPACITY: usize = 4096;
+}
+
 /// System shared by a reference counter.
 ///
 /// A `SharedSystem` is a reference-counted container of a [`System`] instance
@@ -129,11 +167,41 @@ impl SharedSystem {
         SharedSystem(Rc::new(RefCell::new(SelectSystem::new(system))))
     }
 
+    /// Returns the current buffering policy for the standard output.
+    #[must_use]
+    pub fn stdout_buffering(&self) -> Buffering {
+        self.0.borrow().stdout_buffering()
+    }
+
+    /// Sets the buffering policy for the standard output.
+    ///
+    /// Any data buffered under the previous policy is written out before the
+    /// policy is changed.
+    pub fn set_stdout_buffering(&self, buffering: Buffering) {
+        let mut inner = self.0.borrow_mut();
+        _ = inner.flush_stdout();
+        inner.set_stdout_buffering(buffering);
+    }
+
+    /// Writes out data buffered for the standard output.
+    ///
+    /// This function does nothing if the buffer is empty, which is always the
+    /// case when the [buffering policy](Buffering) is `Unbuffered`.
+    pub async fn flush(&self) -> Result<()> {
+        let buffer = self.0.borrow_mut().take_stdout_buffer();
+        self.write_all_unbuffered(Fd::STDOUT, &buffer).await?;
+        Ok(())
+    }
+
     /// Reads from the file descriptor.
     ///
     /// This function waits for one or more bytes to be available for reading.
     /// If successful, returns the number of bytes read.
+    ///
+    /// Data buffered for the standard output is written out before reading.
     pub async fn read_async(&self, fd: Fd, buffer: &mut [u8]) -> Result<usize> {
+        _ = self.flush().await;
+
         let was_nonblocking = (&mut &*self).get_and_set_nonblocking(fd, true)?;
 
         // We need to retain a strong reference to the waker outside the poll_fn
@@ -168,7 +236,34 @@ impl SharedSystem {
     /// returned.
     ///
     /// This function silently ignores signals that may interrupt writes.
-    pub async fn write_all(&self, fd: Fd, mut buffer: &[u8]) -> Result<usize> {
+    ///
+    /// If `fd` is the standard output, the data may be kept in a buffer
+    /// according to the current [buffering policy](Buffering), in which case
+    /// this function returns without writing it. If `fd` is another file
+    /// descriptor, data buffered for the standard output is written out first
+    /// so that the order of outputs is preserved.
+    pub async fn write_all(&self, fd: Fd, buffer: &[u8]) -> Result<usize> {
+        if fd == Fd::STDOUT {
+            let should_flush = {
+                let mut inner = self.0.borrow_mut();
+                (inner.stdout_buffering() != Buffering::Unbuffered)
+                    .then(|| inner.buffer_stdout(buffer))
+            };
+            if let Some(should_flush) = should_flush {
+                if should_flush {
+                    self.flush().await?;
+                }
+                return Ok(buffer.len());
+            }
+        } else {
+            _ = self.flush().await;
+        }
+
+        self.write_all_unbuffered(fd, buffer).await
+    }
+
+    /// Writes to the file descriptor, bypassing the standard output buffer.
+    async fn write_all_unbuffered(&self, fd: Fd, mut buffer: &[u8]) -> Result<usize> {
         if buffer.is_empty() {
             return Ok(0);
         }
@@ -319,7 +414,11 @@ impl System for &SharedSystem {
         self.0.borrow_mut().dup(from, to_min, flags)
     }
     fn dup2(&mut self, from: Fd, to: Fd) -> Result<Fd> {
-        self.0.borrow_mut().dup2(from, to)
+        let mut inner = self.0.borrow_mut();
+        if to == Fd::STDOUT {
+            _ = inner.flush_stdout();
+        }
+        inner.dup2(from, to)
     }
     fn open(
         &mut self,
@@ -334,7 +433,11 @@ impl System for &SharedSystem {
         self.0.borrow_mut().open_tmpfile(parent_dir)
     }
     fn close(&mut self, fd: Fd) -> Result<()> {
-        self.0.borrow_mut().close(fd)
+        let mut inner = self.0.borrow_mut();
+        if fd == Fd::STDOUT {
+            _ = inner.flush_stdout();
+        }
+        inner.close(fd)
     }
     fn ofd_access(&self, fd: Fd) -> Result<OfdAccess> {
         self.0.borrow().ofd_access(fd)
@@ -351,6 +454,9 @@ impl System for &SharedSystem {
     fn isatty(&self, fd: Fd) -> bool {
         self.0.borrow().isatty(fd)
     }
+    fn terminal_size(&self, fd: Fd) -> Result<TerminalSize> {
+        self.0.borrow().terminal_size(fd)
+    }
     fn read(&mut self, fd: Fd, buffer: &mut [u8]) -> Result<usize> {
         self.0.borrow_mut().read(fd, buffer)
     }
@@ -375,6 +481,9 @@ impl System for &SharedSystem {
     fn times(&self) -> Result<Times> {
         self.0.borrow().times()
     }
+    fn local_time(&self) -> Result<LocalTime> {
+        self.0.borrow().local_time()
+    }
     fn validate_signal(&self, number: signal::RawNumber) -> Option<(signal::Name, signal::Number)> {
         self.0.borrow().validate_signal(number)
     }
@@ -429,13 +538,17 @@ impl System for &SharedSystem {
         self.0.borrow_mut().tcsetpgrp(fd, pgid)
     }
     fn new_child_process(&mut self) -> Result<ChildProcessStarter> {
-        self.0.borrow_mut().new_child_process()
+        let mut inner = self.0.borrow_mut();
+        _ = inner.flush_stdout();
+        inner.new_child_process()
     }
     fn wait(&mut self, target: Pid) -> Result<Option<(Pid, ProcessState)>> {
         self.0.borrow_mut().wait(target)
     }
     fn execve(&mut self, path: &CStr, args: &[CString], envs: &[CString]) -> Result<Infallible> {
-        self.0.borrow_mut().execve(path, args, envs)
+        let mut inner = self.0.borrow_mut();
+        _ = inner.flush_stdout();
+        inner.execve(path, args, envs)
     }
     fn getcwd(&self) -> Result<PathBuf> {
         self.0.borrow().getcwd()
@@ -458,12 +571,21 @@ impl System for &SharedSystem {
     fn getpwnam_dir(&self, name: &str) -> Result<Option<PathBuf>> {
         self.0.borrow().getpwnam_dir(name)
     }
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<String>> {
+        self.0.borrow().getpwuid_name(uid)
+    }
+    fn gethostname(&self) -> Result<UnixString> {
+        self.0.borrow().gethostname()
+    }
     fn confstr_path(&self) -> Result<UnixString> {
         self.0.borrow().confstr_path()
     }
     fn shell_path(&self) -> CString {
         self.0.borrow().shell_path()
     }
+    fn getrandom(&self, buffer: &mut [u8]) -> Result<()> {
+        self.0.borrow().getrandom(buffer)
+    }
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         self.0.borrow().getrlimit(resource)
     }
@@ -543,6 +665,10 @@ impl System for SharedSystem {
         (&self).isatty(fd)
     }
     #[inline]
+    fn terminal_size(&self, fd: Fd) -> Result<TerminalSize> {
+        (&self).terminal_size(fd)
+    }
+    #[inline]
     fn read(&mut self, fd: Fd, buffer: &mut [u8]) -> Result<usize> {
         (&mut &*self).read(fd, buffer)
     }
@@ -575,6 +701,10 @@ impl System for SharedSystem {
         (&self).times()
     }
     #[inline]
+    fn local_time(&self) -> Result<LocalTime> {
+        (&self).local_time()
+    }
+    #[inline]
     fn validate_signal(&self, number: signal::RawNumber) -> Option<(signal::Name, signal::Number)> {
         (&self).validate_signal(number)
     }
@@ -681,6 +811,14 @@ impl System for SharedSystem {
         (&self).getpwnam_dir(name)
     }
     #[inline]
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<String>> {
+        (&self).getpwuid_name(uid)
+    }
+    #[inline]
+    fn gethostname(&self) -> Result<UnixString> {
+        (&self).gethostname()
+    }
+    #[inline]
     fn confstr_path(&self) -> Result<UnixString> {
         (&self).confstr_path()
     }
@@ -689,6 +827,10 @@ impl System for SharedSystem {
         (&self).shell_path()
     }
     #[inline]
+    fn getrandom(&self, buffer: &mut [u8]) -> Result<()> {
+        (&self).getrandom(buffer)
+    }
+    #[inline]
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         (&self).getrlimit(resource)
     }
@@ -741,6 +883,7 @@ impl SignalSystem for SharedSystem {
 
 #[cfg(test)]
 mod tests {
+    use super::super::r#virtual::FileBody;
     use super::super::r#virtual::VirtualSystem;
     use super::super::r#virtual::PIPE_SIZE;
     use super::super::r#virtual::{SIGCHLD, SIGINT, SIGTERM, SIGUSR1};
@@ -892,6 +1035,190 @@ mod tests {
 
     // TODO Test SharedSystem::write_all where second write returns EINTR
 
+    fn file_content(system: &VirtualSystem, path: &str) -> Vec<u8> {
+        let file = system.state.borrow().file_system.get(path).unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => content.clone())
+    }
+
+    fn shared_system_with_buffering(buffering: Buffering) -> (VirtualSystem, SharedSystem) {
+        let system = VirtualSystem::new();
+        let shared_system = SharedSystem::new(Box::new(system.clone()));
+        shared_system.set_stdout_buffering(buffering);
+        (system, shared_system)
+    }
+
+    #[test]
+    fn shared_system_write_all_unbuffered_stdout() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Unbuffered);
+
+        let result = shared_system.write_all(Fd::STDOUT, b"foo").now_or_never();
+        assert_eq!(result, Some(Ok(3)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foo");
+
+        let result = shared_system.write_all(Fd::STDOUT, b"bar").now_or_never();
+        assert_eq!(result, Some(Ok(3)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foobar");
+    }
+
+    #[test]
+    fn shared_system_write_all_line_buffered_stdout() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Line);
+
+        let result = shared_system.write_all(Fd::STDOUT, b"foo").now_or_never();
+        assert_eq!(result, Some(Ok(3)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"");
+
+        let result = shared_system
+            .write_all(Fd::STDOUT, b"bar\nbaz")
+            .now_or_never();
+        assert_eq!(result, Some(Ok(7)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foobar\nbaz");
+
+        let result = shared_system.write_all(Fd::STDOUT, b"qux").now_or_never();
+        assert_eq!(result, Some(Ok(3)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foobar\nbaz");
+    }
+
+    #[test]
+    fn shared_system_write_all_fully_buffered_stdout() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Full);
+
+        let data = [b'x'; Buffering::CAPACITY / 2];
+        let result = shared_system.write_all(Fd::STDOUT, &data).now_or_never();
+        assert_eq!(result, Some(Ok(data.len())));
+        let result = shared_system.write_all(Fd::STDOUT, b"\n").now_or_never();
+        assert_eq!(result, Some(Ok(1)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"");
+
+        let result = shared_system.write_all(Fd::STDOUT, &data).now_or_never();
+        assert_eq!(result, Some(Ok(data.len())));
+        assert_eq!(
+            file_content(&system, "/dev/stdout").len(),
+            data.len() * 2 + 1
+        );
+    }
+
+    #[test]
+    fn shared_system_buffering_reduces_write_calls() {
+        fn count_writes(buffering: Buffering) -> usize {
+            let (system, shared_system) = shared_system_with_buffering(buffering);
+            for _ in 0..10 {
+                let result = shared_system.write_all(Fd::STDOUT, b"foo\n").now_or_never();
+                assert_eq!(result, Some(Ok(4)));
+            }
+            shared_system.flush().now_or_never().unwrap().unwrap();
+            assert_eq!(file_content(&system, "/dev/stdout"), b"foo\n".repeat(10));
+            let count = system.state.borrow().write_count;
+            count
+        }
+
+        assert_eq!(count_writes(Buffering::Unbuffered), 10);
+        assert_eq!(count_writes(Buffering::Line), 10);
+        assert_eq!(count_writes(Buffering::Full), 1);
+    }
+
+    #[test]
+    fn shared_system_flush_writes_buffered_stdout() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Full);
+        shared_system
+            .write_all(Fd::STDOUT, b"foo\n")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let result = shared_system.flush().now_or_never();
+        assert_eq!(result, Some(Ok(())));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foo\n");
+
+        // Flushing an empty buffer is a no-op.
+        let result = shared_system.flush().now_or_never();
+        assert_eq!(result, Some(Ok(())));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foo\n");
+    }
+
+    #[test]
+    fn shared_system_write_to_other_fd_flushes_stdout_first() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Full);
+        shared_system
+            .write_all(Fd::STDOUT, b"out")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        shared_system
+            .write_all(Fd::STDERR, b"err")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(file_content(&system, "/dev/stdout"), b"out");
+        assert_eq!(file_content(&system, "/dev/stderr"), b"err");
+    }
+
+    #[test]
+    fn shared_system_read_async_flushes_stdout_first() {
+        let (system, mut shared_system) = shared_system_with_buffering(Buffering::Full);
+        let (reader, writer) = shared_system.pipe().unwrap();
+        shared_system.write(writer, &[1]).unwrap();
+        shared_system
+            .write_all(Fd::STDOUT, b"prompt")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let mut buffer = [0; 1];
+        let result = shared_system.read_async(reader, &mut buffer).now_or_never();
+        assert_eq!(result, Some(Ok(1)));
+        assert_eq!(file_content(&system, "/dev/stdout"), b"prompt");
+    }
+
+    #[test]
+    fn shared_system_dup2_to_stdout_flushes_stdout_first() {
+        let (system, mut shared_system) = shared_system_with_buffering(Buffering::Full);
+        shared_system
+            .write_all(Fd::STDOUT, b"before")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        shared_system.dup2(Fd::STDERR, Fd::STDOUT).unwrap();
+        shared_system
+            .write_all(Fd::STDOUT, b"after")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        shared_system.flush().now_or_never().unwrap().unwrap();
+        assert_eq!(file_content(&system, "/dev/stdout"), b"before");
+        assert_eq!(file_content(&system, "/dev/stderr"), b"after");
+    }
+
+    #[test]
+    fn shared_system_close_stdout_flushes_stdout_first() {
+        let (system, mut shared_system) = shared_system_with_buffering(Buffering::Line);
+        shared_system
+            .write_all(Fd::STDOUT, b"foo")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        shared_system.close(Fd::STDOUT).unwrap();
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foo");
+    }
+
+    #[test]
+    fn shared_system_set_stdout_buffering_flushes_stdout() {
+        let (system, shared_system) = shared_system_with_buffering(Buffering::Full);
+        shared_system
+            .write_all(Fd::STDOUT, b"foo")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        shared_system.set_stdout_buffering(Buffering::Unbuffered);
+        assert_eq!(shared_system.stdout_buffering(), Buffering::Unbuffered);
+        assert_eq!(file_content(&system, "/dev/stdout"), b"foo");
+    }
+
     #[test]
     fn shared_system_wait_until() {
         let system = VirtualSystem::new();
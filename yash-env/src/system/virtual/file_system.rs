@@ -252,6 +252,11 @@ pub enum FileBody {
         /// Virtual file content
         content: Vec<u8>,
     },
+    /// Socket
+    ///
+    /// This is a dummy socket that is not connected to any peer. Reading from
+    /// or writing to it fails with `ENOTCONN`.
+    Socket,
     // TODO Other filetypes
 }
 
@@ -283,6 +288,7 @@ impl FileBody {
             Self::Fifo { .. } => FileType::Fifo,
             Self::Symlink { .. } => FileType::Symlink,
             Self::Terminal { .. } => FileType::CharacterDevice,
+            Self::Socket => FileType::Socket,
         }
     }
 
@@ -294,7 +300,7 @@ impl FileBody {
             Self::Directory { files } => files.len(),
             Self::Fifo { content, .. } => content.len(),
             Self::Symlink { target } => target.as_unix_str().len(),
-            Self::Terminal { .. } => 0,
+            Self::Terminal { .. } | Self::Socket => 0,
         }
     }
 }
@@ -52,7 +52,11 @@ pub struct OpenFileDescription {
     pub(crate) is_writable: bool,
     /// Whether this file is opened for appending
     pub(crate) is_appending: bool,
-    // TODO is_nonblocking
+    /// Whether this file is in the non-blocking mode
+    ///
+    /// This flag is only recorded and does not affect the behavior of
+    /// [`read`](Self::read) and [`write`](Self::write).
+    pub(crate) is_nonblocking: bool,
     // TODO consider making these fields public
 }
 
@@ -85,14 +89,21 @@ impl OpenFileDescription {
         self.is_writable
     }
 
+    /// Returns true if this open file description is in the non-blocking mode.
+    #[must_use]
+    pub fn is_nonblocking(&self) -> bool {
+        self.is_nonblocking
+    }
+
     /// Returns true if you can read from this open file description without
     /// blocking.
     #[must_use]
     pub fn is_ready_for_reading(&self) -> bool {
         match &self.file.borrow().body {
-            FileBody::Regular { .. } | FileBody::Directory { .. } | FileBody::Terminal { .. } => {
-                true
-            }
+            FileBody::Regular { .. }
+            | FileBody::Directory { .. }
+            | FileBody::Terminal { .. }
+            | FileBody::Socket => true,
             FileBody::Fifo {
                 content, writers, ..
             } => !self.is_readable || !content.is_empty() || *writers == 0,
@@ -105,9 +116,10 @@ impl OpenFileDescription {
     #[must_use]
     pub fn is_ready_for_writing(&self) -> bool {
         match &self.file.borrow().body {
-            FileBody::Regular { .. } | FileBody::Directory { .. } | FileBody::Terminal { .. } => {
-                true
-            }
+            FileBody::Regular { .. }
+            | FileBody::Directory { .. }
+            | FileBody::Terminal { .. }
+            | FileBody::Socket => true,
             FileBody::Fifo {
                 content, readers, ..
             } => *readers == 0 || PIPE_SIZE - content.len() >= PIPE_BUF,
@@ -158,6 +170,7 @@ impl OpenFileDescription {
             }
             FileBody::Directory { .. } => Err(Errno::EISDIR),
             FileBody::Symlink { target: _ } => Err(Errno::ENOTSUP),
+            FileBody::Socket => Err(Errno::ENOTCONN),
         }
     }
 
@@ -208,6 +221,7 @@ impl OpenFileDescription {
             }
             FileBody::Directory { .. } => Err(Errno::EISDIR),
             FileBody::Symlink { target: _ } => Err(Errno::ENOTSUP),
+            FileBody::Socket => Err(Errno::ENOTCONN),
         }
     }
 
@@ -216,7 +230,7 @@ impl OpenFileDescription {
         let len = match &self.file.borrow().body {
             FileBody::Regular { content, .. } => content.len(),
             FileBody::Directory { files, .. } => files.len(),
-            FileBody::Fifo { .. } => return Err(Errno::ESPIPE),
+            FileBody::Fifo { .. } | FileBody::Socket => return Err(Errno::ESPIPE),
             FileBody::Symlink { .. } | FileBody::Terminal { .. } => return Err(Errno::ENOTSUP),
         };
 
@@ -277,6 +291,7 @@ mod tests {
             is_readable: false,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [0];
@@ -292,6 +307,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [0];
@@ -313,6 +329,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [0; 3];
@@ -330,6 +347,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [0; 3];
@@ -347,6 +365,7 @@ mod tests {
             is_readable: false,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[0]);
@@ -361,6 +380,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[9, 8, 7]);
@@ -382,6 +402,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[9, 8, 7, 6]);
@@ -403,6 +424,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[2, 3]);
@@ -424,6 +446,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: true,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[4, 5]);
@@ -445,6 +468,7 @@ mod tests {
             is_readable: true,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.seek(SeekFrom::Start(10));
@@ -468,6 +492,7 @@ mod tests {
             is_readable: true,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.seek(SeekFrom::Current(10));
@@ -495,6 +520,7 @@ mod tests {
             is_readable: true,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.seek(SeekFrom::End(7));
@@ -530,6 +556,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
         drop(open_file);
 
@@ -555,6 +582,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
         drop(open_file);
 
@@ -580,6 +608,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         assert!(open_file.is_ready_for_writing());
@@ -614,6 +643,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [100; 5];
@@ -636,6 +666,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [100; 4];
@@ -666,6 +697,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let mut buffer = [100; 5];
@@ -689,6 +721,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[1, 1, 2, 3]);
@@ -717,6 +750,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         open_file.write(&[0; PIPE_SIZE]).unwrap();
@@ -748,6 +782,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         const LEN: usize = PIPE_SIZE - PIPE_BUF + 1;
@@ -778,6 +813,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         const LEN: usize = PIPE_SIZE - PIPE_BUF;
@@ -805,6 +841,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let result = open_file.write(&[1; 1]);
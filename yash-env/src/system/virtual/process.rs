@@ -642,6 +642,7 @@ mod tests {
             is_readable: true,
             is_writable: false,
             is_appending: false,
+            is_nonblocking: false,
         };
         let writer = OpenFileDescription {
             file: Rc::clone(&file),
@@ -649,6 +650,7 @@ mod tests {
             is_readable: false,
             is_writable: true,
             is_appending: false,
+            is_nonblocking: false,
         };
 
         let reader = FdBody {
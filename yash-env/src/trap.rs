@@ -251,7 +251,9 @@ impl TrapSet {
 
         for (&cond, state) in &mut self.traps {
             let option = match cond {
-                Condition::Exit => EnterSubshellOption::ClearInternalDisposition,
+                Condition::Exit | Condition::Err | Condition::Debug => {
+                    EnterSubshellOption::ClearInternalDisposition
+                }
                 Condition::Signal(number) => {
                     use signal::Name::*;
                     match system.signal_name_from_number(number) {
@@ -346,6 +348,21 @@ impl TrapSet {
         self.set_internal_disposition(signal::Name::Chld, Disposition::Catch, system)
     }
 
+    /// Installs the internal disposition for `SIGWINCH`.
+    ///
+    /// An interactive shell should install the internal disposition for
+    /// `SIGWINCH` by using this function so that it can notice changes of the
+    /// terminal size. The disposition allows catching `SIGWINCH`.
+    ///
+    /// This function remembers that the disposition has been installed, so a
+    /// second call to the function will be a no-op.
+    pub fn enable_internal_disposition_for_sigwinch<S: SignalSystem>(
+        &mut self,
+        system: &mut S,
+    ) -> Result<(), Errno> {
+        self.set_internal_disposition(signal::Name::Winch, Disposition::Catch, system)
+    }
+
     /// Installs the internal dispositions for `SIGINT`, `SIGTERM`, and `SIGQUIT`.
     ///
     /// An interactive shell should install the internal dispositions for these
@@ -410,6 +427,7 @@ impl TrapSet {
         system: &mut S,
     ) -> Result<(), Errno> {
         self.set_internal_disposition(signal::Name::Chld, Disposition::Default, system)?;
+        self.set_internal_disposition(signal::Name::Winch, Disposition::Default, system)?;
         self.disable_internal_dispositions_for_terminators(system)?;
         self.disable_internal_dispositions_for_stoppers(system)
     }
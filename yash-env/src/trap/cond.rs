@@ -28,6 +28,11 @@ use std::borrow::Cow;
 pub enum Condition {
     /// When the shell exits
     Exit,
+    /// When a command fails under the conditions where the `ErrExit` shell
+    /// option would cause the shell to exit
+    Err,
+    /// Before each simple command is executed
+    Debug,
     /// When the specified signal is delivered to the shell process
     Signal(signal::Number),
 }
@@ -52,11 +57,18 @@ impl From<signal::RawNumber> for Condition {
     }
 }
 
-impl From<Condition> for signal::RawNumber {
-    fn from(cond: Condition) -> Self {
+/// Conversion from `Condition` to raw signal number
+///
+/// [`Condition::Exit`] is converted to zero, and [`Condition::Signal`] to the
+/// signal number. Other conditions have no corresponding number, so the
+/// conversion fails returning the original condition.
+impl TryFrom<Condition> for signal::RawNumber {
+    type Error = Condition;
+    fn try_from(cond: Condition) -> Result<Self, Condition> {
         match cond {
-            Condition::Exit => 0,
-            Condition::Signal(number) => number.as_raw(),
+            Condition::Exit => Ok(0),
+            Condition::Signal(number) => Ok(number.as_raw()),
+            Condition::Err | Condition::Debug => Err(cond),
         }
     }
 }
@@ -65,13 +77,15 @@ impl Condition {
     /// Converts this `Condition` to a `String`.
     ///
     /// The result is an uppercase string representing the condition such as
-    /// `"EXIT"` and `"TERM"`. Signal names are obtained from
+    /// `"EXIT"`, `"ERR"`, and `"TERM"`. Signal names are obtained from
     /// [`signal::Name::as_string`]. This function depends on the signal system
     /// to convert signal numbers to names.
     #[must_use]
     pub fn to_string<S: SignalSystem>(&self, system: &S) -> Cow<'static, str> {
         match self {
             Self::Exit => Cow::Borrowed("EXIT"),
+            Self::Err => Cow::Borrowed("ERR"),
+            Self::Debug => Cow::Borrowed("DEBUG"),
             Self::Signal(number) => system.signal_name_from_number(*number).as_string(),
         }
     }
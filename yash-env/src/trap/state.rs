@@ -167,6 +167,19 @@ pub struct GrandState {
     /// is computed as the maximum of the user-defined disposition and the
     /// internal disposition.
     internal_disposition: Disposition,
+
+    /// Signal disposition the shell inherited on startup
+    ///
+    /// This is either `Default` or `Ignore`. When the user resets the trap to
+    /// the default action, the signal disposition is restored to this value
+    /// rather than `SIG_DFL`.
+    initial_disposition: Disposition,
+}
+
+/// Converts a signal disposition inherited on startup to the one that should
+/// be restored when the trap is reset to the default action.
+fn normalize_initial_disposition(disposition: Disposition) -> Disposition {
+    (&Setting::from_initial_disposition(disposition)).into()
 }
 
 impl GrandState {
@@ -190,6 +203,26 @@ impl GrandState {
         self.parent_setting = None;
     }
 
+    /// Returns the signal disposition for the given setting.
+    ///
+    /// The result is the same as the conversion from `&Setting` to
+    /// `Disposition` except that `Action::Default` yields the initial
+    /// disposition.
+    fn disposition_for(&self, setting: &Setting) -> Disposition {
+        match setting {
+            Setting::UserSpecified(TrapState {
+                action: Action::Default,
+                ..
+            }) => self.initial_disposition,
+            _ => setting.into(),
+        }
+    }
+
+    /// Returns the signal disposition for the current setting.
+    fn current_disposition(&self) -> Disposition {
+        self.disposition_for(&self.current_setting)
+    }
+
     /// Updates the entry with the new action.
     pub fn set_action<S: SignalSystem>(
         system: &mut S,
@@ -204,34 +237,37 @@ impl GrandState {
             origin,
             pending: false,
         });
-        let disposition = (&setting).into();
 
         match entry {
             Entry::Vacant(vacant) => {
+                let mut state = GrandState {
+                    current_setting: setting,
+                    parent_setting: None,
+                    internal_disposition: Disposition::Default,
+                    initial_disposition: Disposition::Default,
+                };
+
                 if let Condition::Signal(signal) = cond {
-                    if !override_ignore {
-                        let initial_disposition =
-                            system.set_disposition(signal, Disposition::Ignore)?;
-                        if initial_disposition == Disposition::Ignore {
-                            vacant.insert(GrandState {
-                                current_setting: Setting::InitiallyIgnored,
-                                parent_setting: None,
-                                internal_disposition: Disposition::Default,
-                            });
-                            return Err(SetActionError::InitiallyIgnored);
-                        }
+                    let initial_disposition =
+                        system.set_disposition(signal, Disposition::Ignore)?;
+                    if !override_ignore && initial_disposition == Disposition::Ignore {
+                        vacant.insert(GrandState {
+                            current_setting: Setting::InitiallyIgnored,
+                            parent_setting: None,
+                            internal_disposition: Disposition::Default,
+                            initial_disposition: Disposition::Ignore,
+                        });
+                        return Err(SetActionError::InitiallyIgnored);
                     }
+                    state.initial_disposition = normalize_initial_disposition(initial_disposition);
 
-                    if override_ignore || disposition != Disposition::Ignore {
+                    let disposition = state.current_disposition();
+                    if disposition != Disposition::Ignore {
                         system.set_disposition(signal, disposition)?;
                     }
                 }
 
-                vacant.insert(GrandState {
-                    current_setting: setting,
-                    parent_setting: None,
-                    internal_disposition: Disposition::Default,
-                });
+                vacant.insert(state);
             }
 
             Entry::Occupied(mut occupied) => {
@@ -242,8 +278,8 @@ impl GrandState {
 
                 if let Condition::Signal(signal) = cond {
                     let internal = state.internal_disposition;
-                    let old_disposition = internal.max((&state.current_setting).into());
-                    let new_disposition = internal.max(disposition);
+                    let old_disposition = internal.max(state.current_disposition());
+                    let new_disposition = internal.max(state.disposition_for(&setting));
                     if old_disposition != new_disposition {
                         system.set_disposition(signal, new_disposition)?;
                     }
@@ -273,7 +309,9 @@ impl GrandState {
     ) -> Result<(), Errno> {
         let signal = match *entry.key() {
             Condition::Signal(signal) => signal,
-            Condition::Exit => panic!("exit condition cannot have an internal disposition"),
+            Condition::Exit | Condition::Err | Condition::Debug => {
+                panic!("non-signal condition cannot have an internal disposition")
+            }
         };
 
         match entry {
@@ -285,12 +323,13 @@ impl GrandState {
                     current_setting: Setting::from_initial_disposition(initial_disposition),
                     parent_setting: None,
                     internal_disposition: disposition,
+                    initial_disposition: normalize_initial_disposition(initial_disposition),
                 });
             }
 
             Entry::Occupied(mut occupied) => {
                 let state = occupied.get_mut();
-                let setting = (&state.current_setting).into();
+                let setting = state.current_disposition();
                 let old_disposition = state.internal_disposition.max(setting);
                 let new_disposition = disposition.max(setting);
                 if old_disposition != new_disposition {
@@ -316,7 +355,7 @@ impl GrandState {
         cond: Condition,
         option: EnterSubshellOption,
     ) -> Result<(), Errno> {
-        let old_setting = (&self.current_setting).into();
+        let old_setting = self.current_disposition();
         let old_disposition = self.internal_disposition.max(old_setting);
 
         if self.current_setting.is_user_defined_command() {
@@ -326,7 +365,7 @@ impl GrandState {
             ));
         }
 
-        let new_setting = (&self.current_setting).into();
+        let new_setting = self.current_disposition();
         let new_disposition = match option {
             EnterSubshellOption::KeepInternalDisposition => {
                 self.internal_disposition.max(new_setting)
@@ -363,13 +402,16 @@ impl GrandState {
     ) -> Result<(), Errno> {
         let signal = match *vacant.key() {
             Condition::Signal(signal) => signal,
-            Condition::Exit => panic!("exit condition cannot be ignored"),
+            Condition::Exit | Condition::Err | Condition::Debug => {
+                panic!("non-signal condition cannot be ignored")
+            }
         };
         let initial_disposition = system.set_disposition(signal, Disposition::Ignore)?;
         vacant.insert(GrandState {
             current_setting: Setting::from_initial_disposition(initial_disposition),
             parent_setting: None,
             internal_disposition: Disposition::Default,
+            initial_disposition: normalize_initial_disposition(initial_disposition),
         });
         Ok(())
     }
@@ -547,6 +589,48 @@ mod tests {
         assert_eq!(system.0[&SIGCHLD], Disposition::Ignore);
     }
 
+    #[test]
+    fn resetting_trap_to_default_restores_initially_ignored_disposition() {
+        let mut system = DummySystem::default();
+        system.0.insert(SIGCHLD, Disposition::Ignore);
+        let mut map = BTreeMap::new();
+        let origin = Location::dummy("origin");
+        let action = Action::Command("echo".into());
+        let entry = map.entry(SIGCHLD.into());
+        GrandState::set_action(&mut system, entry, action, origin.clone(), true).unwrap();
+        assert_eq!(system.0[&SIGCHLD], Disposition::Catch);
+
+        let entry = map.entry(SIGCHLD.into());
+        let result =
+            GrandState::set_action(&mut system, entry, Action::Default, origin.clone(), true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            map[&SIGCHLD.into()].get_state(),
+            (
+                Some(&TrapState {
+                    action: Action::Default,
+                    origin,
+                    pending: false
+                }),
+                None
+            )
+        );
+        assert_eq!(system.0[&SIGCHLD], Disposition::Ignore);
+    }
+
+    #[test]
+    fn setting_trap_to_default_for_initially_ignored_signal_with_override() {
+        let mut system = DummySystem::default();
+        system.0.insert(SIGCHLD, Disposition::Ignore);
+        let mut map = BTreeMap::new();
+        let entry = map.entry(SIGCHLD.into());
+        let origin = Location::dummy("origin");
+
+        let result = GrandState::set_action(&mut system, entry, Action::Default, origin, true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(system.0[&SIGCHLD], Disposition::Ignore);
+    }
+
     #[test]
     fn internal_disposition_ignore() {
         let mut system = DummySystem::default();
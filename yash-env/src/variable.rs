@@ -105,6 +105,7 @@ use std::ffi::CString;
 use std::fmt::Write;
 use std::hash::Hash;
 use std::iter::FusedIterator;
+use std::rc::Rc;
 use thiserror::Error;
 use yash_syntax::source::Location;
 
@@ -115,8 +116,10 @@ pub use self::value::Value::{self, Array, Scalar};
 
 mod quirk;
 
+pub use self::quirk::Clock;
 pub use self::quirk::Expansion;
 pub use self::quirk::Quirk;
+pub use self::quirk::SecondsState;
 
 mod main;
 
@@ -124,6 +127,12 @@ pub use self::main::AssignError;
 pub use self::main::Variable;
 pub use self::main::VariableRefMut;
 
+mod hook;
+
+pub use self::hook::ChangeFlag;
+use self::hook::Hook;
+pub use self::hook::VariableHook;
+
 mod constants;
 
 // Export variable name and initial value constants
@@ -216,6 +225,9 @@ pub struct VariableSet {
     /// The stack can never be empty since the base context is always the first
     /// item.
     contexts: Vec<Context>,
+
+    /// Hooks notified of changes to variables, keyed by variable name
+    hooks: HashMap<String, Vec<Hook>>,
 }
 
 impl Default for VariableSet {
@@ -223,6 +235,7 @@ impl Default for VariableSet {
         VariableSet {
             all_variables: Default::default(),
             contexts: vec![Context::default()],
+            hooks: Default::default(),
         }
     }
 }
@@ -383,6 +396,7 @@ impl VariableSet {
     }
 
     fn get_or_new_impl(&mut self, name: String, scope: Scope) -> VariableRefMut {
+        let hooks = self.hooks.get_key_value(&name);
         let stack = match self.all_variables.entry(name) {
             Vacant(vacant) => vacant.insert(Vec::new()),
             Occupied(occupied) => occupied.into_mut(),
@@ -445,7 +459,11 @@ impl VariableSet {
             }
         }
 
-        VariableRefMut::from(&mut stack.last_mut().unwrap().variable)
+        let variable = &mut stack.last_mut().unwrap().variable;
+        match hooks {
+            None => VariableRefMut::from(variable),
+            Some((name, hooks)) => VariableRefMut::with_hooks(variable, name, hooks),
+        }
     }
 
     /// Panics if the set contains any variable with an invalid context index.
@@ -552,7 +570,27 @@ impl VariableSet {
             });
         }
 
-        Ok(stack.drain(index..).next_back().map(|vic| vic.variable))
+        let removed = stack.drain(index..).next_back().map(|vic| vic.variable);
+        if removed.is_some() {
+            if let Some(hooks) = self.hooks.get(name) {
+                hook::notify(name, hooks);
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Registers a hook notified of changes to variables of the given name.
+    ///
+    /// The hook is notified when a variable of the name is assigned through a
+    /// [`VariableRefMut`] obtained from this set, [unset](Self::unset), or
+    /// removed by [popping a context](Self::pop_context). Assigning a value
+    /// to a [`Variable`] that is not in this set does not notify the hook.
+    ///
+    /// More than one hook can be registered for the same name. The hooks are
+    /// notified in the order of registration. Cloning the variable set clones
+    /// the reference to the hook, so the clone notifies the same hook.
+    pub fn add_hook<S: Into<String>>(&mut self, name: S, hook: Rc<dyn VariableHook>) {
+        self.hooks.entry(name.into()).or_default().push(Hook(hook));
     }
 
     /// Returns an iterator of variables.
@@ -595,6 +633,14 @@ impl VariableSet {
             .collect()
     }
 
+    /// Removes all variables from this set.
+    ///
+    /// The contexts, positional parameters, and [hooks](Self::add_hook) are
+    /// retained. The hooks are not notified of the removal.
+    pub fn clear(&mut self) {
+        self.all_variables.clear();
+    }
+
     /// Imports environment variables from an iterator.
     ///
     /// The argument iterator must yield name-value pairs. This function assigns
@@ -691,6 +737,16 @@ impl VariableSet {
             .expect("base context has gone")
     }
 
+    /// Tests whether there is a regular context other than the base context.
+    ///
+    /// Since every function invocation pushes a regular context, this function
+    /// tells whether a function is being executed, in which case the
+    /// [local scope](Scope::Local) is distinct from the global scope.
+    #[must_use]
+    pub fn has_local_context(&self) -> bool {
+        Self::index_of_topmost_regular_context(&self.contexts) > 0
+    }
+
     fn push_context_impl(&mut self, context: Context) {
         self.contexts.push(context);
     }
@@ -699,14 +755,21 @@ impl VariableSet {
         debug_assert!(!self.contexts.is_empty());
         assert_ne!(self.contexts.len(), 1, "cannot pop the base context");
         self.contexts.pop();
-        self.all_variables.retain(|_, stack| {
+        let mut changed = Vec::new();
+        self.all_variables.retain(|name, stack| {
             if let Some(vic) = stack.last() {
                 if vic.context_index >= self.contexts.len() {
                     stack.pop();
+                    if self.hooks.contains_key(name) {
+                        changed.push(name.clone());
+                    }
                 }
             }
             !stack.is_empty()
-        })
+        });
+        for name in changed {
+            hook::notify(&name, &self.hooks[&name]);
+        }
     }
 }
 
@@ -1364,6 +1427,30 @@ mod tests {
         assert!(bar.is_exported);
     }
 
+    #[test]
+    fn clear() {
+        let mut variables = VariableSet::new();
+        variables
+            .get_or_new("foo", Scope::Global)
+            .assign("FOO", None)
+            .unwrap();
+        variables.push_context_impl(Context::default());
+        variables
+            .get_or_new("bar", Scope::Local)
+            .assign("BAR", None)
+            .unwrap();
+        variables.positional_params_mut().values = vec!["1".to_string()];
+
+        variables.clear();
+        assert_eq!(variables.get("foo"), None);
+        assert_eq!(variables.get("bar"), None);
+        assert_eq!(variables.positional_params().values, ["1"]);
+
+        variables.pop_context_impl();
+        assert_eq!(variables.get("foo"), None);
+        assert_eq!(variables.positional_params().values, [] as [String; 0]);
+    }
+
     #[test]
     fn init_lineno() {
         let mut variables = VariableSet::new();
@@ -1403,6 +1490,90 @@ mod tests {
         assert_eq!(variables.positional_params().values, ["1".to_string()]);
     }
 
+    #[derive(Debug, Default)]
+    struct Recorder(std::cell::RefCell<Vec<String>>);
+
+    impl VariableHook for Recorder {
+        fn variable_changed(&self, name: &str) {
+            self.0.borrow_mut().push(name.to_owned());
+        }
+    }
+
+    #[test]
+    fn hook_notified_on_assignment() {
+        let recorder = Rc::new(Recorder::default());
+        let mut variables = VariableSet::new();
+        variables.add_hook("foo", recorder.clone());
+
+        variables
+            .get_or_new("foo", Scope::Global)
+            .assign("1", None)
+            .unwrap();
+        assert_eq!(*recorder.0.borrow(), ["foo"]);
+
+        variables
+            .get_or_new("bar", Scope::Global)
+            .assign("2", None)
+            .unwrap();
+        assert_eq!(*recorder.0.borrow(), ["foo"]);
+    }
+
+    #[test]
+    fn hook_not_notified_on_failed_assignment() {
+        let recorder = Rc::new(Recorder::default());
+        let mut variables = VariableSet::new();
+        variables.add_hook("foo", recorder.clone());
+        let mut var = variables.get_or_new("foo", Scope::Global);
+        var.make_read_only(Location::dummy("read-only"));
+
+        var.assign("1", None).unwrap_err();
+        assert_eq!(*recorder.0.borrow(), [] as [&str; 0]);
+    }
+
+    #[test]
+    fn hook_notified_on_unset() {
+        let recorder = Rc::new(Recorder::default());
+        let mut variables = VariableSet::new();
+        variables.get_or_new("foo", Scope::Global);
+        variables.add_hook("foo", recorder.clone());
+
+        variables.unset("foo", Scope::Global).unwrap();
+        assert_eq!(*recorder.0.borrow(), ["foo"]);
+
+        // Unsetting a non-existent variable is not a change.
+        variables.unset("foo", Scope::Global).unwrap();
+        assert_eq!(*recorder.0.borrow(), ["foo"]);
+    }
+
+    #[test]
+    fn hook_notified_on_popping_context() {
+        let recorder = Rc::new(Recorder::default());
+        let mut variables = VariableSet::new();
+        variables.add_hook("foo", recorder.clone());
+        variables.push_context_impl(Context::Volatile);
+        variables.get_or_new("foo", Scope::Volatile);
+        variables.get_or_new("bar", Scope::Volatile);
+        assert_eq!(*recorder.0.borrow(), [] as [&str; 0]);
+
+        variables.pop_context_impl();
+        assert_eq!(*recorder.0.borrow(), ["foo"]);
+    }
+
+    #[test]
+    fn local_context_existence() {
+        let mut variables = VariableSet::new();
+        assert!(!variables.has_local_context());
+
+        variables.push_context_impl(Context::Volatile);
+        assert!(!variables.has_local_context());
+
+        variables.push_context_impl(Context::default());
+        assert!(variables.has_local_context());
+
+        variables.push_context_impl(Context::Volatile);
+        assert!(variables.has_local_context());
+    }
+
     #[test]
     fn getting_positional_params_in_volatile_context() {
         let mut variables = VariableSet::new();
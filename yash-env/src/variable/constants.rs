@@ -22,6 +22,12 @@
 /// directories. Its value is a colon-separated list of directories.
 pub const CDPATH: &str = "CDPATH";
 
+/// The name of the `COLUMNS` variable
+///
+/// The `COLUMNS` variable stores the width of the terminal. The shell updates
+/// the variable when it notices a change of the terminal size.
+pub const COLUMNS: &str = "COLUMNS";
+
 /// The name of the `ENV` variable
 ///
 /// The `ENV` variable specifies the file to read for environment
@@ -33,6 +39,12 @@ pub const ENV: &str = "ENV";
 /// The `HOME` variable stores the path to the user's home directory.
 pub const HOME: &str = "HOME";
 
+/// The name of the `HISTSIZE` variable
+///
+/// The `HISTSIZE` variable specifies the maximum number of entries kept in the
+/// [command history](crate::history::History).
+pub const HISTSIZE: &str = "HISTSIZE";
+
 /// The name of the `IFS` variable
 ///
 /// The `IFS` variable separator characters for field splitting.
@@ -42,11 +54,34 @@ pub const IFS: &str = "IFS";
 /// The initial value of the `IFS` variable (`" \t\n"`)
 pub const IFS_INITIAL_VALUE: &str = " \t\n";
 
+/// The name of the `LANG` variable
+///
+/// The `LANG` variable specifies the default locale for the categories not
+/// specified by `LC_ALL` or the other `LC_*` variables.
+pub const LANG: &str = "LANG";
+
+/// The name of the `LC_ALL` variable
+///
+/// The `LC_ALL` variable specifies the locale for all categories, overriding
+/// `LANG` and the other `LC_*` variables.
+pub const LC_ALL: &str = "LC_ALL";
+
+/// The name of the `LC_CTYPE` variable
+///
+/// The `LC_CTYPE` variable specifies the locale for character classification.
+pub const LC_CTYPE: &str = "LC_CTYPE";
+
 /// The name of the `LINENO` variable
 ///
 /// The `LINENO` variable expands to the line number of the current command.
 pub const LINENO: &str = "LINENO";
 
+/// The name of the `LINES` variable
+///
+/// The `LINES` variable stores the height of the terminal. The shell updates
+/// the variable when it notices a change of the terminal size.
+pub const LINES: &str = "LINES";
+
 /// The name of the `OLDPWD` variable
 ///
 /// The `cd` built-in sets the `OLDPWD` variable to the previous working directory.
@@ -77,6 +112,12 @@ pub const PATH: &str = "PATH";
 /// The `PPID` variable stores the process ID of the parent process.
 pub const PPID: &str = "PPID";
 
+/// The name of the `PROMPT_COMMAND` variable
+///
+/// The value of the `PROMPT_COMMAND` variable is executed as a command before
+/// the interactive shell shows the primary prompt.
+pub const PROMPT_COMMAND: &str = "PROMPT_COMMAND";
+
 /// The name of the `PS1` variable
 ///
 /// The `PS1` variable is the primary prompt string.
@@ -114,3 +155,28 @@ pub const PS4_INITIAL_VALUE: &str = "+ ";
 ///
 /// The `PWD` variable stores the current working directory.
 pub const PWD: &str = "PWD";
+
+/// The name of the `RANDOM` variable
+///
+/// The `RANDOM` variable expands to a pseudo-random integer between 0 and
+/// 32767. See [`Quirk::Random`](super::Quirk::Random).
+pub const RANDOM: &str = "RANDOM";
+
+/// The name of the `SECONDS` variable
+///
+/// The `SECONDS` variable expands to the number of seconds elapsed since the
+/// shell started. See [`Quirk::Seconds`](super::Quirk::Seconds).
+pub const SECONDS: &str = "SECONDS";
+
+/// The name of the `SHLVL` variable
+///
+/// The `SHLVL` variable stores the nesting level of the shell. The shell
+/// increments the inherited value on startup.
+pub const SHLVL: &str = "SHLVL";
+
+/// The name of the `_` variable
+///
+/// After a simple command is expanded, the `_` variable is set to the last
+/// field of the command. At startup, it is set to the name of the shell or
+/// shell script.
+pub const UNDERSCORE: &str = "_";
@@ -0,0 +1,93 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Hooks notified of changes to variables
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Observer notified when a variable is changed
+///
+/// A hook can be [registered](super::VariableSet::add_hook) in a
+/// [`VariableSet`](super::VariableSet) for a specific variable name. The hook
+/// is notified when a variable of the name is assigned a value, unset, or
+/// removed by popping a context. This allows the shell to perform side
+/// effects of special variables, such as invalidating caches that depend on
+/// `$PATH`.
+///
+/// The hook is notified after the change has been made, but it cannot access
+/// the variable set in the callback. Implementors usually record the
+/// notification in interior mutable state for later processing.
+pub trait VariableHook: Debug {
+    /// Called after the variable named `name` has changed.
+    fn variable_changed(&self, name: &str);
+}
+
+/// Simple hook that remembers whether it has been notified
+///
+/// The flag is set when the hook is notified, and cleared by
+/// [`take`](Self::take).
+#[derive(Debug, Default)]
+pub struct ChangeFlag(Cell<bool>);
+
+impl ChangeFlag {
+    /// Returns whether the hook has been notified since the last call to this
+    /// function, clearing the flag.
+    pub fn take(&self) -> bool {
+        self.0.take()
+    }
+}
+
+impl VariableHook for ChangeFlag {
+    fn variable_changed(&self, _name: &str) {
+        self.0.set(true)
+    }
+}
+
+/// Wrapper of a hook that is compared by identity
+#[derive(Clone, Debug)]
+pub(super) struct Hook(pub(super) Rc<dyn VariableHook>);
+
+impl PartialEq for Hook {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Hook {}
+
+/// Notifies all the hooks of a change to the variable.
+pub(super) fn notify(name: &str, hooks: &[Hook]) {
+    for hook in hooks {
+        hook.0.variable_changed(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_flag() {
+        let flag = ChangeFlag::default();
+        assert!(!flag.take());
+
+        flag.variable_changed("FOO");
+        assert!(flag.take());
+        assert!(!flag.take());
+    }
+}
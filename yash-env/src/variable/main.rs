@@ -16,6 +16,8 @@
 
 //! Module that defines the main `Variable` type.
 
+use super::hook::notify;
+use super::hook::Hook;
 use super::Expansion;
 use super::Quirk;
 use super::Value;
@@ -163,7 +165,7 @@ impl Variable {
 /// To obtain an instance of `VariableRefMut`, use
 /// [`VariableSet::get_or_new`](super::VariableSet::get_or_new).
 #[derive(Debug, Eq, PartialEq)]
-pub struct VariableRefMut<'a>(&'a mut Variable);
+pub struct VariableRefMut<'a>(&'a mut Variable, Option<(&'a str, &'a [Hook])>);
 
 /// Error that occurs when assigning a value to a read-only variable.
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -179,7 +181,15 @@ pub struct AssignError {
 
 impl<'a> From<&'a mut Variable> for VariableRefMut<'a> {
     fn from(variable: &'a mut Variable) -> Self {
-        VariableRefMut(variable)
+        VariableRefMut(variable, None)
+    }
+}
+
+impl<'a> VariableRefMut<'a> {
+    /// Creates a reference that notifies the hooks when the variable is
+    /// assigned.
+    pub(super) fn with_hooks(variable: &'a mut Variable, name: &'a str, hooks: &'a [Hook]) -> Self {
+        VariableRefMut(variable, Some((name, hooks)))
     }
 }
 
@@ -225,8 +235,11 @@ impl VariableRefMut<'_> {
 
         let old_value = std::mem::replace(&mut self.0.value, Some(value));
         let old_location = std::mem::replace(&mut self.0.last_assigned_location, location);
+        super::quirk::assign(self.0);
+        if let Some((name, hooks)) = self.1 {
+            notify(name, hooks);
+        }
         Ok((old_value, old_location))
-        // TODO Apply quirk
     }
 
     /// Sets whether this variable is exported or not.
@@ -18,8 +18,14 @@
 
 use super::Value;
 use super::Variable;
+use crate::system::SharedSystem;
+use crate::system::System;
 use either::{Left, Right};
 use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::Instant;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
 
@@ -42,10 +48,104 @@ pub enum Quirk {
     /// the location of the parameter expansion. This `Quirk` is lost when an
     /// assignment sets a new value to the variable.
     LineNumber,
-    // TODO Random(RefCell<RandomState>)
+
+    /// Quirk for the `$RANDOM` variable
+    ///
+    /// The value of a variable having this variant of `Quirk` is computed
+    /// dynamically from the state of a pseudo-random number generator contained
+    /// in the cell. Every expansion advances the state and yields a new integer
+    /// between 0 and 32767 (inclusive).
+    ///
+    /// Assigning a non-negative integer to the variable resets the state to the
+    /// integer, so that the following expansions yield a reproducible sequence
+    /// of numbers. If any other value is assigned, the variable loses this
+    /// `Quirk`.
+    Random(Cell<u64>),
+
+    /// Quirk for the `$SECONDS` variable
+    ///
+    /// The value of a variable having this variant of `Quirk` is computed
+    /// dynamically from the [`SecondsState`]. The result is the number of
+    /// seconds elapsed since the shell started, or since the last assignment
+    /// to the variable plus the assigned value.
+    ///
+    /// Assigning a non-negative integer to the variable resets the baseline of
+    /// the state, so that the following expansions count up from the integer.
+    /// If any other value is assigned, the variable loses this `Quirk`.
+    Seconds(SecondsState),
     // TODO Path(...)
 }
 
+/// Source of the current time
+///
+/// This trait abstracts the clock used by [`Quirk::Seconds`]. It is
+/// implemented for [`SharedSystem`] so that the variable follows the
+/// simulated clock of a virtual system as well as the real clock.
+pub trait Clock: Debug {
+    /// Returns the current time.
+    #[must_use]
+    fn now(&self) -> Instant;
+}
+
+impl Clock for SharedSystem {
+    fn now(&self) -> Instant {
+        System::now(self)
+    }
+}
+
+/// State of [`Quirk::Seconds`]
+///
+/// The state consists of a clock, a baseline time, and an offset. The value of
+/// the variable is the offset plus the number of whole seconds elapsed since
+/// the baseline time. Two states are equal if they share the same clock
+/// instance and have the same baseline and offset.
+#[derive(Clone, Debug)]
+pub struct SecondsState {
+    clock: Rc<dyn Clock>,
+    baseline: Cell<Instant>,
+    offset: Cell<u64>,
+}
+
+impl PartialEq for SecondsState {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.clock, &other.clock)
+            && self.baseline == other.baseline
+            && self.offset == other.offset
+    }
+}
+
+impl Eq for SecondsState {}
+
+impl SecondsState {
+    /// Creates a new state that counts up from `offset` starting now.
+    #[must_use]
+    pub fn new(clock: Rc<dyn Clock>, offset: u64) -> Self {
+        let baseline = Cell::new(clock.now());
+        let offset = Cell::new(offset);
+        SecondsState {
+            clock,
+            baseline,
+            offset,
+        }
+    }
+
+    /// Returns the current value of the variable.
+    #[must_use]
+    pub fn value(&self) -> u64 {
+        let elapsed = self
+            .clock
+            .now()
+            .saturating_duration_since(self.baseline.get());
+        self.offset.get().saturating_add(elapsed.as_secs())
+    }
+
+    /// Resets the baseline to the current time with a new offset.
+    pub fn reset(&self, offset: u64) {
+        self.baseline.set(self.clock.now());
+        self.offset.set(offset);
+    }
+}
+
 /// Expanded value of a variable
 ///
 /// Variables with a [`Quirk`] may have their values computed dynamically when
@@ -240,6 +340,16 @@ impl Expansion<'_> {
     }
 }
 
+/// Advances the state of the pseudo-random number generator and returns the
+/// next random number in the range of 0 to 32767.
+///
+/// The algorithm is the same as the sample implementation of `rand` in POSIX.
+fn next_random(state: &Cell<u64>) -> u64 {
+    let next = state.get().wrapping_mul(1103515245).wrapping_add(12345);
+    state.set(next);
+    (next >> 16) & 0x7FFF
+}
+
 /// Implementation of [`Variable::expand`].
 pub fn expand<'a>(var: &'a Variable, mut location: &Location) -> Expansion<'a> {
     match &var.quirk {
@@ -252,14 +362,48 @@ pub fn expand<'a>(var: &'a Variable, mut location: &Location) -> Expansion<'a> {
             let line_number = location.code.line_number(location.range.start);
             line_number.to_string().into()
         }
+
+        Some(Quirk::Random(state)) => next_random(state).to_string().into(),
+
+        Some(Quirk::Seconds(state)) => state.value().to_string().into(),
+    }
+}
+
+/// Applies the quirk of a variable after a new value is assigned to it.
+pub(super) fn assign(var: &mut Variable) {
+    match &var.quirk {
+        None => {}
+
+        Some(Quirk::LineNumber) => var.quirk = None,
+
+        Some(Quirk::Random(state)) => match &var.value {
+            Some(Value::Scalar(value)) => match value.parse() {
+                Ok(seed) => state.set(seed),
+                Err(_) => var.quirk = None,
+            },
+            _ => var.quirk = None,
+        },
+
+        Some(Quirk::Seconds(state)) => match &var.value {
+            Some(Value::Scalar(value)) => match value.parse() {
+                Ok(offset) => state.reset(offset),
+                Err(_) => var.quirk = None,
+            },
+            _ => var.quirk = None,
+        },
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::VariableRefMut;
     use super::*;
+    use crate::system::r#virtual::SystemState;
+    use crate::system::r#virtual::VirtualSystem;
+    use assert_matches::assert_matches;
+    use std::cell::RefCell;
     use std::num::NonZeroU64;
-    use std::rc::Rc;
+    use std::time::Duration;
     use yash_syntax::alias::Alias;
     use yash_syntax::source::Code;
 
@@ -336,4 +480,143 @@ mod tests {
         let result = var.expand(&loc);
         assert_eq!(result, Expansion::Scalar("44".into()));
     }
+
+    #[test]
+    fn line_number_quirk_is_lost_on_assignment() {
+        let mut var = Variable {
+            quirk: Some(Quirk::LineNumber),
+            ..Default::default()
+        };
+        VariableRefMut::from(&mut var).assign("7", None).unwrap();
+        assert_eq!(var.quirk, None);
+        let result = var.expand(&Location::dummy(""));
+        assert_eq!(result, Expansion::Scalar("7".into()));
+    }
+
+    #[test]
+    fn expand_random() {
+        let var = Variable {
+            quirk: Some(Quirk::Random(Cell::new(1))),
+            ..Default::default()
+        };
+        let loc = Location::dummy("");
+        // The sample implementation of rand in POSIX yields these numbers when
+        // seeded with 1.
+        assert_eq!(var.expand(&loc), Expansion::Scalar("16838".into()));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("5758".into()));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("10113".into()));
+    }
+
+    #[test]
+    fn expand_random_in_range() {
+        let var = Variable {
+            quirk: Some(Quirk::Random(Cell::new(u64::MAX))),
+            ..Default::default()
+        };
+        let loc = Location::dummy("");
+        for _ in 0..1000 {
+            let value = var.expand(&loc).into_owned().unwrap();
+            let Value::Scalar(value) = value else {
+                panic!("not a scalar: {value:?}");
+            };
+            let number: u32 = value.parse().unwrap();
+            assert!(number < 32768, "{number}");
+        }
+    }
+
+    #[test]
+    fn assigning_seed_to_random() {
+        let mut var = Variable {
+            quirk: Some(Quirk::Random(Cell::new(0))),
+            ..Default::default()
+        };
+        VariableRefMut::from(&mut var).assign("1", None).unwrap();
+        assert_eq!(var.quirk, Some(Quirk::Random(Cell::new(1))));
+        let loc = Location::dummy("");
+        assert_eq!(var.expand(&loc), Expansion::Scalar("16838".into()));
+
+        VariableRefMut::from(&mut var).assign("1", None).unwrap();
+        assert_eq!(var.expand(&loc), Expansion::Scalar("16838".into()));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("5758".into()));
+    }
+
+    #[test]
+    fn random_quirk_is_lost_on_assigning_non_integer() {
+        let mut var = Variable {
+            quirk: Some(Quirk::Random(Cell::new(0))),
+            ..Default::default()
+        };
+        VariableRefMut::from(&mut var).assign("foo", None).unwrap();
+        assert_eq!(var.quirk, None);
+        let result = var.expand(&Location::dummy(""));
+        assert_eq!(result, Expansion::Scalar("foo".into()));
+
+        let mut var = Variable {
+            quirk: Some(Quirk::Random(Cell::new(0))),
+            ..Default::default()
+        };
+        VariableRefMut::from(&mut var).assign("-1", None).unwrap();
+        assert_eq!(var.quirk, None);
+    }
+
+    fn virtual_clock() -> (Rc<dyn Clock>, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        (Rc::new(SharedSystem::new(Box::new(system))), state)
+    }
+
+    fn advance(state: &RefCell<SystemState>, duration: Duration) {
+        *state.borrow_mut().now.as_mut().unwrap() += duration;
+    }
+
+    #[test]
+    fn expand_seconds() {
+        let (clock, state) = virtual_clock();
+        let var = Variable {
+            quirk: Some(Quirk::Seconds(SecondsState::new(clock, 0))),
+            ..Default::default()
+        };
+        let loc = Location::dummy("");
+        assert_eq!(var.expand(&loc), Expansion::Scalar("0".into()));
+
+        advance(&state, Duration::from_secs(3));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("3".into()));
+
+        advance(&state, Duration::from_millis(999));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("3".into()));
+
+        advance(&state, Duration::from_secs(60));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("63".into()));
+    }
+
+    #[test]
+    fn assigning_offset_to_seconds() {
+        let (clock, state) = virtual_clock();
+        let mut var = Variable {
+            quirk: Some(Quirk::Seconds(SecondsState::new(clock, 0))),
+            ..Default::default()
+        };
+        advance(&state, Duration::from_secs(5));
+        VariableRefMut::from(&mut var).assign("10", None).unwrap();
+        assert_matches!(var.quirk, Some(Quirk::Seconds(_)));
+        let loc = Location::dummy("");
+        assert_eq!(var.expand(&loc), Expansion::Scalar("10".into()));
+
+        advance(&state, Duration::from_secs(2));
+        assert_eq!(var.expand(&loc), Expansion::Scalar("12".into()));
+    }
+
+    #[test]
+    fn seconds_quirk_is_lost_on_assigning_non_integer() {
+        let (clock, _state) = virtual_clock();
+        let mut var = Variable {
+            quirk: Some(Quirk::Seconds(SecondsState::new(clock, 0))),
+            ..Default::default()
+        };
+        VariableRefMut::from(&mut var).assign("foo", None).unwrap();
+        assert_eq!(var.quirk, None);
+        let result = var.expand(&Location::dummy(""));
+        assert_eq!(result, Expansion::Scalar("foo".into()));
+    }
 }
@@ -7,6 +7,7 @@ use crate::forwarder::{forwarder, Receiver};
 use crate::{Executor, ExecutorState, Spawner, Task};
 use alloc::boxed::Box;
 use alloc::rc::Rc;
+use alloc::string::String;
 use core::cell::RefCell;
 use core::future::{Future, IntoFuture};
 use core::pin::Pin;
@@ -38,7 +39,7 @@ impl<'a> Executor<'a> {
     /// responsibility to ensure that the `Waker` is not passed to or accessed
     /// from other threads.
     pub unsafe fn spawn_pinned(&self, future: Pin<Box<dyn Future<Output = ()> + 'a>>) {
-        ExecutorState::enqueue(&self.state, future);
+        ExecutorState::enqueue(&self.state, None, future);
     }
 
     /// Adds a task to the task queue.
@@ -60,7 +61,29 @@ impl<'a> Executor<'a> {
         F: IntoFuture<Output = T> + 'a,
         T: 'a,
     {
-        ExecutorState::enqueue_forwarding(&self.state, future)
+        ExecutorState::enqueue_forwarding(&self.state, None, future)
+    }
+
+    /// Adds a named task to the task queue.
+    ///
+    /// This method is the same as [`spawn`] except that the task is given a
+    /// name. The name is included in the `Debug` output of the executor until
+    /// the task completes, which helps find out which task is running or
+    /// stuck.
+    ///
+    /// # Safety
+    ///
+    /// See [`spawn_pinned`] for safety considerations.
+    ///
+    /// [`spawn`]: Self::spawn
+    /// [`spawn_pinned`]: Self::spawn_pinned
+    pub unsafe fn spawn_named<N, F, T>(&self, name: N, future: F) -> Receiver<T>
+    where
+        N: Into<String>,
+        F: IntoFuture<Output = T> + 'a,
+        T: 'a,
+    {
+        ExecutorState::enqueue_forwarding(&self.state, Some(name.into()), future)
     }
 
     /// Returns a `Spawner` that can spawn tasks.
@@ -78,7 +101,9 @@ impl<'a> Executor<'a> {
     /// - `Some(false)` if the task is not complete
     /// - `None` if there are no tasks to run
     ///
-    /// This method panics if the task is polled recursively.
+    /// This method panics if the task is polled recursively. If the `std`
+    /// feature is disabled, a panic in the task propagates to the caller.
+    /// See the [crate-level documentation](crate#panics-in-tasks) for details.
     pub fn step(&self) -> Option<bool> {
         let task = self.state.borrow_mut().wake_queue.pop_front()?;
         Some(task.poll())
@@ -100,33 +125,105 @@ impl<'a> Executor<'a> {
         }
         completed
     }
+
+    /// Runs tasks and then drops the executor.
+    ///
+    /// This method is intended for graceful shutdown. Like
+    /// [`run_until_stalled`](Self::run_until_stalled), it repeatedly calls
+    /// [`step`](Self::step) until there are no more tasks that have been woken
+    /// up, so that the tasks get a chance to run their cleanup code before
+    /// being dropped. Unlike `run_until_stalled`, this method stops after
+    /// polling `budget` tasks so that tasks that keep waking themselves up do
+    /// not prevent the shutdown. Returns the number of completed tasks.
+    ///
+    /// This method consumes the executor. If this executor was the last clone
+    /// sharing the task queue, the tasks remaining in the queue are dropped
+    /// when this method returns. Tasks that are not in the queue (that is,
+    /// tasks waiting to be woken up) are dropped when their wakers are dropped.
+    /// Calling `drain(usize::MAX)` is equivalent to calling `run_until_stalled`
+    /// and then dropping the executor.
+    ///
+    /// This method panics if a task is polled recursively.
+    pub fn drain(self, budget: usize) -> usize {
+        let mut completed = 0;
+        for _ in 0..budget {
+            match self.step() {
+                None => break,
+                Some(true) => completed += 1,
+                Some(false) => {}
+            }
+        }
+        completed
+    }
 }
 
 impl<'a> ExecutorState<'a> {
     pub(crate) fn enqueue(
         this: &Rc<RefCell<Self>>,
+        name: Option<String>,
         future: Pin<Box<dyn Future<Output = ()> + 'a>>,
     ) {
         let task = Task {
+            name,
             executor: Rc::downgrade(this),
             future: RefCell::new(Some(future)),
         };
-        this.borrow_mut().wake_queue.push_back(Rc::new(task));
+        Self::push(this, task);
     }
 
-    pub(crate) fn enqueue_forwarding<F, T>(this: &Rc<RefCell<Self>>, future: F) -> Receiver<T>
+    pub(crate) fn enqueue_forwarding<F, T>(
+        this: &Rc<RefCell<Self>>,
+        name: Option<String>,
+        future: F,
+    ) -> Receiver<T>
     where
         F: IntoFuture<Output = T> + 'a,
         T: 'a,
     {
         let (sender, receiver) = forwarder();
+
+        #[cfg(not(feature = "std"))]
+        let future = async move { sender.send(future.await).unwrap_or_default() };
+
+        #[cfg(feature = "std")]
+        let future = {
+            use core::task::Poll;
+
+            let mut future = Box::pin(future.into_future());
+            let future = core::future::poll_fn(move |context| {
+                match crate::task::catch_unwind(|| future.as_mut().poll(context)) {
+                    Ok(poll) => poll.map(Ok),
+                    Err(()) => Poll::Ready(Err(())),
+                }
+            });
+            async move {
+                match future.await {
+                    Ok(value) => sender.send(value).unwrap_or_default(),
+                    Err(()) => sender.send_panic(),
+                }
+            }
+        };
+
         let task = Task {
+            name,
             executor: Rc::downgrade(this),
-            future: RefCell::new(Some(Box::pin(async move {
-                sender.send(future.await).unwrap_or_default()
-            }))),
+            future: RefCell::new(Some(Box::pin(future))),
         };
-        this.borrow_mut().wake_queue.push_back(Rc::new(task));
+        Self::push(this, task);
         receiver
     }
+
+    /// Adds a new task to the wake queue.
+    ///
+    /// If the task has a name, a weak reference to it is also added to
+    /// `named_tasks`.
+    fn push(this: &Rc<RefCell<Self>>, task: Task<'a>) {
+        let task = Rc::new(task);
+        let mut state = this.borrow_mut();
+        if task.name.is_some() {
+            state.named_tasks.retain(|task| task.strong_count() > 0);
+            state.named_tasks.push(Rc::downgrade(&task));
+        }
+        state.wake_queue.push_back(task);
+    }
 }
@@ -43,6 +43,9 @@ enum Relay<T> {
     Polled(Waker),
     /// The result has been computed, but the receiver has not received it yet.
     Computed(T),
+    /// The task computing the result has panicked.
+    #[cfg(feature = "std")]
+    Panicked,
     /// The receiver has received the result.
     Done,
 }
@@ -83,6 +86,7 @@ pub fn forwarder<T>() -> (Sender<T>, Receiver<T>) {
 ///
 /// This error may be returned from the [`Receiver::try_receive`] method.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum TryReceiveError {
     /// The sender has been dropped, which means the receiver will never receive
     /// the value.
@@ -91,6 +95,11 @@ pub enum TryReceiveError {
     NotSent,
     /// The value has already been received.
     AlreadyReceived,
+    /// The task that was to send the value has panicked.
+    ///
+    /// This error is returned only if the task was spawned by an executor
+    /// with the `std` feature enabled.
+    Panicked,
 }
 
 impl<T> Sender<T> {
@@ -115,6 +124,21 @@ impl<T> Sender<T> {
             }
             // We can send only once, so these cases are impossible
             Relay::Computed(_) | Relay::Done => unreachable!(),
+            #[cfg(feature = "std")]
+            Relay::Panicked => unreachable!(),
+        }
+    }
+
+    /// Notifies the receiver that the task computing the value has panicked.
+    #[cfg(feature = "std")]
+    pub(crate) fn send_panic(self) {
+        let Some(relay) = self.relay.upgrade() else {
+            return;
+        };
+
+        let relay = &mut *relay.borrow_mut();
+        if let Relay::Polled(waker) = core::mem::replace(relay, Relay::Panicked) {
+            waker.wake();
         }
     }
 }
@@ -143,6 +167,9 @@ impl<T> Receiver<T> {
                 Ok(value)
             }
 
+            #[cfg(feature = "std")]
+            Relay::Panicked => Err(TryReceiveError::Panicked),
+
             Relay::Done => Err(TryReceiveError::AlreadyReceived),
         }
     }
@@ -159,6 +186,9 @@ impl<T> Future for Receiver<T> {
     /// for waking up the current task when the value is sent.
     ///
     /// This method should not be called after the value has been received.
+    ///
+    /// This method panics if the task that was to send the value has
+    /// panicked.
     fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<T> {
         let relay = &mut *self.relay.borrow_mut();
         match relay {
@@ -174,6 +204,9 @@ impl<T> Future for Receiver<T> {
                 Poll::Ready(value)
             }
 
+            #[cfg(feature = "std")]
+            Relay::Panicked => panic!("Receiver polled after the sender panicked"),
+
             Relay::Done => panic!("Receiver polled after receiving the value"),
         }
     }
@@ -185,6 +218,7 @@ impl Display for TryReceiveError {
             TryReceiveError::SenderDropped => "sender already dropped".fmt(f),
             TryReceiveError::NotSent => "result not sent yet".fmt(f),
             TryReceiveError::AlreadyReceived => "result already received".fmt(f),
+            TryReceiveError::Panicked => "sender panicked".fmt(f),
         }
     }
 }
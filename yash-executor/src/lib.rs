@@ -45,15 +45,41 @@
 //! to communicate the result of a future. A `Receiver` is also returned from
 //! the [`Executor::spawn`] method to receive the result of a future.
 //!
+//! The [`yield_now`] function returns a future that lets other tasks run
+//! before the current task continues, which is useful for fair scheduling
+//! between long-running tasks.
+//!
+//! # Panics in tasks
+//!
+//! By default, a panic in a task propagates to the caller of
+//! [`Executor::step`] or [`Executor::run_until_stalled`] that polled the task.
+//! If the `std` feature is enabled, the executor catches panics in tasks. A
+//! panicking task is removed from the executor as if it had completed, and
+//! other tasks continue to run. If the task was spawned with
+//! [`Executor::spawn`], the panic is reported to the [`Receiver`] as
+//! [`TryReceiveError::Panicked`].
+//!
+//! Panics are caught with `std::panic::catch_unwind`, but futures are not
+//! required to be `UnwindSafe`. If a task shares state with other tasks, it is
+//! your responsibility to make sure that the state is not left inconsistent by
+//! a panic. Note that the panic hook is still called when a task panics, so
+//! the panic message is printed to the standard error by default. A panic
+//! caused by polling a task recursively is not caught.
+//!
 //! [`Sender`]: forwarder::Sender
 //! [`Receiver`]: forwarder::Receiver
+//! [`TryReceiveError::Panicked`]: forwarder::TryReceiveError::Panicked
 
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use alloc::boxed::Box;
 use alloc::collections::VecDeque;
 use alloc::rc::{Rc, Weak};
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::cell::RefCell;
 use core::fmt::Debug;
 use core::future::Future;
@@ -120,21 +146,46 @@ struct ExecutorState<'a> {
     // We don't need to store tasks that are waiting to be woken up because they
     // are retained by wakers. This also prevents leaking tasks that are never
     // woken up.
+    /// Weak references to tasks that have a name
+    ///
+    /// This list is used to show the names of all live tasks in the `Debug`
+    /// output, including tasks that are waiting to be woken up. References to
+    /// dropped tasks are removed when a new named task is spawned.
+    named_tasks: Vec<Weak<Task<'a>>>,
+}
+
+/// Helper for showing the names of live tasks in the `Debug` output
+struct LiveTaskNames<'b, 'a>(&'b [Weak<Task<'a>>]);
+
+impl Debug for LiveTaskNames<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        for task in self.0.iter().filter_map(Weak::upgrade) {
+            if !task.is_complete() {
+                list.entry(&task.name);
+            }
+        }
+        list.finish()
+    }
 }
 
 impl Debug for ExecutorState<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ExecutorState")
-            .field(
-                "wake_queue",
-                &format_args!("(len = {})", self.wake_queue.len()),
-            )
+            .field("wake_queue", &self.wake_queue)
+            .field("named_tasks", &LiveTaskNames(&self.named_tasks))
             .finish()
     }
 }
 
 /// State of a task to be executed
 struct Task<'a> {
+    /// Name of the task for debugging
+    ///
+    /// The name is shown in the `Debug` output of the executor until the task
+    /// completes or is dropped.
+    name: Option<String>,
+
     /// Shared state of the executor for running this task
     executor: Weak<RefCell<ExecutorState<'a>>>,
 
@@ -145,11 +196,21 @@ struct Task<'a> {
     future: RefCell<Option<Pin<Box<dyn Future<Output = ()> + 'a>>>>,
 }
 
+impl Debug for Task<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Task")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
 pub mod forwarder;
 
 mod executor;
 mod spawner;
 mod task;
 mod waker;
+mod yield_now;
 
 pub use spawner::SpawnError;
+pub use yield_now::{yield_now, YieldNow};
@@ -6,6 +6,7 @@
 use crate::forwarder::Receiver;
 use crate::{ExecutorState, Spawner};
 use alloc::boxed::Box;
+use alloc::string::String;
 use core::fmt::Debug;
 use core::future::{Future, IntoFuture};
 use core::pin::Pin;
@@ -62,7 +63,7 @@ impl<'a> Spawner<'a> {
         future: Pin<Box<dyn Future<Output = ()> + 'a>>,
     ) -> Result<(), SpawnError<Pin<Box<dyn Future<Output = ()> + 'a>>>> {
         if let Some(state) = self.state.upgrade() {
-            ExecutorState::enqueue(&state, future);
+            ExecutorState::enqueue(&state, None, future);
             Ok(())
         } else {
             Err(SpawnError(future))
@@ -94,7 +95,40 @@ impl<'a> Spawner<'a> {
         T: 'a,
     {
         if let Some(state) = self.state.upgrade() {
-            Ok(ExecutorState::enqueue_forwarding(&state, future))
+            Ok(ExecutorState::enqueue_forwarding(&state, None, future))
+        } else {
+            Err(SpawnError(future))
+        }
+    }
+
+    /// Adds a named task to the executor's task queue.
+    ///
+    /// This method is the same as [`spawn`] except that the task is given a
+    /// name. See [`Executor::spawn_named`] for details.
+    ///
+    /// # Safety
+    ///
+    /// See [`spawn_pinned`] for safety considerations.
+    ///
+    /// [`spawn`]: Self::spawn
+    /// [`spawn_pinned`]: Self::spawn_pinned
+    /// [`Executor::spawn_named`]: crate::Executor::spawn_named
+    pub unsafe fn spawn_named<N, F, T>(
+        &self,
+        name: N,
+        future: F,
+    ) -> Result<Receiver<T>, SpawnError<F>>
+    where
+        N: Into<String>,
+        F: IntoFuture<Output = T> + 'a,
+        T: 'a,
+    {
+        if let Some(state) = self.state.upgrade() {
+            Ok(ExecutorState::enqueue_forwarding(
+                &state,
+                Some(name.into()),
+                future,
+            ))
         } else {
             Err(SpawnError(future))
         }
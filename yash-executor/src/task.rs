@@ -8,6 +8,25 @@ use crate::Task;
 use alloc::rc::Rc;
 use core::task::Context;
 
+/// Message of the panic that occurs when a task is polled recursively
+///
+/// This must be the same as the literal passed to `panic!` in [`Task::poll`].
+#[cfg(feature = "std")]
+const RECURSIVE_POLL_MESSAGE: &str = "future polled recursively";
+
+/// Calls the function, catching a panic in it.
+///
+/// A panic caused by recursive polling is not caught since it is a bug in the
+/// caller of the executor rather than in the task.
+#[cfg(feature = "std")]
+pub(crate) fn catch_unwind<F: FnOnce() -> R, R>(f: F) -> Result<R, ()> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|payload| {
+        if payload.downcast_ref::<&str>() == Some(&RECURSIVE_POLL_MESSAGE) {
+            std::panic::resume_unwind(payload)
+        }
+    })
+}
+
 impl Task<'_> {
     /// Wakes the task so that it will be polled again by the executor.
     pub fn wake(self: Rc<Self>) {
@@ -25,6 +44,15 @@ impl Task<'_> {
         wake_queue.push_back(self);
     }
 
+    /// Returns whether the future contained in the task has completed.
+    ///
+    /// A task that is being polled is regarded as not complete.
+    pub fn is_complete(&self) -> bool {
+        self.future
+            .try_borrow()
+            .map_or(false, |future_or_none| future_or_none.is_none())
+    }
+
     /// Polls the future contained in the task.
     ///
     /// If the future completes, this method returns `true` and will do
@@ -33,21 +61,29 @@ impl Task<'_> {
     ///
     /// If `self.executor` has been dropped or the task is polled recursively,
     /// this method panics.
+    ///
+    /// If the `std` feature is enabled and the future panics, the panic is
+    /// caught and the future is dropped as if it had completed.
     pub fn poll(self: &Rc<Self>) -> bool {
         assert_ne!(self.executor.strong_count(), 0, "executor has been dropped");
 
-        let mut future_or_none = self
-            .future
-            .try_borrow_mut()
-            .expect("future polled recursively");
+        let Ok(mut future_or_none) = self.future.try_borrow_mut() else {
+            panic!("future polled recursively")
+        };
         let Some(future) = future_or_none.as_mut() else {
             return true;
         };
 
         let waker = into_waker(Rc::clone(self));
         let mut context = Context::from_waker(&waker);
-        let poll = future.as_mut().poll(&mut context);
-        let is_ready = poll.is_ready();
+
+        #[cfg(not(feature = "std"))]
+        let is_ready = future.as_mut().poll(&mut context).is_ready();
+
+        #[cfg(feature = "std")]
+        let is_ready = catch_unwind(|| future.as_mut().poll(&mut context))
+            .map_or(true, |poll| poll.is_ready());
+
         if is_ready {
             *future_or_none = None;
         }
@@ -66,6 +102,7 @@ mod tests {
     #[test]
     fn waking_without_executor_does_nothing() {
         let task = Rc::new(Task {
+            name: None,
             executor: Weak::new(),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
@@ -76,6 +113,7 @@ mod tests {
     fn task_enqueues_itself_when_woken_with_executor() {
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
@@ -89,6 +127,7 @@ mod tests {
     fn task_does_not_enqueue_again_if_already_enqueued() {
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
@@ -103,10 +142,12 @@ mod tests {
     fn multiple_tasks_can_be_enqueued_at_once() {
         let executor = Rc::default();
         let task1 = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
         let task2 = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
@@ -120,6 +161,7 @@ mod tests {
     #[should_panic = "executor has been dropped"]
     fn polling_without_executor_panics() {
         let task = Rc::new(Task {
+            name: None,
             executor: Weak::new(),
             future: RefCell::new(Some(Box::pin(async { unreachable!() }))),
         });
@@ -131,6 +173,7 @@ mod tests {
         let polled = Rc::new(Cell::new(false));
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async { polled.set(true) }))),
         });
@@ -142,6 +185,7 @@ mod tests {
     fn polling_pending_future() {
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(pending()))),
         });
@@ -153,6 +197,7 @@ mod tests {
         let poll_count = Rc::new(Cell::new(0));
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(Some(Box::pin(async {
                 poll_count.set(poll_count.get() + 1)
@@ -169,6 +214,7 @@ mod tests {
     fn recursive_poll_panics() {
         let executor = Rc::default();
         let task = Rc::new(Task {
+            name: None,
             executor: Rc::downgrade(&executor),
             future: RefCell::new(None),
         });
@@ -0,0 +1,63 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+
+//! Implementation of `yield_now`
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Future returned by [`yield_now`]
+#[derive(Clone, Debug, Default)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        context.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Yields execution to other tasks.
+///
+/// The returned future is pending when polled for the first time. It wakes
+/// the current task immediately, so the executor puts the task at the end of
+/// the wake queue and runs other woken tasks before resuming this task. The
+/// future completes when polled again.
+///
+/// This function is useful for cooperative scheduling in long-running tasks
+/// that would otherwise prevent other tasks from running.
+///
+/// ```
+/// # use yash_executor::{yield_now, Executor};
+/// # use core::cell::RefCell;
+/// let log = RefCell::new(Vec::new());
+/// let executor = Executor::new();
+/// unsafe {
+///     executor.spawn_pinned(Box::pin(async {
+///         log.borrow_mut().push("A1");
+///         yield_now().await;
+///         log.borrow_mut().push("A2");
+///     }));
+///     executor.spawn_pinned(Box::pin(async {
+///         log.borrow_mut().push("B1");
+///         yield_now().await;
+///         log.borrow_mut().push("B2");
+///     }));
+/// }
+/// executor.run_until_stalled();
+/// assert_eq!(*log.borrow(), ["A1", "B1", "A2", "B2"]);
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow::default()
+}
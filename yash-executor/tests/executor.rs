@@ -1,9 +1,10 @@
 // This file is part of yash, an extended POSIX shell.
 // Copyright (C) 2024 WATANABE Yuki
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::future::{pending, poll_fn};
-use std::task::Poll;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
 use yash_executor::forwarder::{Receiver, TryReceiveError};
 use yash_executor::Executor;
 
@@ -70,6 +71,60 @@ mod spawn {
     }
 }
 
+mod spawn_named {
+    use super::*;
+
+    #[test]
+    fn name_appears_in_debug_output_while_task_is_in_wake_queue() {
+        let executor = Executor::new();
+        let receiver = unsafe { executor.spawn_named("my task", async { 42 }) };
+        unsafe { executor.spawn_pinned(Box::pin(pending())) };
+
+        let debug = format!("{executor:?}");
+        assert!(debug.contains("\"my task\""), "{debug}");
+        assert!(debug.contains("None"), "{debug}");
+
+        executor.run_until_stalled();
+        assert_eq!(receiver.try_receive(), Ok(42));
+        let debug = format!("{executor:?}");
+        assert!(!debug.contains("my task"), "{debug}");
+    }
+
+    #[test]
+    fn name_appears_in_debug_output_while_task_is_pending() {
+        let executor = Executor::new();
+        let waker = Rc::new(RefCell::new(None::<Waker>));
+        let waker_2 = Rc::clone(&waker);
+        let woken = Rc::new(Cell::new(false));
+        let woken_2 = Rc::clone(&woken);
+        let receiver = unsafe {
+            executor.spawn_named(
+                "stuck task",
+                poll_fn(move |context| {
+                    if woken_2.get() {
+                        Poll::Ready(())
+                    } else {
+                        *waker_2.borrow_mut() = Some(context.waker().clone());
+                        Poll::Pending
+                    }
+                }),
+            )
+        };
+
+        executor.run_until_stalled();
+        assert_eq!(executor.wake_count(), 0);
+        let debug = format!("{executor:?}");
+        assert!(debug.contains("\"stuck task\""), "{debug}");
+
+        woken.set(true);
+        waker.borrow_mut().take().unwrap().wake();
+        executor.run_until_stalled();
+        assert_eq!(receiver.try_receive(), Ok(()));
+        let debug = format!("{executor:?}");
+        assert!(!debug.contains("stuck task"), "{debug}");
+    }
+}
+
 mod step {
     use super::*;
 
@@ -168,3 +223,183 @@ mod run_until_stalled {
         assert_eq!(executor1.wake_count(), 0);
     }
 }
+
+mod drain {
+    use super::*;
+
+    struct SetOnDrop<'a>(&'a Cell<bool>);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn returns_zero_when_no_tasks() {
+        let executor = Executor::new();
+        assert_eq!(executor.drain(usize::MAX), 0);
+    }
+
+    #[test]
+    fn runs_woken_tasks_to_completion() {
+        let cleaned_up = Cell::new(false);
+        let executor = Executor::new();
+        unsafe {
+            executor.spawn_pinned(Box::pin(async {
+                let _guard = SetOnDrop(&cleaned_up);
+                let mut yielded = false;
+                poll_fn(|cx| {
+                    if yielded {
+                        Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+                assert!(!cleaned_up.get());
+            }))
+        };
+
+        assert_eq!(executor.drain(usize::MAX), 1);
+        assert!(cleaned_up.get());
+    }
+
+    #[test]
+    fn drops_pending_tasks() {
+        let cleaned_up = Cell::new(false);
+        let executor = Executor::new();
+        unsafe {
+            executor.spawn_pinned(Box::pin(async {
+                let _guard = SetOnDrop(&cleaned_up);
+                pending::<()>().await;
+            }))
+        };
+
+        assert_eq!(executor.drain(usize::MAX), 0);
+        assert!(cleaned_up.get());
+    }
+
+    #[test]
+    fn stops_after_budget() {
+        let poll_count = Cell::new(0);
+        let cleaned_up = Cell::new(false);
+        let executor = Executor::new();
+        unsafe {
+            executor.spawn_pinned(Box::pin(async {
+                let _guard = SetOnDrop(&cleaned_up);
+                poll_fn(|cx| {
+                    poll_count.set(poll_count.get() + 1);
+                    cx.waker().wake_by_ref();
+                    Poll::<()>::Pending
+                })
+                .await;
+            }))
+        };
+
+        assert_eq!(executor.drain(3), 0);
+        assert_eq!(poll_count.get(), 3);
+        assert!(cleaned_up.get());
+    }
+
+    #[test]
+    fn keeps_tasks_shared_with_other_clones() {
+        let cleaned_up = Cell::new(false);
+        let executor1 = Executor::new();
+        let executor2 = executor1.clone();
+        unsafe {
+            executor1.spawn_pinned(Box::pin(async {
+                let _guard = SetOnDrop(&cleaned_up);
+                poll_fn(|cx| {
+                    cx.waker().wake_by_ref();
+                    Poll::<()>::Pending
+                })
+                .await;
+            }))
+        };
+
+        assert_eq!(executor1.drain(1), 0);
+        assert!(!cleaned_up.get());
+        assert_eq!(executor2.wake_count(), 1);
+        drop(executor2);
+        assert!(cleaned_up.get());
+    }
+}
+
+#[cfg(feature = "std")]
+mod panic_isolation {
+    use super::*;
+    use std::future::Future as _;
+    use std::pin::Pin;
+    use std::rc::Rc;
+
+    #[test]
+    fn panicking_task_does_not_stop_sibling_task() {
+        let executor = Executor::new();
+        let panicking = unsafe { executor.spawn(async { panic!("task panicked") }) };
+        let sibling = unsafe { executor.spawn(async { 42 }) };
+
+        assert_eq!(executor.run_until_stalled(), 2);
+        assert_eq!(
+            panicking.try_receive(),
+            Err::<(), _>(TryReceiveError::Panicked)
+        );
+        assert_eq!(sibling.try_receive(), Ok(42));
+    }
+
+    #[test]
+    fn panicking_task_wakes_receiver() {
+        let executor = Executor::new();
+        let mut receiver = unsafe {
+            executor.spawn(async {
+                let mut yielded = false;
+                poll_fn(|context| {
+                    if yielded {
+                        panic!("task panicked")
+                    }
+                    yielded = true;
+                    context.waker().wake_by_ref();
+                    Poll::<()>::Pending
+                })
+                .await
+            })
+        };
+        let result = Rc::new(Cell::new(None));
+        let result2 = Rc::clone(&result);
+        unsafe {
+            executor.spawn_pinned(Box::pin(async move {
+                let error = poll_fn(|context| match receiver.try_receive() {
+                    Err(TryReceiveError::NotSent) => {
+                        // Poll the receiver to register the waker
+                        assert!(Pin::new(&mut receiver).poll(context).is_pending());
+                        Poll::Pending
+                    }
+                    other => Poll::Ready(other),
+                })
+                .await;
+                result2.set(Some(error));
+            }));
+        }
+
+        assert_eq!(executor.run_until_stalled(), 2);
+        assert_eq!(result.get(), Some(Err(TryReceiveError::Panicked)));
+    }
+
+    #[test]
+    fn panicking_pinned_task_is_removed() {
+        let executor = Executor::new();
+        let completed = Rc::new(Cell::new(false));
+        let completed2 = Rc::clone(&completed);
+        unsafe {
+            executor.spawn_pinned(Box::pin(async { panic!("task panicked") }));
+            executor.spawn_pinned(Box::pin(async move { completed2.set(true) }));
+        }
+
+        assert_eq!(executor.step(), Some(true));
+        assert_eq!(executor.step(), Some(true));
+        assert_eq!(executor.step(), None);
+        assert!(completed.get());
+    }
+}
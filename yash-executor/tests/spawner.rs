@@ -138,3 +138,28 @@ mod spawn {
         assert_eq!(executor.run_until_stalled(), 2);
     }
 }
+
+mod spawn_named {
+    use super::*;
+
+    #[test]
+    fn dead_spawner_returns_future() {
+        let spawner = Spawner::dead();
+        let result = unsafe { spawner.spawn_named("task", async { 1 }) };
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn spawning_named_task() {
+        let executor = Executor::new();
+        let spawner = executor.spawner();
+
+        let receiver = unsafe { spawner.spawn_named("my task", async { 123 }) }.unwrap();
+        assert_eq!(executor.wake_count(), 1);
+        let debug = format!("{executor:?}");
+        assert!(debug.contains("\"my task\""), "{debug}");
+
+        executor.step();
+        assert_eq!(receiver.try_receive(), Ok(123));
+    }
+}
@@ -0,0 +1,54 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+
+use futures_task::noop_waker_ref;
+use pin_utils::pin_mut;
+use std::cell::RefCell;
+use std::future::Future as _;
+use std::task::{Context, Poll};
+use yash_executor::{yield_now, Executor};
+
+#[test]
+fn yield_now_is_pending_once() {
+    let future = yield_now();
+    pin_mut!(future);
+    let mut context = Context::from_waker(noop_waker_ref());
+    assert_eq!(future.as_mut().poll(&mut context), Poll::Pending);
+    assert_eq!(future.as_mut().poll(&mut context), Poll::Ready(()));
+}
+
+#[test]
+fn yield_now_wakes_task() {
+    let executor = Executor::new();
+    unsafe { executor.spawn_pinned(Box::pin(yield_now())) };
+
+    assert_eq!(executor.step(), Some(false));
+    assert_eq!(executor.wake_count(), 1);
+    assert_eq!(executor.step(), Some(true));
+    assert_eq!(executor.step(), None);
+}
+
+#[test]
+fn tasks_interleave() {
+    let log = RefCell::new(Vec::new());
+    let executor = Executor::new();
+    let task = |name| {
+        let log = &log;
+        async move {
+            for i in 0..3 {
+                log.borrow_mut().push((name, i));
+                yield_now().await;
+            }
+        }
+    };
+    unsafe {
+        executor.spawn_pinned(Box::pin(task('A')));
+        executor.spawn_pinned(Box::pin(task('B')));
+    }
+
+    assert_eq!(executor.run_until_stalled(), 2);
+    assert_eq!(
+        *log.borrow(),
+        [('A', 0), ('B', 0), ('A', 1), ('B', 1), ('A', 2), ('B', 2),]
+    );
+}
@@ -139,6 +139,29 @@ impl Ast {
             .collect()
     }
 
+    /// Returns the literal characters at the beginning of this pattern.
+    ///
+    /// This function returns the longest leading sequence of
+    /// `Atom::Char(_)`s as a string. If `self` is
+    /// [literal](Self::is_literal), the result is the whole pattern.
+    ///
+    /// ```
+    /// # use yash_fnmatch::{ast::{Ast, Atom}, without_escape};
+    /// assert_eq!(Ast::new(without_escape("abc")).prefix_literal(), "abc");
+    /// assert_eq!(Ast::new(without_escape("a*c")).prefix_literal(), "a");
+    /// assert_eq!(Ast::new(without_escape("?bc")).prefix_literal(), "");
+    /// ```
+    #[must_use]
+    pub fn prefix_literal(&self) -> String {
+        self.atoms
+            .iter()
+            .map_while(|atom| match atom {
+                Atom::Char(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    }
+
     #[must_use]
     pub(crate) fn starts_with_literal_dot(&self) -> bool {
         self.atoms.first() == Some(&Atom::Char('.'))
@@ -91,7 +91,7 @@ impl Bracket {
         self.items.iter().any(BracketItem::matches_multi_character)
     }
 
-    fn fmt_regex(&self, regex: &mut dyn Write) -> Result {
+    fn fmt_regex(&self, config: &Config, regex: &mut dyn Write) -> Result {
         if self.items.is_empty() {
             return Err(Error::EmptyBracket);
         }
@@ -103,6 +103,9 @@ impl Bracket {
             for item in &self.items {
                 item.fmt_regex(regex)?;
             }
+            if self.complement && config.path_mode {
+                regex.write_char('/').unwrap();
+            }
             regex.write_char(']').unwrap();
         } else if !self.complement {
             regex.write_str("(?:").unwrap();
@@ -130,6 +133,9 @@ impl Bracket {
                     item.fmt_regex(regex)?;
                 }
             }
+            if config.path_mode {
+                regex.write_char('/').unwrap();
+            }
             regex.write_char(']').unwrap();
         }
         Ok(())
@@ -137,7 +143,7 @@ impl Bracket {
 }
 
 impl Atom {
-    fn fmt_regex(&self, _config: &Config, regex: &mut dyn Write) -> Result {
+    fn fmt_regex(&self, config: &Config, regex: &mut dyn Write) -> Result {
         match self {
             Atom::Char(c) => {
                 if SPECIAL_CHARS.contains(*c) {
@@ -145,9 +151,11 @@ impl Atom {
                 }
                 regex.write_char(*c).unwrap();
             }
+            Atom::AnyChar if config.path_mode => regex.write_str("[^/]").unwrap(),
             Atom::AnyChar => regex.write_char('.').unwrap(),
+            Atom::AnyString if config.path_mode => regex.write_str("[^/]*").unwrap(),
             Atom::AnyString => regex.write_str(".*").unwrap(),
-            Atom::Bracket(bracket) => bracket.fmt_regex(regex)?,
+            Atom::Bracket(bracket) => bracket.fmt_regex(config, regex)?,
         }
         Ok(())
     }
@@ -156,8 +164,8 @@ impl Atom {
 impl Ast {
     /// Writes the AST as a regular expression.
     ///
-    /// Only the `anchor_begin` and `anchor_end` options in `config` affect the
-    /// results. The other options are ignored.
+    /// Only the `anchor_begin`, `anchor_end`, and `path_mode` options in
+    /// `config` affect the results. The other options are ignored.
     pub fn fmt_regex(&self, config: &Config, regex: &mut dyn Write) -> Result {
         if config.anchor_begin {
             regex.write_str(r"\A").unwrap();
@@ -176,8 +184,8 @@ impl Ast {
 
     /// Converts the AST to a regular expression.
     ///
-    /// Only the `anchor_begin` and `anchor_end` options in `config` affect the
-    /// results. The other options are ignored.
+    /// Only the `anchor_begin`, `anchor_end`, and `path_mode` options in
+    /// `config` affect the results. The other options are ignored.
     pub fn to_regex(&self, config: &Config) -> std::result::Result<String, Error> {
         let mut regex = String::new();
         self.fmt_regex(config, &mut regex)?;
@@ -576,4 +584,67 @@ mod tests {
         let regex = ast.to_regex(&config).unwrap();
         assert_eq!(regex, r"\A1.9\z");
     }
+
+    #[test]
+    fn any_patterns_in_path_mode() {
+        let atoms = vec![Atom::AnyChar, Atom::AnyString, Atom::AnyChar];
+        let ast = Ast { atoms };
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let regex = ast.to_regex(&config).unwrap();
+        assert_eq!(regex, "[^/][^/]*[^/]");
+    }
+
+    #[test]
+    fn bracket_expression_in_path_mode() {
+        let bracket = Bracket {
+            complement: false,
+            items: vec![BracketItem::Atom(BracketAtom::Char('/'))],
+        };
+        let atoms = vec![Atom::Bracket(bracket)];
+        let ast = Ast { atoms };
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let regex = ast.to_regex(&config).unwrap();
+        assert_eq!(regex, "[/]");
+    }
+
+    #[test]
+    fn bracket_expression_complement_in_path_mode() {
+        let bracket = Bracket {
+            complement: true,
+            items: vec![BracketItem::Atom(BracketAtom::Char('a'))],
+        };
+        let atoms = vec![Atom::Bracket(bracket)];
+        let ast = Ast { atoms };
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let regex = ast.to_regex(&config).unwrap();
+        assert_eq!(regex, "[^a/]");
+    }
+
+    #[test]
+    fn complex_bracket_expression_complement_in_path_mode() {
+        let bracket = Bracket {
+            complement: true,
+            items: vec![
+                BracketItem::Atom(BracketAtom::CollatingSymbol("ch".to_string())),
+                BracketItem::Atom(BracketAtom::Char('a')),
+            ],
+        };
+        let atoms = vec![Atom::Bracket(bracket)];
+        let ast = Ast { atoms };
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let regex = ast.to_regex(&config).unwrap();
+        assert_eq!(regex, "[^a/]");
+    }
 }
@@ -86,6 +86,18 @@ pub struct Config {
     /// For non-literal patterns, the "simple" case folding rules defined by
     /// Unicode are applied to allow case-insensitive matches.
     pub case_insensitive: bool,
+
+    /// Whether a slash has to be matched explicitly
+    ///
+    /// When `path_mode` is `true`, a slash in the text must be matched by a
+    /// literal slash in the pattern. In other words, a wildcard pattern (`*` or
+    /// `?`) or bracket expression complement (`[!...]`) does not match a
+    /// slash. For example, the pattern `a*b` does not match the text `a/b`.
+    /// This is useful for pathname expansion, where patterns are matched
+    /// against each pathname component.
+    ///
+    /// When `path_mode` is `false`, the above restriction does not apply.
+    pub path_mode: bool,
 }
 
 /// Error that may happen in building a pattern.
@@ -134,6 +146,7 @@ enum Body {
     Regex {
         regex: Regex,
         starts_with_literal_dot: bool,
+        prefix_literal: String,
     },
 }
 
@@ -182,6 +195,7 @@ impl Pattern {
                     .swap_greed(config.shortest_match)
                     .build()?,
                 starts_with_literal_dot: ast.starts_with_literal_dot(),
+                prefix_literal: ast.prefix_literal(),
             }
         };
         Ok(Pattern { body, config })
@@ -219,6 +233,32 @@ impl Pattern {
         }
     }
 
+    /// Returns the literal characters at the beginning of the pattern.
+    ///
+    /// The result is the longest leading part of the pattern that contains no
+    /// `?`, `*`, or bracket expression. If the pattern is made up only of
+    /// literal characters, the result is the whole pattern, the same as
+    /// [`as_literal`](Self::as_literal).
+    ///
+    /// Any text matched by an [anchored](Config::anchor_begin) pattern starts
+    /// with the prefix, which can be used to skip irrelevant text before
+    /// performing the actual matching. Note that, if the pattern is
+    /// [case-insensitive](Config::case_insensitive), the text may differ from
+    /// the prefix in case.
+    ///
+    /// ```
+    /// # use yash_fnmatch::{Pattern, without_escape};
+    /// let p = Pattern::parse(without_escape("src/foo*")).unwrap();
+    /// assert_eq!(p.prefix_literal(), "src/foo");
+    /// ```
+    #[must_use]
+    pub fn prefix_literal(&self) -> &str {
+        match &self.body {
+            Body::Literal(s) => s,
+            Body::Regex { prefix_literal, .. } => prefix_literal,
+        }
+    }
+
     /// Tests whether this pattern matches the given text.
     #[must_use]
     pub fn is_match(&self, text: &str) -> bool {
@@ -232,6 +272,7 @@ impl Pattern {
             Body::Regex {
                 regex,
                 starts_with_literal_dot,
+                prefix_literal: _,
             } => {
                 let reject_initial_dot =
                     self.config.literal_period && !starts_with_literal_dot && text.starts_with('.');
@@ -258,6 +299,7 @@ impl Pattern {
             Body::Regex {
                 regex,
                 starts_with_literal_dot,
+                prefix_literal: _,
             } => {
                 let reject_initial_dot =
                     self.config.literal_period && !starts_with_literal_dot && text.starts_with('.');
@@ -285,6 +327,7 @@ impl Pattern {
             Body::Regex {
                 regex,
                 starts_with_literal_dot: _,
+                prefix_literal: _,
             } => {
                 let mut range = self.find(text)?;
 
@@ -1022,6 +1065,52 @@ mod tests {
         assert_eq!(p.find("aaa"), None);
     }
 
+    #[test]
+    fn prefix_literal_of_literal_pattern() {
+        let p = Pattern::parse(without_escape("")).unwrap();
+        assert_eq!(p.prefix_literal(), "");
+
+        let p = Pattern::parse(without_escape("abc")).unwrap();
+        assert_eq!(p.prefix_literal(), "abc");
+
+        let p = Pattern::parse(with_escape(r"a\*\?")).unwrap();
+        assert_eq!(p.prefix_literal(), "a*?");
+    }
+
+    #[test]
+    fn prefix_literal_of_non_literal_pattern() {
+        let p = Pattern::parse(without_escape("abc*")).unwrap();
+        assert_eq!(p.prefix_literal(), "abc");
+
+        let p = Pattern::parse(without_escape("abc?d")).unwrap();
+        assert_eq!(p.prefix_literal(), "abc");
+
+        let p = Pattern::parse(without_escape("[a]bc")).unwrap();
+        assert_eq!(p.prefix_literal(), "");
+
+        let p = Pattern::parse(without_escape("src/foo*/bar")).unwrap();
+        assert_eq!(p.prefix_literal(), "src/foo");
+
+        let p = Pattern::parse(with_escape(r"\[a\]b[c]")).unwrap();
+        assert_eq!(p.prefix_literal(), "[a]b");
+    }
+
+    #[test]
+    fn prefix_literal_with_anchor() {
+        let config = Config {
+            anchor_begin: true,
+            anchor_end: true,
+            ..Config::default()
+        };
+        let p = Pattern::parse_with_config(without_escape("ab*c"), config).unwrap();
+        assert_eq!(p.prefix_literal(), "ab");
+        assert!(p.is_match("abxc"));
+        assert!(!p.is_match("xabc"));
+
+        let p = Pattern::parse_with_config(without_escape("abc"), config).unwrap();
+        assert_eq!(p.prefix_literal(), "abc");
+    }
+
     #[test]
     fn literal_with_anchor_begin() {
         let config = Config {
@@ -1319,4 +1408,56 @@ mod tests {
         assert_eq!(p.rfind("A-Z"), Some(0..3));
         assert_eq!(p.rfind("b&b"), None);
     }
+
+    #[test]
+    fn non_literal_without_path_mode() {
+        let p = Pattern::parse(without_escape("a*b")).unwrap();
+        assert!(p.is_match("ab"));
+        assert!(p.is_match("a/b"));
+        assert_eq!(p.find("xa/b"), Some(1..4));
+
+        let p = Pattern::parse(without_escape("a?b")).unwrap();
+        assert!(p.is_match("a/b"));
+
+        let p = Pattern::parse(without_escape("a[!x]b")).unwrap();
+        assert!(p.is_match("a/b"));
+    }
+
+    #[test]
+    fn non_literal_with_path_mode() {
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let p = Pattern::parse_with_config(without_escape("a*b"), config).unwrap();
+        assert!(p.is_match("ab"));
+        assert!(p.is_match("axyb"));
+        assert!(!p.is_match("a/b"));
+        assert!(!p.is_match("ax/yb"));
+        assert_eq!(p.find("a/ab"), Some(2..4));
+        assert_eq!(p.rfind("ab/axb"), Some(3..6));
+
+        let p = Pattern::parse_with_config(without_escape("a?b"), config).unwrap();
+        assert!(p.is_match("axb"));
+        assert!(!p.is_match("a/b"));
+
+        let p = Pattern::parse_with_config(without_escape("a[!x]b"), config).unwrap();
+        assert!(p.is_match("ayb"));
+        assert!(!p.is_match("axb"));
+        assert!(!p.is_match("a/b"));
+    }
+
+    #[test]
+    fn literal_slash_with_path_mode() {
+        let config = Config {
+            path_mode: true,
+            ..Config::default()
+        };
+        let p = Pattern::parse_with_config(without_escape("a/b"), config).unwrap();
+        assert!(p.is_match("a/b"));
+
+        let p = Pattern::parse_with_config(without_escape("*/?"), config).unwrap();
+        assert_eq!(p.find("foo/bar"), Some(0..5));
+        assert!(!p.is_match("foo"));
+    }
 }
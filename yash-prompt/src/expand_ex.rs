@@ -0,0 +1,307 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Prompt string expansion (yash-specific)
+
+use crate::expand_posix::replace_exclamation_marks;
+use futures_util::FutureExt as _;
+use yash_env::variable::{HOME, PWD};
+use yash_env::Env;
+use yash_env::System as _;
+use yash_semantics::expansion::expand_text;
+use yash_syntax::parser::lex::Lexer;
+use yash_syntax::syntax::Text;
+use yash_syntax::syntax::TextUnit::{self, Backslashed, Literal};
+
+/// Expands the prompt string with yash-specific backslash escapes.
+///
+/// This function works like [`expand_posix`](crate::expand_posix()), but
+/// additionally recognizes the following backslash escapes in the prompt
+/// string:
+///
+/// - `\w`: the current working directory (the value of `$PWD`), where a
+///   leading `$HOME` is abbreviated to `~`
+/// - `\u`: the name of the current user
+/// - `\h`: the host name up to the first `.`
+/// - `\$`: `#` if the effective user ID is zero, `$` otherwise
+/// - `\t`: the current time in the `HH:MM:SS` format
+/// - `\d`: the current date in the `Www Mmm dd` format (e.g., `Tue May 26`)
+/// - `\n`: a newline
+/// - `\\`: a backslash
+///
+/// A backslash followed by any other character is left intact. The results of
+/// the escapes are not subject to further expansion. If the value needed for
+/// an escape cannot be obtained, the escape expands to an empty string.
+pub async fn expand_ex(env: &mut Env, prompt: &str, excl: bool) -> String {
+    let mut lexer = Lexer::with_code(prompt);
+    let text_result = lexer.text(|_| false, |_| true).now_or_never().unwrap();
+
+    let mut text = text_result.unwrap_or_else(|_| {
+        // If expansions in the prompt string cannot be parsed, treat all
+        // characters as literals.
+        Text::from_literal_chars(prompt.chars())
+    });
+
+    if excl {
+        replace_exclamation_marks(&mut text.0);
+    }
+
+    replace_escapes(env, &mut text.0);
+
+    match expand_text(env, &text).await {
+        Ok((expansion, _exit_status)) => expansion,
+        Err(_) => text.to_string(),
+    }
+}
+
+/// Replaces backslash escapes in the text with their results.
+fn replace_escapes(env: &Env, text: &mut Vec<TextUnit>) {
+    *text = std::mem::take(text)
+        .into_iter()
+        .flat_map(|unit| match unit {
+            Backslashed(c) => escape(env, c).chars().map(Literal).collect(),
+            unit => vec![unit],
+        })
+        .collect();
+}
+
+/// Returns the result of the backslash escape `\c`.
+fn escape(env: &Env, c: char) -> String {
+    match c {
+        'w' => working_directory(env),
+        'u' => user_name(env),
+        'h' => host_name(env),
+        '$' => if env.system.geteuid().0 == 0 {
+            "#"
+        } else {
+            "$"
+        }
+        .to_owned(),
+        't' => time(env),
+        'd' => date(env),
+        'n' => "\n".to_owned(),
+        '\\' => "\\".to_owned(),
+        _ => format!("\\{c}"),
+    }
+}
+
+fn working_directory(env: &Env) -> String {
+    let pwd = env.variables.get_scalar(PWD).unwrap_or_default();
+    match env.variables.get_scalar(HOME) {
+        Some(home) if !home.is_empty() => match pwd.strip_prefix(home.trim_end_matches('/')) {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{rest}"),
+            _ => pwd.to_owned(),
+        },
+        _ => pwd.to_owned(),
+    }
+}
+
+fn user_name(env: &Env) -> String {
+    let uid = env.system.getuid();
+    env.system
+        .getpwuid_name(uid)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn host_name(env: &Env) -> String {
+    let Ok(hostname) = env.system.gethostname() else {
+        return String::new();
+    };
+    let hostname = hostname.to_string_lossy();
+    let end = hostname.find('.').unwrap_or(hostname.len());
+    hostname[..end].to_owned()
+}
+
+fn time(env: &Env) -> String {
+    match env.system.local_time() {
+        Ok(t) => format!("{:02}:{:02}:{:02}", t.hour, t.minute, t.second),
+        Err(_) => String::new(),
+    }
+}
+
+fn date(env: &Env) -> String {
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let Ok(t) = env.system.local_time() else {
+        return String::new();
+    };
+    let weekday = WEEKDAYS.get(usize::from(t.weekday)).copied().unwrap_or("");
+    let month = MONTHS
+        .get(usize::from(t.month).wrapping_sub(1))
+        .copied()
+        .unwrap_or("");
+    format!("{weekday} {month} {:02}", t.day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::LocalTime;
+    use yash_env::system::Uid;
+    use yash_env::variable::Scope::Global;
+    use yash_env::variable::Value;
+
+    fn env_with_state() -> (Env, Rc<std::cell::RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        (Env::with_system(Box::new(system)), state)
+    }
+
+    fn define_variable<N: Into<String>, V: Into<Value>>(env: &mut Env, name: N, value: V) {
+        env.variables
+            .get_or_new(name, Global)
+            .assign(value, None)
+            .unwrap();
+    }
+
+    fn expand(env: &mut Env, prompt: &str) -> String {
+        expand_ex(env, prompt, false).now_or_never().unwrap()
+    }
+
+    #[test]
+    fn plain_prompt() {
+        let mut env = Env::new_virtual();
+        assert_eq!(expand(&mut env, "my prompt > "), "my prompt > ");
+    }
+
+    #[test]
+    fn parameter_expansion() {
+        let mut env = Env::new_virtual();
+        define_variable(&mut env, "FOO", "bar");
+        assert_eq!(expand(&mut env, "my $FOO > "), "my bar > ");
+    }
+
+    #[test]
+    fn working_directory_outside_home() {
+        let mut env = Env::new_virtual();
+        define_variable(&mut env, PWD, "/usr/local");
+        define_variable(&mut env, HOME, "/home/me");
+        assert_eq!(expand(&mut env, "\\w"), "/usr/local");
+    }
+
+    #[test]
+    fn working_directory_in_home() {
+        let mut env = Env::new_virtual();
+        define_variable(&mut env, HOME, "/home/me");
+        define_variable(&mut env, PWD, "/home/me");
+        assert_eq!(expand(&mut env, "[\\w]"), "[~]");
+        define_variable(&mut env, PWD, "/home/me/src");
+        assert_eq!(expand(&mut env, "[\\w]"), "[~/src]");
+        define_variable(&mut env, PWD, "/home/menu");
+        assert_eq!(expand(&mut env, "[\\w]"), "[/home/menu]");
+    }
+
+    #[test]
+    fn working_directory_is_not_expanded_further() {
+        let mut env = Env::new_virtual();
+        define_variable(&mut env, PWD, "/tmp/$FOO\\n");
+        define_variable(&mut env, "FOO", "bar");
+        assert_eq!(expand(&mut env, "\\w"), "/tmp/$FOO\\n");
+    }
+
+    #[test]
+    fn user_name() {
+        let (mut env, state) = env_with_state();
+        let uid = env.system.getuid();
+        state
+            .borrow_mut()
+            .user_names
+            .insert(uid, "alice".to_owned());
+        assert_eq!(expand(&mut env, "\\u@"), "alice@");
+    }
+
+    #[test]
+    fn unknown_user_name() {
+        let (mut env, state) = env_with_state();
+        state
+            .borrow_mut()
+            .user_names
+            .insert(Uid(12345), "bob".to_owned());
+        assert_eq!(expand(&mut env, "\\u@"), "@");
+    }
+
+    #[test]
+    fn host_name() {
+        let (mut env, state) = env_with_state();
+        state.borrow_mut().hostname = "myhost.example.com".into();
+        assert_eq!(expand(&mut env, "@\\h:"), "@myhost:");
+    }
+
+    #[test]
+    fn privilege_indicator() {
+        let (mut env, state) = env_with_state();
+        assert_eq!(expand(&mut env, "\\$ "), "$ ");
+
+        let pid = env.system.getpid();
+        state
+            .borrow_mut()
+            .processes
+            .get_mut(&pid)
+            .unwrap()
+            .set_euid(Uid(0));
+        assert_eq!(expand(&mut env, "\\$ "), "# ");
+    }
+
+    #[test]
+    fn time_and_date() {
+        let (mut env, state) = env_with_state();
+        state.borrow_mut().local_time = Some(LocalTime {
+            year: 2025,
+            month: 5,
+            day: 6,
+            weekday: 2,
+            hour: 9,
+            minute: 8,
+            second: 7,
+        });
+        assert_eq!(expand(&mut env, "\\t"), "09:08:07");
+        assert_eq!(expand(&mut env, "\\d"), "Tue May 06");
+    }
+
+    #[test]
+    fn time_and_date_unavailable() {
+        let mut env = Env::new_virtual();
+        assert_eq!(expand(&mut env, "[\\t][\\d]"), "[][]");
+    }
+
+    #[test]
+    fn newline_and_backslash() {
+        let mut env = Env::new_virtual();
+        assert_eq!(expand(&mut env, "a\\nb\\\\c"), "a\nb\\c");
+    }
+
+    #[test]
+    fn unknown_escape_is_left_intact() {
+        let mut env = Env::new_virtual();
+        assert_eq!(expand(&mut env, "\\q\\!\\\""), "\\q\\!\\\"");
+    }
+
+    #[test]
+    fn exclamation_marks_with_escapes() {
+        let mut env = Env::new_virtual();
+        let result = expand_ex(&mut env, "! !! \\n", true)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "0 ! \n");
+    }
+}
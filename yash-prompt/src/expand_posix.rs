@@ -38,8 +38,9 @@ use yash_syntax::syntax::TextUnit::{self, Literal};
 ///
 /// The current implementation does not recognize any backslash escapes in the
 /// text since the POSIX standard does not specify any. However, other shell
-/// implementations support backslash escapes in the prompt string. This
-/// discrepancy may be reconsidered in the future.
+/// implementations support backslash escapes in the prompt string. Use
+/// [`expand_ex`](crate::expand_ex()) to expand yash-specific backslash
+/// escapes.
 pub async fn expand_posix(env: &mut Env, prompt: &str, excl: bool) -> String {
     let mut lexer = Lexer::with_code(prompt);
     let text_result = lexer.text(|_| false, |_| false).now_or_never().unwrap();
@@ -62,7 +63,7 @@ pub async fn expand_posix(env: &mut Env, prompt: &str, excl: bool) -> String {
 
 /// Replaces all occurrences of `!` in the text with the history number of the
 /// current command and `!!` with `!`.
-fn replace_exclamation_marks(text: &mut Vec<TextUnit>) {
+pub(crate) fn replace_exclamation_marks(text: &mut Vec<TextUnit>) {
     let mut i = 0;
     while i < text.len() {
         if text[i] == Literal('!') {
@@ -30,10 +30,16 @@
 //! - [`fetch_posix`]: Fetches the value of a variable defined by POSIX for
 //!   a prompt string.
 //! - [`expand_posix`]: Expands a prompt string in a POSIX-compliant manner.
-//! - `expand_ex`: Expands a prompt string with yash-specific expansions.
-//!   (This function is not yet implemented.)
+//! - [`expand_ex`]: Expands a prompt string with yash-specific expansions.
+//! - [`run_prompt_command`]: Executes the `PROMPT_COMMAND` variable before
+//!   the primary prompt is shown.
+//!
+//! The prompter uses `expand_posix` if the
+//! [`PosixlyCorrect`](yash_env::option::Option::PosixlyCorrect) option is on
+//! and `expand_ex` otherwise.
 //!
 //! [`expand_posix`]: expand_posix()
+//! [`expand_ex`]: expand_ex()
 //!
 //! # Examples
 //!
@@ -70,8 +76,10 @@
 mod expand_posix;
 pub use expand_posix::expand_posix;
 
-// TODO Yash-specific prompt expansion
+mod expand_ex;
+pub use expand_ex::expand_ex;
 
 mod prompter;
 pub use prompter::fetch_posix;
+pub use prompter::run_prompt_command;
 pub use prompter::Prompter;
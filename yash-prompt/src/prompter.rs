@@ -17,19 +17,69 @@
 //! Defines the `Prompter` decorator.
 
 use std::cell::RefCell;
+use unicode_width::UnicodeWidthStr as _;
 use yash_env::input::{Context, Input, Result};
-use yash_env::variable::{VariableSet, PS1, PS2};
+use yash_env::io::Fd;
+use yash_env::option::{On, PosixlyCorrect};
+use yash_env::variable::{VariableSet, PROMPT_COMMAND, PS1, PS2};
 use yash_env::Env;
+use yash_env::System as _;
+use yash_semantics::read_eval_loop;
+use yash_syntax::input::Memory;
+use yash_syntax::parser::lex::Lexer;
+use yash_syntax::source::Source;
 
 /// [`Input`] decorator that shows a command prompt
 ///
 /// This decorator expands and shows the command prompt before the input is read
 /// by the inner `Input`.
+///
+/// Before showing the primary prompt, the decorator executes the value of the
+/// `PROMPT_COMMAND` variable as a command if the variable is set. See
+/// [`run_prompt_command`] for details.
+///
+/// Optionally, the prompter can show a right-hand-side prompt (RPROMPT) on the
+/// same line as the main prompt. See [`with_rprompt`](Self::with_rprompt).
+///
+/// By default, the prompt is expanded each time a line is read. If caching is
+/// enabled by [`with_cache`](Self::with_cache), the result of expansion is
+/// reused while the raw prompt string is unchanged and no new command has been
+/// added to the [history](Env::history). This avoids repeating expensive or
+/// side-effecting expansions (such as command substitutions) while reading
+/// the lines of a single command.
 #[derive(Clone, Debug)]
 #[must_use = "Prompter does nothing unless used by a parser"]
 pub struct Prompter<'a, 'b, T> {
     inner: T,
     env: &'a RefCell<&'b mut Env>,
+    rprompt: Option<String>,
+    cache: Option<Cache>,
+}
+
+/// Result of prompt expansion remembered by [`Prompter`]
+#[derive(Clone, Debug, Default)]
+struct Cache {
+    entry: Option<(CacheKey, Expansion)>,
+}
+
+/// Conditions under which a cached expansion is valid
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct CacheKey {
+    /// Raw prompt string before expansion
+    prompt: String,
+    /// Whether the prompt is for the first line of a command
+    is_first_line: bool,
+    /// Whether the prompt was expanded in the POSIX-compliant manner
+    posixly_correct: bool,
+    /// History number of the next command
+    history_number: usize,
+}
+
+/// Expanded prompt strings
+#[derive(Clone, Debug)]
+struct Expansion {
+    prompt: String,
+    rprompt: Option<String>,
 }
 
 impl<'a, 'b, T> Prompter<'a, 'b, T> {
@@ -41,7 +91,43 @@ impl<'a, 'b, T> Prompter<'a, 'b, T> {
     /// error. It is wrapped in a `RefCell` so that it can be shared with other
     /// decorators and the parser.
     pub fn new(inner: T, env: &'a RefCell<&'b mut Env>) -> Self {
-        Self { inner, env }
+        Self {
+            inner,
+            env,
+            rprompt: None,
+            cache: None,
+        }
+    }
+
+    /// Sets the right-hand-side prompt string.
+    ///
+    /// The RPROMPT string is expanded in the same way as the main prompt and
+    /// shown right-aligned on the line of the main prompt. It is not shown for
+    /// continuation lines. The width of the terminal is obtained from the
+    /// standard error. The RPROMPT is omitted if the width is not available or
+    /// the line is too narrow to show both the main prompt and the RPROMPT.
+    pub fn with_rprompt(mut self, rprompt: String) -> Self {
+        self.rprompt = Some(rprompt);
+        self
+    }
+
+    /// Enables caching of prompt expansion.
+    ///
+    /// See the [type-level documentation](Self) for when the cached expansion
+    /// is reused.
+    pub fn with_cache(mut self) -> Self {
+        self.cache = Some(Cache::default());
+        self
+    }
+
+    /// Discards the cached expansion, if any.
+    ///
+    /// The prompt will be expanded again the next time a line is read. This
+    /// function does nothing if caching is not enabled.
+    pub fn invalidate_cache(&mut self) {
+        if let Some(cache) = &mut self.cache {
+            cache.entry = None;
+        }
     }
 }
 
@@ -51,22 +137,142 @@ where
 {
     #[allow(clippy::await_holding_refcell_ref)]
     async fn next_line(&mut self, context: &Context) -> Result {
-        print_prompt(&mut self.env.borrow_mut(), context).await;
+        print_prompt(
+            &mut self.env.borrow_mut(),
+            context,
+            self.rprompt.as_deref(),
+            self.cache.as_mut(),
+        )
+        .await;
         self.inner.next_line(context).await
     }
 }
 
-async fn print_prompt(env: &mut Env, context: &Context) {
+async fn print_prompt(
+    env: &mut Env,
+    context: &Context,
+    rprompt: Option<&str>,
+    cache: Option<&mut Cache>,
+) {
+    if context.is_first_line() {
+        run_prompt_command(env).await;
+    }
+
     // Obtain the prompt string
     let prompt = fetch_posix(&env.variables, context);
 
-    // Perform parameter expansion in the prompt string
-    let expanded_prompt = super::expand_posix(env, &prompt, context.is_first_line()).await;
+    // Perform parameter expansion in the prompt string, unless cached
+    let expansion = match cache {
+        None => expand_prompts(env, context, &prompt, rprompt).await,
+        Some(cache) => {
+            let key = CacheKey {
+                prompt,
+                is_first_line: context.is_first_line(),
+                posixly_correct: env.options.get(PosixlyCorrect) == On,
+                history_number: env.history.next_number(),
+            };
+            match &cache.entry {
+                Some((cached_key, expansion)) if *cached_key == key => expansion.clone(),
+                _ => {
+                    let expansion = expand_prompts(env, context, &key.prompt, rprompt).await;
+                    cache.entry = Some((key, expansion.clone()));
+                    expansion
+                }
+            }
+        }
+    };
+    let mut expanded_prompt = expansion.prompt;
+
+    // Append the right-hand-side prompt if any
+    if let Some(expanded_rprompt) = expansion.rprompt {
+        if let Ok(size) = env.system.terminal_size(Fd::STDERR) {
+            let width = size.columns.into();
+            if let Some(suffix) = layout_rprompt(&expanded_prompt, &expanded_rprompt, width) {
+                expanded_prompt.push_str(&suffix);
+            }
+        }
+    }
 
     // Print the prompt to the standard error
     env.system.print_error(&expanded_prompt).await;
 }
 
+/// Executes the value of the `PROMPT_COMMAND` variable as a command.
+///
+/// This function does nothing if the variable is not set. Errors in the
+/// command, including syntax errors, are reported by the read-eval loop, but do
+/// not cause the shell to exit. Likewise, the `exit`, `return`, `break`, and
+/// `continue` built-ins executed in the command only stop executing the
+/// command. The exit status of the command does not affect `$?`.
+pub async fn run_prompt_command(env: &mut Env) {
+    let Some(code) = env.variables.get_scalar(PROMPT_COMMAND) else {
+        return;
+    };
+    let code = code.to_owned();
+    let mut config = Lexer::config();
+    config.source = Some(
+        Source::VariableValue {
+            name: PROMPT_COMMAND.to_owned(),
+        }
+        .into(),
+    );
+    let mut lexer = config.input(Box::new(Memory::new(&code)));
+
+    let previous_exit_status = env.exit_status;
+    // Boxing needed for recursion
+    _ = Box::pin(read_eval_loop(&RefCell::new(&mut *env), &mut lexer)).await;
+    env.exit_status = previous_exit_status;
+}
+
+async fn expand_prompts(
+    env: &mut Env,
+    context: &Context,
+    prompt: &str,
+    rprompt: Option<&str>,
+) -> Expansion {
+    let excl = context.is_first_line();
+    let prompt = expand(env, prompt, excl).await;
+    let rprompt = match rprompt.filter(|_| context.is_first_line()) {
+        Some(rprompt) => Some(expand(env, rprompt, excl).await),
+        None => None,
+    };
+    Expansion { prompt, rprompt }
+}
+
+async fn expand(env: &mut Env, prompt: &str, excl: bool) -> String {
+    if env.options.get(PosixlyCorrect) == On {
+        super::expand_posix(env, prompt, excl).await
+    } else {
+        super::expand_ex(env, prompt, excl).await
+    }
+}
+
+/// Computes the string to be printed after the main prompt to show the
+/// right-hand-side prompt.
+///
+/// The returned string pads the last line of the main prompt with spaces,
+/// prints the RPROMPT, and then returns the cursor to the end of the main
+/// prompt by printing a carriage return and the last line of the main prompt
+/// again. The RPROMPT ends one column before the right edge of the terminal so
+/// that the terminal does not wrap the line.
+///
+/// The widths of the prompts are measured in terminal columns, so wide
+/// characters such as CJK ideographs count as two columns. `None` is returned
+/// if the RPROMPT is empty or contains a newline, or if the terminal is too
+/// narrow to show both prompts separated by at least one space.
+fn layout_rprompt(prompt: &str, rprompt: &str, columns: usize) -> Option<String> {
+    if rprompt.is_empty() || rprompt.contains('\n') {
+        return None;
+    }
+    let last_line = prompt.rsplit('\n').next().unwrap_or_default();
+    let prompt_width = last_line.width();
+    let rprompt_width = rprompt.width();
+    let padding = columns
+        .checked_sub(1 + prompt_width + rprompt_width)
+        .filter(|&padding| padding >= 1)?;
+    Some(format!("{}{rprompt}\r{last_line}", " ".repeat(padding)))
+}
+
 /// Fetches the command prompt string from the variable set.
 ///
 /// The return value is the raw value taken from the `PS1` or `PS2` variable
@@ -90,8 +296,10 @@ mod tests {
     use futures_util::FutureExt as _;
     use std::rc::Rc;
     use yash_env::input::Memory;
+    use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::SystemState;
     use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::TerminalSize;
     use yash_env::variable::Value;
     use yash_env::variable::PS1;
     use yash_env::variable::PS1_INITIAL_VALUE_NON_ROOT;
@@ -196,4 +404,301 @@ mod tests {
             .ok();
         assert_stderr(&state, |stderr| assert_eq!(stderr, "foo $ "));
     }
+
+    #[test]
+    fn backslash_escapes_in_prompt_string() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "\\w\\$ ");
+        define_variable(&mut env, "PWD", "/tmp");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "/tmp$ "));
+    }
+
+    #[test]
+    fn no_backslash_escapes_in_posix_mode() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        env.options.set(PosixlyCorrect, On);
+        define_variable(&mut env, PS1, "\\w\\$ ");
+        define_variable(&mut env, "PWD", "/tmp");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "\\w\\$ "));
+    }
+
+    #[test]
+    fn layout_rprompt_pads_to_right_edge() {
+        let suffix = layout_rprompt("$ ", "[R]", 10);
+        assert_eq!(suffix.unwrap(), "    [R]\r$ ");
+        // "$ " + "    " + "[R]" fills 9 columns, leaving the last column empty.
+    }
+
+    #[test]
+    fn layout_rprompt_considers_last_line_of_prompt_only() {
+        let suffix = layout_rprompt("first line\n> ", "R", 6);
+        assert_eq!(suffix.unwrap(), "  R\r> ");
+    }
+
+    #[test]
+    fn layout_rprompt_omitted_on_narrow_width() {
+        assert_eq!(layout_rprompt("$ ", "[R]", 7).as_deref(), Some(" [R]\r$ "));
+        assert_eq!(layout_rprompt("$ ", "[R]", 6), None);
+        assert_eq!(layout_rprompt("$ ", "[R]", 0), None);
+    }
+
+    #[test]
+    fn layout_rprompt_omitted_if_empty_or_multiline() {
+        assert_eq!(layout_rprompt("$ ", "", 80), None);
+        assert_eq!(layout_rprompt("$ ", "a\nb", 80), None);
+    }
+
+    #[test]
+    fn layout_rprompt_counts_wide_characters_as_two_columns() {
+        let suffix = layout_rprompt("\u{3042}$ ", "[\u{6F22}]", 12);
+        assert_eq!(suffix.unwrap(), "   [\u{6F22}]\r\u{3042}$ ");
+        assert_eq!(layout_rprompt("\u{3042}$ ", "[\u{6F22}]", 9), None);
+    }
+
+    fn set_stderr_to_terminal(state: &RefCell<SystemState>, width: Option<u16>) {
+        let mut state = state.borrow_mut();
+        let stderr = state.file_system.get("/dev/stderr").unwrap();
+        stderr.borrow_mut().body = FileBody::Terminal { content: vec![] };
+        state.terminal_size = width.map(|columns| TerminalSize { rows: 24, columns });
+    }
+
+    fn terminal_content(state: &RefCell<SystemState>) -> String {
+        let stderr = state.borrow().file_system.get("/dev/stderr").unwrap();
+        let stderr = stderr.borrow();
+        match &stderr.body {
+            FileBody::Terminal { content } => String::from_utf8(content.clone()).unwrap(),
+            body => panic!("unexpected file body: {body:?}"),
+        }
+    }
+
+    #[test]
+    fn rprompt_shown_on_terminal() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        set_stderr_to_terminal(&state, Some(16));
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$X$ ");
+        define_variable(&mut env, "X", "foo");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter =
+            Prompter::new(Memory::new(""), &ref_env).with_rprompt("<$X>".to_string());
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_eq!(terminal_content(&state), "foo$      <foo>\rfoo$ ");
+    }
+
+    #[test]
+    fn rprompt_omitted_on_narrow_terminal() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        set_stderr_to_terminal(&state, Some(8));
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "foo$ ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter =
+            Prompter::new(Memory::new(""), &ref_env).with_rprompt("<foo>".to_string());
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_eq!(terminal_content(&state), "foo$ ");
+    }
+
+    #[test]
+    fn rprompt_omitted_without_terminal_width() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$ ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_rprompt("R".to_string());
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "$ "));
+    }
+
+    #[test]
+    fn rprompt_not_shown_for_continuation_lines() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        set_stderr_to_terminal(&state, Some(80));
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS2, "> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_rprompt("R".to_string());
+        let mut context = Context::default();
+        context.set_is_first_line(false);
+
+        prompter.next_line(&context).now_or_never().unwrap().ok();
+        assert_eq!(terminal_content(&state), "> ");
+    }
+
+    fn read_twice<T: Input>(prompter: &mut Prompter<'_, '_, T>) {
+        for _ in 0..2 {
+            prompter
+                .next_line(&Context::default())
+                .now_or_never()
+                .unwrap()
+                .ok();
+        }
+    }
+
+    #[test]
+    fn prompt_is_expanded_for_each_read_without_cache() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$((N+=1))> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+
+        read_twice(&mut prompter);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 2> "));
+    }
+
+    #[test]
+    fn cached_expansion_is_reused() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$((N+=1))> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_cache();
+
+        read_twice(&mut prompter);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 1> "));
+        drop(prompter);
+        assert_eq!(env.variables.get_scalar("N"), Some("1"));
+    }
+
+    #[test]
+    fn prompt_is_expanded_again_after_invalidation() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$((N+=1))> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_cache();
+
+        read_twice(&mut prompter);
+        prompter.invalidate_cache();
+        read_twice(&mut prompter);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 1> 2> 2> "));
+    }
+
+    #[test]
+    fn prompt_is_expanded_again_after_new_history_entry() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$((N+=1))> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_cache();
+
+        read_twice(&mut prompter);
+        ref_env.borrow_mut().add_history("echo\n");
+        read_twice(&mut prompter);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 1> 2> 2> "));
+    }
+
+    #[test]
+    fn prompt_is_expanded_again_after_prompt_change() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "$((N+=1))> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env).with_cache();
+
+        read_twice(&mut prompter);
+        define_variable(&mut ref_env.borrow_mut(), PS1, "$((N+=1))$ ");
+        read_twice(&mut prompter);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 1> 2$ 2$ "));
+    }
+
+    #[test]
+    fn prompt_command_runs_once_per_primary_prompt() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PROMPT_COMMAND, "N=$((N+1))");
+        define_variable(&mut env, PS1, "$N> ");
+        define_variable(&mut env, PS2, "$N+ ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+        let mut context = Context::default();
+
+        read_twice(&mut prompter);
+        context.set_is_first_line(false);
+        prompter.next_line(&context).now_or_never().unwrap().ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "1> 2> 2+ "));
+    }
+
+    #[test]
+    fn prompt_command_can_set_variable_for_next_command() {
+        let mut env = Env::new_virtual();
+        define_variable(&mut env, PROMPT_COMMAND, "X=foo");
+        let ref_env = RefCell::new(&mut env);
+        let input = Box::new(Prompter::new(Memory::new("Y=$X\n"), &ref_env));
+        let mut lexer = Lexer::config().input(input);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        drop(lexer);
+        assert_eq!(result, std::ops::ControlFlow::Continue(()));
+        assert_eq!(env.variables.get_scalar("Y"), Some("foo"));
+    }
+
+    #[test]
+    fn error_in_prompt_command_is_reported_and_ignored() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        env.exit_status = yash_env::semantics::ExitStatus(3);
+        define_variable(&mut env, PROMPT_COMMAND, "fi");
+        define_variable(&mut env, PS1, "$?> ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new("foo"), &ref_env);
+
+        let result = prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), "foo");
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("PROMPT_COMMAND"), "{stderr}");
+            assert!(stderr.ends_with("3> "), "{stderr}");
+        });
+        drop(prompter);
+        assert_eq!(env.exit_status, yash_env::semantics::ExitStatus(3));
+    }
 }
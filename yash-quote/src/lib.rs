@@ -34,6 +34,10 @@
 //! [`quote`] function returns a `Cow<str>`, avoiding unnecessary clone of the
 //! string if it requires no quoting.
 //!
+//! `Quoted` honors the width, fill, and alignment flags of the formatter, so
+//! you can quote and pad a string in one step. The width is compared with the
+//! number of characters in the quoted form, not the original string.
+//!
 //! # Examples
 //!
 //! ```
@@ -42,6 +46,8 @@
 //! assert_eq!(format!("value={}", quoted("")), "value=''");
 //! assert_eq!(format!("value={}", quoted("$foo")), "value='$foo'");
 //! assert_eq!(format!("value={}", quoted("'$foo'")), r#"value="'\$foo'""#);
+//! assert_eq!(format!("[{:8}]", quoted("a b")), "['a b'   ]");
+//! assert_eq!(format!("[{:>8}]", quoted("a b")), "[   'a b']");
 //! ```
 //!
 //! ```
@@ -53,6 +59,7 @@
 //! ```
 
 use std::borrow::Cow::{self, Borrowed, Owned};
+use unicode_width::UnicodeWidthStr as _;
 
 #[must_use]
 fn char_needs_quoting(c: char) -> bool {
@@ -130,11 +137,57 @@ impl<'a> Quoted<'a> {
     pub fn needs_quoting(&self) -> bool {
         self.needs_quoting
     }
-}
 
-/// Quotes the contained string.
-impl std::fmt::Display for Quoted<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// Returns the display width of the quoted string.
+    ///
+    /// The width is measured in terminal columns, so wide characters such as
+    /// CJK ideographs count as two columns. This is the width that the
+    /// `Display` implementation compares with the width specified in the
+    /// formatter when padding the result.
+    #[must_use]
+    pub fn quoted_len(&self) -> usize {
+        let len = self.raw.width();
+        if !self.needs_quoting {
+            len
+        } else if !self.raw.contains('\'') {
+            len + 2
+        } else {
+            let escapes = self
+                .raw
+                .chars()
+                .filter(|c| matches!(c, '"' | '`' | '$' | '\\'))
+                .count();
+            len + escapes + 2
+        }
+    }
+
+    /// Writes the quoted string padded to the specified width.
+    ///
+    /// The string is padded with the fill character of the formatter so that
+    /// the result occupies at least `width` columns as measured by
+    /// [`quoted_len`](Self::quoted_len). The alignment of the
+    /// formatter determines where the padding is inserted. The default
+    /// alignment is left. The string is never truncated.
+    pub fn fmt_padded(&self, f: &mut std::fmt::Formatter<'_>, width: usize) -> std::fmt::Result {
+        use std::fmt::{Alignment, Write};
+        let padding = width.saturating_sub(self.quoted_len());
+        let (before, after) = match f.align() {
+            None | Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Right) => (padding, 0),
+            Some(Alignment::Center) => (padding / 2, (padding + 1) / 2),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        self.fmt_unpadded(f)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_unpadded(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use std::fmt::Write;
         if !self.needs_quoting {
             f.write_str(self.raw)
@@ -153,6 +206,19 @@ impl std::fmt::Display for Quoted<'_> {
     }
 }
 
+/// Quotes the contained string.
+///
+/// If the formatter has a width, the result is padded as in
+/// [`Quoted::fmt_padded`]. The precision is ignored.
+impl std::fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.width() {
+            Some(width) => self.fmt_padded(f, width),
+            None => self.fmt_unpadded(f),
+        }
+    }
+}
+
 /// Wraps a string in [`Quoted`].
 ///
 /// This function scans the string to cache the value for
@@ -251,4 +317,46 @@ mod tests {
         test(r"'\'\\''", r#""'\\'\\\\''""#);
         test("'{\n}'", "\"'{\n}'\"");
     }
+
+    #[test]
+    fn quoted_len() {
+        assert_eq!(quoted("foo").quoted_len(), 3);
+        assert_eq!(quoted("").quoted_len(), 2);
+        assert_eq!(quoted("a b").quoted_len(), 5);
+        assert_eq!(quoted("'$").quoted_len(), 5);
+        assert_eq!(quoted("\u{3000}").quoted_len(), 4);
+    }
+
+    #[test]
+    fn padding_unquoted() {
+        assert_eq!(format!("{:5}|", quoted("foo")), "foo  |");
+        assert_eq!(format!("{:>5}|", quoted("foo")), "  foo|");
+        assert_eq!(format!("{:^6}|", quoted("foo")), " foo  |");
+    }
+
+    #[test]
+    fn padding_single_quoted() {
+        assert_eq!(format!("{:8}|", quoted("a b")), "'a b'   |");
+        assert_eq!(format!("{:>8}|", quoted("a b")), "   'a b'|");
+        assert_eq!(format!("{:*^8}|", quoted("a b")), "*'a b'**|");
+    }
+
+    #[test]
+    fn padding_double_quoted() {
+        assert_eq!(format!("{:7}|", quoted("'$")), r#""'\$"  |"#);
+        assert_eq!(format!("{:>7}|", quoted("'$")), r#"  "'\$"|"#);
+    }
+
+    #[test]
+    fn padding_narrower_than_quoted() {
+        assert_eq!(format!("{:2}|", quoted("a b")), "'a b'|");
+        assert_eq!(format!("{:.1}|", quoted("a b")), "'a b'|");
+    }
+
+    #[test]
+    fn padding_multibyte() {
+        // U+3000 (ideographic space) occupies two columns.
+        assert_eq!(format!("{:5}|", quoted("\u{3000}")), "'\u{3000}' |");
+        assert_eq!(format!("{:4}|", quoted("\u{3000}")), "'\u{3000}'|");
+    }
 }
@@ -23,10 +23,12 @@ mod item;
 mod pipeline;
 pub mod simple_command;
 
+use crate::trap::run_err_trap;
 use crate::trap::run_traps_for_caught_signals;
 use std::ops::ControlFlow::{Break, Continue};
 use yash_env::semantics::Result;
 use yash_env::Env;
+use yash_env::System;
 use yash_syntax::syntax;
 
 /// Syntactic construct that can be executed.
@@ -39,20 +41,42 @@ pub trait Command {
     async fn execute(&self, env: &mut Env) -> Result;
 }
 
+/// Runs the ERR trap and applies the `ErrExit` shell option.
+///
+/// This function should be called after executing a command whose failure is
+/// subject to the `ErrExit` option. If `env.exit_status` is non-zero, the ERR
+/// trap is [run](run_err_trap) and then [`Env::apply_errexit`] is called.
+/// Neither takes effect in a condition context such as the condition of an
+/// `if` command or the left-hand side of an `&&` or `||` operator.
+async fn apply_err_trap_and_errexit(env: &mut Env) -> Result {
+    run_err_trap(env).await?;
+    env.apply_errexit()
+}
+
 /// Executes the command.
 ///
 /// After executing the command body, the `execute` function [runs
 /// traps](run_traps_for_caught_signals) if any caught signals are pending, and
 /// [updates subshell statuses](Env::update_all_subshell_statuses).
+/// File descriptors that were left open by process substitutions performed in
+/// the command are closed after the command body.
 impl Command for syntax::Command {
     async fn execute(&self, env: &mut Env) -> Result {
         use syntax::Command::*;
+        let process_subst_fd_count = env.process_subst_fds.len();
         let main_result = match self {
             Simple(command) => command.execute(env).await,
             Compound(command) => command.execute(env).await,
             Function(definition) => definition.execute(env).await,
         };
 
+        // Close the file descriptors opened by process substitutions in this command
+        if env.process_subst_fds.len() > process_subst_fd_count {
+            for fd in env.process_subst_fds.drain(process_subst_fd_count..) {
+                env.system.close(fd).ok();
+            }
+        }
+
         let trap_result = run_traps_for_caught_signals(env).await;
         env.update_all_subshell_statuses();
 
@@ -86,8 +110,11 @@ impl Command for syntax::List {
 mod tests {
     use super::*;
     use crate::tests::echo_builtin;
+    use crate::tests::env_with_err_trap;
     use crate::tests::return_builtin;
     use futures_util::FutureExt;
+    use yash_env::option::Option::ErrExit;
+    use yash_env::option::State::On;
     use yash_env::semantics::Divert;
     use yash_env::semantics::ExitStatus;
     use yash_env::system::r#virtual::VirtualSystem;
@@ -145,4 +172,43 @@ mod tests {
         assert_eq!(result, Break(Divert::Return(Some(ExitStatus(2)))));
         assert_eq!(env.exit_status, ExitStatus(1));
     }
+
+    #[test]
+    fn err_trap_on_failing_simple_command() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?");
+        let list: syntax::List = "return -n 3; echo next".parse().unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "ERR 3\nnext\n"));
+    }
+
+    #[test]
+    fn err_trap_before_errexit() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?");
+        env.options.set(ErrExit, On);
+        let list: syntax::List = "return -n 5; echo next".parse().unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Exit(None)));
+        assert_eq!(env.exit_status, ExitStatus(5));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "ERR 5\n"));
+    }
+
+    #[test]
+    fn no_err_trap_on_tested_commands() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?");
+        let list: syntax::List = "if return -n 1; then echo then; fi
+            while return -n 2; do echo do; done
+            return -n 3 && echo and
+            return -n 4 || echo or
+            ! return -n 5"
+            .parse()
+            .unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "or\n"));
+    }
 }
@@ -50,6 +50,7 @@ async fn evaluate_condition(env: &mut Env, condition: &syntax::List) -> Result<b
     Continue(env.exit_status.is_successful())
 }
 
+mod arith;
 mod case;
 mod for_loop;
 mod r#if;
@@ -59,7 +60,7 @@ mod while_loop;
 /// Executes the compound command.
 ///
 /// The redirections are performed, if any, before executing the command body.
-/// Redirection errors are subject to the `ErrExit` option
+/// Redirection errors are subject to the ERR trap and the `ErrExit` option
 /// (`Env::apply_errexit`).
 impl Command for syntax::FullCompoundCommand {
     async fn execute(&self, env: &mut Env) -> Result {
@@ -68,7 +69,7 @@ impl Command for syntax::FullCompoundCommand {
             Ok(_) => self.command.execute(&mut env).await,
             Err(error) => {
                 error.handle(&mut env).await?;
-                env.apply_errexit()
+                super::apply_err_trap_and_errexit(&mut env).await
             }
         }
     }
@@ -85,7 +86,8 @@ impl Command for syntax::FullCompoundCommand {
 /// A subshell is executed by running the contained list in a separate
 /// environment ([`Subshell`]).
 ///
-/// After the subshell has finished, [`Env::apply_errexit`] is called.
+/// After the subshell has finished, the ERR trap is run and
+/// [`Env::apply_errexit`] is called.
 ///
 /// # For loop
 ///
@@ -127,6 +129,12 @@ impl Command for syntax::FullCompoundCommand {
 ///
 /// After executing the body of the matching item, the case command may process
 /// the next item depending on the continuation.
+///
+/// # Arithmetic command
+///
+/// The arithmetic command expands and evaluates the expression in the same
+/// manner as an arithmetic expansion. The exit status is zero if the result is
+/// non-zero, and one if the result is zero.
 impl Command for syntax::CompoundCommand {
     async fn execute(&self, env: &mut Env) -> Result {
         use syntax::CompoundCommand::*;
@@ -143,6 +151,7 @@ impl Command for syntax::CompoundCommand {
                 r#else,
             } => r#if::execute(env, condition, body, elifs, r#else).await,
             Case { subject, items } => case::execute(env, subject, items).await,
+            Arith { content, location } => arith::execute(env, content, location).await,
         }
     }
 }
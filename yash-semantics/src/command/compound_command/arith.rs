@@ -0,0 +1,98 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Semantics of arithmetic commands
+
+use crate::expansion::expand_text;
+use crate::Handle;
+use std::ops::ControlFlow::Continue;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Result;
+use yash_env::Env;
+use yash_syntax::source::Location;
+use yash_syntax::syntax::Text;
+use yash_syntax::syntax::TextUnit;
+
+/// Executes an arithmetic command.
+pub async fn execute(env: &mut Env, content: &Text, location: &Location) -> Result {
+    let content = content.clone();
+    let location = location.clone();
+    let text = Text(vec![TextUnit::Arith { content, location }]);
+    match expand_text(env, &text).await {
+        Ok((value, _exit_status)) => {
+            env.exit_status = if value == "0" {
+                ExitStatus::FAILURE
+            } else {
+                ExitStatus::SUCCESS
+            };
+            Continue(())
+        }
+        Err(error) => error.handle(env).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use futures_util::FutureExt;
+    use std::ops::ControlFlow::Break;
+    use yash_env::semantics::Divert;
+    use yash_env::variable::Scope;
+    use yash_syntax::syntax::CompoundCommand;
+
+    fn execute_str(env: &mut Env, code: &str) -> Result {
+        let command: CompoundCommand = code.parse().unwrap();
+        command.execute(env).now_or_never().unwrap()
+    }
+
+    #[test]
+    fn non_zero_result_is_success() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus(42);
+        let result = execute_str(&mut env, "((1 + 2))");
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+    }
+
+    #[test]
+    fn zero_result_is_failure() {
+        let mut env = Env::new_virtual();
+        let result = execute_str(&mut env, "((1 - 1))");
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::FAILURE);
+    }
+
+    #[test]
+    fn assignment_in_expression() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new("a", Scope::Global)
+            .assign("3", None)
+            .unwrap();
+        let result = execute_str(&mut env, "((b = a * 2))");
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_eq!(env.variables.get_scalar("b"), Some("6"));
+    }
+
+    #[test]
+    fn expansion_error() {
+        let mut env = Env::new_virtual();
+        let result = execute_str(&mut env, "((1 +))");
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+    }
+}
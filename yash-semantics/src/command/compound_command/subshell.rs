@@ -17,7 +17,7 @@
 //! Semantics of subshell compound commands
 
 use crate::command::Command;
-use crate::trap::run_exit_trap;
+use crate::shutdown;
 use std::ops::ControlFlow::Break;
 use std::rc::Rc;
 use yash_env::io::print_error;
@@ -47,7 +47,7 @@ pub async fn execute(env: &mut Env, body: Rc<List>, location: &Location) -> Resu
             }
 
             env.exit_status = result.into();
-            env.apply_errexit()
+            crate::command::apply_err_trap_and_errexit(env).await
         }
         Err(errno) => {
             print_error(
@@ -65,9 +65,7 @@ pub async fn execute(env: &mut Env, body: Rc<List>, location: &Location) -> Resu
 /// Executes the content of the shell.
 async fn subshell_main(env: &mut Env, body: Rc<List>) {
     let result = body.execute(env).await;
-    env.apply_result(result);
-
-    run_exit_trap(env).await;
+    shutdown(env, result).await;
 }
 
 #[cfg(test)]
@@ -86,6 +84,7 @@ mod tests {
     use yash_env::option::State::On;
     use yash_env::system::r#virtual::VirtualSystem;
     use yash_env::system::r#virtual::SIGSTOP;
+    use yash_env::system::Buffering;
     use yash_env_test_helper::assert_stderr;
     use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
@@ -208,23 +207,52 @@ mod tests {
         })
     }
 
+    fn trap_builtin(
+        env: &mut Env,
+        _args: Vec<yash_env::semantics::Field>,
+    ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+        Box::pin(async move {
+            env.traps
+                .set_action(
+                    &mut env.system,
+                    yash_env::trap::Condition::Exit,
+                    yash_env::trap::Action::Command("echo exiting".into()),
+                    Location::dummy(""),
+                    false,
+                )
+                .unwrap();
+            yash_env::builtin::Result::default()
+        })
+    }
+
     #[test]
     fn exit_trap() {
-        fn trap_builtin(
-            env: &mut Env,
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.builtins.insert(
+                "trap",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Special, trap_builtin),
+            );
+
+            let command: CompoundCommand = "(trap)".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "exiting\n"));
+        })
+    }
+
+    #[test]
+    fn exit_trap_is_not_run_on_abort() {
+        fn abort_builtin(
+            _env: &mut Env,
             _args: Vec<yash_env::semantics::Field>,
         ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
             Box::pin(async move {
-                env.traps
-                    .set_action(
-                        &mut env.system,
-                        yash_env::trap::Condition::Exit,
-                        yash_env::trap::Action::Command("echo exiting".into()),
-                        Location::dummy(""),
-                        false,
-                    )
-                    .unwrap();
-                yash_env::builtin::Result::default()
+                yash_env::builtin::Result::with_exit_status_and_divert(
+                    ExitStatus(126),
+                    Break(Divert::Abort(None)),
+                )
             })
         }
 
@@ -234,12 +262,45 @@ mod tests {
                 "trap",
                 yash_env::builtin::Builtin::new(yash_env::builtin::Type::Special, trap_builtin),
             );
+            env.builtins.insert(
+                "abort",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Special, abort_builtin),
+            );
 
-            let command: CompoundCommand = "(trap)".parse().unwrap();
+            let command: CompoundCommand = "(trap; abort)".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(126));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        })
+    }
+
+    #[test]
+    fn exit_trap_output_precedes_final_flush() {
+        fn buffer_builtin(
+            env: &mut Env,
+            _args: Vec<yash_env::semantics::Field>,
+        ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+            env.system.set_stdout_buffering(Buffering::Full);
+            Box::pin(async move { yash_env::builtin::Result::default() })
+        }
+
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.builtins.insert(
+                "trap",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Special, trap_builtin),
+            );
+            env.builtins.insert(
+                "buffer",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Mandatory, buffer_builtin),
+            );
+
+            let command: CompoundCommand = "(buffer; trap; echo main)".parse().unwrap();
             let result = command.execute(&mut env).await;
             assert_eq!(result, Continue(()));
             assert_eq!(env.exit_status, ExitStatus::SUCCESS);
-            assert_stdout(&state, |stdout| assert_eq!(stdout, "exiting\n"));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "main\nexiting\n"));
         })
     }
 }
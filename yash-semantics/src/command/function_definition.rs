@@ -41,11 +41,12 @@ use yash_syntax::syntax;
 /// Finally, the function definition is inserted into the environment, and the
 /// execution ends with an exit status of zero.
 ///
-/// The `ErrExit` shell option is [applied](Env::apply_errexit) on error.
+/// On error, the ERR trap is run and the `ErrExit` shell option is
+/// [applied](Env::apply_errexit).
 impl Command for syntax::FunctionDefinition {
     async fn execute(&self, env: &mut Env) -> Result {
         define_function(env, self).await?;
-        env.apply_errexit()
+        super::apply_err_trap_and_errexit(env).await
     }
 }
 
@@ -16,7 +16,7 @@
 
 //! Implementation for Item.
 
-use crate::trap::run_exit_trap;
+use crate::shutdown;
 
 use super::Command;
 use std::ops::ControlFlow::{Break, Continue};
@@ -107,9 +107,7 @@ async fn async_body(env: &mut Env, job_control: Option<JobControl>, and_or: &And
         nullify_stdin(env).ok();
     }
     let result = and_or.execute(env).await;
-    env.apply_result(result);
-
-    run_exit_trap(env).await;
+    shutdown(env, result).await;
 }
 
 fn nullify_stdin(env: &mut Env) -> std::result::Result<(), yash_env::system::Errno> {
@@ -223,6 +221,23 @@ mod tests {
         })
     }
 
+    #[test]
+    fn item_execute_async_job_name_of_pipeline() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            env.builtins.insert("echo", echo_builtin());
+
+            let item = syntax::Item {
+                and_or: Rc::new("echo  foo|cat  &&  ! cat".parse().unwrap()),
+                async_flag: Some(Location::dummy("")),
+            };
+            item.execute(&mut env).await;
+
+            let job = &env.jobs[0];
+            assert_eq!(job.name, "echo foo | cat && ! cat");
+        })
+    }
+
     #[test]
     fn item_execute_async_pid() {
         in_virtual_system(|mut env, state| async move {
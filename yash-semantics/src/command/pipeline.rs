@@ -16,18 +16,21 @@
 
 //! Implementation of pipeline semantics.
 
-use crate::trap::run_exit_trap;
+use crate::shutdown;
 
 use super::Command;
 use enumset::EnumSet;
 use itertools::Itertools;
+use std::future::Future;
 use std::ops::ControlFlow::{Break, Continue};
+use std::pin::Pin;
 use std::rc::Rc;
+use std::time::Instant;
 use yash_env::io::Fd;
 use yash_env::job::Job;
 use yash_env::job::Pid;
-use yash_env::option::Option::{Exec, Interactive};
-use yash_env::option::State::Off;
+use yash_env::option::Option::{Exec, Interactive, PipeFail};
+use yash_env::option::State::{Off, On};
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Result;
@@ -36,6 +39,7 @@ use yash_env::subshell::JobControl;
 use yash_env::subshell::Subshell;
 use yash_env::system::Errno;
 use yash_env::system::SystemEx;
+use yash_env::system::Times;
 use yash_env::Env;
 use yash_env::System;
 use yash_syntax::syntax;
@@ -61,11 +65,31 @@ use yash_syntax::syntax;
 /// command). If the pipeline starts with an `!`, the exit status is inverted:
 /// zero becomes one, and non-zero becomes zero.
 ///
+/// If the [`PipeFail`] option is [`On`] in `env.options`, the exit status of
+/// the pipeline is that of the last command that exited with a non-zero exit
+/// status, or zero if all the commands succeeded. The inversion by `!` applies
+/// to this exit status.
+///
 /// In POSIX, the expected exit status is unclear when an inverted pipeline
 /// performs a jump as in `! return 42`. The behavior disagrees among existing
 /// shells. This implementation does not invert the exit status when the return
 /// value is `Err(Divert::...)`.
 ///
+/// # Timing
+///
+/// If `self.timed` is true, the pipeline is timed. After the pipeline is
+/// executed, the elapsed real time and the user and system CPU time consumed
+/// by the shell and its children are printed to the standard error in the
+/// format of the `-p` option of the POSIX `time` utility:
+///
+/// ```text
+/// real 1.23
+/// user 0.45
+/// sys 0.06
+/// ```
+///
+/// The times are printed even if the pipeline performs a jump.
+///
 /// # `noexec` option
 ///
 /// If the [`Exec`] and [`Interactive`] options are [`Off`] in `env.options`,
@@ -94,18 +118,61 @@ impl Command for syntax::Pipeline {
             }
         }
 
-        if !self.negation {
-            return execute_commands_in_pipeline(env, &self.commands).await;
+        if !self.timed {
+            return execute_maybe_negated(env, self).await;
         }
 
-        let mut env = env.push_frame(Frame::Condition);
-        execute_commands_in_pipeline(&mut env, &self.commands).await?;
-        env.exit_status = if env.exit_status.is_successful() {
-            ExitStatus::FAILURE
-        } else {
-            ExitStatus::SUCCESS
-        };
-        Continue(())
+        let start = Timing::now(env);
+        let result = execute_maybe_negated(env, self).await;
+        start.print_elapsed(env).await;
+        result
+    }
+}
+
+async fn execute_maybe_negated(env: &mut Env, pipeline: &syntax::Pipeline) -> Result {
+    if !pipeline.negation {
+        return execute_commands_in_pipeline(env, &pipeline.commands).await;
+    }
+
+    let mut env = env.push_frame(Frame::Condition);
+    execute_commands_in_pipeline(&mut env, &pipeline.commands).await?;
+    env.exit_status = if env.exit_status.is_successful() {
+        ExitStatus::FAILURE
+    } else {
+        ExitStatus::SUCCESS
+    };
+    Continue(())
+}
+
+/// Start time of a timed pipeline
+struct Timing {
+    real: Instant,
+    cpu: Times,
+}
+
+impl Timing {
+    /// Records the current real and CPU time.
+    fn now(env: &Env) -> Self {
+        let real = env.system.now();
+        let cpu = env.system.times().unwrap_or_default();
+        Timing { real, cpu }
+    }
+
+    /// Prints the time elapsed since `self` was recorded.
+    async fn print_elapsed(&self, env: &mut Env) {
+        let end = Timing::now(env);
+        let real = end.real.saturating_duration_since(self.real).as_secs_f64();
+        let user = (end.cpu.self_user + end.cpu.children_user)
+            - (self.cpu.self_user + self.cpu.children_user);
+        let system = (end.cpu.self_system + end.cpu.children_system)
+            - (self.cpu.self_system + self.cpu.children_system);
+        let message = format!(
+            "real {:.2}\nuser {:.2}\nsys {:.2}\n",
+            real,
+            user.max(0.0),
+            system.max(0.0)
+        );
+        env.system.print_error(&message).await;
     }
 }
 
@@ -124,7 +191,7 @@ async fn execute_commands_in_pipeline(env: &mut Env, commands: &[Rc<syntax::Comm
             } else {
                 execute_multi_command_pipeline(env, commands).await?
             }
-            env.apply_errexit()
+            super::apply_err_trap_and_errexit(env).await
         }
     }
 }
@@ -137,8 +204,7 @@ async fn execute_job_controlled_pipeline(
     let subshell = Subshell::new(|sub_env, _job_control| {
         Box::pin(async move {
             let result = execute_multi_command_pipeline(sub_env, &commands_2).await;
-            sub_env.apply_result(result);
-            run_exit_trap(sub_env).await;
+            shutdown(sub_env, result).await;
         })
     })
     .job_control(JobControl::Foreground);
@@ -181,29 +247,34 @@ async fn execute_multi_command_pipeline(env: &mut Env, commands: &[Rc<syntax::Co
         let has_next = commands.peek().is_some();
         shift_or_fail(env, &mut pipes, has_next).await?;
 
-        let pipes = pipes;
         let subshell = Subshell::new(move |env, _job_control| {
             Box::pin(async move {
-                let result = connect_pipe_and_execute_command(env, pipes, command).await;
-                env.apply_result(result);
-                run_exit_trap(env).await;
+                let result = command.execute(env).await;
+                shutdown(env, result).await;
             })
         });
+        let subshell = pipes.arrange_fds(subshell);
         let start_result = subshell.start(env).await;
         pids.push(pid_or_fail(env, start_result).await?);
     }
 
     shift_or_fail(env, &mut pipes, false).await?;
 
-    // Await the last command
+    // Await the commands
+    let pipe_fail = env.options.get(PipeFail) == On;
+    let mut exit_status = ExitStatus::SUCCESS;
     for pid in pids {
         // TODO Report if the child was signaled and the shell is interactive
-        env.exit_status = env
+        let status = env
             .wait_for_subshell_to_finish(pid)
             .await
             .expect("cannot receive exit status of child process")
             .1;
+        if !pipe_fail || !status.is_successful() {
+            exit_status = status;
+        }
     }
+    env.exit_status = exit_status;
     Continue(())
 }
 
@@ -219,24 +290,6 @@ async fn shift_or_fail(env: &mut Env, pipes: &mut PipeSet, has_next: bool) -> Re
     }
 }
 
-async fn connect_pipe_and_execute_command(
-    env: &mut Env,
-    pipes: PipeSet,
-    command: Rc<syntax::Command>,
-) -> Result {
-    match pipes.move_to_stdin_stdout(env) {
-        Ok(()) => (),
-        Err(errno) => {
-            // TODO print error location using yash_env::io::print_error
-            let message = format!("cannot connect pipes in the pipeline: {}\n", errno);
-            env.system.print_error(&message).await;
-            return Break(Divert::Interrupt(Some(ExitStatus::NOEXEC)));
-        }
-    }
-
-    command.execute(env).await
-}
-
 async fn pid_or_fail(
     env: &mut Env,
     start_result: std::result::Result<(Pid, Option<JobControl>), Errno>,
@@ -290,37 +343,56 @@ impl PipeSet {
 
         self.next = None;
         if has_next {
-            self.next = Some(env.system.pipe()?);
+            let (reader, writer) = env.system.pipe_cloexec()?;
+            self.next = Some((reader, writer));
+
+            // A pipe end that already occupies its target file descriptor is
+            // not duplicated in the subshell, so it must not be closed on exec.
+            for (fd, target) in [(reader, Fd::STDIN), (writer, Fd::STDOUT)] {
+                if fd == target {
+                    env.system.fcntl_setfd(fd, EnumSet::empty())?;
+                }
+            }
+
+            // The reader must not occupy the standard output, which is to be
+            // replaced with the writer to the next command.
+            if self.read_previous == Some(Fd::STDOUT) {
+                let reader = env.system.dup(Fd::STDOUT, Fd(0), EnumSet::empty())?;
+                env.system.close(Fd::STDOUT)?;
+                self.read_previous = Some(reader);
+            }
         }
 
         Ok(())
     }
 
-    /// Moves the pipe FDs to stdin/stdout and closes the FDs that are no longer
-    /// necessary.
-    fn move_to_stdin_stdout(mut self, env: &mut Env) -> std::result::Result<(), Errno> {
+    /// Makes the subshell move the pipe FDs to stdin/stdout and close the FDs
+    /// that are no longer necessary.
+    fn arrange_fds<F>(&self, mut subshell: Subshell<F>) -> Subshell<F>
+    where
+        F: for<'a> FnOnce(
+                &'a mut Env,
+                Option<JobControl>,
+            ) -> Pin<Box<dyn Future<Output = ()> + 'a>>
+            + 'static,
+    {
         if let Some((reader, writer)) = self.next {
             assert_ne!(reader, writer);
             assert_ne!(self.read_previous, Some(reader));
             assert_ne!(self.read_previous, Some(writer));
+            assert_ne!(self.read_previous, Some(Fd::STDOUT));
 
-            env.system.close(reader)?;
+            subshell = subshell.close_fd(reader);
             if writer != Fd::STDOUT {
-                if self.read_previous == Some(Fd::STDOUT) {
-                    self.read_previous =
-                        Some(env.system.dup(Fd::STDOUT, Fd(0), EnumSet::empty())?);
-                }
-                env.system.dup2(writer, Fd::STDOUT)?;
-                env.system.close(writer)?;
+                subshell = subshell.dup2_fd(writer, Fd::STDOUT).close_fd(writer);
             }
         }
         if let Some(reader) = self.read_previous {
             if reader != Fd::STDIN {
-                env.system.dup2(reader, Fd::STDIN)?;
-                env.system.close(reader)?;
+                subshell = subshell.dup2_fd(reader, Fd::STDIN).close_fd(reader);
             }
         }
-        Ok(())
+        subshell
     }
 }
 
@@ -328,6 +400,7 @@ impl PipeSet {
 mod tests {
     use super::*;
     use crate::tests::cat_builtin;
+    use crate::tests::echo_builtin;
     use crate::tests::return_builtin;
     use crate::tests::suspend_builtin;
     use assert_matches::assert_matches;
@@ -335,6 +408,7 @@ mod tests {
     use std::future::Future;
     use std::pin::Pin;
     use std::rc::Rc;
+    use std::time::Duration;
     use yash_env::builtin::Builtin;
     use yash_env::builtin::Type::Special;
     use yash_env::job::ProcessResult;
@@ -345,7 +419,9 @@ mod tests {
     use yash_env::semantics::Field;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::SIGSTOP;
+    use yash_env::system::FdFlag;
     use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
     use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
     use yash_env_test_helper::stub_tty;
@@ -356,6 +432,7 @@ mod tests {
         let pipeline = syntax::Pipeline {
             commands: vec![],
             negation: false,
+            timed: false,
         };
         let result = pipeline.execute(&mut env).now_or_never().unwrap();
         assert_eq!(result, Continue(()));
@@ -545,6 +622,143 @@ mod tests {
         });
     }
 
+    #[test]
+    fn pipefail_option_off() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+
+            let pipeline: syntax::Pipeline = "return -n 1 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn pipefail_option_on() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.options.set(PipeFail, On);
+
+            let pipeline: syntax::Pipeline = "return -n 1 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(1));
+        });
+    }
+
+    #[test]
+    fn pipefail_option_on_with_all_commands_successful() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.options.set(PipeFail, On);
+            env.exit_status = ExitStatus(5);
+
+            let pipeline: syntax::Pipeline = "return -n 0 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        });
+    }
+
+    #[test]
+    fn pipefail_option_on_with_failing_middle_command() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.options.set(PipeFail, On);
+
+            let pipeline: syntax::Pipeline =
+                "return -n 0 | return -n 42 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(42));
+        });
+    }
+
+    #[test]
+    fn pipefail_option_on_returns_rightmost_failure() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.options.set(PipeFail, On);
+
+            let pipeline: syntax::Pipeline =
+                "return -n 2 | return -n 7 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(7));
+        });
+    }
+
+    fn sleep_builtin() -> Builtin {
+        fn main(
+            env: &mut Env,
+            _args: Vec<Field>,
+        ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+            Box::pin(async move {
+                let target = env.system.now() + Duration::from_millis(1500);
+                env.system.wait_until(target).await;
+                Default::default()
+            })
+        }
+        Builtin::new(Special, main)
+    }
+
+    #[test]
+    fn timed_pipeline() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("sleep", sleep_builtin());
+            state.borrow_mut().now = Some(Instant::now());
+
+            let pipeline: syntax::Pipeline = "time sleep".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "real 1.50\nuser 0.00\nsys 0.00\n")
+            });
+        });
+    }
+
+    #[test]
+    fn timed_negated_pipeline() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("return", return_builtin());
+            state.borrow_mut().now = Some(Instant::now());
+
+            let pipeline: syntax::Pipeline = "time ! return -n 0 | return -n 0".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::FAILURE);
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "real 0.00\nuser 0.00\nsys 0.00\n")
+            });
+        });
+    }
+
+    #[test]
+    fn timed_pipeline_with_divert() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("return", return_builtin());
+
+        let pipeline: syntax::Pipeline = "time return 5".parse().unwrap();
+        let result = pipeline.execute(&mut env).now_or_never().unwrap();
+
+        assert_eq!(result, Break(Divert::Return(Some(ExitStatus(5)))));
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "real 0.00\nuser 0.00\nsys 0.00\n")
+        });
+    }
+
     #[test]
     fn stack_without_inversion() {
         fn stub_builtin(
@@ -651,8 +865,14 @@ mod tests {
         assert_eq!(pipes.next, Some((Fd(3), Fd(4))));
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(4)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
+        assert_eq!(
+            process.fds().get(&Fd(4)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
     }
 
     #[test]
@@ -670,9 +890,18 @@ mod tests {
         assert_eq!(pipes.next, Some((Fd(4), Fd(5))));
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(4)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(5)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
+        assert_eq!(
+            process.fds().get(&Fd(4)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
+        assert_eq!(
+            process.fds().get(&Fd(5)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
     }
 
     #[test]
@@ -690,8 +919,67 @@ mod tests {
         assert_eq!(pipes.next, None);
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            EnumSet::only(FdFlag::CloseOnExec)
+        );
     }
 
-    // TODO test PipeSet::move_to_stdin_stdout
+    #[test]
+    fn pipe_set_shift_moves_reader_off_stdout() {
+        let system = VirtualSystem::new();
+        let process_id = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.system.close(Fd::STDOUT).unwrap();
+        let mut pipes = PipeSet::new();
+
+        let _ = pipes.shift(&mut env, true);
+        assert_eq!(pipes.next, Some((Fd::STDOUT, Fd(3))));
+        let result = pipes.shift(&mut env, true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(pipes.read_previous, Some(Fd(5)));
+        assert_eq!(pipes.next, Some((Fd(3), Fd(4))));
+        let state = state.borrow();
+        let process = &state.processes[&process_id];
+        assert!(!process.fds().contains_key(&Fd::STDOUT));
+    }
+
+    #[test]
+    fn pipe_set_shift_keeps_standard_fds_open_on_exec() {
+        let system = VirtualSystem::new();
+        let process_id = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.system.close(Fd::STDIN).unwrap();
+        env.system.close(Fd::STDOUT).unwrap();
+        let mut pipes = PipeSet::new();
+
+        let result = pipes.shift(&mut env, true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(pipes.next, Some((Fd::STDIN, Fd::STDOUT)));
+        let state = state.borrow();
+        let process = &state.processes[&process_id];
+        let stdin = process.fds().get(&Fd::STDIN).unwrap();
+        assert_eq!(stdin.flags, EnumSet::empty());
+        let stdout = process.fds().get(&Fd::STDOUT).unwrap();
+        assert_eq!(stdout.flags, EnumSet::empty());
+    }
+
+    #[test]
+    fn pipeline_with_closed_stdout() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            env.builtins.insert("echo", echo_builtin());
+            let fd = env.system.dup(Fd::STDOUT, Fd(3), EnumSet::empty()).unwrap();
+            assert_eq!(fd, Fd(3));
+            env.system.close(Fd::STDOUT).unwrap();
+
+            let pipeline: syntax::Pipeline = "echo foo | cat | cat >&3".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "foo\n"));
+        })
+    }
 }
@@ -24,6 +24,7 @@
 use crate::command::Command;
 use crate::command_search::search;
 use crate::expansion::expand_word_with_mode;
+use crate::trap::run_debug_trap;
 use crate::xtrace::XTrace;
 use crate::Handle;
 use std::ffi::CString;
@@ -36,6 +37,7 @@ use yash_env::semantics::Result;
 #[cfg(doc)]
 use yash_env::variable::Context;
 use yash_env::variable::Scope;
+use yash_env::variable::UNDERSCORE;
 use yash_env::Env;
 use yash_syntax::syntax;
 use yash_syntax::syntax::Assign;
@@ -46,12 +48,16 @@ use yash_syntax::syntax::Word;
 ///
 /// # Outline
 ///
-/// The execution starts with the [expansion](crate::expansion) of the command
-/// words. Next, the [command search](crate::command_search) is performed to
-/// find an execution [target](crate::command_search::Target) named by the first
+/// The execution starts with [running the DEBUG trap](run_debug_trap), if any.
+/// Then, the command words are [expanded](crate::expansion). If
+/// [`Env::command_observer`] is set, the observer is notified of the command
+/// and the expanded fields. If there is any field, the last field is assigned
+/// to the `_` variable so that `$_` expands to it in the next command. Next,
+/// the [command search](crate::command_search) is performed to find an
+/// execution [target](crate::command_search::Target) named by the first
 /// [field](Field) of the expansion results. The target type defines how the
-/// target is executed. After the execution, the `ErrExit` option is applied
-/// with [`Env::apply_errexit`].
+/// target is executed. After the execution, the ERR trap is run and the
+/// `ErrExit` option is applied with [`Env::apply_errexit`].
 ///
 /// # Target types and their semantics
 ///
@@ -107,7 +113,8 @@ use yash_syntax::syntax::Word;
 ///
 /// If `execve` fails with an `ENOEXEC` error, it is re-called with the current
 /// executable file so that the restarted shell executes the external utility as
-/// a shell script.
+/// a shell script. If the shell cannot be re-invoked, the subshell reads the
+/// file and executes it as a shell script by itself.
 ///
 /// ## Target not found
 ///
@@ -115,6 +122,13 @@ use yash_syntax::syntax::Word;
 /// in the same manner as an external utility except that it does not call
 /// `execve` and performs error handling as if it failed with `ENOENT`.
 ///
+/// However, if a function named `command_not_found_handler` is defined, the
+/// function is called with the command name and arguments as its positional
+/// parameters instead of reporting the error. The exit status of the handler
+/// becomes that of the simple command. A command that is not found in the
+/// handler does not invoke the handler recursively. See
+/// [`Env::command_not_found_handler`](yash_env::Env::command_not_found_handler).
+///
 /// # Redirections
 ///
 /// Redirections are performed in the order of appearance. The file descriptors
@@ -163,11 +177,24 @@ use yash_syntax::syntax::Word;
 /// detail semantics may differ in other shell implementations.
 impl Command for syntax::SimpleCommand {
     async fn execute(&self, env: &mut Env) -> Result {
+        run_debug_trap(env).await?;
+
         let (fields, exit_status) = match expand_words(env, &self.words).await {
             Ok(result) => result,
             Err(error) => return error.handle(env).await,
         };
 
+        if let Some(observer) = env.command_observer.clone() {
+            observer.before_simple_command(env, self, &fields);
+        }
+
+        if let Some(last) = fields.last() {
+            env.variables
+                .get_or_new(UNDERSCORE, Scope::Global)
+                .assign(last.value.clone(), None)
+                .ok();
+        }
+
         use crate::command_search::Target::{Builtin, External, Function};
         if let Some(name) = fields.first() {
             match search(env, &name.value) {
@@ -190,7 +217,7 @@ impl Command for syntax::SimpleCommand {
             execute_absent_target(env, &self.assigns, &self.redirs, exit_status).await
         }?;
 
-        env.apply_errexit()
+        super::apply_err_trap_and_errexit(env).await
     }
 }
 
@@ -248,12 +275,20 @@ pub use external::to_c_strings;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::echo_builtin;
     use crate::tests::return_builtin;
+    use crate::tests::set_debug_trap;
     use futures_util::FutureExt;
+    use std::cell::RefCell;
     use std::ops::ControlFlow::Break;
+    use std::rc::Rc;
     use yash_env::option::Option::ErrExit;
     use yash_env::option::State::On;
-    use yash_env::semantics::Divert;
+    use yash_env::semantics::{CommandObserver, Divert};
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::variable::Scope;
+    use yash_env_test_helper::assert_stdout;
+    use yash_env_test_helper::in_virtual_system;
 
     #[test]
     fn errexit_on_simple_command() {
@@ -265,4 +300,152 @@ mod tests {
         assert_eq!(result, Break(Divert::Exit(None)));
         assert_eq!(env.exit_status, ExitStatus(93));
     }
+
+    #[test]
+    fn underscore_is_set_to_last_field() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        let command: syntax::SimpleCommand = "echo a b c".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.variables.get_scalar(UNDERSCORE), Some("c"));
+
+        let command: syntax::SimpleCommand = "echo $_ d".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.variables.get_scalar(UNDERSCORE), Some("d"));
+    }
+
+    #[test]
+    fn underscore_expands_to_last_argument_of_previous_command() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let list: syntax::List = "echo a b c; echo $_".parse().unwrap();
+            let result = list.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "a b c\nc\n"));
+        })
+    }
+
+    #[test]
+    fn underscore_is_not_changed_without_fields() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new(UNDERSCORE, Scope::Global)
+            .assign("foo", None)
+            .unwrap();
+        let command: syntax::SimpleCommand = "v=bar".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.variables.get_scalar(UNDERSCORE), Some("foo"));
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        records: RefCell<Vec<(String, Vec<String>)>>,
+    }
+
+    impl CommandObserver for RecordingObserver {
+        fn before_simple_command(
+            &self,
+            _env: &mut Env,
+            command: &syntax::SimpleCommand,
+            fields: &[Field],
+        ) {
+            let fields = fields.iter().map(|f| f.value.clone()).collect();
+            self.records
+                .borrow_mut()
+                .push((command.to_string(), fields));
+        }
+    }
+
+    #[test]
+    fn observer_is_called_with_expanded_fields() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.variables
+            .get_or_new("x", Scope::Global)
+            .assign("42", None)
+            .unwrap();
+        let observer = Rc::new(RecordingObserver::default());
+        env.command_observer = Some(observer.clone());
+
+        let command: syntax::SimpleCommand = "return -n $x".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(42));
+        assert_eq!(
+            *observer.records.borrow(),
+            [(
+                "return -n $x".to_string(),
+                vec!["return".to_string(), "-n".to_string(), "42".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn observer_is_called_for_absent_target() {
+        let mut env = Env::new_virtual();
+        let observer = Rc::new(RecordingObserver::default());
+        env.command_observer = Some(observer.clone());
+
+        let command: syntax::SimpleCommand = "x=1".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(*observer.records.borrow(), [("x=1".to_string(), vec![])]);
+    }
+
+    #[test]
+    fn observer_is_called_once_per_command_in_pipeline() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            let observer = Rc::new(RecordingObserver::default());
+            env.command_observer = Some(observer.clone());
+
+            let pipeline: syntax::Pipeline =
+                "return -n 1 | return -n 2 | return -n 3".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+
+            let mut commands = observer
+                .records
+                .borrow()
+                .iter()
+                .map(|(command, _)| command.clone())
+                .collect::<Vec<_>>();
+            commands.sort();
+            assert_eq!(commands, ["return -n 1", "return -n 2", "return -n 3"]);
+        });
+    }
+
+    #[test]
+    fn debug_trap_is_run_before_each_simple_command() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        set_debug_trap(&mut env, "n=$((n+1))");
+
+        let list: syntax::List = "return -n 3; echo $? >/dev/null; x=1; { echo; echo; }"
+            .parse()
+            .unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.variables.get_scalar("n"), Some("5"));
+    }
+
+    #[test]
+    fn debug_trap_sees_exit_status_of_previous_command() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        set_debug_trap(&mut env, "echo DEBUG $?");
+
+        let list: syntax::List = "return -n 3; echo $?".parse().unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| {
+            assert_eq!(stdout, "DEBUG 0\nDEBUG 3\n3\n")
+        });
+    }
 }
@@ -16,27 +16,43 @@
 
 //! Simple command semantics for external utilities
 
+use super::execute_function_body;
 use super::perform_assignments;
+use crate::read_eval_loop;
 use crate::redir::RedirGuard;
 use crate::xtrace::print;
 use crate::xtrace::trace_fields;
 use crate::xtrace::XTrace;
 use crate::Handle;
 use itertools::Itertools;
+use std::cell::RefCell;
 use std::ffi::CString;
-use std::ops::ControlFlow::Continue;
+use std::ops::ControlFlow::{Break, Continue};
+use std::rc::Rc;
+use yash_env::function::Function;
+use yash_env::input::Echo;
+use yash_env::input::FdReader;
 use yash_env::io::print_error;
 use yash_env::job::Job;
+use yash_env::option::Option::Restricted;
+use yash_env::option::State::On;
+use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::semantics::Result;
+use yash_env::stack::Frame;
 use yash_env::subshell::JobControl;
 use yash_env::subshell::Subshell;
 use yash_env::system::Errno;
+use yash_env::system::Mode;
+use yash_env::system::OfdAccess;
+use yash_env::system::OpenFlag;
+use yash_env::system::SystemEx;
 use yash_env::variable::Context;
 use yash_env::Env;
 use yash_env::System;
 use yash_syntax::source::Location;
+use yash_syntax::source::Source;
 use yash_syntax::syntax::Assign;
 use yash_syntax::syntax::Redir;
 
@@ -61,6 +77,10 @@ pub async fn execute_external_utility(
     print(&mut env, xtrace).await;
 
     if path.to_bytes().is_empty() {
+        if let Some(handler) = env.command_not_found_handler() {
+            return execute_command_not_found_handler(&mut env, handler, fields).await;
+        }
+
         let name = &fields[0];
         print_error(
             &mut env,
@@ -78,6 +98,26 @@ pub async fn execute_external_utility(
     Continue(())
 }
 
+/// Calls the command-not-found handler function.
+///
+/// The handler is called with the command name and arguments as its
+/// positional parameters. The exit status of the handler becomes that of the
+/// command.
+async fn execute_command_not_found_handler(
+    env: &mut Env,
+    handler: Rc<Function>,
+    mut fields: Vec<Field>,
+) -> Result {
+    let handler_name = Field {
+        value: handler.name.clone(),
+        origin: fields[0].origin.clone(),
+    };
+    fields.insert(0, handler_name);
+
+    let mut env = env.push_frame(Frame::CommandNotFoundHandler);
+    execute_function_body(&mut env, handler, fields, |_| ()).await
+}
+
 /// Starts an external utility in a subshell and waits for it to finish.
 ///
 /// `path` is the path to the external utility. `fields` are the command line
@@ -105,7 +145,9 @@ pub async fn start_external_utility_in_subshell_and_wait(
     };
     let args = to_c_strings(fields);
     let subshell = Subshell::new(move |env, _job_control| {
-        Box::pin(replace_current_process(env, path, args, location))
+        Box::pin(async move {
+            _ = replace_current_process(env, path, args, location).await;
+        })
     })
     .job_control(JobControl::Foreground);
 
@@ -163,13 +205,41 @@ pub fn to_c_strings(s: Vec<Field>) -> Vec<CString> {
 /// If the `execve` call fails with `ENOEXEC`, this function falls back on
 /// invoking the shell with the given arguments, so that the shell can interpret
 /// the script. The path to the shell executable is taken from
-/// [`System::shell_path`].
+/// [`System::shell_path`]. If the shell cannot be invoked either, this function
+/// reads the file and executes it as a shell script in the current process,
+/// with the environment reset as if a new shell were invoked and the
+/// positional parameters set to the rest of the arguments. In this
+/// case, `env.exit_status` is set to the exit status of the script, and this
+/// function returns `Break(Divert::Exit(None))`. The caller must then exit the
+/// current process with the exit status even if the shell is interactive
+/// because the environment no longer reflects the state before the call.
+/// Otherwise, this function returns `Continue(())`.
+///
+/// If the [`Restricted`] option is on and the command name (`args[0]`)
+/// contains a slash, this function does not call `execve` but prints an error
+/// message and sets `env.exit_status` to [`ExitStatus::NOEXEC`].
 pub async fn replace_current_process(
     env: &mut Env,
     path: CString,
     args: Vec<CString>,
     location: Location,
-) {
+) -> Result {
+    if env.options.get(Restricted) == On
+        && args
+            .first()
+            .is_some_and(|name| name.as_bytes().contains(&b'/'))
+    {
+        env.exit_status = ExitStatus::NOEXEC;
+        print_error(
+            env,
+            format!("cannot execute external utility {path:?}").into(),
+            "restricted shell cannot run a command specified by pathname".into(),
+            &location,
+        )
+        .await;
+        return Continue(());
+    }
+
     env.traps
         .disable_internal_dispositions(&mut env.system)
         .ok();
@@ -180,7 +250,10 @@ pub async fn replace_current_process(
     let errno = result.unwrap_err();
     match errno {
         Errno::ENOEXEC => {
-            fall_back_on_sh(&mut env.system, path.clone(), args, envs);
+            fall_back_on_sh(&mut env.system, path.clone(), args.clone(), envs);
+            if interpret_script(env, path.clone(), args).await.is_ok() {
+                return Break(Divert::Exit(None));
+            }
             env.exit_status = ExitStatus::NOEXEC;
         }
         Errno::ENOENT | Errno::ENOTDIR => {
@@ -197,6 +270,7 @@ pub async fn replace_current_process(
         &location,
     )
     .await;
+    Continue(())
 }
 
 /// Invokes the shell with the given arguments.
@@ -223,23 +297,104 @@ fn fall_back_on_sh<S: System>(
     let _ = system.execve(&sh_path, &args, &envs);
 }
 
+/// Executes the script file in the current process.
+///
+/// This function reads the file at `path` and executes it as a shell script.
+/// The environment is reset to that of a newly started non-interactive shell
+/// (see [`reset_to_new_shell`]). `args[0]` is ignored and the rest of `args`
+/// become the positional parameters. Returns an error if the file cannot be
+/// opened.
+async fn interpret_script(
+    env: &mut Env,
+    path: CString,
+    mut args: Vec<CString>,
+) -> std::result::Result<(), Errno> {
+    let system = &mut env.system;
+    let fd = system
+        .open(
+            &path,
+            OfdAccess::ReadOnly,
+            OpenFlag::CloseOnExec.into(),
+            Mode::empty(),
+        )
+        .and_then(|fd| system.move_fd_internal(fd))?;
+
+    let path = path.to_string_lossy().into_owned();
+    reset_to_new_shell(env);
+    env.arg0.clone_from(&path);
+    env.init_variables();
+    env.variables.positional_params_mut().values = args
+        .drain(1..)
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+
+    let system = env.system.clone();
+    let mut config = env.lexer_config();
+    let ref_env = RefCell::new(&mut *env);
+    config.source = Some(Rc::new(Source::CommandFile { path }));
+    let input = Box::new(Echo::new(FdReader::new(fd, system), &ref_env));
+    let lexer = config.input(input);
+    let result = read_eval_loop(&ref_env, &mut { lexer }).await;
+
+    _ = env.system.close(fd);
+    if let Break(divert) = result {
+        if let Some(exit_status) = divert.exit_status() {
+            env.exit_status = exit_status;
+        }
+    }
+    Ok(())
+}
+
+/// Resets the environment as if a new shell process were started.
+///
+/// Exported variables are imported again as environment variables, and all
+/// the other variables are removed. Functions, aliases, jobs, and options are
+/// reset to the defaults of a non-interactive shell, and traps other than
+/// ignored ones are reset. The caller should set `env.arg0` and call
+/// [`Env::init_variables`] after this function.
+fn reset_to_new_shell(env: &mut Env) {
+    let exported = env.variables.env_c_strings();
+    env.variables.clear();
+    env.variables.extend_env(
+        exported
+            .iter()
+            .filter_map(|var| var.to_str().ok()?.split_once('=')),
+    );
+
+    env.aliases.clear();
+    env.command_paths.clear();
+    env.exit_status = ExitStatus::SUCCESS;
+    env.functions = Default::default();
+    env.getopts_state = None;
+    env.jobs = Default::default();
+    env.main_pgid = env.system.getpgrp();
+    env.main_pid = env.system.getpid();
+    env.options = Default::default();
+    env.traps.enter_subshell(&mut env.system, false, false);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::command::Command;
+    use crate::tests::echo_builtin;
+    use crate::tests::exit_builtin;
+    use crate::tests::return_builtin;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
     use std::cell::RefCell;
     use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
     use std::str::from_utf8;
-    use yash_env::option::State::On;
+    use std::time::Instant;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::Inode;
     use yash_env::system::Mode;
     use yash_env::variable::Scope;
     use yash_env::variable::Value;
+    use yash_env::VirtualSystem;
     use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
     use yash_env_test_helper::stub_tty;
     use yash_syntax::syntax;
@@ -351,6 +506,10 @@ mod tests {
     fn simple_command_returns_126_on_exec_failure() {
         in_virtual_system(|mut env, state| async move {
             let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
             content.permissions.set(Mode::USER_EXEC, true);
             let content = Rc::new(RefCell::new(content));
             state
@@ -366,6 +525,120 @@ mod tests {
         });
     }
 
+    #[test]
+    fn command_name_with_slash_in_restricted_shell() {
+        in_virtual_system(|mut env, state| async move {
+            let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/file", content)
+                .unwrap();
+            env.options.set(Restricted, On);
+
+            let command: syntax::SimpleCommand = "/some/file".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::NOEXEC);
+
+            {
+                let state = state.borrow();
+                let process = state.processes.values().last().unwrap();
+                assert_eq!(process.last_exec(), &None);
+            }
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("restricted"), "{stderr}")
+            });
+        });
+    }
+
+    #[test]
+    fn non_executable_file_is_executed_as_script() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.builtins.insert("echo", echo_builtin());
+            let mut content = Inode::new("echo $0 \"$@\"\necho $#\n");
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/script", content)
+                .unwrap();
+
+            let command: syntax::SimpleCommand = "/some/script foo 'bar  baz'".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| {
+                assert_eq!(stdout, "/some/script foo bar  baz\n2\n")
+            });
+        });
+    }
+
+    #[test]
+    fn exit_status_of_script_executed_as_fallback() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.builtins.insert("exit", exit_builtin());
+            let mut content = Inode::new("exit 42\nexit 1\n");
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/script", content)
+                .unwrap();
+
+            let command: syntax::SimpleCommand = "/some/script".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(42));
+        });
+    }
+
+    #[test]
+    fn script_executed_as_fallback_runs_in_new_shell_environment() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.builtins.insert("echo", echo_builtin());
+            let function = Function::new(
+                "echo",
+                "{ :; }".parse::<syntax::FullCompoundCommand>().unwrap(),
+                Location::dummy("function"),
+            );
+            env.functions.define(function).unwrap();
+            env.variables
+                .get_or_new("local", Scope::Global)
+                .assign("LOCAL", None)
+                .unwrap();
+            let mut exported = env.variables.get_or_new("exported", Scope::Global);
+            exported.assign("EXPORTED", None).unwrap();
+            exported.export(true);
+            env.variables.positional_params_mut().values = vec!["param".to_string()];
+            let mut content = Inode::new("echo \"${local-unset}\" \"${exported-unset}\" \"$#\"\n");
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/script", content)
+                .unwrap();
+
+            let command: syntax::SimpleCommand = "/some/script".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "unset EXPORTED 0\n"));
+        });
+    }
+
     #[test]
     fn simple_command_returns_126_on_fork_failure() {
         let mut env = Env::new_virtual();
@@ -384,6 +657,52 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
     }
 
+    fn define_command_not_found_handler(env: &mut Env, body: &str) {
+        let function = Function::new(
+            yash_env::function::COMMAND_NOT_FOUND_HANDLER,
+            body.parse::<syntax::FullCompoundCommand>().unwrap(),
+            Location::dummy("handler"),
+        );
+        env.functions.define(function).unwrap();
+    }
+
+    #[test]
+    fn command_not_found_handler_is_called_with_command_and_arguments() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        define_command_not_found_handler(&mut env, "{ echo \"$#\" \"$@\"; return -n 42; }");
+        let command: syntax::SimpleCommand = "no_such_command foo 'bar  baz'".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(42));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "3 no_such_command foo bar  baz\n")
+        });
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn command_not_found_handler_is_not_called_recursively() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        define_command_not_found_handler(&mut env, "{ echo \"$1\"; another_missing_command; }");
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "no_such_command\n"));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("another_missing_command"), "{stderr}")
+        });
+    }
+
     #[test]
     fn simple_command_assigns_variables_in_volatile_context_for_external_utility() {
         in_virtual_system(|mut env, _state| async move {
@@ -42,6 +42,7 @@ use yash_env::builtin::Builtin;
 use yash_env::builtin::Type::{Elective, Extension, Mandatory, Special, Substitutive};
 use yash_env::function::Function;
 use yash_env::path::PathBuf;
+use yash_env::str::UnixString;
 use yash_env::variable::Expansion;
 use yash_env::variable::PATH;
 use yash_env::Env;
@@ -117,7 +118,27 @@ pub trait PathEnv {
     /// Whether there is an executable file at the specified path.
     #[must_use]
     fn is_executable_file(&self, path: &CStr) -> bool;
-    // TODO Cache the results of external utility search
+
+    /// Remembers the path to an external utility found by [`search_path`].
+    ///
+    /// This function is called with the command name and the path to the
+    /// executable file found for it. The implementation may record the pair
+    /// for later inspection, as in the `hash` built-in.
+    fn remember_path(&mut self, name: &str, path: &CStr);
+
+    /// Returns the path to an external utility remembered in a cache.
+    ///
+    /// [`search_path`] calls this function before scanning `$PATH`. If this
+    /// function returns a path that names an executable file, the path is
+    /// used without scanning `$PATH`. The implementation is responsible for
+    /// invalidating the cache when `$PATH` changes.
+    ///
+    /// The default implementation returns `None`, that is, the cache is
+    /// disabled.
+    fn cached_path(&mut self, name: &str) -> Option<CString> {
+        let _ = name;
+        None
+    }
 }
 
 /// Part of the shell execution environment command search depends on.
@@ -149,6 +170,27 @@ impl PathEnv for Env {
     fn is_executable_file(&self, path: &CStr) -> bool {
         self.system.is_executable_file(path)
     }
+
+    /// Records the path in [`Env::command_paths`].
+    fn remember_path(&mut self, name: &str, path: &CStr) {
+        let path = UnixString::from_vec(path.to_bytes().to_vec());
+        self.command_paths.insert(name.to_owned(), path.into());
+    }
+
+    /// Looks up [`Env::command_paths`] if [`Env::command_path_cache`] is
+    /// enabled.
+    ///
+    /// If `$PATH` has changed since the last call, this function clears
+    /// `command_paths` and returns `None`. See
+    /// [`Env::invalidate_command_paths_on_path_change`].
+    fn cached_path(&mut self, name: &str) -> Option<CString> {
+        if !self.command_path_cache || self.invalidate_command_paths_on_path_change() {
+            return None;
+        }
+
+        let path = self.command_paths.get(name)?;
+        CString::new(path.as_unix_str().as_bytes()).ok()
+    }
 }
 
 impl SearchEnv for Env {
@@ -164,10 +206,9 @@ impl SearchEnv for Env {
 
 /// Performs command search.
 ///
-/// This function requires a mutable reference to the environment because it may
-/// need to update a cache of the results of external utility search (TODO:
-/// which is not yet implemented). The function does not otherwise modify the
-/// environment.
+/// This function requires a mutable reference to the environment because it
+/// [remembers](PathEnv::remember_path) the results of external utility search.
+/// The function does not otherwise modify the environment.
 ///
 /// If the given name contains a slash, the function immediately returns an
 /// external utility target, regardless of whether the named external utility
@@ -217,8 +258,22 @@ pub fn search<E: SearchEnv>(env: &mut E, name: &str) -> Option<Target> {
 ///
 /// Returns the path to the executable if found. Note that the returned path may
 /// not be absolute if the `$PATH` contains a relative path.
+///
+/// The found path is passed to [`PathEnv::remember_path`] before being
+/// returned.
+///
+/// Before scanning `$PATH`, this function consults the cache by calling
+/// [`PathEnv::cached_path`]. If the cache returns a path to an executable
+/// file, the path is returned without scanning `$PATH`.
 pub fn search_path<E: PathEnv>(env: &mut E, name: &str) -> Option<CString> {
-    env.path()
+    if let Some(path) = env.cached_path(name) {
+        if env.is_executable_file(&path) {
+            return Some(path);
+        }
+    }
+
+    let path = env
+        .path()
         .split()
         .filter_map(|dir| {
             let candidate = PathBuf::from_iter([dir, name])
@@ -226,7 +281,9 @@ pub fn search_path<E: PathEnv>(env: &mut E, name: &str) -> Option<CString> {
                 .into_vec();
             CString::new(candidate).ok()
         })
-        .find(|path| env.is_executable_file(path))
+        .find(|path| env.is_executable_file(path))?;
+    env.remember_path(name, &path);
+    Some(path)
 }
 
 #[allow(clippy::field_reassign_with_default)]
@@ -234,9 +291,15 @@ pub fn search_path<E: PathEnv>(env: &mut E, name: &str) -> Option<CString> {
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
+    use std::cell::RefCell;
     use std::collections::HashMap;
     use std::collections::HashSet;
     use yash_env::function::FunctionSet;
+    use yash_env::system::r#virtual::Inode;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::Mode;
+    use yash_env::variable::Scope;
     use yash_env::variable::Value;
     use yash_syntax::source::Location;
     use yash_syntax::syntax::CompoundCommand;
@@ -248,6 +311,7 @@ mod tests {
         functions: FunctionSet,
         path: Expansion<'static>,
         executables: HashSet<String>,
+        remembered_paths: Vec<(String, String)>,
     }
 
     impl PathEnv for DummyEnv {
@@ -261,6 +325,10 @@ mod tests {
                 false
             }
         }
+        fn remember_path(&mut self, name: &str, path: &CStr) {
+            let path = path.to_str().unwrap().to_owned();
+            self.remembered_paths.push((name.to_owned(), path));
+        }
     }
 
     impl SearchEnv for DummyEnv {
@@ -556,4 +624,157 @@ mod tests {
             assert_eq!(path.to_bytes(), "foo".as_bytes());
         });
     }
+
+    #[test]
+    fn found_external_utility_is_remembered() {
+        let mut env = DummyEnv::default();
+        env.path = Expansion::from("/usr/bin:/bin");
+        env.executables.insert("/bin/foo".to_string());
+
+        search(&mut env, "foo");
+        assert_eq!(
+            env.remembered_paths,
+            [("foo".to_string(), "/bin/foo".to_string())]
+        );
+    }
+
+    #[test]
+    fn nothing_is_remembered_if_not_found() {
+        let mut env = DummyEnv::default();
+        env.path = Expansion::from("/usr/bin:/bin");
+
+        search(&mut env, "foo");
+        search(&mut env, "bar/foo");
+        assert_eq!(env.remembered_paths, []);
+    }
+
+    #[test]
+    fn env_remembers_path_in_command_paths() {
+        let mut env = Env::new_virtual();
+        env.remember_path("foo", c"/bin/foo");
+        assert_eq!(
+            env.command_paths.get("foo").map(|path| path.as_unix_str()),
+            Some(UnixString::from("/bin/foo").as_unix_str())
+        );
+    }
+
+    fn env_with_path(path: &str) -> (Env, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign(path, None)
+            .unwrap();
+        (env, state)
+    }
+
+    fn save_executable(state: &RefCell<SystemState>, path: &str) {
+        let mut content = Inode::default();
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        state.borrow_mut().file_system.save(path, content).unwrap();
+    }
+
+    #[test]
+    fn env_rescans_path_without_cache() {
+        let (mut env, state) = env_with_path("/usr/bin:/bin");
+        save_executable(&state, "/bin/foo");
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/bin/foo"
+        );
+
+        save_executable(&state, "/usr/bin/foo");
+        let path = search_path(&mut env, "foo").unwrap();
+        assert_eq!(path.as_c_str(), c"/usr/bin/foo");
+    }
+
+    #[test]
+    fn env_uses_cache_without_rescanning_path() {
+        let (mut env, state) = env_with_path("/usr/bin:/bin");
+        env.command_path_cache = true;
+        save_executable(&state, "/bin/foo");
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/bin/foo"
+        );
+
+        // The new executable is not found because $PATH is not scanned again.
+        save_executable(&state, "/usr/bin/foo");
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/bin/foo"
+        );
+    }
+
+    #[test]
+    fn env_rescans_path_if_cached_file_is_gone() {
+        let (mut env, state) = env_with_path("/usr/bin:/bin");
+        env.command_path_cache = true;
+        env.remember_path("foo", c"/usr/bin/foo");
+        save_executable(&state, "/bin/foo");
+
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/bin/foo"
+        );
+        assert_eq!(
+            env.command_paths.get("foo"),
+            Some(&PathBuf::from("/bin/foo"))
+        );
+    }
+
+    #[test]
+    fn env_invalidates_cache_on_path_change() {
+        let (mut env, state) = env_with_path("/usr/bin:/bin");
+        env.command_path_cache = true;
+        save_executable(&state, "/bin/foo");
+        save_executable(&state, "/usr/bin/foo");
+        env.remember_path("foo", c"/bin/foo");
+        env.remember_path("bar", c"/bin/bar");
+
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/usr/bin:/bin:/sbin", None)
+            .unwrap();
+        let path = search_path(&mut env, "foo").unwrap();
+        assert_eq!(path.as_c_str(), c"/usr/bin/foo");
+        assert!(!env.command_paths.contains_key("bar"));
+    }
+
+    #[test]
+    fn env_invalidates_cache_on_path_reassignment_with_same_value() {
+        let (mut env, state) = env_with_path("/usr/bin:/bin");
+        env.command_path_cache = true;
+        save_executable(&state, "/bin/foo");
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/bin/foo"
+        );
+
+        // Assigning $PATH notifies the hook even if the value is the same,
+        // so the new executable is found by scanning $PATH again.
+        save_executable(&state, "/usr/bin/foo");
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/usr/bin:/bin", None)
+            .unwrap();
+        assert_eq!(
+            search_path(&mut env, "foo").unwrap().as_c_str(),
+            c"/usr/bin/foo"
+        );
+    }
+
+    #[test]
+    fn env_invalidates_cache_on_path_unset() {
+        let (mut env, state) = env_with_path("/bin");
+        env.command_path_cache = true;
+        save_executable(&state, "/bin/foo");
+        assert!(search_path(&mut env, "foo").is_some());
+
+        env.variables.unset(PATH, Scope::Global).unwrap();
+        assert_eq!(search_path(&mut env, "foo"), None);
+        assert!(env.command_paths.is_empty());
+    }
 }
@@ -43,8 +43,9 @@
 //!
 //! ## Brace expansion
 //!
-//! The brace expansion produces copies of a field containing a pair of braces.
-//! (TODO: This feature is not yet implemented.)
+//! The [brace expansion](brace) produces copies of a field containing a pair
+//! of braces. This operation is performed only if the
+//! [`BraceExpand`](yash_env::option::Option::BraceExpand) option is on.
 //!
 //! ## Field splitting
 //!
@@ -70,6 +71,7 @@
 
 pub mod attr;
 pub mod attr_strip;
+pub mod brace;
 pub mod glob;
 pub mod initial;
 pub mod phrase;
@@ -92,6 +94,8 @@ use self::quote_removal::skip_quotes;
 use self::split::Ifs;
 use std::borrow::Cow;
 use thiserror::Error;
+use yash_env::option::Option::BraceExpand;
+use yash_env::option::State::On;
 use yash_env::semantics::ExitStatus;
 use yash_env::system::Errno;
 use yash_env::variable::Value;
@@ -135,6 +139,14 @@ pub enum ErrorCause {
     #[error("error in command substitution: {0}")]
     CommandSubstError(Errno),
 
+    /// System error while performing a process substitution.
+    #[error("error in process substitution: {0}")]
+    ProcessSubstError(Errno),
+
+    /// Process substitution while the `procsubst` option is off.
+    #[error("process substitution is disabled")]
+    ProcessSubstDisabled,
+
     /// Error while evaluating an arithmetic expansion.
     #[error(transparent)]
     ArithError(#[from] ArithError),
@@ -164,6 +176,8 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_) => "error performing the command substitution",
+            ProcessSubstError(_) => "error performing the process substitution",
+            ProcessSubstDisabled => "process substitution is disabled",
             ArithError(_) => "error evaluating the arithmetic expansion",
             AssignReadOnly(_) => "error assigning to variable",
             UnsetParameter { .. } => "cannot expand unset parameter",
@@ -179,6 +193,8 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(e) => e.to_string(),
+            ProcessSubstError(e) => e.to_string(),
+            ProcessSubstDisabled => "process substitution used here".to_string(),
             ArithError(e) => e.to_string(),
             AssignReadOnly(e) => e.to_string(),
             UnsetParameter { param } => format!("parameter `{param}` is not set"),
@@ -203,6 +219,7 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_) => None,
+            ProcessSubstError(_) | ProcessSubstDisabled => None,
             ArithError(e) => e.related_location(),
             AssignReadOnly(e) => Some((
                 &e.read_only_location,
@@ -220,12 +237,14 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_)
+            | ProcessSubstError(_)
             | ArithError(_)
             | AssignReadOnly(_)
             | VacantExpansion(_)
             | NonassignableParameter(_) => None,
 
             UnsetParameter { .. } => Some("unset parameters are disallowed by the nounset option"),
+            ProcessSubstDisabled => Some("process substitution is enabled by the procsubst option"),
         }
     }
 }
@@ -260,6 +279,8 @@ impl MessageBase for Error {
         // Report the vacancy that caused the assignment that led to the error.
         let vacancy = match &self.cause {
             ErrorCause::CommandSubstError(_) => None,
+            ErrorCause::ProcessSubstError(_) => None,
+            ErrorCause::ProcessSubstDisabled => None,
             ErrorCause::ArithError(_) => None,
             ErrorCause::AssignReadOnly(e) => e.vacancy,
             ErrorCause::UnsetParameter { .. } => None,
@@ -388,7 +409,18 @@ where
     // initial expansion //
     let phrase = word.expand(&mut env).await?;
 
-    // TODO brace expansion //
+    // brace expansion //
+    let mut fields = Vec::with_capacity(phrase.field_count());
+    let brace_expand = env.inner.options.get(BraceExpand) == On;
+    for chars in phrase {
+        let origin = word.location.clone();
+        let attr_field = AttrField { chars, origin };
+        if brace_expand {
+            brace::expand_into(attr_field, &mut fields);
+        } else {
+            fields.push(attr_field);
+        }
+    }
 
     // field splitting //
     let ifs = env
@@ -397,10 +429,8 @@ where
         .get_scalar(IFS)
         .map(Ifs::new)
         .unwrap_or_default();
-    let mut split_fields = Vec::with_capacity(phrase.field_count());
-    for chars in phrase {
-        let origin = word.location.clone();
-        let attr_field = AttrField { chars, origin };
+    let mut split_fields = Vec::with_capacity(fields.len());
+    for attr_field in fields {
         split::split_into(attr_field, &ifs, &mut split_fields);
     }
     drop(ifs);
@@ -642,6 +672,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_word_multiple_performs_brace_expansion_if_enabled() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(BraceExpand, On);
+        let word = "a{b,c{1..3..2}}'{x,y}'".parse().unwrap();
+        let mut fields = Vec::new();
+        let exit_status = expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(exit_status, None);
+        let values = fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(values, ["ab{x,y}", "ac1{x,y}", "ac3{x,y}"]);
+    }
+
+    #[test]
+    fn expand_word_multiple_skips_brace_expansion_if_disabled() {
+        let mut env = yash_env::Env::new_virtual();
+        let word = "a{b,c}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => {
+            assert_eq!(f.value, "a{b,c}");
+        });
+    }
+
+    #[test]
+    fn brace_expansion_ignores_braces_from_parameter_expansion() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(BraceExpand, On);
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign("{b,c}", None)
+            .unwrap();
+        let word = "a$v".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => {
+            assert_eq!(f.value, "a{b,c}");
+        });
+    }
+
     #[test]
     fn expand_words_returns_exit_status_of_last_command_substitution() {
         in_virtual_system(|mut env, _state| async move {
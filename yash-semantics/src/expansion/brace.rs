@@ -0,0 +1,464 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Brace expansion
+//!
+//! The brace expansion produces copies of a field containing a pair of braces.
+//! There are two forms of brace expansion:
+//!
+//! - A comma-separated list of alternatives, as in `pre{a,b,c}post`, yields a
+//!   copy of the field for each alternative (`preapost`, `prebpost`,
+//!   `precpost`). An alternative may be empty and may contain another brace
+//!   expansion, as in `{a,{b,c}}`.
+//! - A sequence, as in `{1..5}` or `{a..e}`, yields a copy of the field for
+//!   each integer or character in the range. The start and end of the range
+//!   must be both integers or both single non-digit characters. An optional
+//!   step can be given as a third integer, as in `{1..10..2}`. The sign of the
+//!   step is ignored; the sequence is descending if the start is greater than
+//!   the end.
+//!
+//! Only [unquoted characters](AttrChar) having a `Literal` [origin](Origin)
+//! are recognized as braces, commas, and sequence operands. Braces resulting
+//! from the initial expansion or quoted by a backslash are not subject to
+//! brace expansion. A brace that does not make a valid brace expansion
+//! remains in the field intact.
+//!
+//! Characters produced by a sequence have the `HardExpansion` origin, so they
+//! are not subject to field splitting or pathname expansion.
+//!
+//! # Example
+//!
+//! ```
+//! use yash_syntax::source::Location;
+//! use yash_semantics::expansion::attr::{AttrChar, AttrField, Origin};
+//! use yash_semantics::expansion::brace::expand;
+//!
+//! fn field(s: &str) -> AttrField {
+//!     let chars = s.chars()
+//!         .map(|c| AttrChar {
+//!             value: c,
+//!             origin: Origin::Literal,
+//!             is_quoted: false,
+//!             is_quoting: false,
+//!         })
+//!         .collect();
+//!     let origin = Location::dummy(s);
+//!     AttrField { chars, origin }
+//! }
+//!
+//! fn values(fields: Vec<AttrField>) -> Vec<String> {
+//!     fields
+//!         .into_iter()
+//!         .map(|f| f.chars.iter().map(|c| c.value).collect())
+//!         .collect()
+//! }
+//!
+//! let fields: Vec<AttrField> = expand(field("a{b,c}d"));
+//! assert_eq!(values(fields), ["abd", "acd"]);
+//!
+//! let fields: Vec<AttrField> = expand(field("{1..9..3}"));
+//! assert_eq!(values(fields), ["1", "4", "7"]);
+//!
+//! let fields: Vec<AttrField> = expand(field("{a}"));
+//! assert_eq!(values(fields), ["{a}"]);
+//! ```
+
+use super::attr::AttrChar;
+use super::attr::AttrField;
+use super::attr::Origin;
+
+/// Tests whether the character is an unquoted literal `value`.
+fn is_syntactic(c: &AttrChar, value: char) -> bool {
+    c.value == value && c.origin == Origin::Literal && !c.is_quoted && !c.is_quoting
+}
+
+/// Valid pair of braces found in a field
+#[derive(Debug)]
+struct Group {
+    /// Index of the opening brace
+    open: usize,
+    /// Index of the closing brace
+    close: usize,
+    /// Strings that replace the braces and their content
+    alternatives: Vec<Vec<AttrChar>>,
+}
+
+/// Finds the first valid pair of braces in the characters.
+fn find_group(chars: &[AttrChar]) -> Option<Group> {
+    let mut start = 0;
+    while let Some(offset) = chars[start..].iter().position(|c| is_syntactic(c, '{')) {
+        let open = start + offset;
+        if let Some(group) = parse_group(chars, open) {
+            return Some(group);
+        }
+        start = open + 1;
+    }
+    None
+}
+
+/// Parses the brace expansion starting at the opening brace at `open`.
+fn parse_group(chars: &[AttrChar], open: usize) -> Option<Group> {
+    let mut depth = 0_usize;
+    let mut commas = Vec::new();
+    let mut close = None;
+    for (index, c) in chars.iter().enumerate().skip(open) {
+        if is_syntactic(c, '{') {
+            depth += 1;
+        } else if is_syntactic(c, '}') {
+            depth -= 1;
+            if depth == 0 {
+                close = Some(index);
+                break;
+            }
+        } else if depth == 1 && is_syntactic(c, ',') {
+            commas.push(index);
+        }
+    }
+    let close = close?;
+
+    let alternatives = if commas.is_empty() {
+        parse_sequence(&chars[open + 1..close])?
+    } else {
+        let mut alternatives = Vec::with_capacity(commas.len() + 1);
+        let mut start = open + 1;
+        for end in commas.into_iter().chain(std::iter::once(close)) {
+            alternatives.push(chars[start..end].to_vec());
+            start = end + 1;
+        }
+        alternatives
+    };
+
+    Some(Group {
+        open,
+        close,
+        alternatives,
+    })
+}
+
+/// Parses the content of a sequence brace expansion.
+///
+/// Returns `None` if the content is not a valid sequence.
+fn parse_sequence(chars: &[AttrChar]) -> Option<Vec<Vec<AttrChar>>> {
+    if chars
+        .iter()
+        .any(|c| c.origin != Origin::Literal || c.is_quoted || c.is_quoting)
+    {
+        return None;
+    }
+
+    let content = chars.iter().map(|c| c.value).collect::<String>();
+    let mut operands = content.split("..");
+    let start = operands.next()?;
+    let end = operands.next()?;
+    let step = match operands.next() {
+        None => 1,
+        Some(step) => usize::try_from(step.parse::<i64>().ok()?.unsigned_abs())
+            .ok()?
+            .max(1),
+    };
+    if operands.next().is_some() {
+        return None;
+    }
+
+    let values: Vec<String> = match (start.parse(), end.parse()) {
+        (Ok(start), Ok(end)) => range::<i64>(start, end, step)
+            .map(|i| i.to_string())
+            .collect(),
+        (Err(_), Err(_)) => {
+            let (start, end) = (single_char(start)?, single_char(end)?);
+            range(u32::from(start), u32::from(end), step)
+                .filter_map(char::from_u32)
+                .map(String::from)
+                .collect()
+        }
+        _ => return None,
+    };
+
+    let to_chars = |value: String| {
+        value
+            .chars()
+            .map(|value| AttrChar {
+                value,
+                origin: Origin::HardExpansion,
+                is_quoted: false,
+                is_quoting: false,
+            })
+            .collect()
+    };
+    Some(values.into_iter().map(to_chars).collect())
+}
+
+/// Returns the only character of the string.
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+/// Returns an iterator from `start` to `end` (inclusive) by `step`.
+fn range<T>(start: T, end: T, step: usize) -> Box<dyn Iterator<Item = T>>
+where
+    T: Ord + 'static,
+    std::ops::RangeInclusive<T>: DoubleEndedIterator<Item = T>,
+{
+    if start <= end {
+        Box::new((start..=end).step_by(step))
+    } else {
+        Box::new((end..=start).rev().step_by(step))
+    }
+}
+
+/// Expands all brace expansions in the characters.
+fn expand_chars(chars: &[AttrChar]) -> Vec<Vec<AttrChar>> {
+    let Some(group) = find_group(chars) else {
+        return vec![chars.to_vec()];
+    };
+
+    let prefix = &chars[..group.open];
+    let suffixes = expand_chars(&chars[group.close + 1..]);
+    let mut results = Vec::new();
+    for alternative in group.alternatives {
+        for middle in expand_chars(&alternative) {
+            for suffix in &suffixes {
+                let mut result = Vec::with_capacity(prefix.len() + middle.len() + suffix.len());
+                result.extend_from_slice(prefix);
+                result.extend_from_slice(&middle);
+                result.extend_from_slice(suffix);
+                results.push(result);
+            }
+        }
+    }
+    results
+}
+
+/// Performs brace expansion and appends the result to a collection.
+///
+/// This function applies brace expansion to the given field and extends the
+/// given collection with the results. The resultant fields share the same
+/// origin as the input field. If the field does not contain a valid brace
+/// expansion, the field is added to the collection intact.
+///
+/// See also [`expand`], which returns the results in a new collection rather
+/// than extending an existing one.
+pub fn expand_into<R>(field: AttrField, results: &mut R)
+where
+    R: Extend<AttrField>,
+{
+    if find_group(&field.chars).is_none() {
+        results.extend(std::iter::once(field));
+        return;
+    }
+
+    let origin = field.origin;
+    results.extend(
+        expand_chars(&field.chars)
+            .into_iter()
+            .map(|chars| AttrField {
+                chars,
+                origin: origin.clone(),
+            }),
+    );
+}
+
+/// Performs brace expansion and returns the result in a new collection.
+///
+/// This function works similarly to [`expand_into`], but returns the results
+/// in a new collection.
+pub fn expand<R>(field: AttrField) -> R
+where
+    R: Default + Extend<AttrField>,
+{
+    let mut results = R::default();
+    expand_into(field, &mut results);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yash_syntax::source::Location;
+
+    fn literal(value: char) -> AttrChar {
+        AttrChar {
+            value,
+            origin: Origin::Literal,
+            is_quoted: false,
+            is_quoting: false,
+        }
+    }
+
+    fn field(s: &str) -> AttrField {
+        AttrField {
+            chars: s.chars().map(literal).collect(),
+            origin: Location::dummy(s),
+        }
+    }
+
+    fn expand_str(s: &str) -> Vec<String> {
+        expand::<Vec<_>>(field(s))
+            .into_iter()
+            .map(|f| f.chars.iter().map(|c| c.value).collect())
+            .collect()
+    }
+
+    #[test]
+    fn no_braces() {
+        assert_eq!(expand_str(""), [""]);
+        assert_eq!(expand_str("abc"), ["abc"]);
+    }
+
+    #[test]
+    fn simple_alternatives() {
+        assert_eq!(expand_str("{a,b,c}"), ["a", "b", "c"]);
+        assert_eq!(expand_str("pre{a,b}post"), ["preapost", "prebpost"]);
+        assert_eq!(expand_str("x{,y}"), ["x", "xy"]);
+    }
+
+    #[test]
+    fn multiple_groups() {
+        assert_eq!(expand_str("{a,b}{1,2}"), ["a1", "a2", "b1", "b2"]);
+    }
+
+    #[test]
+    fn nested_alternatives() {
+        assert_eq!(expand_str("{a,{b,c}}"), ["a", "b", "c"]);
+        assert_eq!(expand_str("x{a,b{c,d}e}y"), ["xay", "xbcey", "xbdey"]);
+        assert_eq!(expand_str("{a,{b}}"), ["a", "{b}"]);
+    }
+
+    #[test]
+    fn numeric_sequences() {
+        assert_eq!(expand_str("{1..5}"), ["1", "2", "3", "4", "5"]);
+        assert_eq!(expand_str("{3..-1}"), ["3", "2", "1", "0", "-1"]);
+        assert_eq!(expand_str("{7..7}"), ["7"]);
+        assert_eq!(expand_str("n{1..2}"), ["n1", "n2"]);
+    }
+
+    #[test]
+    fn numeric_sequences_with_step() {
+        assert_eq!(expand_str("{1..10..2}"), ["1", "3", "5", "7", "9"]);
+        assert_eq!(expand_str("{10..1..3}"), ["10", "7", "4", "1"]);
+        assert_eq!(expand_str("{1..5..-2}"), ["1", "3", "5"]);
+        assert_eq!(expand_str("{1..3..0}"), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn character_sequences() {
+        assert_eq!(expand_str("{a..e}"), ["a", "b", "c", "d", "e"]);
+        assert_eq!(expand_str("{e..a..2}"), ["e", "c", "a"]);
+    }
+
+    #[test]
+    fn sequence_characters_are_hard_expansion() {
+        let fields: Vec<AttrField> = expand(field("x{1..2}"));
+        assert_eq!(fields[0].chars[0].origin, Origin::Literal);
+        assert_eq!(fields[0].chars[1].origin, Origin::HardExpansion);
+    }
+
+    #[test]
+    fn malformed_braces() {
+        assert_eq!(expand_str("{"), ["{"]);
+        assert_eq!(expand_str("}"), ["}"]);
+        assert_eq!(expand_str("{}"), ["{}"]);
+        assert_eq!(expand_str("{a}"), ["{a}"]);
+        assert_eq!(expand_str("}{a,b"), ["}{a,b"]);
+        assert_eq!(expand_str("{1..}"), ["{1..}"]);
+        assert_eq!(expand_str("{1..a}"), ["{1..a}"]);
+        assert_eq!(expand_str("{ab..c}"), ["{ab..c}"]);
+        assert_eq!(expand_str("{1..2..3..4}"), ["{1..2..3..4}"]);
+        assert_eq!(expand_str("{1..5..x}"), ["{1..5..x}"]);
+    }
+
+    #[test]
+    fn malformed_braces_around_valid_ones() {
+        assert_eq!(expand_str("{a{b,c}"), ["{ab", "{ac"]);
+        assert_eq!(expand_str("{x}{a,b}"), ["{x}a", "{x}b"]);
+    }
+
+    #[test]
+    fn quoted_braces_are_not_expanded() {
+        // \{a,b}
+        let mut chars = vec![AttrChar {
+            value: '\\',
+            origin: Origin::Literal,
+            is_quoted: false,
+            is_quoting: true,
+        }];
+        chars.push(AttrChar {
+            is_quoted: true,
+            ..literal('{')
+        });
+        chars.extend("a,b}".chars().map(literal));
+        let field = AttrField {
+            chars,
+            origin: Location::dummy(""),
+        };
+        let fields: Vec<AttrField> = expand(field.clone());
+        assert_eq!(fields, [field]);
+    }
+
+    #[test]
+    fn quoted_comma_is_not_separator() {
+        // {a\,b,c}
+        let mut chars = "{a".chars().map(literal).collect::<Vec<_>>();
+        chars.push(AttrChar {
+            is_quoting: true,
+            ..literal('\\')
+        });
+        chars.push(AttrChar {
+            is_quoted: true,
+            ..literal(',')
+        });
+        chars.extend("b,c}".chars().map(literal));
+        let field = AttrField {
+            chars,
+            origin: Location::dummy(""),
+        };
+        let fields: Vec<AttrField> = expand(field);
+        let values = fields
+            .iter()
+            .map(|f| f.chars.iter().map(|c| c.value).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["a\\,b", "c"]);
+    }
+
+    #[test]
+    fn braces_from_expansion_are_not_expanded() {
+        let chars = "{a,b}"
+            .chars()
+            .map(|value| AttrChar {
+                value,
+                origin: Origin::SoftExpansion,
+                is_quoted: false,
+                is_quoting: false,
+            })
+            .collect();
+        let field = AttrField {
+            chars,
+            origin: Location::dummy(""),
+        };
+        let fields: Vec<AttrField> = expand(field.clone());
+        assert_eq!(fields, [field]);
+    }
+
+    #[test]
+    fn results_share_origin() {
+        let fields: Vec<AttrField> = expand(field("{a,b}"));
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].origin, Location::dummy("{a,b}"));
+        assert_eq!(fields[1].origin, Location::dummy("{a,b}"));
+    }
+}
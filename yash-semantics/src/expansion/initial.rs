@@ -73,6 +73,7 @@ pub trait Expand {
 mod arith;
 mod command_subst;
 mod param;
+mod process_subst;
 mod slice;
 mod text;
 mod tilde;
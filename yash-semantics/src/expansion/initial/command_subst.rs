@@ -23,16 +23,18 @@ use super::Env;
 use super::Error;
 use crate::expansion::ErrorCause;
 use crate::read_eval_loop;
-use crate::trap::run_exit_trap;
+use crate::shutdown;
 use crate::Handle;
+use enumset::EnumSet;
 use std::cell::RefCell;
 use yash_env::io::Fd;
 use yash_env::job::Pid;
 use yash_env::subshell::JobControl;
 use yash_env::subshell::Subshell;
 use yash_env::system::Errno;
+use yash_env::system::SystemEx as _;
 use yash_env::System;
-use yash_syntax::parser::lex::Lexer;
+use yash_syntax::input::Memory;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
 
@@ -44,7 +46,7 @@ where
     let original = location.clone();
 
     // Open a pipe to read the output from the command
-    let (reader, writer) = match env.inner.system.pipe() {
+    let (reader, writer) = match env.inner.system.pipe_cloexec() {
         Ok(pipes) => pipes,
         Err(errno) => {
             return Err(Error {
@@ -58,8 +60,7 @@ where
     let subshell = Subshell::new(move |env, _job_control| {
         Box::pin(async move {
             let result = subshell_body(env, reader, writer, original, command).await;
-            env.apply_result(result);
-            run_exit_trap(env).await;
+            shutdown(env, result).await;
         })
     });
     let subshell_result = subshell.start(env.inner).await;
@@ -88,10 +89,15 @@ where
             return error.handle(env).await;
         }
         env.system.close(writer).ok();
+    } else {
+        // The writer must not be closed on exec as it is the standard output.
+        env.system.fcntl_setfd(writer, EnumSet::empty()).ok();
     }
 
     // Run the command
-    let mut lexer = Lexer::from_memory(command.as_ref(), Source::CommandSubst { original });
+    let mut config = env.lexer_config();
+    config.source = Some(Source::CommandSubst { original }.into());
+    let mut lexer = config.input(Box::new(Memory::new(command.as_ref())));
     read_eval_loop(&RefCell::new(env), &mut lexer).await
 }
 
@@ -348,6 +348,61 @@ pub mod tests {
         assert_eq!(phrase, Phrase::one_empty_field());
     }
 
+    #[test]
+    fn nounset_option_is_ignored_with_default_switch() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("foo");
+        param.modifier = Modifier::Switch(Switch {
+            r#type: SwitchType::Default,
+            condition: SwitchCondition::UnsetOrEmpty,
+            word: "x".parse().unwrap(),
+        });
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("x")));
+    }
+
+    #[test]
+    fn nounset_option_with_positional_parameter_out_of_range() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let param = braced_param(Param::from(1));
+        let pr = ParamRef::from(&param);
+
+        let e = pr.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::UnsetParameter { param: param.param });
+    }
+
+    #[test]
+    fn nounset_option_with_unset_special_parameter() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let param = braced_param(SpecialParam::Exclamation);
+        let pr = ParamRef::from(&param);
+
+        let e = pr.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::UnsetParameter { param: param.param });
+    }
+
+    #[test]
+    fn nounset_option_is_ignored_for_at_and_asterisk() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+
+        for special in [SpecialParam::At, SpecialParam::Asterisk] {
+            let param = braced_param(special);
+            let param = ParamRef::from(&param);
+            let result = param.expand(&mut env).now_or_never().unwrap();
+            assert!(result.is_ok(), "{special:?}: {result:?}");
+        }
+    }
+
     #[test]
     fn expand_at_no_join_in_non_splitting_context() {
         let mut env = env_with_positional_params_and_ifs();
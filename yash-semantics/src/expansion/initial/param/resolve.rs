@@ -16,6 +16,7 @@
 
 //! Resolving parameter names to values
 
+use yash_env::job::Pid;
 use yash_env::variable::Expansion;
 use yash_env::Env;
 use yash_syntax::source::Location;
@@ -52,7 +53,11 @@ pub fn resolve<'a>(env: &'a Env, param: &Param, location: &Location) -> Expansio
         Special(Question) => env.exit_status.to_string().into(),
         Special(Hyphen) => options(env),
         Special(Dollar) => env.main_pid.to_string().into(),
-        Special(Exclamation) => env.jobs.last_async_pid().to_string().into(),
+        Special(Exclamation) => match env.jobs.last_async_pid() {
+            // No asynchronous command has been executed yet
+            Pid(0) => Expansion::Unset,
+            pid => pid.to_string().into(),
+        },
         Special(Zero) => env.arg0.as_str().into(),
         Positional(0) => Expansion::Unset,
         Positional(index) => positional(env).get(index - 1).into(),
@@ -62,7 +67,6 @@ pub fn resolve<'a>(env: &'a Env, param: &Param, location: &Location) -> Expansio
 #[cfg(test)]
 mod tests {
     use super::*;
-    use yash_env::job::Pid;
     use yash_env::variable::Scope;
     use yash_env::variable::Value;
     use yash_env::variable::PATH;
@@ -221,7 +225,7 @@ mod tests {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
         let result = resolve(&env, &Param::from(SpecialParam::Exclamation), &loc);
-        assert_eq!(result, Expansion::Scalar("0".into()));
+        assert_eq!(result, Expansion::Unset);
 
         env.jobs.set_last_async_pid(Pid(72));
         let result = resolve(&env, &Param::from(SpecialParam::Exclamation), &loc);
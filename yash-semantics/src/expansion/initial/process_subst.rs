@@ -0,0 +1,292 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Expansion of process substitution
+
+use super::super::attr::AttrChar;
+use super::super::attr::Origin;
+use super::super::phrase::Phrase;
+use super::Env;
+use super::Error;
+use crate::expansion::ErrorCause;
+use crate::read_eval_loop;
+use crate::shutdown;
+use enumset::EnumSet;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yash_env::io::Fd;
+use yash_env::io::MIN_INTERNAL_FD;
+use yash_env::option::Option::ProcSubst;
+use yash_env::option::State::Off;
+use yash_env::subshell::Subshell;
+use yash_env::system::SystemEx as _;
+use yash_env::System;
+use yash_syntax::input::Memory;
+use yash_syntax::source::Location;
+use yash_syntax::source::Source;
+use yash_syntax::syntax::ProcessSubstDirection;
+
+/// Performs process substitution
+///
+/// This function starts a subshell that runs the command with its standard
+/// output (for [`In`](ProcessSubstDirection::In)) or standard input (for
+/// [`Out`](ProcessSubstDirection::Out)) connected to a pipe. The shell keeps
+/// the other end of the pipe open at a file descriptor not less than
+/// [`MIN_INTERNAL_FD`] and records it in
+/// [`process_subst_fds`](yash_env::Env::process_subst_fds) so that it can be
+/// closed after the command using it finishes. The result is a pathname of the
+/// form `/dev/fd/N` that refers to the file descriptor.
+///
+/// The subshell is not waited for.
+///
+/// This function fails if the [`ProcSubst`] option is off.
+pub async fn expand(
+    direction: ProcessSubstDirection,
+    command: Rc<str>,
+    location: &Location,
+    env: &mut Env<'_>,
+) -> Result<Phrase, Error> {
+    let error = |cause| Error {
+        cause,
+        location: location.clone(),
+    };
+
+    if env.inner.options.get(ProcSubst) == Off {
+        return Err(error(ErrorCause::ProcessSubstDisabled));
+    }
+
+    // Open a pipe connecting the shell and the subshell
+    let (reader, writer) = env
+        .inner
+        .system
+        .pipe_cloexec()
+        .map_err(|errno| error(ErrorCause::ProcessSubstError(errno)))?;
+    let (shell_fd, subshell_fd, target_fd) = match direction {
+        ProcessSubstDirection::In => (reader, writer, Fd::STDOUT),
+        ProcessSubstDirection::Out => (writer, reader, Fd::STDIN),
+    };
+    if subshell_fd == target_fd {
+        // The subshell does not duplicate the file descriptor in this case,
+        // so it must not be closed on exec.
+        env.inner
+            .system
+            .fcntl_setfd(subshell_fd, EnumSet::empty())
+            .ok();
+    }
+
+    // Start a subshell to run the command. The file descriptors kept open for
+    // preceding process substitutions are not needed in the subshell.
+    let original = location.clone();
+    let mut subshell = Subshell::new(move |env, _job_control| {
+        Box::pin(async move {
+            let result = subshell_body(env, original, command).await;
+            shutdown(env, result).await;
+        })
+    })
+    .close_fd(shell_fd);
+    for &fd in &env.inner.process_subst_fds {
+        subshell = subshell.close_fd(fd);
+    }
+    if subshell_fd != target_fd {
+        subshell = subshell
+            .dup2_fd(subshell_fd, target_fd)
+            .close_fd(subshell_fd);
+    }
+    let subshell_result = subshell.start(env.inner).await;
+    env.inner.system.close(subshell_fd).ok();
+    if let Err(errno) = subshell_result {
+        env.inner.system.close(shell_fd).ok();
+        return Err(error(ErrorCause::ProcessSubstError(errno)));
+    }
+
+    // Move the shell's end of the pipe out of the way of user redirections.
+    // The file descriptor must be inherited by the command using it, so it is
+    // not made close-on-exec.
+    let result = env
+        .inner
+        .system
+        .dup(shell_fd, MIN_INTERNAL_FD, EnumSet::empty());
+    env.inner.system.close(shell_fd).ok();
+    let fd = result.map_err(|errno| error(ErrorCause::ProcessSubstError(errno)))?;
+    env.inner.process_subst_fds.push(fd);
+
+    let chars = format!("/dev/fd/{}", fd.0)
+        .chars()
+        .map(|value| AttrChar {
+            value,
+            origin: Origin::HardExpansion,
+            is_quoted: false,
+            is_quoting: false,
+        })
+        .collect();
+    Ok(Phrase::Field(chars))
+}
+
+async fn subshell_body(
+    env: &mut yash_env::Env,
+    original: Location,
+    command: Rc<str>,
+) -> yash_env::semantics::Result {
+    // The subshell has closed these file descriptors before running this.
+    env.process_subst_fds.clear();
+
+    // Run the command
+    let mut config = env.lexer_config();
+    config.source = Some(Source::ProcessSubst { original }.into());
+    let mut lexer = config.input(Box::new(Memory::new(&command)));
+    read_eval_loop(&RefCell::new(env), &mut lexer).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::cat_builtin;
+    use crate::tests::echo_builtin;
+    use futures_util::FutureExt;
+    use yash_env::job::Pid;
+    use yash_env::option::State::On;
+    use yash_env::semantics::ExitStatus;
+    use yash_env_test_helper::assert_stdout;
+    use yash_env_test_helper::in_virtual_system;
+    use yash_syntax::syntax::List;
+
+    fn fd_of(phrase: &Phrase) -> Fd {
+        let Phrase::Field(chars) = phrase else {
+            panic!("unexpected phrase: {phrase:?}");
+        };
+        let path: String = chars.iter().map(|c| c.value).collect();
+        let fd = path.strip_prefix("/dev/fd/").unwrap().parse().unwrap();
+        assert!(
+            chars.iter().all(|c| c.origin == Origin::HardExpansion),
+            "{chars:?}"
+        );
+        Fd(fd)
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let location = Location::dummy("<(echo)");
+        let result = expand(
+            ProcessSubstDirection::In,
+            "echo".into(),
+            &location,
+            &mut env,
+        )
+        .now_or_never()
+        .unwrap();
+        assert_eq!(
+            result,
+            Err(Error {
+                cause: ErrorCause::ProcessSubstDisabled,
+                location,
+            })
+        );
+        assert_eq!(env.inner.process_subst_fds, []);
+    }
+
+    #[test]
+    fn reading_output_of_command() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.options.set(ProcSubst, On);
+            let location = Location::dummy("<(echo ok)");
+            let mut env = Env::new(&mut env);
+            let result = expand(
+                ProcessSubstDirection::In,
+                "echo ok".into(),
+                &location,
+                &mut env,
+            )
+            .await
+            .unwrap();
+
+            let fd = fd_of(&result);
+            assert!(fd >= MIN_INTERNAL_FD, "{fd:?}");
+            assert_eq!(env.inner.process_subst_fds, [fd]);
+
+            let mut buffer = [0; 10];
+            let count = env.inner.system.read_async(fd, &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..count], b"ok\n");
+            let count = env.inner.system.read_async(fd, &mut buffer).await.unwrap();
+            assert_eq!(count, 0);
+        })
+    }
+
+    #[test]
+    fn writing_input_to_command() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            env.options.set(ProcSubst, On);
+            let location = Location::dummy(">(cat)");
+            let mut env = Env::new(&mut env);
+            let result = expand(
+                ProcessSubstDirection::Out,
+                "cat".into(),
+                &location,
+                &mut env,
+            )
+            .await
+            .unwrap();
+
+            let fd = fd_of(&result);
+            env.inner.system.write_all(fd, b"data\n").await.unwrap();
+            env.inner.system.close(fd).unwrap();
+            let (_pid, exit_status) = env
+                .inner
+                .wait_for_subshell_to_finish(Pid::ALL)
+                .await
+                .unwrap();
+            assert_eq!(exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "data\n"));
+        })
+    }
+
+    #[test]
+    fn multiple_substitutions_passed_to_function() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            env.builtins.insert("echo", echo_builtin());
+            env.options.set(ProcSubst, On);
+            let list: List = "f() { cat <\"$1\"; cat <\"$2\"; }\nf <(echo a) <(echo b)"
+                .parse()
+                .unwrap();
+
+            let result = list.execute(&mut env).await;
+            assert_eq!(result, std::ops::ControlFlow::Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_eq!(env.process_subst_fds, []);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "a\nb\n"));
+        })
+    }
+
+    #[test]
+    fn file_descriptors_are_closed_after_command() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.options.set(ProcSubst, On);
+            let list: List = "echo <(echo a)".parse().unwrap();
+
+            let result = list.execute(&mut env).await;
+            assert_eq!(result, std::ops::ControlFlow::Continue(()));
+            assert_eq!(env.process_subst_fds, []);
+            let result = env.system.fcntl_getfd(MIN_INTERNAL_FD);
+            assert_eq!(result, Err(yash_env::system::Errno::EBADF));
+        })
+    }
+}
@@ -284,6 +284,20 @@ mod tests {
         })
     }
 
+    #[test]
+    fn nested_backquotes() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let mut env = Env::new(&mut env);
+            let text: Text = r"`echo \`echo hi\``".parse().unwrap();
+            let result = text.expand(&mut env).await.unwrap();
+
+            let chars = result.ifs_join(&env.inner.variables);
+            let values: String = chars.iter().map(|c| c.value).collect();
+            assert_eq!(values, "hi");
+        })
+    }
+
     #[test]
     fn arithmetic() {
         let mut env = yash_env::Env::new_virtual();
@@ -22,6 +22,7 @@ use super::super::Error;
 use super::Env;
 use super::Expand;
 use super::Phrase;
+use std::rc::Rc;
 use yash_syntax::syntax::Unquote as _;
 use yash_syntax::syntax::Word;
 use yash_syntax::syntax::WordUnit::{self, *};
@@ -126,6 +127,12 @@ fn double_quote(phrase: &mut Phrase) {
 /// `Tilde(user)` expands to the `user`'s home directory.
 ///
 /// TODO: `~+`, `~-`, `~+n`, `~-n`
+///
+/// # Process substitution
+///
+/// `ProcessSubst { .. }` starts a subshell connected to the shell via a pipe
+/// and expands to a `/dev/fd/N` pathname that refers to the pipe. This fails
+/// unless the [`ProcSubst`](yash_env::option::Option::ProcSubst) option is on.
 impl Expand for WordUnit {
     async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
         match self {
@@ -142,6 +149,11 @@ impl Expand for WordUnit {
             }
             DollarSingleQuote(string) => Ok(dollar_single_quote(&string.unquote().0)),
             Tilde(name) => Ok(super::tilde::expand(name, env.inner).into()),
+            ProcessSubst {
+                direction,
+                content,
+                location,
+            } => super::process_subst::expand(*direction, Rc::clone(content), location, env).await,
         }
     }
 }
@@ -21,7 +21,9 @@
 //! A command can be executed by calling
 //! [`Command::execute`](command::Command::execute).
 //! A word can be expanded by using functions and traits defined in
-//! [`expansion`].
+//! [`expansion`]. The [`expand_word`] function is a simplified interface to
+//! the word expansion that returns the resulting fields as strings, and the
+//! [`glob`] function performs the pathname expansion on a pattern string.
 //!
 //! The [`read_eval_loop`] reads, parses, and executes commands from an input.
 //! It is a utility for running a shell script.
@@ -44,6 +46,13 @@ mod runner;
 pub use runner::interactive_read_eval_loop;
 pub use runner::read_eval_loop;
 
+mod shutdown;
+pub use shutdown::shutdown;
+
+mod word;
+pub use word::expand_word;
+pub use word::glob;
+
 mod runner_legacy;
 #[allow(deprecated)]
 pub use runner_legacy::ReadEvalLoop;
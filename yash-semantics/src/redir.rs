@@ -48,6 +48,10 @@
 //! If the `Clobber` [shell option](yash_env::option::Option) is off and a
 //! regular file exists at the target pathname, then `FileOut` will fail.
 //!
+//! If the `Restricted` shell option is on, `FileInOut`, `FileOut`,
+//! `FileClobber`, and `FileAppend` fail without opening the file since they
+//! may create a file.
+//!
 //! If the body is `HereDoc`, the redirection opens a readable file descriptor
 //! that yields [expansion](crate::expansion) of the content. The current
 //! implementation uses an unnamed temporary file for the file descriptor, but
@@ -89,9 +93,8 @@ use std::ops::Deref;
 use std::ops::DerefMut;
 use thiserror::Error;
 use yash_env::io::Fd;
-use yash_env::io::MIN_INTERNAL_FD;
-use yash_env::option::Option::Clobber;
-use yash_env::option::State::Off;
+use yash_env::option::Option::{Clobber, Restricted};
+use yash_env::option::State::{Off, On};
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::system::Errno;
@@ -100,6 +103,7 @@ use yash_env::system::FileType;
 use yash_env::system::Mode;
 use yash_env::system::OfdAccess;
 use yash_env::system::OpenFlag;
+use yash_env::system::SystemEx as _;
 use yash_env::Env;
 use yash_env::System;
 use yash_quote::quoted;
@@ -167,6 +171,10 @@ pub enum ErrorCause {
     /// Error preparing a temporary file to save here-document content
     #[error("cannot prepare temporary file for here-document: {0}")]
     TemporaryFileUnavailable(Errno),
+
+    /// Redirection that may create a file in the restricted shell
+    #[error("cannot redirect to file '{}' in the restricted shell", .0.to_string_lossy())]
+    Restricted(CString),
 }
 
 impl ErrorCause {
@@ -183,6 +191,7 @@ impl ErrorCause {
             MalformedFd(_, _) => "not a valid file descriptor",
             UnreadableFd(_) | UnwritableFd(_) => "cannot copy file descriptor",
             TemporaryFileUnavailable(_) => "cannot prepare here-document",
+            Restricted(_) => "cannot redirect to the file",
         }
     }
 
@@ -201,6 +210,7 @@ impl ErrorCause {
             UnreadableFd(fd) => format!("{fd}: not a readable file descriptor").into(),
             UnwritableFd(fd) => format!("{fd}: not a writable file descriptor").into(),
             TemporaryFileUnavailable(errno) => errno.to_string().into(),
+            Restricted(path) => format!("{}: restricted shell", path.to_string_lossy()).into(),
         }
     }
 }
@@ -414,6 +424,11 @@ async fn open_normal(
 ) -> Result<(FdSpec, Location), Error> {
     use RedirOp::*;
     match operator {
+        FileInOut | FileOut | FileClobber | FileAppend if env.options.get(Restricted) == On => {
+            let (path, location) = into_c_string_value_and_origin(operand)?;
+            let cause = ErrorCause::Restricted(path);
+            Err(Error { cause, location })
+        }
         FileIn => open_file(env, OfdAccess::ReadOnly, EnumSet::empty(), operand),
         FileOut if env.options.get(Clobber) == Off => open_file_noclobber(env, operand),
         FileOut | FileClobber => open_file(
@@ -479,19 +494,10 @@ async fn perform(
     }
 
     // Save the current open file description at target_fd to a new FD
-    let save = match env
-        .system
-        .dup(target_fd, MIN_INTERNAL_FD, FdFlag::CloseOnExec.into())
-    {
-        Ok(save_fd) => Some(save_fd),
-        Err(Errno::EBADF) => None,
-        Err(errno) => {
-            return Err(Error {
-                cause: ErrorCause::FdNotOverwritten(target_fd, errno),
-                location: redir.body.operand().location.clone(),
-            })
-        }
-    };
+    let save = env.system.save_fd(target_fd).map_err(|errno| Error {
+        cause: ErrorCause::FdNotOverwritten(target_fd, errno),
+        location: redir.body.operand().location.clone(),
+    })?;
 
     // Prepare an FD from the redirection body
     let (fd_spec, location, exit_status) = match &redir.body {
@@ -639,13 +645,7 @@ impl<'e> RedirGuard<'e> {
     /// were used for restoration and are no longer needed.
     pub fn undo_redirs(&mut self) {
         for SavedFd { original, save } in self.saved_fds.drain(..).rev() {
-            if let Some(save) = save {
-                assert_ne!(save, original);
-                let _: Result<_, _> = self.env.system.dup2(save, original);
-                let _: Result<_, _> = self.env.system.close(save);
-            } else {
-                let _: Result<_, _> = self.env.system.close(original);
-            }
+            self.env.system.restore_fd(original, save);
         }
     }
 
@@ -671,6 +671,7 @@ mod tests {
     use futures_util::FutureExt;
     use std::cell::RefCell;
     use std::rc::Rc;
+    use yash_env::io::MIN_INTERNAL_FD;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::Inode;
     use yash_env::system::resource::LimitPair;
@@ -1080,6 +1081,28 @@ mod tests {
         });
     }
 
+    #[test]
+    fn file_out_noclobber_creates_new_file() {
+        let system = system_with_nofile_limit();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Clobber, Off);
+        let mut env = RedirGuard::new(&mut env);
+
+        let redir = "3> foo".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        env.system.write(Fd(3), &[42, 123, 57]).unwrap();
+
+        let file = state.borrow().file_system.get("foo").unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(content[..], [42, 123, 57]);
+        });
+    }
+
     #[test]
     fn file_out_noclobber_with_regular_file() {
         let file = Rc::new(RefCell::new(Inode::new([42, 123, 254])));
@@ -1109,6 +1132,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn file_out_in_restricted_shell() {
+        let system = system_with_nofile_limit();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Restricted, On);
+        let mut env = RedirGuard::new(&mut env);
+
+        for redir in ["3> foo", "3>| foo", "3>> foo", "3<> foo"] {
+            let redir = redir.parse().unwrap();
+            let e = env
+                .perform_redir(&redir, None)
+                .now_or_never()
+                .unwrap()
+                .unwrap_err();
+
+            assert_eq!(e.cause, ErrorCause::Restricted(c"foo".to_owned()));
+            assert_eq!(e.location, redir.body.operand().location);
+        }
+        assert!(state.borrow().file_system.get("foo").is_err());
+    }
+
+    #[test]
+    fn file_in_in_restricted_shell() {
+        let file = Rc::new(RefCell::new(Inode::new([42])));
+        let system = system_with_nofile_limit();
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("foo", file).unwrap();
+        drop(state);
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Restricted, On);
+        let mut env = RedirGuard::new(&mut env);
+
+        let redir = "3< foo".parse().unwrap();
+        let result = env.perform_redir(&redir, None).now_or_never().unwrap();
+        assert_eq!(result, Ok(None));
+    }
+
     #[test]
     fn file_out_noclobber_with_non_regular_file() {
         let inode = Inode {
@@ -1196,7 +1257,29 @@ mod tests {
         });
     }
 
-    // TODO file_clobber_with_noclobber_fails_with_existing_file
+    #[test]
+    fn file_clobber_with_noclobber_truncates_existing_file() {
+        let file = Rc::new(RefCell::new(Inode::new([42, 123, 254])));
+        let system = system_with_nofile_limit();
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("foo", Rc::clone(&file)).unwrap();
+        drop(state);
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Clobber, Off);
+        let mut env = RedirGuard::new(&mut env);
+
+        let redir = "3>| foo".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        env.system.write(Fd(3), &[7]).unwrap();
+
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(content[..], [7]);
+        });
+    }
 
     #[test]
     fn file_clobber_closes_opened_file_on_error() {
@@ -110,6 +110,9 @@ pub async fn read_eval_loop(env: &RefCell<&mut Env>, lexer: &mut Lexer<'_>) -> R
 /// error or if the command execution results in an interrupt. Note that I/O
 /// errors detected by the parser are not recovered from.
 ///
+/// Each successfully parsed command line is [added to the
+/// history](Env::add_history) before it is executed.
+///
 /// Also note that the following aspects of the interactive shell are *not*
 /// implemented in this function:
 ///
@@ -142,12 +145,11 @@ async fn read_eval_loop_impl(
         if !lexer.pending() {
             lexer.flush();
         }
-
         let command = Parser::config()
             .aliases(env)
             .declaration_utilities(env)
             .input(lexer)
-            .command_line()
+            .command_line_with_range()
             .await;
 
         let env = &mut **env.borrow_mut();
@@ -162,10 +164,19 @@ async fn read_eval_loop_impl(
             }
 
             // Execute the command
-            Ok(Some(command)) => (run_command(env, &command).await, true),
+            Ok(Some((command, range))) => {
+                // An empty command line (e.g., a comment line) does not count
+                // as a command.
+                executed |= !command.0.is_empty();
+                if is_interactive {
+                    env.add_history(&lexer.source_string(range));
+                }
+                (run_command(env, &command).await, true)
+            }
 
             // Parser error
             Err(error) => {
+                executed = true;
                 let result = error.handle(env).await;
                 let error_recoverable = matches!(error.cause, ErrorCause::Syntax(_));
                 (result, error_recoverable)
@@ -185,8 +196,6 @@ async fn read_eval_loop_impl(
 
         // Break the loop if the command execution results in a divert
         result?;
-
-        executed = true;
     }
 }
 
@@ -227,6 +236,18 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus::SUCCESS);
     }
 
+    #[test]
+    fn exit_status_zero_with_only_comments_and_blank_lines() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus(5);
+        let mut lexer = Lexer::with_code("#!/bin/sh\n\n# comment\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+    }
+
     #[test]
     fn exit_status_in_out() {
         let system = VirtualSystem::new();
@@ -413,6 +434,51 @@ mod tests {
         assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
     }
 
+    #[test]
+    fn interactive_loop_adds_command_lines_to_history() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        let mut lexer = Lexer::with_code("echo 1; echo 2\n\necho 3\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        let commands = env.history.iter().map(|entry| &entry.command[..]);
+        assert_eq!(commands.collect::<Vec<_>>(), ["echo 1; echo 2", "echo 3"]);
+    }
+
+    #[test]
+    fn interactive_loop_adds_multi_line_command_as_single_entry() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        let mut lexer = Lexer::with_code("if echo 1\nthen\n  echo 2\nfi\necho 3\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        let commands = env.history.iter().map(|entry| &entry.command[..]);
+        assert_eq!(
+            commands.collect::<Vec<_>>(),
+            ["if echo 1\nthen\n  echo 2\nfi", "echo 3"]
+        );
+    }
+
+    #[test]
+    fn non_interactive_loop_does_not_add_history() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        let mut lexer = Lexer::with_code("echo 1\necho 2\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert!(env.history.is_empty());
+    }
+
     #[test]
     fn running_traps_between_parsing_and_executing() {
         let system = VirtualSystem::new();
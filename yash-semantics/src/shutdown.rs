@@ -0,0 +1,146 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Orderly exit of the shell
+
+use crate::trap::run_exit_trap;
+use std::ops::ControlFlow::{Break, Continue};
+use yash_env::semantics::Divert;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Result;
+use yash_env::Env;
+
+/// Performs the final steps before the shell (or a subshell) exits.
+///
+/// This function should be called with the result of the last executed
+/// command when the shell is about to exit, either because it has reached the
+/// end of the input or because a [divert](Divert) has been raised. It does the
+/// following in order:
+///
+/// 1. [Applies](Env::apply_result) the result to `env.exit_status`.
+/// 2. Runs the EXIT trap with [`run_exit_trap`], unless the result is
+///    [`Divert::Abort`].
+/// 3. [Flushes](yash_env::SharedSystem::flush) data buffered for the standard
+///    output.
+///
+/// The return value is the final exit status of the shell, that is, the value
+/// of `env.exit_status` after the above steps. The caller should exit the
+/// process with this exit status.
+pub async fn shutdown(env: &mut Env, result: Result) -> ExitStatus {
+    env.apply_result(result);
+
+    match result {
+        Continue(())
+        | Break(Divert::Continue { .. })
+        | Break(Divert::Break { .. })
+        | Break(Divert::Return(_))
+        | Break(Divert::Interrupt(_))
+        | Break(Divert::Exit(_)) => run_exit_trap(env).await,
+        Break(Divert::Abort(_)) => (),
+    }
+
+    _ = env.system.flush().await;
+
+    env.exit_status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::echo_builtin;
+    use crate::tests::exit_builtin;
+    use futures_util::FutureExt;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::io::Fd;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::Buffering;
+    use yash_env::trap::Action;
+    use yash_env::trap::Condition;
+    use yash_env_test_helper::assert_stdout;
+    use yash_syntax::source::Location;
+
+    fn env_with_exit_trap(command: &str) -> (Env, Rc<RefCell<SystemState>>) {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("exit", exit_builtin());
+        env.traps
+            .set_action(
+                &mut env.system,
+                Condition::Exit,
+                Action::Command(command.into()),
+                Location::dummy(""),
+                false,
+            )
+            .unwrap();
+        (env, state)
+    }
+
+    #[test]
+    fn returns_current_exit_status_on_continue() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus(12);
+        let exit_status = shutdown(&mut env, Continue(())).now_or_never().unwrap();
+        assert_eq!(exit_status, ExitStatus(12));
+    }
+
+    #[test]
+    fn applies_result_before_running_exit_trap() {
+        let (mut env, state) = env_with_exit_trap("echo $?");
+        let result = Break(Divert::Exit(Some(ExitStatus(7))));
+
+        let exit_status = shutdown(&mut env, result).now_or_never().unwrap();
+        assert_eq!(exit_status, ExitStatus(7));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "7\n"));
+    }
+
+    #[test]
+    fn exit_status_of_exit_in_trap_is_returned() {
+        let (mut env, _state) = env_with_exit_trap("exit 3");
+        let result = Break(Divert::Exit(Some(ExitStatus(7))));
+
+        let exit_status = shutdown(&mut env, result).now_or_never().unwrap();
+        assert_eq!(exit_status, ExitStatus(3));
+    }
+
+    #[test]
+    fn exit_trap_is_not_run_on_abort() {
+        let (mut env, state) = env_with_exit_trap("echo trap");
+        let result = Break(Divert::Abort(Some(ExitStatus(5))));
+
+        let exit_status = shutdown(&mut env, result).now_or_never().unwrap();
+        assert_eq!(exit_status, ExitStatus(5));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn buffered_output_is_flushed_after_exit_trap() {
+        let (mut env, state) = env_with_exit_trap("echo trap");
+        env.system.set_stdout_buffering(Buffering::Full);
+        env.system
+            .write_all(Fd::STDOUT, b"main\n")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+
+        shutdown(&mut env, Continue(())).now_or_never().unwrap();
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "main\ntrap\n"));
+    }
+}
@@ -28,11 +28,15 @@ use yash_env::job::Pid;
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
+use yash_env::system::r#virtual::VirtualSystem;
 use yash_env::system::r#virtual::SIGSTOP;
 use yash_env::system::Errno;
+use yash_env::trap::Action;
+use yash_env::trap::Condition;
 use yash_env::variable::Scope;
 use yash_env::Env;
 use yash_env::System;
+use yash_syntax::source::Location;
 
 fn exit_builtin_main(
     env: &mut Env,
@@ -210,3 +214,35 @@ fn cat_builtin_main(
 pub fn cat_builtin() -> Builtin {
     Builtin::new(Mandatory, cat_builtin_main)
 }
+
+/// Sets the DEBUG trap to the given command.
+pub fn set_debug_trap(env: &mut Env, command: &str) {
+    env.traps
+        .set_action(
+            &mut env.system,
+            Condition::Debug,
+            Action::Command(command.into()),
+            Location::dummy(""),
+            false,
+        )
+        .unwrap();
+}
+
+/// Returns an environment with the ERR trap set to the given command.
+///
+/// The environment has the `echo` and `return` built-ins.
+pub fn env_with_err_trap(system: VirtualSystem, command: &str) -> Env {
+    let mut env = Env::with_system(Box::new(system));
+    env.builtins.insert("echo", echo_builtin());
+    env.builtins.insert("return", return_builtin());
+    env.traps
+        .set_action(
+            &mut env.system,
+            Condition::Err,
+            Action::Command(command.into()),
+            Location::dummy(""),
+            false,
+        )
+        .unwrap();
+    env
+}
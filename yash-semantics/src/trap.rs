@@ -43,19 +43,27 @@
 //! The EXIT trap is executed when the shell exits normally, by running the exit
 //! built-in or reaching the end of the script. The [`run_exit_trap`] function,
 //! which should be called before exiting, runs the trap.
+//!
+//! The ERR trap is executed when a command fails under the conditions where
+//! the `ErrExit` shell option would make the shell exit. The [`run_err_trap`]
+//! function runs the trap if the current exit status is non-zero.
+//!
+//! The DEBUG trap is executed before each simple command. The
+//! [`run_debug_trap`] function runs the trap.
 
 use crate::read_eval_loop;
 use std::cell::RefCell;
-use std::ops::ControlFlow::Break;
+use std::ops::ControlFlow::{Break, Continue};
 use std::rc::Rc;
 use yash_env::semantics::Divert;
 use yash_env::semantics::Result;
 use yash_env::stack::Frame;
+use yash_env::trap::Action;
 use yash_env::trap::Condition;
 #[cfg(doc)]
 use yash_env::trap::TrapSet;
 use yash_env::Env;
-use yash_syntax::parser::lex::Lexer;
+use yash_syntax::input::Memory;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
 
@@ -77,7 +85,9 @@ use yash_syntax::source::Source;
 #[must_use]
 async fn run_trap(env: &mut Env, cond: Condition, code: Rc<str>, origin: Location) -> Result {
     let condition = cond.to_string(&env.system).into_owned();
-    let mut lexer = Lexer::from_memory(&code, Source::Trap { condition, origin });
+    let mut config = env.lexer_config();
+    config.source = Some(Source::Trap { condition, origin }.into());
+    let mut lexer = config.input(Box::new(Memory::new(&code)));
     let mut env = env.push_frame(Frame::Trap(cond));
 
     let previous_exit_status = env.exit_status;
@@ -98,9 +108,33 @@ async fn run_trap(env: &mut Env, cond: Condition, code: Rc<str>, origin: Locatio
     result
 }
 
+/// Runs the trap action for the condition if it is set to a command.
+///
+/// If the trap is not set or set to an action other than a command, this
+/// function does nothing and returns `Continue(())`. Otherwise, this function
+/// runs the action with [`run_trap`].
+async fn run_trap_if_set(env: &mut Env, cond: Condition) -> Result {
+    let Some(state) = env.traps.get_state(cond).0 else {
+        return Continue(());
+    };
+    let Action::Command(command) = &state.action else {
+        return Continue(());
+    };
+
+    let command = Rc::clone(command);
+    let origin = state.origin.clone();
+    run_trap(env, cond, command, origin).await
+}
+
 mod signal;
 pub use signal::run_trap_if_caught;
 pub use signal::run_traps_for_caught_signals;
 
 mod exit;
 pub use exit::run_exit_trap;
+
+mod err;
+pub use err::run_err_trap;
+
+mod debug;
+pub use debug::run_debug_trap;
@@ -0,0 +1,104 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Running the DEBUG trap
+
+use super::run_trap_if_set;
+use std::ops::ControlFlow::Continue;
+use yash_env::semantics::Result;
+use yash_env::stack::Frame;
+use yash_env::trap::Condition;
+use yash_env::Env;
+
+/// Executes the DEBUG trap.
+///
+/// This function should be called before expanding the words of each simple
+/// command. If the DEBUG trap is not set in the environment, this function
+/// does nothing. Otherwise, this function executes the trap.
+///
+/// To prevent infinite recursion, the trap is not run while the DEBUG trap
+/// itself is running.
+///
+/// The exit status of the trap action does not affect the exit status of the
+/// current environment, so `$?` in the trap action and in the simple command
+/// expands to the exit status of the previous command. See
+/// [`run_trap`](super::run_trap) for how the result of the trap action is
+/// handled.
+pub async fn run_debug_trap(env: &mut Env) -> Result {
+    if env.stack.contains(&Frame::Trap(Condition::Debug)) {
+        return Continue(());
+    }
+
+    run_trap_if_set(env, Condition::Debug).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::echo_builtin;
+    use crate::tests::exit_builtin;
+    use crate::tests::set_debug_trap;
+    use futures_util::FutureExt;
+    use std::ops::ControlFlow::Break;
+    use yash_env::semantics::Divert;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env_test_helper::assert_stdout;
+
+    #[test]
+    fn does_nothing_if_debug_trap_is_not_set() {
+        let mut env = Env::new_virtual();
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+    }
+
+    #[test]
+    fn runs_trap_preserving_exit_status() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.builtins.insert("echo", echo_builtin());
+        set_debug_trap(&mut env, "echo DEBUG $?");
+        env.exit_status = ExitStatus(2);
+
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(2));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "DEBUG 2\n"));
+    }
+
+    #[test]
+    fn does_nothing_in_debug_trap() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.builtins.insert("echo", echo_builtin());
+        set_debug_trap(&mut env, "echo DEBUG");
+        let mut env = env.push_frame(Frame::Trap(Condition::Debug));
+
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn exit_in_trap() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("exit", exit_builtin());
+        set_debug_trap(&mut env, "exit 5");
+
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Exit(Some(ExitStatus(5)))));
+    }
+}
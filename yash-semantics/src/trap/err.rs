@@ -0,0 +1,119 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Running the ERR trap
+
+use super::run_trap_if_set;
+use std::ops::ControlFlow::Continue;
+use yash_env::semantics::Result;
+use yash_env::stack::Frame;
+use yash_env::trap::Condition;
+use yash_env::Env;
+
+/// Tests whether the ERR trap should be run in the current context.
+///
+/// The trap is not run while executing a condition (the stack contains a
+/// [`Frame::Condition`]) or the ERR trap itself.
+fn err_trap_is_applicable(env: &Env) -> bool {
+    !env.stack
+        .iter()
+        .any(|frame| matches!(frame, Frame::Condition | Frame::Trap(Condition::Err)))
+}
+
+/// Executes the ERR trap if the current exit status is non-zero.
+///
+/// This function runs the ERR trap if `env.exit_status` is non-zero and the
+/// trap is applicable in the current context, that is, under the same
+/// conditions where the `ErrExit` shell option would make the shell exit,
+/// regardless of whether the option is actually on. The trap is not run in a
+/// condition such as the condition of an `if` command or the left-hand side of
+/// an `&&` or `||` operator, or while the ERR trap itself is running.
+///
+/// The exit status of the trap action does not affect the exit status of the
+/// current environment. See [`run_trap`](super::run_trap) for how the result
+/// of the trap action is handled.
+pub async fn run_err_trap(env: &mut Env) -> Result {
+    if env.exit_status.is_successful() || !err_trap_is_applicable(env) {
+        return Continue(());
+    }
+
+    run_trap_if_set(env, Condition::Err).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::env_with_err_trap;
+    use futures_util::FutureExt;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env_test_helper::assert_stdout;
+
+    #[test]
+    fn runs_trap_on_non_zero_exit_status() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?; return -n 7");
+        env.exit_status = ExitStatus(3);
+
+        let result = run_err_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(3));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "ERR 3\n"));
+    }
+
+    #[test]
+    fn does_nothing_on_zero_exit_status() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?; return -n 7");
+        env.exit_status = ExitStatus::SUCCESS;
+
+        let result = run_err_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn does_nothing_if_err_trap_is_not_set() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus::FAILURE;
+        let result = run_err_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+    }
+
+    #[test]
+    fn does_nothing_in_condition() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?; return -n 7");
+        let mut env = env.push_frame(Frame::Condition);
+        env.exit_status = ExitStatus::FAILURE;
+
+        let result = run_err_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn does_nothing_in_err_trap() {
+        let system = VirtualSystem::new();
+        let mut env = env_with_err_trap(system.clone(), "echo ERR $?; return -n 7");
+        let mut env = env.push_frame(Frame::Trap(Condition::Err));
+        env.exit_status = ExitStatus::FAILURE;
+
+        let result = run_err_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, ""));
+    }
+}
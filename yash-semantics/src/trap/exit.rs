@@ -16,9 +16,7 @@
 
 //! Running the EXIT trap
 
-use super::run_trap;
-use std::rc::Rc;
-use yash_env::trap::Action;
+use super::run_trap_if_set;
 use yash_env::trap::Condition;
 use yash_env::Env;
 
@@ -32,16 +30,7 @@ use yash_env::Env;
 /// with a `Break(divert)` where `divert.exit_status()` is `Some` exit status,
 /// that exit status is set to `env.exit_status`.
 pub async fn run_exit_trap(env: &mut Env) {
-    let Some(state) = env.traps.get_state(Condition::Exit).0 else {
-        return;
-    };
-    let Action::Command(command) = &state.action else {
-        return;
-    };
-
-    let command = Rc::clone(command);
-    let origin = state.origin.clone();
-    let result = run_trap(env, Condition::Exit, command, origin).await;
+    let result = run_trap_if_set(env, Condition::Exit).await;
     env.apply_result(result);
 }
 
@@ -55,11 +44,13 @@ mod tests {
     use futures_util::FutureExt;
     use std::future::Future;
     use std::pin::Pin;
+    use std::rc::Rc;
     use yash_env::builtin::Builtin;
     use yash_env::semantics::ExitStatus;
     use yash_env::semantics::Field;
     use yash_env::stack::Frame;
     use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::trap::Action;
     use yash_env_test_helper::assert_stdout;
     use yash_syntax::source::Location;
 
@@ -0,0 +1,178 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Convenience functions for word expansion
+
+use crate::expansion::attr::{AttrChar, AttrField, Origin};
+use crate::expansion::{expand_word_multiple, Error};
+use yash_env::Env;
+use yash_syntax::source::Location;
+use yash_syntax::syntax::Word;
+
+/// Expands a word to strings.
+///
+/// This function is a simplified interface to the word expansion for
+/// embedders that do not need the details provided by the [`expansion`]
+/// module. It performs the tilde expansion, parameter expansion, command
+/// substitution, and arithmetic expansion followed by field splitting,
+/// pathname expansion, and quote removal, just like the expansion of a word in
+/// a simple command. The result contains any number of fields.
+///
+/// Unlike [`expansion::expand_word`], this function may produce more than one
+/// field, and the results do not retain the origins of the fields. The exit
+/// status of command substitutions is discarded. Use
+/// [`expand_word_multiple`] if you need them.
+///
+/// [`expansion`]: crate::expansion
+/// [`expansion::expand_word`]: crate::expansion::expand_word
+pub async fn expand_word(env: &mut Env, word: &Word) -> Result<Vec<String>, Error> {
+    let mut fields = Vec::new();
+    expand_word_multiple(env, word, &mut fields).await?;
+    Ok(fields.into_iter().map(|field| field.value).collect())
+}
+
+/// Performs pathname expansion on a pattern.
+///
+/// This function is a simplified interface to the
+/// [pathname expansion](crate::expansion::glob) for embedders. It scans the
+/// file system for pathnames matching the pattern and returns them sorted
+/// alphabetically. A leading period in a filename must be matched explicitly,
+/// so `*` does not match hidden files.
+///
+/// A backslash in the pattern quotes the next character, making it match
+/// literally. If no pathnames match, or if the `Glob` option is off, the
+/// result is the pattern with such backslashes removed.
+pub fn glob(env: &mut Env, pattern: &str) -> Vec<String> {
+    let mut chars = Vec::with_capacity(pattern.len());
+    let mut is_quoted = false;
+    for value in pattern.chars() {
+        let is_quoting = !is_quoted && value == '\\';
+        chars.push(AttrChar {
+            value,
+            origin: Origin::Literal,
+            is_quoted,
+            is_quoting,
+        });
+        is_quoted = is_quoting;
+    }
+    let origin = Location::dummy(pattern);
+    let field = AttrField { chars, origin };
+    crate::expansion::glob::glob(env, field)
+        .map(|field| field.value)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::variable::Scope;
+
+    #[test]
+    fn unquoted_parameter_is_split() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign(" foo  bar ", None)
+            .unwrap();
+        let word = "$v".parse().unwrap();
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        assert_eq!(result.unwrap(), ["foo", "bar"]);
+    }
+
+    #[test]
+    fn quoted_parameter_is_single_field() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign(" foo  bar ", None)
+            .unwrap();
+        let word = "\"$v\"".parse().unwrap();
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        assert_eq!(result.unwrap(), [" foo  bar "]);
+    }
+
+    #[test]
+    fn arithmetic_expansion_and_quote_removal() {
+        let mut env = Env::new_virtual();
+        let word = "'a'$((1+2))\\b".parse().unwrap();
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        assert_eq!(result.unwrap(), ["a3b"]);
+    }
+
+    #[test]
+    fn empty_expansion_yields_no_fields() {
+        let mut env = Env::new_virtual();
+        let word = "$unset".parse().unwrap();
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        assert_eq!(result.unwrap(), [] as [&str; 0]);
+    }
+
+    #[test]
+    fn expansion_error() {
+        let mut env = Env::new_virtual();
+        let word = "${unset?}".parse().unwrap();
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        assert!(result.is_err(), "{result:?}");
+    }
+
+    fn env_with_files<const N: usize>(paths: [&str; N]) -> Env {
+        let system = yash_env::VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        for path in paths {
+            state.file_system.save(path, Default::default()).unwrap();
+        }
+        drop(state);
+        Env::with_system(Box::new(system))
+    }
+
+    #[test]
+    fn glob_lists_sorted_matches() {
+        let mut env = env_with_files(["/dir/foo", "/dir/bar", "/dir/baz/qux"]);
+        let result = glob(&mut env, "/dir/*");
+        assert_eq!(result, ["/dir/bar", "/dir/baz", "/dir/foo"]);
+    }
+
+    #[test]
+    fn glob_does_not_match_hidden_files_with_asterisk() {
+        let mut env = env_with_files(["/dir/.hidden", "/dir/visible"]);
+        let result = glob(&mut env, "/dir/*");
+        assert_eq!(result, ["/dir/visible"]);
+
+        let result = glob(&mut env, "/dir/.h*");
+        assert_eq!(result, ["/dir/.hidden"]);
+    }
+
+    #[test]
+    fn glob_returns_pattern_on_no_match() {
+        let mut env = env_with_files(["/dir/foo"]);
+        let result = glob(&mut env, "/dir/*.txt");
+        assert_eq!(result, ["/dir/*.txt"]);
+    }
+
+    #[test]
+    fn glob_with_escaped_character() {
+        let mut env = env_with_files(["/dir/foo", "/dir/*"]);
+        let result = glob(&mut env, r"/dir/\*");
+        assert_eq!(result, ["/dir/*"]);
+
+        let result = glob(&mut env, r"/dir/\f*");
+        assert_eq!(result, ["/dir/foo"]);
+
+        let result = glob(&mut env, r"/dir/\?");
+        assert_eq!(result, ["/dir/?"]);
+    }
+}
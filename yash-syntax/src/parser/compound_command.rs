@@ -65,7 +65,10 @@ impl Parser<'_, '_> {
     pub async fn compound_command(&mut self) -> Result<Option<CompoundCommand>> {
         match self.peek_token().await?.id {
             Token(Some(OpenBrace)) => self.grouping().await.map(Some),
-            Operator(OpenParen) => self.subshell().await.map(Some),
+            Operator(OpenParen) => match self.arithmetic_command().await? {
+                Some(command) => Ok(Some(command)),
+                None => self.subshell().await.map(Some),
+            },
             Token(Some(For)) => self.for_loop().await.map(Some),
             Token(Some(While)) => self.while_loop().await.map(Some),
             Token(Some(Until)) => self.until_loop().await.map(Some),
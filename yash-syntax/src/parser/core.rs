@@ -23,12 +23,13 @@ use super::error::Error;
 use super::error::SyntaxError;
 use super::lex::Keyword;
 use super::lex::Lexer;
+use super::lex::Operator::OpenParen;
 use super::lex::Token;
 use super::lex::TokenId::*;
 use crate::alias::Glossary;
-use crate::parser::lex::is_blank;
 use crate::syntax::HereDoc;
 use crate::syntax::MaybeLiteral;
+use crate::syntax::TextUnit;
 use crate::syntax::Word;
 use std::rc::Rc;
 
@@ -279,6 +280,27 @@ impl<'a, 'b> Parser<'a, 'b> {
         self.token.take().unwrap()
     }
 
+    /// Parses the next token as the start of an arithmetic command.
+    ///
+    /// If the next token is a `(` operator, this function discards the token
+    /// and lets the lexer parse the characters from the `(` by
+    /// [`Lexer::arithmetic_expansion`]. If the characters are `((` followed by
+    /// an arithmetic expression and `))`, the result is returned as a
+    /// [`TextUnit::Arith`]. Otherwise, the lexer is rewound to the `(` and this
+    /// function returns `Ok(None)`, in which case the next token will be read
+    /// again.
+    pub async fn arithmetic_command_unit(&mut self) -> Result<Option<TextUnit>> {
+        let token = self.peek_token().await?;
+        if token.id != Operator(OpenParen) {
+            return Ok(None);
+        }
+
+        let index = token.index;
+        self.token = None;
+        self.lexer.rewind(index);
+        self.lexer.arithmetic_expansion(index).await
+    }
+
     /// Performs alias substitution on a token that has just been
     /// [taken](Self::take_token_raw).
     fn substitute_alias(&mut self, token: Token, is_command_name: bool) -> Rec<Token> {
@@ -376,8 +398,9 @@ impl<'a, 'b> Parser<'a, 'b> {
     /// peeked.
     pub async fn has_blank(&mut self) -> Result<bool> {
         assert!(self.token.is_none(), "There should be no pending token");
+        let blank_class = self.lexer.blank_class();
         let c = self.lexer.peek_char().await?;
-        Ok(c.map_or(false, is_blank))
+        Ok(c.is_some_and(|c| blank_class.is_blank(c)))
     }
 
     /// Remembers the given partial here-document for later parsing of its content.
@@ -427,6 +450,25 @@ impl<'a, 'b> Parser<'a, 'b> {
         }
     }
 
+    /// Returns the index of the next character to be parsed.
+    ///
+    /// If the next token has already been read from the lexer, the result is
+    /// the index of the first character of the token. Otherwise, the result is
+    /// the current index of the lexer.
+    pub(super) fn next_index(&self) -> usize {
+        match &self.token {
+            Some(Ok(token)) => token.index,
+            _ => self.lexer.index(),
+        }
+    }
+
+    /// Returns the current index of the lexer.
+    ///
+    /// This is the index of the character following the last consumed one.
+    pub(super) fn lexer_index(&self) -> usize {
+        self.lexer.index()
+    }
+
     /// Determines whether a word names a declaration utility.
     ///
     /// See [`decl_utils`](crate::decl_util) for more information.
@@ -645,6 +687,106 @@ mod tests {
         assert_eq!(token.to_string(), "Y");
     }
 
+    #[test]
+    fn parser_take_token_manual_after_chained_blank_ending_substitutions() {
+        let mut lexer = Lexer::with_code("X Y Z");
+        #[allow(clippy::mutable_key_type)]
+        let mut aliases = AliasSet::new();
+        aliases.insert(HashEntry::new(
+            "X".to_string(),
+            "x ".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        aliases.insert(HashEntry::new(
+            "Y".to_string(),
+            "y ".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        aliases.insert(HashEntry::new(
+            "Z".to_string(),
+            "z".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        let mut parser = Parser::config().aliases(&aliases).input(&mut lexer);
+
+        let result = parser.take_token_manual(true).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(true).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "x");
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "y");
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "z");
+    }
+
+    #[test]
+    fn parser_take_token_manual_reserved_word_after_blank_ending_substitution() {
+        let mut lexer = Lexer::with_code("X Y if");
+        #[allow(clippy::mutable_key_type)]
+        let mut aliases = AliasSet::new();
+        aliases.insert(HashEntry::new(
+            "X".to_string(),
+            "x ".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        aliases.insert(HashEntry::new(
+            "Y".to_string(),
+            "if ".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        aliases.insert(HashEntry::new(
+            "if".to_string(),
+            "i".to_string(),
+            false,
+            Location::dummy("?"),
+        ));
+        let mut parser = Parser::config().aliases(&aliases).input(&mut lexer);
+
+        let result = parser.take_token_manual(true).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(true).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "x");
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        // "if" is not a reserved word here, so the one resulting from the
+        // substitution of "Y" is subject to alias substitution.
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "i");
+
+        // The original "if" follows the blank-ending substitution of "Y".
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "i");
+    }
+
     #[test]
     fn parser_take_token_manual_global() {
         let mut lexer = Lexer::with_code("X");
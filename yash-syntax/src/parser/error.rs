@@ -56,6 +56,9 @@ pub enum SyntaxError {
     UnclosedCommandSubstitution { opening_location: Location },
     /// A command substitution started with `` ` `` but lacks a closing `` ` ``.
     UnclosedBackquote { opening_location: Location },
+    /// A process substitution started with `<(` or `>(` but lacks a closing
+    /// `)`.
+    UnclosedProcessSubstitution { opening_location: Location },
     /// An arithmetic expansion lacks a closing `))`.
     UnclosedArith { opening_location: Location },
     /// A command begins with an inappropriate keyword or operator token.
@@ -165,6 +168,8 @@ pub enum SyntaxError {
     MissingCommandAfterBang,
     /// A command is missing after a `|` token.
     MissingCommandAfterBar,
+    /// A command is missing after a `time` token.
+    MissingCommandAfterTime,
     /// There is a redundant token.
     RedundantToken,
     /// A control escape (`\c...`) is incomplete in a dollar-single-quoted string.
@@ -204,6 +209,7 @@ impl SyntaxError {
             MultipleModifier => "a suffix modifier cannot be used together with a prefix modifier",
             UnclosedCommandSubstitution { .. } => "the command substitution is not closed",
             UnclosedBackquote { .. } => "the backquote is not closed",
+            UnclosedProcessSubstitution { .. } => "the process substitution is not closed",
             UnclosedArith { .. } => "the arithmetic expansion is not closed",
             InvalidCommandToken => "the command starts with an inappropriate token",
             MissingSeparator => "a separator is missing between the commands",
@@ -257,6 +263,7 @@ impl SyntaxError {
             BangAfterBar => "`!` cannot be used in the middle of a pipeline",
             MissingCommandAfterBang => "a command is missing after `!`",
             MissingCommandAfterBar => "a command is missing after `|`",
+            MissingCommandAfterTime => "a command is missing after `time`",
             RedundantToken => "there is a redundant token",
             IncompleteControlEscape => "the control escape is incomplete",
             IncompleteControlBackslashEscape => "the control-backslash escape is incomplete",
@@ -279,6 +286,7 @@ impl SyntaxError {
             InvalidEscape => "invalid escape sequence",
             UnclosedParen { .. }
             | UnclosedCommandSubstitution { .. }
+            | UnclosedProcessSubstitution { .. }
             | UnclosedArrayValue { .. }
             | UnclosedSubshell { .. }
             | UnclosedPatternList
@@ -295,7 +303,8 @@ impl SyntaxError {
             | EmptyElse
             | MissingPipeline(_)
             | MissingCommandAfterBang
-            | MissingCommandAfterBar => "expected a command",
+            | MissingCommandAfterBar
+            | MissingCommandAfterTime => "expected a command",
             InvalidForValue | MissingCaseSubject | InvalidCaseSubject | MissingPattern
             | InvalidPattern => "expected a word",
             UnclosedSingleQuote { .. } | UnclosedDollarSingleQuote { .. } => "expected `'`",
@@ -370,6 +379,9 @@ impl SyntaxError {
             UnclosedCommandSubstitution { opening_location } => {
                 Some((opening_location, "the command substitution started here"))
             }
+            UnclosedProcessSubstitution { opening_location } => {
+                Some((opening_location, "the process substitution started here"))
+            }
             UnclosedBackquote { opening_location } => {
                 Some((opening_location, "the opening backquote was here"))
             }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-//! Syntax parser for grouping and subshell
+//! Syntax parser for grouping, subshell, and arithmetic command
 
 use super::core::Parser;
 use super::core::Result;
@@ -24,6 +24,7 @@ use super::lex::Keyword::{CloseBrace, OpenBrace};
 use super::lex::Operator::{CloseParen, OpenParen};
 use super::lex::TokenId::{Operator, Token};
 use crate::syntax::CompoundCommand;
+use crate::syntax::TextUnit;
 use std::rc::Rc;
 
 impl Parser<'_, '_> {
@@ -58,6 +59,24 @@ impl Parser<'_, '_> {
         Ok(CompoundCommand::Grouping(list))
     }
 
+    /// Parses an arithmetic command.
+    ///
+    /// The arithmetic command is a `((`, an arithmetic expression, and a `))`.
+    /// If the next token is not a `(` immediately followed by another `(`, or
+    /// the expression is not closed by `))`, this function returns `Ok(None)`
+    /// without consuming any characters so that the caller can parse the
+    /// command as a [subshell](Self::subshell). This means `((cmd) )` and
+    /// `( (cmd) )` are parsed as nested subshells.
+    pub async fn arithmetic_command(&mut self) -> Result<Option<CompoundCommand>> {
+        match self.arithmetic_command_unit().await? {
+            Some(TextUnit::Arith { content, location }) => {
+                Ok(Some(CompoundCommand::Arith { content, location }))
+            }
+            Some(_) => unreachable!(),
+            None => Ok(None),
+        }
+    }
+
     /// Parses a subshell.
     ///
     /// The next token must be a `(`.
@@ -260,4 +279,75 @@ mod tests {
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 2..3);
     }
+
+    #[test]
+    fn parser_arithmetic_command() {
+        let mut lexer = Lexer::with_code(" ((a+b)) >/dev/null");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::Arith { content, location } => {
+            assert_eq!(content.to_string(), "a+b");
+            assert_eq!(*location.code.value.borrow(), " ((a+b)) >/dev/null");
+            assert_eq!(location.code.start_line_number.get(), 1);
+            assert_eq!(*location.code.source, Source::Unknown);
+            assert_eq!(location.range, 1..8);
+        });
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.to_string(), ">");
+    }
+
+    #[test]
+    fn parser_arithmetic_command_with_nested_parentheses() {
+        let mut lexer = Lexer::with_code("(( (1+2)*$x ))");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::Arith { content, .. } => {
+            assert_eq!(content.to_string(), " (1+2)*$x ");
+        });
+    }
+
+    #[test]
+    fn parser_nested_subshells_with_space() {
+        let mut lexer = Lexer::with_code("( (cmd) )");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::Subshell { body, location } => {
+            assert_eq!(body.to_string(), "(cmd)");
+            assert_eq!(location.range, 0..1);
+        });
+    }
+
+    #[test]
+    fn parser_nested_subshells_without_space() {
+        let mut lexer = Lexer::with_code("((cmd); (cmd2) )");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::Subshell { body, location } => {
+            assert_eq!(body.to_string(), "(cmd); (cmd2)");
+            assert_eq!(location.range, 0..1);
+        });
+    }
+
+    #[test]
+    fn parser_arithmetic_command_unclosed() {
+        let mut lexer = Lexer::with_code("((1+2");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedArith { opening_location }) => {
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 5..5);
+    }
 }
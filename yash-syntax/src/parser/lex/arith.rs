@@ -143,6 +143,25 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
     }
 
+    #[test]
+    fn lexer_arithmetic_expansion_nested_parentheses() {
+        let mut lexer = Lexer::with_code("$(( (1+2)*3 ))");
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.arithmetic_expansion(0).now_or_never().unwrap();
+        let text_unit = result.unwrap().unwrap();
+        assert_matches!(text_unit, TextUnit::Arith { content, location } => {
+            assert_eq!(content.to_string(), " (1+2)*3 ");
+            assert_eq!(*location.code.value.borrow(), "$(( (1+2)*3 ))");
+            assert_eq!(location.code.start_line_number.get(), 1);
+            assert_eq!(*location.code.source, Source::Unknown);
+            assert_eq!(location.range, 0..14);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
     #[test]
     fn lexer_arithmetic_expansion_escapes() {
         let mut lexer = Lexer::with_code(r#".$((\\\"\`\$));"#);
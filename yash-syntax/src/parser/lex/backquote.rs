@@ -87,6 +87,7 @@ mod tests {
     use crate::parser::error::ErrorCause;
     use crate::parser::lex::Lexer;
     use crate::source::Source;
+    use crate::syntax::Unquote;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
 
@@ -219,6 +220,47 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
     }
 
+    #[test]
+    fn lexer_backquote_content_equivalent_to_command_substitution() {
+        let mut lexer = Lexer::with_code("`echo hi`");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let backquote = lexer.backquote().now_or_never().unwrap().unwrap().unwrap();
+
+        let mut lexer = Lexer::with_code("$(echo hi)");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let subst = lexer.text_unit(|_| false, |_| true);
+        let subst = subst.now_or_never().unwrap().unwrap().unwrap();
+
+        let backquote_content = assert_matches!(backquote, TextUnit::Backquote { content, .. } => {
+            content.unquote().0
+        });
+        assert_matches!(subst, TextUnit::CommandSubst { content, .. } => {
+            assert_eq!(&*content, backquote_content);
+        });
+    }
+
+    #[test]
+    fn lexer_backquote_nested_by_escaping() {
+        let mut lexer = Lexer::with_code(r"`echo \`echo \\\`hi\\\`\``");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let result = lexer.backquote().now_or_never().unwrap().unwrap().unwrap();
+        assert_matches!(result, TextUnit::Backquote { content, location } => {
+            assert_eq!(content.unquote().0, r"echo `echo \`hi\``");
+            assert_eq!(location.range, 0..26);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
     #[test]
     fn lexer_backquote_unclosed_empty() {
         let mut lexer = Lexer::with_code("`");
@@ -197,8 +197,10 @@ mod tests {
     use crate::source::Source;
     use crate::syntax::SwitchCondition;
     use crate::syntax::SwitchType;
+    use crate::syntax::TextUnit;
     use crate::syntax::TrimLength;
     use crate::syntax::TrimSide;
+    use crate::syntax::WordUnit;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
 
@@ -791,6 +793,95 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
     }
 
+    #[test]
+    fn lexer_braced_param_nested_expansions_in_switch_word() {
+        let mut lexer = Lexer::with_code("${foo:-${bar:-$baz}$(qux)}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_matches!(param.modifier, Modifier::Switch(switch) => {
+            assert_eq!(switch.r#type, SwitchType::Default);
+            assert_eq!(switch.condition, SwitchCondition::UnsetOrEmpty);
+            assert_matches!(&switch.word.units[..], [
+                WordUnit::Unquoted(TextUnit::BracedParam(inner)),
+                WordUnit::Unquoted(TextUnit::CommandSubst { content, .. }),
+            ] => {
+                assert_eq!(inner.param, Param::variable("bar"));
+                assert_eq!(inner.to_string(), "${bar:-$baz}");
+                assert_eq!(inner.location.range, 7..19);
+                assert_eq!(&**content, "qux");
+            });
+        });
+        assert_eq!(param.location.range, 0..26);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_braced_param_nested_expansion_in_trim_word() {
+        let mut lexer = Lexer::with_code("${foo%%${bar}*}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_matches!(param.modifier, Modifier::Trim(trim) => {
+            assert_eq!(trim.side, TrimSide::Suffix);
+            assert_eq!(trim.length, TrimLength::Longest);
+            assert_eq!(trim.pattern.to_string(), "${bar}*");
+        });
+        assert_eq!(param.location.range, 0..15);
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_after_modifier() {
+        let mut lexer = Lexer::with_code("${foo:-bar");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(*opening_location.code.value.borrow(), "${foo:-bar");
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 10..10);
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_nested_in_modifier() {
+        let mut lexer = Lexer::with_code("${foo:-${bar}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 13..13);
+    }
+
     #[test]
     fn lexer_braced_param_multiple_modifier() {
         let mut lexer = Lexer::with_code("${#x+};");
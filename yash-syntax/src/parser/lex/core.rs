@@ -41,9 +41,46 @@ use std::pin::Pin;
 use std::rc::Rc;
 
 /// Returns true if the character is a blank character.
+///
+/// This function is equivalent to [`BlankClass::is_blank`] with the default
+/// class, [`BlankClass::Unicode`]. The lexer uses the class specified in
+/// [`Config::blank_class`] instead of this function.
 pub fn is_blank(c: char) -> bool {
-    // TODO locale
-    c != '\n' && c.is_whitespace()
+    BlankClass::Unicode.is_blank(c)
+}
+
+/// Set of characters the lexer treats as blanks
+///
+/// Blank characters separate tokens. The newline is never a blank because it
+/// is a token by itself.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BlankClass {
+    /// ASCII whitespace characters other than the newline
+    ///
+    /// This class contains the space, horizontal tab, vertical tab, form feed,
+    /// and carriage return. Use this class to tokenize input as in the POSIX
+    /// locale.
+    Ascii,
+
+    /// Unicode whitespace characters other than the newline
+    ///
+    /// In addition to the characters in [`Ascii`](Self::Ascii), this class
+    /// contains Unicode spaces such as the no-break space (U+00A0) and the
+    /// ideographic space (U+3000). This is the default.
+    #[default]
+    Unicode,
+}
+
+impl BlankClass {
+    /// Returns true if the character is a blank character in this class.
+    #[must_use]
+    pub fn is_blank(self, c: char) -> bool {
+        match self {
+            BlankClass::Ascii => matches!(c, ' ' | '\t' | '\x0B' | '\x0C' | '\r'),
+            BlankClass::Unicode => c != '\n' && c.is_whitespace(),
+        }
+    }
 }
 
 /// Result of [`LexerCore::peek_char`]
@@ -162,10 +199,13 @@ struct LexerCore<'a> {
     raw_code: Rc<Code>,
     source: Vec<SourceCharEx>,
     index: usize,
+    blank_class: BlankClass,
 }
 
 impl<'a> LexerCore<'a> {
     /// Creates a new lexer core that reads using the given input function.
+    ///
+    /// The lexer core uses the default [`BlankClass`].
     #[must_use]
     fn new(
         input: Box<dyn InputObject + 'a>,
@@ -182,9 +222,19 @@ impl<'a> LexerCore<'a> {
             state: InputState::Alive,
             source: Vec::new(),
             index: 0,
+            blank_class: BlankClass::default(),
         }
     }
 
+    /// Creates a new lexer core with the given configuration.
+    #[must_use]
+    fn with_config(input: Box<dyn InputObject + 'a>, config: Config) -> LexerCore<'a> {
+        let source = config.source.unwrap_or_else(|| Rc::new(Source::Unknown));
+        let mut core = Self::new(input, config.start_line_number, source);
+        core.blank_class = config.blank_class;
+        core
+    }
+
     /// Computes the start index of the location at the current position.
     #[must_use]
     fn next_index(&self) -> usize {
@@ -396,9 +446,11 @@ impl<'a> LexerCore<'a> {
     ///
     /// If `index` is larger than the currently read index.
     fn is_after_blank_ending_alias(&self, index: usize) -> bool {
-        fn ends_with_blank(s: &str) -> bool {
-            s.chars().next_back().map_or(false, is_blank)
-        }
+        let ends_with_blank = |s: &str| {
+            s.chars()
+                .next_back()
+                .is_some_and(|c| self.blank_class.is_blank(c))
+        };
         fn is_same_alias(alias: &Alias, sc: Option<&SourceCharEx>) -> bool {
             sc.is_some_and(|sc| sc.value.location.code.source.is_alias_for(&alias.name))
         }
@@ -406,7 +458,7 @@ impl<'a> LexerCore<'a> {
         for index in (0..index).rev() {
             let sc = &self.source[index];
 
-            if !sc.is_line_continuation && !is_blank(sc.value.value) {
+            if !sc.is_line_continuation && !self.blank_class.is_blank(sc.value.value) {
                 return false;
             }
 
@@ -467,6 +519,11 @@ pub struct Config {
     /// indicate the location of possible errors that occur during parsing and
     /// execution.
     pub source: Option<Rc<Source>>,
+
+    /// Set of characters recognized as blanks
+    ///
+    /// The default value is [`BlankClass::Unicode`].
+    pub blank_class: BlankClass,
 }
 
 impl Config {
@@ -477,15 +534,14 @@ impl Config {
         Config {
             start_line_number: NonZeroU64::MIN,
             source: None,
+            blank_class: BlankClass::default(),
         }
     }
 
     /// Creates a lexer with the given input object.
     pub fn input<'a>(self, input: Box<dyn InputObject + 'a>) -> Lexer<'a> {
-        let start_line_number = self.start_line_number;
-        let source = self.source.unwrap_or_else(|| Rc::new(Source::Unknown));
         Lexer {
-            core: LexerCore::new(input, start_line_number, source),
+            core: LexerCore::with_config(input, self),
             line_continuation_enabled: true,
         }
     }
@@ -580,10 +636,11 @@ impl<'a> Lexer<'a> {
     /// string using [`Memory`] with the specified source starting from line
     /// number 1.
     ///
-    /// This function is soft-deprecated. Use [`with_code`](Self::with_code)
+    /// This function is deprecated. Use [`with_code`](Self::with_code)
     /// instead if the source is `Unknown`. Otherwise, use
     /// [`config`](Self::config) to set the source and [`input`](Config::input)
     /// to create a lexer, which is more descriptive.
+    #[deprecated = "use `Lexer::config` or `Lexer::with_code` instead"]
     pub fn from_memory<S: Into<Rc<Source>>>(code: &'a str, source: S) -> Lexer<'a> {
         fn inner(code: &str, source: Rc<Source>) -> Lexer {
             let mut config = Lexer::config();
@@ -714,6 +771,15 @@ impl<'a> Lexer<'a> {
         self.core.index()
     }
 
+    /// Returns the set of characters this lexer recognizes as blanks.
+    ///
+    /// The set is specified by [`Config::blank_class`] when the lexer is
+    /// created.
+    #[must_use]
+    pub fn blank_class(&self) -> BlankClass {
+        self.core.blank_class
+    }
+
     /// Moves the current position back to the given index so that characters that have been
     /// consumed can be read again.
     ///
@@ -958,6 +1024,22 @@ mod tests {
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
 
+    #[test]
+    fn blank_classes() {
+        for c in [' ', '\t', '\x0B', '\x0C', '\r'] {
+            assert!(BlankClass::Ascii.is_blank(c), "{c:?}");
+            assert!(BlankClass::Unicode.is_blank(c), "{c:?}");
+        }
+        for c in ['\u{A0}', '\u{2003}', '\u{3000}'] {
+            assert!(!BlankClass::Ascii.is_blank(c), "{c:?}");
+            assert!(BlankClass::Unicode.is_blank(c), "{c:?}");
+        }
+        for c in ['\n', 'a', '_'] {
+            assert!(!BlankClass::Ascii.is_blank(c), "{c:?}");
+            assert!(!BlankClass::Unicode.is_blank(c), "{c:?}");
+        }
+    }
+
     #[test]
     fn lexer_core_peek_char_empty_source() {
         let input = Memory::new("");
@@ -1514,6 +1596,72 @@ mod tests {
         assert!(lexer.is_after_blank_ending_alias(6));
     }
 
+    #[test]
+    fn lexer_config_default() {
+        let mut lexer = Lexer::config().input(Box::new(Memory::new("foo")));
+        let location = lexer.location().now_or_never().unwrap().unwrap();
+        assert_eq!(location.code.start_line_number.get(), 1);
+        assert_eq!(*location.code.source, Source::Unknown);
+    }
+
+    #[test]
+    fn lexer_config_with_start_line_number_and_source() {
+        let mut config = Lexer::config();
+        config.start_line_number = NonZeroU64::new(10).unwrap();
+        config.source = Some(Source::Stdin.into());
+        let mut lexer = config.input(Box::new(Memory::new("echo\nfoo\n")));
+
+        let token = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(token.word.to_string(), "echo");
+        let location = &token.word.location;
+        assert_eq!(location.code.start_line_number.get(), 10);
+        assert_eq!(*location.code.source, Source::Stdin);
+        assert_eq!(location.code.line_number(location.range.start).get(), 10);
+
+        let token = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(token.id, TokenId::Operator(Operator::Newline));
+
+        let token = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(token.word.to_string(), "foo");
+        let location = &token.word.location;
+        assert_eq!(location.code.start_line_number.get(), 10);
+        assert_eq!(*location.code.source, Source::Stdin);
+        assert_eq!(location.code.line_number(location.range.start).get(), 11);
+    }
+
+    #[test]
+    fn lexer_token_on_second_line() {
+        let mut lexer = Lexer::with_code("echo\n  foo bar\n");
+        lexer.token().now_or_never().unwrap().unwrap();
+        lexer.token().now_or_never().unwrap().unwrap();
+        lexer.skip_blanks().now_or_never().unwrap().unwrap();
+
+        let token = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(token.word.to_string(), "foo");
+        let location = &token.word.location;
+        assert_eq!(location.range, 7..10);
+        assert_eq!(location.code.line_number(location.range.start).get(), 2);
+        lexer.skip_blanks().now_or_never().unwrap().unwrap();
+
+        let token = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(token.word.to_string(), "bar");
+        let location = &token.word.location;
+        assert_eq!(location.code.line_number(location.range.start).get(), 2);
+    }
+
+    #[test]
+    fn lexer_location_at_end_of_input_after_newline() {
+        let mut lexer = Lexer::with_code("foo\n");
+        for _ in 0..4 {
+            lexer.peek_char().now_or_never().unwrap().unwrap();
+            lexer.consume_char();
+        }
+
+        let location = lexer.location().now_or_never().unwrap().unwrap();
+        assert_eq!(location.range, 4..4);
+        assert_eq!(location.code.line_number(location.range.start).get(), 2);
+    }
+
     #[test]
     fn lexer_with_empty_source() {
         let mut lexer = Lexer::with_code("");
@@ -1684,7 +1832,9 @@ mod tests {
 
     #[test]
     fn lexer_location_range_with_nonempty_range() {
-        let mut lexer = Lexer::from_memory("cat foo", Source::Stdin);
+        let mut config = Lexer::config();
+        config.source = Some(Source::Stdin.into());
+        let mut lexer = config.input(Box::new(Memory::new("cat foo")));
         for _ in 0..4 {
             lexer.peek_char().now_or_never().unwrap().unwrap();
             lexer.consume_char();
@@ -1700,7 +1850,9 @@ mod tests {
 
     #[test]
     fn lexer_location_range_with_range_starting_at_end() {
-        let mut lexer = Lexer::from_memory("cat", Source::Stdin);
+        let mut config = Lexer::config();
+        config.source = Some(Source::Stdin.into());
+        let mut lexer = config.input(Box::new(Memory::new("cat")));
         for _ in 0..3 {
             lexer.peek_char().now_or_never().unwrap().unwrap();
             lexer.consume_char();
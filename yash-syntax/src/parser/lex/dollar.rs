@@ -190,6 +190,35 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
     }
 
+    #[test]
+    fn lexer_dollar_unit_command_substitution_with_subshell() {
+        let mut lexer = Lexer::with_code("$( (foo) )");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let result = lexer.dollar_unit().now_or_never().unwrap();
+        let text_unit = result.unwrap().unwrap();
+        assert_matches!(text_unit, TextUnit::CommandSubst { location, content } => {
+            assert_eq!(location.range, 0..10);
+            assert_eq!(&*content, " (foo) ");
+        });
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+
+        let mut lexer = Lexer::with_code("$((foo) )");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let result = lexer.dollar_unit().now_or_never().unwrap();
+        let text_unit = result.unwrap().unwrap();
+        assert_matches!(text_unit, TextUnit::CommandSubst { location, content } => {
+            assert_eq!(location.range, 0..9);
+            assert_eq!(&*content, "(foo) ");
+        });
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
     #[test]
     fn lexer_dollar_unit_line_continuation() {
         let mut lexer = Lexer::with_code("$\\\n\\\n0");
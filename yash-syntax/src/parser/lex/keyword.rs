@@ -51,6 +51,7 @@ pub enum Keyword {
     If,
     In,
     Then,
+    Time,
     Until,
     While,
     /// `{`
@@ -79,6 +80,7 @@ impl Keyword {
             If => "if",
             In => "in",
             Then => "then",
+            Time => "time",
             Until => "until",
             While => "while",
             OpenBrace => "{",
@@ -95,7 +97,7 @@ impl Keyword {
         use Keyword::*;
         match self {
             Do | Done | Elif | Else | Esac | Fi | Then | CloseBrace => true,
-            Bang | OpenBracketBracket | Case | For | Function | If | In | Until | While
+            Bang | OpenBracketBracket | Case | For | Function | If | In | Time | Until | While
             | OpenBrace => false,
         }
     }
@@ -126,6 +128,7 @@ impl FromStr for Keyword {
             "if" => Ok(If),
             "in" => Ok(In),
             "then" => Ok(Then),
+            "time" => Ok(Time),
             "until" => Ok(Until),
             "while" => Ok(While),
             "{" => Ok(OpenBrace),
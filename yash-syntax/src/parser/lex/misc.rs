@@ -16,7 +16,6 @@
 
 //! Extension of the core for implementing the rest of the lexer
 
-use super::core::is_blank;
 use super::core::Lexer;
 use crate::parser::core::Result;
 
@@ -35,8 +34,12 @@ impl Lexer<'_> {
     }
 
     /// Skips blank characters until reaching a non-blank.
+    ///
+    /// Blank characters are determined by the lexer's
+    /// [blank class](Lexer::blank_class).
     pub async fn skip_blanks(&mut self) -> Result<()> {
-        while self.skip_if(is_blank).await? {}
+        let blank_class = self.blank_class();
+        while self.skip_if(|c| blank_class.is_blank(c)).await? {}
         Ok(())
     }
 
@@ -193,4 +196,18 @@ mod tests {
         .unwrap();
         assert_eq!(c, Ok(None));
     }
+
+    #[test]
+    fn lexer_skip_comment_shebang() {
+        let mut lexer = Lexer::with_code("#!/bin/sh\necho");
+
+        let c = async {
+            lexer.skip_comment().await?;
+            lexer.peek_char().await
+        }
+        .now_or_never()
+        .unwrap();
+        assert_eq!(c, Ok(Some('\n')));
+        assert_eq!(lexer.index(), 9);
+    }
 }
@@ -0,0 +1,182 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Part of the lexer that parses process substitutions
+
+use super::core::Lexer;
+use crate::parser::core::Result;
+use crate::parser::error::Error;
+use crate::parser::error::SyntaxError;
+use crate::syntax::ProcessSubstDirection;
+use crate::syntax::WordUnit;
+
+impl Lexer<'_> {
+    /// Parses a process substitution of the form `<(...)` or `>(...)`.
+    ///
+    /// This function examines the next two characters to see if they begin a
+    /// process substitution. If they are `<(` or `>(`, the following
+    /// characters are parsed as commands to find a matching `)`, which will be
+    /// consumed before this function returns. Otherwise, no characters are
+    /// consumed and the return value is `Ok(None)`.
+    pub async fn process_substitution(&mut self) -> Result<Option<WordUnit>> {
+        let start_index = self.index();
+        let direction = match self.peek_char().await? {
+            Some('<') => ProcessSubstDirection::In,
+            Some('>') => ProcessSubstDirection::Out,
+            _ => return Ok(None),
+        };
+        self.consume_char();
+
+        let opening_location = match self.consume_char_if(|c| c == '(').await? {
+            Some(ch) => ch.location.clone(),
+            None => {
+                self.rewind(start_index);
+                return Ok(None);
+            }
+        };
+
+        let content = self.inner_program_boxed().await?.into();
+
+        if !self.skip_if(|c| c == ')').await? {
+            let cause = SyntaxError::UnclosedProcessSubstitution { opening_location }.into();
+            let location = self.location().await?.clone();
+            return Err(Error { cause, location });
+        }
+
+        let location = self.location_range(start_index..self.index());
+        Ok(Some(WordUnit::ProcessSubst {
+            direction,
+            content,
+            location,
+        }))
+    }
+
+    /// Tests if a process substitution starts at the current position.
+    ///
+    /// This function does not consume any characters.
+    pub(super) async fn is_process_substitution_ahead(&mut self) -> Result<bool> {
+        let start_index = self.index();
+        if self
+            .consume_char_if(|c| c == '<' || c == '>')
+            .await?
+            .is_none()
+        {
+            return Ok(false);
+        }
+        let result = self.peek_char().await? == Some('(');
+        self.rewind(start_index);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::error::ErrorCause;
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn lexer_process_substitution_in() {
+        let mut lexer = Lexer::with_code("<( foo bar )baz");
+
+        let result = lexer.process_substitution().now_or_never().unwrap();
+        let unit = result.unwrap().unwrap();
+        assert_matches!(unit, WordUnit::ProcessSubst { direction, content, location } => {
+            assert_eq!(direction, ProcessSubstDirection::In);
+            assert_eq!(&*content, " foo bar ");
+            assert_eq!(location.range, 0..12);
+        });
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('b')));
+    }
+
+    #[test]
+    fn lexer_process_substitution_out() {
+        let mut lexer = Lexer::with_code(">(cat)");
+
+        let result = lexer.process_substitution().now_or_never().unwrap();
+        let unit = result.unwrap().unwrap();
+        assert_matches!(unit, WordUnit::ProcessSubst { direction, content, location } => {
+            assert_eq!(direction, ProcessSubstDirection::Out);
+            assert_eq!(&*content, "cat");
+            assert_eq!(location.range, 0..6);
+        });
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_process_substitution_none() {
+        let mut lexer = Lexer::with_code("< (foo)");
+
+        let result = lexer.process_substitution().now_or_never().unwrap();
+        assert_eq!(result, Ok(None));
+        assert_eq!(lexer.index(), 0);
+    }
+
+    #[test]
+    fn lexer_process_substitution_unclosed() {
+        let mut lexer = Lexer::with_code("<(foo");
+
+        let result = lexer.process_substitution().now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedProcessSubstitution { opening_location }) => {
+            assert_eq!(opening_location.range, 1..2);
+        });
+        assert_eq!(e.location.range, 5..5);
+    }
+
+    #[test]
+    fn lexer_is_process_substitution_ahead() {
+        let mut lexer = Lexer::with_code("<(");
+        assert_eq!(
+            lexer
+                .is_process_substitution_ahead()
+                .now_or_never()
+                .unwrap(),
+            Ok(true)
+        );
+        assert_eq!(lexer.index(), 0);
+
+        let mut lexer = Lexer::with_code(">(");
+        assert_eq!(
+            lexer
+                .is_process_substitution_ahead()
+                .now_or_never()
+                .unwrap(),
+            Ok(true)
+        );
+
+        let mut lexer = Lexer::with_code("<<(");
+        assert_eq!(
+            lexer
+                .is_process_substitution_ahead()
+                .now_or_never()
+                .unwrap(),
+            Ok(false)
+        );
+        assert_eq!(lexer.index(), 0);
+
+        let mut lexer = Lexer::with_code("(");
+        assert_eq!(
+            lexer
+                .is_process_substitution_ahead()
+                .now_or_never()
+                .unwrap(),
+            Ok(false)
+        );
+    }
+}
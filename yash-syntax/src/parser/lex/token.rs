@@ -30,6 +30,8 @@ use crate::syntax::Word;
 /// Tests whether the given character is a token delimiter.
 ///
 /// A character is a token delimiter if it is either a whitespace or [operator](is_operator_char).
+/// Whitespaces are determined by [`is_blank`], but [`Lexer::token`] uses the
+/// [blank class](Lexer::blank_class) of the lexer instead.
 pub fn is_token_delimiter_char(c: char) -> bool {
     is_operator_char(c) || is_blank(c)
 }
@@ -66,17 +68,21 @@ impl Lexer<'_> {
     /// If there is no more token that can be parsed, the result is a token with an empty word and
     /// [`EndOfInput`](TokenId::EndOfInput) token identifier.
     pub async fn token(&mut self) -> Result<Token> {
-        if let Some(op) = self.operator().await? {
-            return Ok(op);
+        if !self.is_process_substitution_ahead().await? {
+            if let Some(op) = self.operator().await? {
+                return Ok(op);
+            }
         }
 
         let index = self.index();
+        let blank_class = self.blank_class();
 
         let mut word_lexer = WordLexer {
             lexer: self,
             context: WordContext::Word,
         };
-        let mut word = word_lexer.word(is_token_delimiter_char).await?;
+        let is_delimiter = |c| is_operator_char(c) || blank_class.is_blank(c);
+        let mut word = word_lexer.word(is_delimiter).await?;
         word.parse_tilde_front();
 
         let id = self.token_id(&word).await?;
@@ -88,11 +94,40 @@ impl Lexer<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::Memory;
+    use crate::parser::lex::BlankClass;
+    use crate::parser::lex::Operator;
     use crate::source::Source;
     use crate::syntax::TextUnit;
     use crate::syntax::WordUnit;
+    use assert_matches::assert_matches;
     use futures_util::FutureExt;
 
+    #[test]
+    fn lexer_token_unicode_space_with_unicode_blank_class() {
+        let mut lexer = Lexer::with_code("a\u{3000}b");
+
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.word.to_string(), "a");
+        lexer.skip_blanks().now_or_never().unwrap().unwrap();
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.word.to_string(), "b");
+        assert_eq!(t.index, 2);
+    }
+
+    #[test]
+    fn lexer_token_unicode_space_with_ascii_blank_class() {
+        let mut config = Lexer::config();
+        config.blank_class = BlankClass::Ascii;
+        let mut lexer = config.input(Box::new(Memory::new("a\u{3000}b c")));
+
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.word.to_string(), "a\u{3000}b");
+        lexer.skip_blanks().now_or_never().unwrap().unwrap();
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.word.to_string(), "c");
+    }
+
     #[test]
     fn lexer_token_empty() {
         // If there's no word unit that can be parsed, it is the end of input.
@@ -134,6 +169,23 @@ mod tests {
         assert_eq!(t.word.units, [WordUnit::Tilde("a:~".to_string())]);
     }
 
+    #[test]
+    fn lexer_token_process_substitution() {
+        let mut lexer = Lexer::with_code("<(a)b>(c) <");
+
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.word.to_string(), "<(a)b>(c)");
+        assert_eq!(t.word.units.len(), 3);
+        assert_matches!(&t.word.units[0], WordUnit::ProcessSubst { .. });
+        assert_eq!(t.word.units[1], WordUnit::Unquoted(TextUnit::Literal('b')));
+        assert_matches!(&t.word.units[2], WordUnit::ProcessSubst { .. });
+        assert_eq!(t.id, TokenId::Token(None));
+
+        lexer.skip_blanks().now_or_never().unwrap().unwrap();
+        let t = lexer.token().now_or_never().unwrap().unwrap();
+        assert_eq!(t.id, TokenId::Operator(Operator::Less));
+    }
+
     #[test]
     fn lexer_token_io_number_delimited_by_less() {
         let mut lexer = Lexer::with_code("12<");
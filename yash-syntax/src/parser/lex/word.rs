@@ -94,6 +94,9 @@ impl WordLexer<'_, '_> {
     /// escaped. If `Text`, then `$`, `"`, `` ` `` and `\` can be escaped as
     /// well as delimiters.
     ///
+    /// In the `Word` context, a process substitution is parsed if it starts
+    /// with a `<` or `>` that is a delimiter.
+    ///
     /// This function does not parse tilde expansion. See [`word`](Self::word).
     pub async fn word_unit<F>(&mut self, is_delimiter: F) -> Result<Option<WordUnit>>
     where
@@ -129,6 +132,9 @@ impl WordLexer<'_, '_> {
                 self.consume_char();
                 self.double_quote(location).await.map(Some)
             }
+            Some(c @ ('<' | '>')) if allow_single_quote && is_delimiter(c) => {
+                self.process_substitution().await
+            }
             _ => {
                 let unit = self.text_unit(is_delimiter, is_escapable).await?;
                 if allow_single_quote && unit == Some(TextUnit::Literal('$')) {
@@ -26,6 +26,7 @@ use super::lex::TokenId::{self, EndOfInput, IoNumber, Operator, Token};
 use crate::syntax::Item;
 use crate::syntax::List;
 use std::future::Future;
+use std::ops::Range;
 use std::pin::Pin;
 use std::rc::Rc;
 
@@ -37,9 +38,8 @@ fn error_type_for_trailing_token_in_command_line(token_id: TokenId) -> Option<Sy
         EndOfInput => None,
         Token(None) | IoNumber => Some(MissingSeparator),
         Token(Some(keyword)) => match keyword {
-            Bang | OpenBracketBracket | Case | For | Function | If | Until | While | OpenBrace => {
-                Some(MissingSeparator)
-            }
+            Bang | OpenBracketBracket | Case | For | Function | If | Time | Until | While
+            | OpenBrace => Some(MissingSeparator),
             Do => Some(UnopenedLoop),
             Done => Some(UnopenedDoClause),
             Elif | Else | Fi | Then => Some(UnopenedIf),
@@ -156,6 +156,26 @@ impl Parser<'_, '_> {
         Ok(Some(list))
     }
 
+    /// Parses a complete command and reports the source range it occupied.
+    ///
+    /// This function works like [`command_line`](Self::command_line), but
+    /// also returns the range of character indices of the source code consumed
+    /// for the complete command. The range starts at the next token (or at the
+    /// current position of the lexer if the next token has not yet been read)
+    /// and ends after the delimiting newline and here-document contents, if
+    /// any. Pass the range to [`Lexer::source_string`] or
+    /// [`Lexer::location_range`] to obtain the source text or location of the
+    /// command.
+    ///
+    /// [`Lexer::source_string`]: super::lex::Lexer::source_string
+    /// [`Lexer::location_range`]: super::lex::Lexer::location_range
+    pub async fn command_line_with_range(&mut self) -> Result<Option<(List, Range<usize>)>> {
+        let start = self.next_index();
+        let list = self.command_line().await?;
+        let end = self.lexer_index();
+        Ok(list.map(|list| (list, start..end)))
+    }
+
     /// Parses an optional compound list.
     ///
     /// A compound list is a sequence of one or more and-or lists that are
@@ -210,10 +230,14 @@ mod tests {
     use crate::source::Source;
     use crate::syntax::AndOrList;
     use crate::syntax::Command;
+    use crate::syntax::HereDoc;
+    use crate::syntax::Param;
     use crate::syntax::Pipeline;
     use crate::syntax::RedirBody;
+    use crate::syntax::TextUnit;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
+    use std::rc::Rc;
 
     #[test]
     fn parser_list_eof() {
@@ -293,8 +317,13 @@ mod tests {
         assert_eq!(item.async_flag, None);
         let AndOrList { first, rest } = &*item.and_or;
         assert!(rest.is_empty(), "expected empty rest: {rest:?}");
-        let Pipeline { commands, negation } = first;
+        let Pipeline {
+            commands,
+            negation,
+            timed,
+        } = first;
         assert_eq!(*negation, false);
+        assert_eq!(*timed, false);
         assert_eq!(commands.len(), 1);
         let cmd = assert_matches!(*commands[0], Command::Simple(ref c) => c);
         assert_eq!(cmd.words, []);
@@ -307,6 +336,68 @@ mod tests {
         });
     }
 
+    fn here_doc_of_command_line(code: &str) -> Rc<HereDoc> {
+        let mut lexer = Lexer::with_code(code);
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let List(items) = result.unwrap().unwrap();
+        let first = &items[0].and_or.first;
+        let cmd = assert_matches!(*first.commands[0], Command::Simple(ref c) => c);
+        assert_matches!(cmd.redirs[0].body, RedirBody::HereDoc(ref here_doc) => {
+            Rc::clone(here_doc)
+        })
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_with_unquoted_delimiter() {
+        let here_doc = here_doc_of_command_line("cat <<END\n$foo `bar`\nEND\n");
+        assert_eq!(here_doc.delimiter.to_string(), "END");
+        let content = here_doc.content.get().unwrap();
+        assert_matches!(&content.0[..], [
+            TextUnit::RawParam { param, .. },
+            TextUnit::Literal(' '),
+            TextUnit::Backquote { .. },
+            TextUnit::Literal('\n'),
+        ] => {
+            assert_eq!(*param, Param::variable("foo"));
+        });
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_with_quoted_delimiter() {
+        for delimiter in ["'END'", "\"END\"", "\\END", "E'N'D"] {
+            let code = format!("cat <<{delimiter}\n$foo `bar`\nEND\n");
+            let here_doc = here_doc_of_command_line(&code);
+            assert_eq!(here_doc.delimiter.to_string(), delimiter);
+            let content = here_doc.content.get().unwrap();
+            assert_eq!(content.to_string(), "$foo `bar`\n");
+            assert!(
+                content
+                    .0
+                    .iter()
+                    .all(|unit| matches!(unit, TextUnit::Literal(_))),
+                "{delimiter}: {content:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_with_tabs_removed() {
+        let here_doc = here_doc_of_command_line("cat <<-END\n\t\tfoo\n\t bar\n\tEND\n");
+        assert_eq!(here_doc.remove_tabs, true);
+        let content = here_doc.content.get().unwrap();
+        assert_eq!(content.to_string(), "foo\n bar\n");
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_with_tabs_kept() {
+        let here_doc = here_doc_of_command_line("cat <<END\n\t\tfoo\n\tEND\nEND\n");
+        assert_eq!(here_doc.remove_tabs, false);
+        let content = here_doc.content.get().unwrap();
+        assert_eq!(content.to_string(), "\t\tfoo\n\tEND\n");
+    }
+
     #[test]
     fn parser_command_line_command_without_newline() {
         let mut lexer = Lexer::with_code("foo");
@@ -385,6 +476,76 @@ mod tests {
         assert_eq!(e.location.range, 9..10);
     }
 
+    #[test]
+    fn parser_command_line_with_range_single_line() {
+        let mut lexer = Lexer::with_code("echo 1; echo 2\necho 3\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let (list, range) = parser
+            .command_line_with_range()
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(list.to_string(), "echo 1; echo 2");
+        assert_eq!(range, 0..15);
+
+        let (list, range) = parser
+            .command_line_with_range()
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(list.to_string(), "echo 3");
+        assert_eq!(range, 15..22);
+
+        let result = parser.command_line_with_range().now_or_never().unwrap();
+        assert_eq!(result, Ok(None));
+        drop(parser);
+
+        assert_eq!(lexer.source_string(0..15), "echo 1; echo 2\n");
+        assert_eq!(lexer.source_string(15..22), "echo 3\n");
+    }
+
+    #[test]
+    fn parser_command_line_with_range_multiple_lines() {
+        let code = "if true\nthen\n  cat <<END\nhere\nEND\nfi\nnext";
+        let mut lexer = Lexer::with_code(code);
+        let mut parser = Parser::new(&mut lexer);
+
+        let (_, range) = parser
+            .command_line_with_range()
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        drop(parser);
+
+        let expected = "if true\nthen\n  cat <<END\nhere\nEND\nfi\n";
+        assert_eq!(range, 0..expected.chars().count());
+        assert_eq!(lexer.source_string(range.clone()), expected);
+        let location = lexer.location_range(range.clone());
+        assert_eq!(location.code.start_line_number.get(), 1);
+        assert_eq!(location.range, range);
+    }
+
+    #[test]
+    fn parser_command_line_with_range_after_peeked_token() {
+        let mut lexer = Lexer::with_code("  foo bar");
+        let mut parser = Parser::new(&mut lexer);
+        parser.peek_token().now_or_never().unwrap().unwrap();
+
+        let (_, range) = parser
+            .command_line_with_range()
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        drop(parser);
+        assert_eq!(range, 2..9);
+        assert_eq!(lexer.source_string(range), "foo bar");
+    }
+
     #[test]
     fn parser_maybe_compound_list_empty() {
         let mut lexer = Lexer::with_code("");
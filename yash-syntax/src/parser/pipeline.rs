@@ -21,7 +21,7 @@ use super::core::Rec;
 use super::core::Result;
 use super::error::Error;
 use super::error::SyntaxError;
-use super::lex::Keyword::Bang;
+use super::lex::Keyword::{Bang, Time};
 use super::lex::Operator::Bar;
 use super::lex::TokenId::{Operator, Token};
 use crate::syntax::Pipeline;
@@ -33,38 +33,51 @@ impl Parser<'_, '_> {
     /// If there is no valid pipeline at the current position, this function
     /// returns `Ok(Rec::Parsed(None))`.
     pub async fn pipeline(&mut self) -> Result<Rec<Option<Pipeline>>> {
-        // Parse the first command
-        let (first, negation) = match self.command().await? {
-            Rec::AliasSubstituted => return Ok(Rec::AliasSubstituted),
-            Rec::Parsed(Some(first)) => (first, false),
-            Rec::Parsed(None) => {
-                // Parse the `!` reserved word
-                if self.peek_token().await?.id != Token(Some(Bang)) {
-                    return Ok(Rec::Parsed(None));
+        // Parse the first command, optionally preceded by `!` and `time`.
+        // The `time` reserved word is recognized only here; elsewhere it is
+        // parsed as a normal word by the simple command parser.
+        let mut negation = false;
+        let mut timed = false;
+        let mut last_reserved_word = None;
+        let first = loop {
+            let keyword = match self.peek_token().await?.id {
+                Token(Some(Bang)) if negation => {
+                    let location = self.take_token_raw().await?.word.location;
+                    let cause = SyntaxError::DoubleNegation.into();
+                    return Err(Error { cause, location });
                 }
-                self.take_token_raw().await?;
-                // TODO Warn if `!` is immediately followed by `(`, which is
-                // not POSIXly portable.
-
-                // Parse the command after the `!`
-                loop {
-                    match self.command().await? {
-                        Rec::AliasSubstituted => continue,
-                        Rec::Parsed(Some(first)) => break (first, true),
-                        Rec::Parsed(None) => {
-                            // Error: the command is missing
-                            let next = self.take_token_raw().await?;
-                            let cause = if next.id == Token(Some(Bang)) {
-                                SyntaxError::DoubleNegation.into()
-                            } else {
-                                SyntaxError::MissingCommandAfterBang.into()
-                            };
-                            let location = next.word.location;
-                            return Err(Error { cause, location });
-                        }
-                    }
+                Token(Some(Bang)) => {
+                    negation = true;
+                    Bang
+                }
+                Token(Some(Time)) if !timed => {
+                    timed = true;
+                    Time
                 }
-            }
+                _ => match self.command().await? {
+                    Rec::AliasSubstituted if last_reserved_word.is_none() => {
+                        return Ok(Rec::AliasSubstituted)
+                    }
+                    Rec::AliasSubstituted => continue,
+                    Rec::Parsed(Some(first)) => break first,
+                    Rec::Parsed(None) => {
+                        let cause = match last_reserved_word {
+                            None => return Ok(Rec::Parsed(None)),
+                            Some(Bang) => SyntaxError::MissingCommandAfterBang,
+                            Some(_) => SyntaxError::MissingCommandAfterTime,
+                        };
+                        // Error: the command is missing
+                        let location = self.take_token_raw().await?.word.location;
+                        let cause = cause.into();
+                        return Err(Error { cause, location });
+                    }
+                },
+            };
+
+            self.take_token_raw().await?;
+            last_reserved_word = Some(keyword);
+            // TODO Warn if `!` is immediately followed by `(`, which is
+            // not POSIXly portable.
         };
 
         // Parse `|`
@@ -95,7 +108,11 @@ impl Parser<'_, '_> {
             commands.push(Rc::new(next));
         }
 
-        Ok(Rec::Parsed(Some(Pipeline { commands, negation })))
+        Ok(Rec::Parsed(Some(Pipeline {
+            commands,
+            negation,
+            timed,
+        })))
     }
 }
 
@@ -186,6 +203,109 @@ mod tests {
         assert_eq!(e.location.range, 1..2);
     }
 
+    #[test]
+    fn parser_pipeline_timed() {
+        let mut lexer = Lexer::with_code("time foo | bar");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, true);
+        assert_eq!(p.negation, false);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "foo");
+        assert_eq!(p.commands[1].to_string(), "bar");
+    }
+
+    #[test]
+    fn parser_pipeline_timed_and_negated() {
+        let mut lexer = Lexer::with_code("time ! foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, true);
+        assert_eq!(p.negation, true);
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "foo");
+    }
+
+    #[test]
+    fn parser_pipeline_time_as_argument() {
+        let mut lexer = Lexer::with_code("foo time");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, false);
+        assert_eq!(p.commands[0].to_string(), "foo time");
+    }
+
+    #[test]
+    fn parser_pipeline_missing_command_after_time() {
+        let mut lexer = Lexer::with_code("time\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.pipeline().now_or_never().unwrap().unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::MissingCommandAfterTime)
+        );
+        assert_eq!(*e.location.code.value.borrow(), "time\n");
+        assert_eq!(e.location.range, 4..5);
+    }
+
+    #[test]
+    fn parser_pipeline_negated_and_timed() {
+        let mut lexer = Lexer::with_code("! time foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, true);
+        assert_eq!(p.negation, true);
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "foo");
+    }
+
+    #[test]
+    fn parser_pipeline_missing_command_after_negation_and_time() {
+        let mut lexer = Lexer::with_code("! time\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.pipeline().now_or_never().unwrap().unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::MissingCommandAfterTime)
+        );
+        assert_eq!(e.location.range, 6..7);
+    }
+
+    #[test]
+    fn parser_pipeline_time_after_bar() {
+        let mut lexer = Lexer::with_code("foo | time bar");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, false);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "foo");
+        assert_eq!(p.commands[1].to_string(), "time bar");
+    }
+
+    #[test]
+    fn parser_pipeline_time_after_time() {
+        let mut lexer = Lexer::with_code("time time foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.timed, true);
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "time foo");
+    }
+
     #[test]
     fn parser_pipeline_missing_command_after_bar() {
         let mut lexer = Lexer::with_code("foo | ;");
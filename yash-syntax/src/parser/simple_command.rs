@@ -21,6 +21,7 @@ use super::core::Rec;
 use super::core::Result;
 use super::error::Error;
 use super::error::SyntaxError;
+use super::lex::Keyword::Time;
 use super::lex::Operator::{CloseParen, Newline, OpenParen};
 use super::lex::TokenId::{Operator, Token};
 use crate::syntax::Array;
@@ -130,7 +131,9 @@ impl Parser<'_, '_> {
 
             // Filter token type
             match self.peek_token().await?.id {
-                Token(Some(_keyword)) if result.is_empty() => break,
+                // `time` is a reserved word only at the beginning of a pipeline,
+                // which the pipeline parser handles.
+                Token(Some(keyword)) if result.is_empty() && keyword != Time => break,
                 Token(_) => (),
                 _ => break,
             }
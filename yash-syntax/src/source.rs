@@ -29,6 +29,7 @@ use std::cell::RefCell;
 use std::num::NonZeroU64;
 use std::ops::Range;
 use std::rc::Rc;
+use unicode_width::UnicodeWidthChar;
 
 /// Origin of source code
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -61,6 +62,9 @@ pub enum Source {
     /// Command substitution
     CommandSubst { original: Location },
 
+    /// Process substitution
+    ProcessSubst { original: Location },
+
     /// Arithmetic expansion
     Arith { original: Location },
 
@@ -170,6 +174,7 @@ impl Source {
             CommandFile { path } => path,
             Alias { .. } => "<alias>",
             CommandSubst { .. } => "<command_substitution>",
+            ProcessSubst { .. } => "<process_substitution>",
             Arith { .. } => "<arithmetic_expansion>",
             Eval { .. } => "<eval>",
             DotScript { name, .. } => name,
@@ -223,8 +228,39 @@ impl Code {
             .unwrap_or(u64::MAX);
         self.start_line_number.saturating_add(newlines)
     }
+
+    /// Computes the column number of the character at the given index.
+    ///
+    /// The index should be between 0 and `self.value.borrow().chars().count()`.
+    /// The return value is the display column where the character at
+    /// `char_index` starts in its line, counted from 1. Columns are counted in
+    /// the display width of the preceding characters in the line rather than
+    /// the number of characters or bytes: wide characters such as CJK
+    /// ideographs occupy two columns, a tab advances to the next tab stop (at
+    /// every [`TAB_WIDTH`] columns), and control characters occupy no column.
+    /// If `char_index` is out of bounds, the return value is for the end of
+    /// the last line.
+    ///
+    /// This function will panic if `self.value` has been mutually borrowed.
+    #[must_use]
+    pub fn column_number(&self, char_index: usize) -> NonZeroU64 {
+        let value = self.value.borrow();
+        let preceding_chars = value.chars().take(char_index);
+        let width = preceding_chars.fold(0, |width, c| match c {
+            '\n' => 0,
+            '\t' => (width / TAB_WIDTH + 1) * TAB_WIDTH,
+            _ => width + c.width().unwrap_or(0),
+        });
+        let width = u64::try_from(width).unwrap_or(u64::MAX);
+        NonZeroU64::MIN.saturating_add(width)
+    }
 }
 
+/// Number of columns between tab stops
+///
+/// See [`Code::column_number`].
+pub const TAB_WIDTH: usize = 8;
+
 /// Creates an iterator of [source char](SourceChar)s from a string.
 ///
 /// `index_offset` will be the index of the first source char's location.
@@ -298,6 +334,47 @@ impl Location {
         }
         with_line(value.into())
     }
+
+    /// Returns the line and column numbers of the start of this location.
+    ///
+    /// The line number is computed by [`Code::line_number`] and the column
+    /// number by [`Code::column_number`]. The result can be formatted as
+    /// `line:column`:
+    ///
+    /// ```
+    /// # use yash_syntax::source::Location;
+    /// let mut location = Location::dummy("echo\n\t日本 foo\n");
+    /// location.range = 9..12;
+    /// assert_eq!(location.position().to_string(), "2:14");
+    /// ```
+    ///
+    /// This function will panic if `self.code.value` has been mutually
+    /// borrowed.
+    #[must_use]
+    pub fn position(&self) -> Position {
+        Position {
+            line: self.code.line_number(self.range.start),
+            column: self.code.column_number(self.range.start),
+        }
+    }
+}
+
+/// Line and column numbers of a position in source code
+///
+/// An instance of `Position` is usually obtained by [`Location::position`].
+/// The `Display` implementation formats the position as `line:column`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Position {
+    /// Line number, counted from 1
+    pub line: NonZeroU64,
+    /// Display column number, counted from 1
+    pub column: NonZeroU64,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
 }
 
 /// Character with source description
@@ -344,4 +421,85 @@ mod tests {
         assert_eq!(code.line_number(7).get(), 5);
         assert_eq!(code.line_number(usize::MAX).get(), 5);
     }
+
+    fn code(value: &str) -> Code {
+        Code {
+            value: RefCell::new(value.to_string()),
+            start_line_number: NonZeroU64::new(1).unwrap(),
+            source: Rc::new(Source::Unknown),
+        }
+    }
+
+    #[test]
+    fn column_number_of_ascii_characters() {
+        let code = code("abc\nde\n");
+        assert_eq!(code.column_number(0).get(), 1);
+        assert_eq!(code.column_number(1).get(), 2);
+        assert_eq!(code.column_number(2).get(), 3);
+        assert_eq!(code.column_number(3).get(), 4);
+        assert_eq!(code.column_number(4).get(), 1);
+        assert_eq!(code.column_number(5).get(), 2);
+        assert_eq!(code.column_number(6).get(), 3);
+        assert_eq!(code.column_number(7).get(), 1);
+        assert_eq!(code.column_number(usize::MAX).get(), 1);
+    }
+
+    #[test]
+    fn column_number_with_tabs() {
+        let code = code("\ta\tb\n1234567\tc\n12345678\td");
+        assert_eq!(code.column_number(0).get(), 1);
+        assert_eq!(code.column_number(1).get(), 9);
+        assert_eq!(code.column_number(2).get(), 10);
+        assert_eq!(code.column_number(3).get(), 17);
+        assert_eq!(code.column_number(12).get(), 8);
+        assert_eq!(code.column_number(13).get(), 9);
+        assert_eq!(code.column_number(23).get(), 9);
+        assert_eq!(code.column_number(24).get(), 17);
+    }
+
+    #[test]
+    fn column_number_with_wide_characters() {
+        let code = code("日本語x\nかな\tz");
+        assert_eq!(code.column_number(0).get(), 1);
+        assert_eq!(code.column_number(1).get(), 3);
+        assert_eq!(code.column_number(2).get(), 5);
+        assert_eq!(code.column_number(3).get(), 7);
+        assert_eq!(code.column_number(4).get(), 8);
+        assert_eq!(code.column_number(5).get(), 1);
+        assert_eq!(code.column_number(6).get(), 3);
+        assert_eq!(code.column_number(7).get(), 5);
+        assert_eq!(code.column_number(8).get(), 9);
+    }
+
+    #[test]
+    fn column_number_with_zero_width_characters() {
+        // U+0301 is a combining acute accent.
+        let code = code("e\u{301}x\u{7}y");
+        assert_eq!(code.column_number(1).get(), 2);
+        assert_eq!(code.column_number(2).get(), 2);
+        assert_eq!(code.column_number(3).get(), 3);
+        assert_eq!(code.column_number(4).get(), 3);
+    }
+
+    #[test]
+    fn location_position() {
+        let code = Rc::new(Code {
+            start_line_number: NonZeroU64::new(10).unwrap(),
+            ..code("foo\n\t漢字 bar\n")
+        });
+        let location = Location {
+            code: Rc::clone(&code),
+            range: 0..3,
+        };
+        let position = location.position();
+        assert_eq!(position.line.get(), 10);
+        assert_eq!(position.column.get(), 1);
+        assert_eq!(position.to_string(), "10:1");
+
+        let location = Location { code, range: 8..11 };
+        let position = location.position();
+        assert_eq!(position.line.get(), 11);
+        assert_eq!(position.column.get(), 14);
+        assert_eq!(position.to_string(), "11:14");
+    }
 }
@@ -159,6 +159,14 @@ impl super::Source {
                     original,
                 )));
             }
+            ProcessSubst { original } => {
+                // TODO Use Extend::extend_one
+                result.extend(std::iter::once(Annotation::new(
+                    AnnotationType::Info,
+                    "process substitution appeared here".into(),
+                    original,
+                )));
+            }
             Arith { original } => {
                 // TODO Use Extend::extend_one
                 result.extend(std::iter::once(Annotation::new(
@@ -400,6 +400,16 @@ pub enum EscapeUnit {
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct EscapedString(pub Vec<EscapeUnit>);
 
+/// Direction of a [process substitution](WordUnit::ProcessSubst)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ProcessSubstDirection {
+    /// `<(...)`: The command's output can be read from the substituted path.
+    In,
+    /// `>(...)`: Data written to the substituted path is passed to the
+    /// command's input.
+    Out,
+}
+
 /// Element of a [Word], i.e., text with quotes and tilde expansion
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum WordUnit {
@@ -415,6 +425,18 @@ pub enum WordUnit {
     ///
     /// The `String` value does not contain the initial tilde.
     Tilde(String),
+    /// Process substitution of the form `<(...)` or `>(...)`
+    ///
+    /// This is a non-POSIX extension.
+    ProcessSubst {
+        /// Whether the substitution is `<(...)` or `>(...)`
+        direction: ProcessSubstDirection,
+        /// Command string that will be parsed and executed when the process
+        /// substitution is expanded
+        content: Rc<str>,
+        /// Position of this process substitution in the source code
+        location: Location,
+    },
 }
 
 pub use WordUnit::*;
@@ -480,6 +502,20 @@ impl Fd {
     pub const STDOUT: Fd = Fd(1);
     /// File descriptor for the standard error
     pub const STDERR: Fd = Fd(2);
+
+    /// Creates a file descriptor from a raw value.
+    ///
+    /// Returns `None` if the value is negative. Unlike `Fd(raw_fd)` and
+    /// `Fd::from(raw_fd)`, this function ensures that the result is a valid
+    /// file descriptor number.
+    #[must_use]
+    pub const fn new(raw_fd: RawFd) -> Option<Fd> {
+        if raw_fd < 0 {
+            None
+        } else {
+            Some(Fd(raw_fd))
+        }
+    }
 }
 
 /// Redirection operators
@@ -694,6 +730,11 @@ pub enum CompoundCommand {
     },
     /// Case conditional construct
     Case { subject: Word, items: Vec<CaseItem> },
+    /// Arithmetic command
+    ///
+    /// The content is the arithmetic expression between `((` and `))`. The
+    /// location covers the whole command including the parentheses.
+    Arith { content: Text, location: Location },
     // TODO [[ ]]
 }
 
@@ -740,6 +781,10 @@ pub struct Pipeline {
     pub commands: Vec<Rc<Command>>,
     /// Whether the pipeline begins with a `!`
     pub negation: bool,
+    /// Whether the pipeline begins with a `time`
+    ///
+    /// The `time` reserved word precedes the `!`, if any.
+    pub timed: bool,
 }
 
 /// Condition that decides if a [Pipeline] in an [and-or list](AndOrList) should be executed
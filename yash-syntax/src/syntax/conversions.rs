@@ -455,12 +455,22 @@ impl Unquote for WordUnit {
                 w.write_str(inner)?;
                 Ok(true)
             }
-            DoubleQuote(inner) => inner.write_unquoted(w),
-            DollarSingleQuote(inner) => inner.write_unquoted(w),
+            DoubleQuote(inner) => {
+                inner.write_unquoted(w)?;
+                Ok(true)
+            }
+            DollarSingleQuote(inner) => {
+                inner.write_unquoted(w)?;
+                Ok(true)
+            }
             Tilde(s) => {
                 write!(w, "~{s}")?;
                 Ok(false)
             }
+            ProcessSubst { .. } => {
+                write!(w, "{self}")?;
+                Ok(false)
+            }
         }
     }
 }
@@ -525,6 +535,12 @@ impl From<RawFd> for Fd {
     }
 }
 
+impl From<Fd> for RawFd {
+    fn from(fd: Fd) -> RawFd {
+        fd.0
+    }
+}
+
 impl TryFrom<Operator> for RedirOp {
     type Error = TryFromOperatorError;
     fn try_from(op: Operator) -> Result<RedirOp, TryFromOperatorError> {
@@ -630,6 +646,23 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
 
+    #[test]
+    fn fd_conversions() {
+        assert_eq!(Fd::from(5), Fd(5));
+        assert_eq!(RawFd::from(Fd(7)), 7);
+        let raw_fd: RawFd = Fd::STDERR.into();
+        assert_eq!(raw_fd, 2);
+    }
+
+    #[test]
+    fn fd_new() {
+        assert_eq!(Fd::new(0), Some(Fd::STDIN));
+        assert_eq!(Fd::new(10), Some(Fd(10)));
+        assert_eq!(Fd::new(RawFd::MAX), Some(Fd(RawFd::MAX)));
+        assert_eq!(Fd::new(-1), None);
+        assert_eq!(Fd::new(RawFd::MIN), None);
+    }
+
     #[test]
     fn special_param_from_str() {
         assert_eq!("@".parse(), Ok(SpecialParam::At));
@@ -905,6 +938,18 @@ mod tests {
         assert_eq!(is_quoted, true);
     }
 
+    #[test]
+    fn word_unquote_with_quotes_without_escapes() {
+        let word = Word::from_str(r#""abc""#).unwrap();
+        assert_eq!(word.unquote(), ("abc".to_string(), true));
+
+        let word = Word::from_str(r#""""#).unwrap();
+        assert_eq!(word.unquote(), ("".to_string(), true));
+
+        let word = Word::from_str("$'abc'").unwrap();
+        assert_eq!(word.unquote(), ("abc".to_string(), true));
+    }
+
     #[test]
     fn word_to_string_if_literal_success() {
         let empty = Word::from_str("").unwrap();
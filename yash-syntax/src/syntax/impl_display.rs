@@ -158,6 +158,15 @@ impl fmt::Display for EscapedString {
     }
 }
 
+impl fmt::Display for ProcessSubstDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessSubstDirection::In => f.write_str("<"),
+            ProcessSubstDirection::Out => f.write_str(">"),
+        }
+    }
+}
+
 impl fmt::Display for WordUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -166,6 +175,9 @@ impl fmt::Display for WordUnit {
             DoubleQuote(content) => write!(f, "\"{content}\""),
             DollarSingleQuote(content) => write!(f, "$'{content}'"),
             Tilde(s) => write!(f, "~{s}"),
+            ProcessSubst {
+                direction, content, ..
+            } => write!(f, "{direction}({content})"),
         }
     }
 }
@@ -322,6 +334,7 @@ impl fmt::Display for CompoundCommand {
                 }
                 f.write_str("esac")
             }
+            Arith { content, .. } => write!(f, "(({content}))"),
         }
     }
 }
@@ -355,6 +368,9 @@ impl fmt::Display for Command {
 
 impl fmt::Display for Pipeline {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        if self.timed {
+            write!(f, "time ")?;
+        }
         if self.negation {
             write!(f, "! ")?;
         }
@@ -424,6 +440,13 @@ impl fmt::Display for List {
 mod tests {
     use super::*;
 
+    #[test]
+    fn fd_display() {
+        assert_eq!(Fd::STDIN.to_string(), "0");
+        assert_eq!(Fd(12).to_string(), "12");
+        assert_eq!(format!("{:>3}", Fd(5)), "  5");
+    }
+
     #[test]
     fn switch_display() {
         let switch = Switch {
@@ -625,6 +648,19 @@ mod tests {
         assert_eq!(tilde.to_string(), "~");
         let tilde = Tilde("foo".to_string());
         assert_eq!(tilde.to_string(), "~foo");
+
+        let process_subst = ProcessSubst {
+            direction: ProcessSubstDirection::In,
+            content: "echo a".into(),
+            location: Location::dummy(""),
+        };
+        assert_eq!(process_subst.to_string(), "<(echo a)");
+        let process_subst = ProcessSubst {
+            direction: ProcessSubstDirection::Out,
+            content: "cat".into(),
+            location: Location::dummy(""),
+        };
+        assert_eq!(process_subst.to_string(), ">(cat)");
     }
 
     #[test]
@@ -957,6 +993,12 @@ mod tests {
         assert_eq!(case.to_string(), "case baz in (1) ;; (a | b | c) :&;; esac");
     }
 
+    #[test]
+    fn arith_command_display() {
+        let arith = "(( a + $b ))".parse::<CompoundCommand>().unwrap();
+        assert_eq!(arith.to_string(), "(( a + $b ))");
+    }
+
     #[test]
     fn function_definition_display() {
         let body = FullCompoundCommand {
@@ -976,6 +1018,7 @@ mod tests {
         let mut p = Pipeline {
             commands: vec![Rc::new("first".parse::<Command>().unwrap())],
             negation: false,
+            timed: false,
         };
         assert_eq!(p.to_string(), "first");
 
@@ -988,6 +1031,12 @@ mod tests {
         p.commands.push(Rc::new("third".parse().unwrap()));
         p.negation = false;
         assert_eq!(p.to_string(), "first | second | third");
+
+        p.timed = true;
+        assert_eq!(p.to_string(), "time first | second | third");
+
+        p.negation = true;
+        assert_eq!(p.to_string(), "time ! first | second | third");
     }
 
     #[test]